@@ -0,0 +1,683 @@
+//! 아카이브 마운트 모듈 - 서버의 tar/zip 파일 내용을 읽기 전용 드라이브로 노출
+//!
+//! 연결의 원격 경로가 `.tar`, `.tar.gz`/`.tgz`, `.zip` 파일이면 아카이브 안의 목록을
+//! 드라이브로 보여준다. 목록은 마운트할 때 한 번 읽어 스냅샷 트리로 고정한다.
+//!
+//! - zip: 끝부분의 중앙 디렉토리만 읽는다. 압축하지 않은 항목은 바로 범위 읽기하고,
+//!   deflate 항목은 처음 읽을 때 그 항목만 풀어 로컬 임시 파일에 둔다.
+//! - tar: 헤더만 건너뛰며 읽어 색인하고, 내용은 아카이브에서 바로 범위 읽기한다.
+//! - tar.gz: 색인하려면 전체를 한 번 흘려 읽어야 한다. 항목은 처음 읽을 때 서버에서
+//!   `tar -xzOf`로 풀어 받고 (exec를 못 쓰면 압축을 풀며 흘려 읽음) 임시 파일에 둔다.
+//!
+//! 임시 파일은 언마운트하거나 새로 고치면 지운다.
+
+use crate::sftp_client::{shell_quote, CancelToken, RemoteReader, SharedSftpClient};
+use flate2::read::{DeflateDecoder, MultiGzDecoder};
+use log::{info, warn};
+use parking_lot::Mutex;
+use ssh2::FileStat;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// tar 블록 크기
+const TAR_BLOCK: u64 = 512;
+
+/// zip 끝 레코드(EOCD)와 최대 주석 길이, zip64 위치 레코드를 담을 만큼 끝부분을 읽음
+const ZIP_TAIL_BYTES: u64 = 22 + 65_535 + 20;
+
+/// 중앙 디렉토리 최대 크기 (넘으면 손상되었거나 지원하지 않는 아카이브로 봄)
+const MAX_CENTRAL_DIRECTORY_BYTES: u64 = 256 * 1024 * 1024;
+
+const ZIP_EOCD_SIGNATURE: u32 = 0x0605_4b50;
+const ZIP64_LOCATOR_SIGNATURE: u32 = 0x0706_4b50;
+const ZIP64_EOCD_SIGNATURE: u32 = 0x0606_4b50;
+const ZIP_CENTRAL_SIGNATURE: u32 = 0x0201_4b50;
+const ZIP_LOCAL_SIGNATURE: u32 = 0x0403_4b50;
+const ZIP_METHOD_STORED: u16 = 0;
+const ZIP_METHOD_DEFLATE: u16 = 8;
+
+const S_IFDIR: u32 = 0o040_000;
+const S_IFREG: u32 = 0o100_000;
+
+/// 원격 아카이브 형식
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArchiveKind {
+    Tar,
+    TarGz,
+    Zip,
+}
+
+impl ArchiveKind {
+    /// 확장자로 아카이브 형식 판별
+    pub fn detect(path: &str) -> Option<Self> {
+        let lower = path.to_lowercase();
+        if lower.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if lower.ends_with(".tar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+/// 아카이브 안에서 항목 내용이 있는 위치
+enum Location {
+    /// 압축 해제된 스트림 기준 내용 시작 위치 (tar는 파일 위치와 같음)
+    Tar { name: String, offset: u64 },
+    Zip {
+        header_offset: u64,
+        compressed_size: u64,
+        method: u16,
+    },
+}
+
+struct Member {
+    size: u64,
+    location: Location,
+}
+
+/// 색인 결과 (스냅샷 트리를 만들 항목 목록과 읽기용 색인)
+pub struct ArchiveListing {
+    /// 항목 전체 경로(아카이브 경로 아래)와 stat (상위 디렉토리는 없을 수 있음)
+    pub entries: Vec<(String, FileStat)>,
+    pub index: ArchiveIndex,
+}
+
+/// 아카이브 항목 색인과 풀어 둔 항목 임시 파일
+pub struct ArchiveIndex {
+    kind: ArchiveKind,
+    path: String,
+    members: HashMap<String, Member>,
+    temp_dir: PathBuf,
+    // 항목 경로 → 풀어 둔 임시 파일 (한 번에 하나씩 풂)
+    extracted: Mutex<HashMap<String, PathBuf>>,
+    // zip 로컬 헤더 위치 → 내용 시작 위치
+    data_offsets: Mutex<HashMap<u64, u64>>,
+}
+
+/// 원격 경로가 아카이브 파일이라 아카이브로 마운트해야 하는지
+pub fn is_archive_root(client: &SharedSftpClient, path: &str) -> bool {
+    ArchiveKind::detect(path).is_some() && client.lock().stat(path).is_ok_and(|s| !s.is_dir())
+}
+
+/// 아카이브 목록 읽기
+pub fn index(client: &SharedSftpClient, path: &str) -> Result<ArchiveListing, String> {
+    let kind =
+        ArchiveKind::detect(path).ok_or_else(|| format!("지원하지 않는 아카이브: {}", path))?;
+    let client = client.lock();
+    let archive_stat = client.stat(path)?;
+    let (members, entries) = match kind {
+        ArchiveKind::Zip => {
+            let size = archive_stat.size.unwrap_or(0);
+            let read = |offset: u64, len: u64| {
+                client.read_file_range_full(path, offset, len as usize, &CancelToken::default())
+            };
+            index_zip(path, size, read)?
+        }
+        ArchiveKind::Tar => index_tar(path, TarSource::Plain(client.open_reader(path, 0)?))?,
+        ArchiveKind::TarGz => {
+            let reader = MultiGzDecoder::new(BufReader::new(client.open_reader(path, 0)?));
+            index_tar(path, TarSource::Gzip(Box::new(reader)))?
+        }
+    };
+    info!("[Archive] indexed {} entries in '{}'", members.len(), path);
+
+    Ok(ArchiveListing {
+        entries,
+        index: ArchiveIndex {
+            kind,
+            path: path.to_string(),
+            members,
+            temp_dir: std::env::temp_dir().join(format!("ssh-virtual-drive-{}", Uuid::new_v4())),
+            extracted: Mutex::new(HashMap::new()),
+            data_offsets: Mutex::new(HashMap::new()),
+        },
+    })
+}
+
+impl ArchiveIndex {
+    /// 항목 내용의 일부 읽기 (`path`는 드라이브 경로를 바꾼 원격 전체 경로)
+    pub fn read(
+        &self,
+        client: &SharedSftpClient,
+        path: &str,
+        offset: u64,
+        len: usize,
+        cancel: &CancelToken,
+    ) -> Result<Vec<u8>, String> {
+        let member = self
+            .members
+            .get(path)
+            .ok_or_else(|| format!("아카이브에 없는 항목: {}", path))?;
+        let len =
+            len.min(usize::try_from(member.size.saturating_sub(offset)).unwrap_or(usize::MAX));
+        if len == 0 {
+            return Ok(Vec::new());
+        }
+
+        match (&member.location, self.kind) {
+            (Location::Tar { offset: start, .. }, ArchiveKind::Tar) => client
+                .lock()
+                .read_file_range_full(&self.path, start + offset, len, cancel),
+            (
+                Location::Zip {
+                    header_offset,
+                    method: ZIP_METHOD_STORED,
+                    ..
+                },
+                _,
+            ) => {
+                let start = self.zip_data_offset(client, *header_offset)?;
+                client
+                    .lock()
+                    .read_file_range_full(&self.path, start + offset, len, cancel)
+            }
+            _ => {
+                let local = self.extract(client, path, member)?;
+                let mut file =
+                    File::open(&local).map_err(|e| format!("임시 파일 열기 실패: {}", e))?;
+                file.seek(SeekFrom::Start(offset))
+                    .map_err(|e| format!("임시 파일 탐색 실패: {}", e))?;
+                let mut data = Vec::with_capacity(len);
+                file.take(len as u64)
+                    .read_to_end(&mut data)
+                    .map_err(|e| format!("임시 파일 읽기 실패: {}", e))?;
+                Ok(data)
+            }
+        }
+    }
+
+    /// 항목을 풀어 임시 파일로 저장 (이미 풀었으면 그 파일)
+    fn extract(
+        &self,
+        client: &SharedSftpClient,
+        path: &str,
+        member: &Member,
+    ) -> Result<PathBuf, String> {
+        let mut extracted = self.extracted.lock();
+        if let Some(local) = extracted.get(path) {
+            return Ok(local.clone());
+        }
+
+        std::fs::create_dir_all(&self.temp_dir)
+            .map_err(|e| format!("임시 디렉토리 생성 실패: {}", e))?;
+        let local = self.temp_dir.join(Uuid::new_v4().to_string());
+        let mut out = File::create(&local).map_err(|e| format!("임시 파일 생성 실패: {}", e))?;
+        let result = match &member.location {
+            Location::Zip {
+                header_offset,
+                compressed_size,
+                method,
+            } => {
+                if *method != ZIP_METHOD_DEFLATE {
+                    Err(format!("지원하지 않는 zip 압축 방식: {}", method))
+                } else {
+                    self.zip_data_offset(client, *header_offset)
+                        .and_then(|start| client.lock().open_reader(&self.path, start))
+                        .and_then(|file| {
+                            // 풀기가 끝날 때까지 세션을 다른 작업과 나누지 않음
+                            let _session = client.lock();
+                            let mut decoder = DeflateDecoder::new(file.take(*compressed_size));
+                            io::copy(&mut decoder, &mut out)
+                                .map(|_| ())
+                                .map_err(|e| format!("압축 해제 실패: {}", e))
+                        })
+                }
+            }
+            Location::Tar { name, offset } => {
+                let client = client.lock();
+                if client.capabilities().exec {
+                    // 서버에서 풀어 항목 내용만 받음
+                    client.exec_to(
+                        &format!(
+                            "tar -xzOf {} -- {}",
+                            shell_quote(&self.path),
+                            shell_quote(name)
+                        ),
+                        &mut out,
+                    )
+                } else {
+                    client.open_reader(&self.path, 0).and_then(|file| {
+                        let mut decoder = MultiGzDecoder::new(BufReader::new(file));
+                        io::copy(&mut (&mut decoder).take(*offset), &mut io::sink())
+                            .and_then(|_| io::copy(&mut decoder.take(member.size), &mut out))
+                            .map(|_| ())
+                            .map_err(|e| format!("압축 해제 실패: {}", e))
+                    })
+                }
+            }
+        };
+        if let Err(e) = result {
+            let _ = std::fs::remove_file(&local);
+            return Err(e);
+        }
+
+        extracted.insert(path.to_string(), local.clone());
+        Ok(local)
+    }
+
+    /// zip 항목의 로컬 헤더 뒤 내용 시작 위치
+    fn zip_data_offset(
+        &self,
+        client: &SharedSftpClient,
+        header_offset: u64,
+    ) -> Result<u64, String> {
+        if let Some(start) = self.data_offsets.lock().get(&header_offset) {
+            return Ok(*start);
+        }
+        let header = client.lock().read_file_range_full(
+            &self.path,
+            header_offset,
+            30,
+            &CancelToken::default(),
+        )?;
+        if header.len() < 30 || le_u32(&header, 0) != ZIP_LOCAL_SIGNATURE {
+            return Err("zip 항목 헤더가 올바르지 않습니다.".to_string());
+        }
+        let name_len = u64::from(le_u16(&header, 26));
+        let extra_len = u64::from(le_u16(&header, 28));
+        let start = header_offset + 30 + name_len + extra_len;
+        self.data_offsets.lock().insert(header_offset, start);
+        Ok(start)
+    }
+}
+
+impl Drop for ArchiveIndex {
+    fn drop(&mut self) {
+        if self.temp_dir.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&self.temp_dir) {
+                warn!("[Archive] temp files not removed: {}", e);
+            }
+        }
+    }
+}
+
+/// 아카이브 안의 이름을 원격 전체 경로로 (빈 이름이나 `..`이 있는 이름은 None)
+fn member_path(archive: &str, name: &str) -> Option<String> {
+    let name = name.replace('\\', "/");
+    let parts: Vec<&str> = name
+        .split('/')
+        .filter(|part| !part.is_empty() && *part != ".")
+        .collect();
+    if parts.is_empty() || parts.contains(&"..") {
+        return None;
+    }
+    Some(format!(
+        "{}/{}",
+        archive.trim_end_matches('/'),
+        parts.join("/")
+    ))
+}
+
+fn member_stat(is_dir: bool, size: u64, mode: u32, mtime: u64) -> FileStat {
+    FileStat {
+        size: Some(if is_dir { 0 } else { size }),
+        uid: None,
+        gid: None,
+        perm: Some(if is_dir { S_IFDIR } else { S_IFREG } | (mode & 0o7777)),
+        atime: Some(mtime),
+        mtime: Some(mtime),
+    }
+}
+
+type Indexed = (HashMap<String, Member>, Vec<(String, FileStat)>);
+
+/// tar 헤더를 차례로 읽어 색인 (압축하지 않은 tar는 내용을 읽지 않고 건너뜀)
+fn index_tar(archive: &str, source: TarSource) -> Result<Indexed, String> {
+    let mut input = TarInput {
+        inner: source,
+        pos: 0,
+    };
+    let mut members = HashMap::new();
+    let mut entries = Vec::new();
+    // GNU 긴 이름('L')이나 pax 헤더('x')가 다음 항목에 덮어쓸 값
+    let mut next_name: Option<String> = None;
+    let mut next_size: Option<u64> = None;
+    let mut next_mtime: Option<u64> = None;
+
+    let mut header = [0u8; TAR_BLOCK as usize];
+    loop {
+        if !input.read_block(&mut header)? {
+            break;
+        }
+        if header.iter().all(|b| *b == 0) {
+            break;
+        }
+
+        let mut size = parse_octal(&header[124..136]);
+        let data_offset = input.pos;
+        let padded = size.div_ceil(TAR_BLOCK) * TAR_BLOCK;
+        match header[156] {
+            b'L' => {
+                next_name = Some(c_string(&input.read_data(size, padded)?));
+                continue;
+            }
+            b'x' => {
+                let data = input.read_data(size, padded)?;
+                for (key, value) in parse_pax(&data) {
+                    match key.as_str() {
+                        "path" => next_name = Some(value),
+                        "size" => next_size = value.parse().ok(),
+                        "mtime" => {
+                            next_mtime = value.split('.').next().and_then(|v| v.parse().ok())
+                        }
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        let name = next_name.take().unwrap_or_else(|| {
+            let name = c_string(&header[0..100]);
+            let prefix = if &header[257..262] == b"ustar" {
+                c_string(&header[345..500])
+            } else {
+                String::new()
+            };
+            if prefix.is_empty() {
+                name
+            } else {
+                format!("{}/{}", prefix, name)
+            }
+        });
+        if let Some(pax_size) = next_size.take() {
+            size = pax_size;
+        }
+        let padded = size.div_ceil(TAR_BLOCK) * TAR_BLOCK;
+        let mtime = next_mtime
+            .take()
+            .unwrap_or_else(|| parse_octal(&header[136..148]));
+        let mode = parse_octal(&header[100..108]) as u32;
+
+        let kind = header[156];
+        let is_dir = kind == b'5' || (kind == 0 && name.ends_with('/'));
+        let is_file = matches!(kind, 0 | b'0' | b'7') && !is_dir;
+        // 링크, 장치 파일 등은 드라이브에 보이지 않음
+        if is_dir || is_file {
+            if let Some(path) = member_path(archive, &name) {
+                if is_file {
+                    members.insert(
+                        path.clone(),
+                        Member {
+                            size,
+                            location: Location::Tar {
+                                name: name.clone(),
+                                offset: data_offset,
+                            },
+                        },
+                    );
+                }
+                entries.push((path, member_stat(is_dir, size, mode, mtime)));
+            }
+        }
+        input.skip(padded)?;
+    }
+    Ok((members, entries))
+}
+
+/// tar 원본 (압축하지 않은 tar만 탐색 가능)
+enum TarSource {
+    Plain(RemoteReader),
+    Gzip(Box<MultiGzDecoder<BufReader<RemoteReader>>>),
+}
+
+impl Read for TarSource {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(file) => file.read(buf),
+            Self::Gzip(decoder) => decoder.read(buf),
+        }
+    }
+}
+
+/// 위치를 세며 읽는 tar 입력
+struct TarInput {
+    inner: TarSource,
+    pos: u64,
+}
+
+impl TarInput {
+    /// 블록 하나 읽기 (아카이브 끝이면 false)
+    fn read_block(&mut self, block: &mut [u8]) -> Result<bool, String> {
+        match self.inner.read_exact(block) {
+            Ok(()) => {
+                self.pos += block.len() as u64;
+                Ok(true)
+            }
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+            Err(e) => Err(format!("아카이브 읽기 실패: {}", e)),
+        }
+    }
+
+    /// 확장 헤더 내용 읽기 (블록 단위 패딩까지 소비)
+    fn read_data(&mut self, size: u64, padded: u64) -> Result<Vec<u8>, String> {
+        let mut data = Vec::new();
+        (&mut self.inner)
+            .take(size)
+            .read_to_end(&mut data)
+            .map_err(|e| format!("아카이브 읽기 실패: {}", e))?;
+        self.pos += data.len() as u64;
+        self.skip(padded - data.len() as u64)?;
+        Ok(data)
+    }
+
+    fn skip(&mut self, bytes: u64) -> Result<(), String> {
+        if bytes == 0 {
+            return Ok(());
+        }
+        self.pos += bytes;
+        match &mut self.inner {
+            TarSource::Plain(file) => file
+                .seek(SeekFrom::Start(self.pos))
+                .map(|_| ())
+                .map_err(|e| format!("아카이브 탐색 실패: {}", e)),
+            TarSource::Gzip(decoder) => io::copy(&mut decoder.take(bytes), &mut io::sink())
+                .map(|_| ())
+                .map_err(|e| format!("아카이브 읽기 실패: {}", e)),
+        }
+    }
+}
+
+/// tar 숫자 필드 (8진수, 큰 값은 GNU base-256)
+fn parse_octal(field: &[u8]) -> u64 {
+    if field.first().is_some_and(|b| b & 0x80 != 0) {
+        return field[1..]
+            .iter()
+            .fold(u64::from(field[0] & 0x7f), |acc, b| {
+                (acc << 8) | u64::from(*b)
+            });
+    }
+    let text = String::from_utf8_lossy(field);
+    u64::from_str_radix(text.trim_matches(|c: char| c == '\0' || c == ' '), 8).unwrap_or(0)
+}
+
+fn c_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+/// pax 확장 헤더 레코드 ("길이 키=값\n") 파싱
+fn parse_pax(data: &[u8]) -> Vec<(String, String)> {
+    let mut records = Vec::new();
+    let mut rest = data;
+    while let Some(space) = rest.iter().position(|b| *b == b' ') {
+        let Some(len) = std::str::from_utf8(&rest[..space])
+            .ok()
+            .and_then(|n| n.parse::<usize>().ok())
+        else {
+            break;
+        };
+        if len <= space + 1 || len > rest.len() {
+            break;
+        }
+        let record = String::from_utf8_lossy(&rest[space + 1..len]);
+        if let Some((key, value)) = record.trim_end_matches('\n').split_once('=') {
+            records.push((key.to_string(), value.to_string()));
+        }
+        rest = &rest[len..];
+    }
+    records
+}
+
+/// zip 중앙 디렉토리 색인 (`read`는 아카이브의 offset부터 len바이트를 읽음)
+fn index_zip(
+    archive: &str,
+    size: u64,
+    read: impl Fn(u64, u64) -> Result<Vec<u8>, String>,
+) -> Result<Indexed, String> {
+    let tail_start = size.saturating_sub(ZIP_TAIL_BYTES);
+    let tail = read(tail_start, size - tail_start)?;
+    let eocd = (0..tail.len().saturating_sub(21))
+        .rev()
+        .find(|&i| le_u32(&tail, i) == ZIP_EOCD_SIGNATURE)
+        .ok_or_else(|| "zip 끝 레코드를 찾을 수 없습니다.".to_string())?;
+
+    let mut count = u64::from(le_u16(&tail, eocd + 10));
+    let mut cd_size = u64::from(le_u32(&tail, eocd + 12));
+    let mut cd_offset = u64::from(le_u32(&tail, eocd + 16));
+    if count == 0xffff || cd_size == 0xffff_ffff || cd_offset == 0xffff_ffff {
+        // zip64: 끝 레코드 바로 앞의 위치 레코드가 zip64 끝 레코드를 가리킴
+        if eocd < 20 || le_u32(&tail, eocd - 20) != ZIP64_LOCATOR_SIGNATURE {
+            return Err("zip64 위치 레코드를 찾을 수 없습니다.".to_string());
+        }
+        let record = read(le_u64(&tail, eocd - 20 + 8), 56)?;
+        if record.len() < 56 || le_u32(&record, 0) != ZIP64_EOCD_SIGNATURE {
+            return Err("zip64 끝 레코드가 올바르지 않습니다.".to_string());
+        }
+        count = le_u64(&record, 32);
+        cd_size = le_u64(&record, 40);
+        cd_offset = le_u64(&record, 48);
+    }
+    if cd_size > MAX_CENTRAL_DIRECTORY_BYTES {
+        return Err("zip 중앙 디렉토리가 너무 큽니다.".to_string());
+    }
+
+    let cd = read(cd_offset, cd_size)?;
+    let mut members = HashMap::new();
+    let mut entries = Vec::new();
+    let mut encrypted = 0;
+    let mut pos = 0;
+    for _ in 0..count {
+        if pos + 46 > cd.len() || le_u32(&cd, pos) != ZIP_CENTRAL_SIGNATURE {
+            return Err("zip 중앙 디렉토리가 올바르지 않습니다.".to_string());
+        }
+        let flags = le_u16(&cd, pos + 8);
+        let method = le_u16(&cd, pos + 10);
+        let dos_time = le_u16(&cd, pos + 12);
+        let dos_date = le_u16(&cd, pos + 14);
+        let mut compressed_size = u64::from(le_u32(&cd, pos + 20));
+        let mut size = u64::from(le_u32(&cd, pos + 24));
+        let name_len = usize::from(le_u16(&cd, pos + 28));
+        let extra_len = usize::from(le_u16(&cd, pos + 30));
+        let comment_len = usize::from(le_u16(&cd, pos + 32));
+        let external = le_u32(&cd, pos + 38);
+        let mut header_offset = u64::from(le_u32(&cd, pos + 42));
+        let name_start = pos + 46;
+        let extra_start = name_start + name_len;
+        let next = extra_start + extra_len + comment_len;
+        if next > cd.len() {
+            return Err("zip 중앙 디렉토리가 올바르지 않습니다.".to_string());
+        }
+        let name = String::from_utf8_lossy(&cd[name_start..extra_start]).into_owned();
+        let mut mtime = dos_to_unix(dos_date, dos_time);
+
+        // 확장 필드: zip64 크기/위치, 확장 타임스탬프
+        let mut extra = &cd[extra_start..extra_start + extra_len];
+        while extra.len() >= 4 {
+            let id = le_u16(extra, 0);
+            let len = usize::from(le_u16(extra, 2)).min(extra.len() - 4);
+            let data = &extra[4..4 + len];
+            match id {
+                0x0001 => {
+                    let mut field = 0;
+                    for value in [&mut size, &mut compressed_size, &mut header_offset] {
+                        if *value == 0xffff_ffff && field + 8 <= data.len() {
+                            *value = le_u64(data, field);
+                            field += 8;
+                        }
+                    }
+                }
+                0x5455 if data.len() >= 5 && data[0] & 1 != 0 => {
+                    mtime = u64::from(le_u32(data, 1));
+                }
+                _ => {}
+            }
+            extra = &extra[4 + len..];
+        }
+        pos = next;
+
+        if flags & 1 != 0 {
+            encrypted += 1;
+            continue;
+        }
+        let Some(path) = member_path(archive, &name) else {
+            continue;
+        };
+        let is_dir = name.ends_with('/') || name.ends_with('\\');
+        // 유닉스에서 만든 zip은 외부 속성 상위 16비트에 권한이 있음
+        let mode = match external >> 16 {
+            0 => 0o644,
+            unix => unix,
+        };
+        if !is_dir {
+            members.insert(
+                path.clone(),
+                Member {
+                    size,
+                    location: Location::Zip {
+                        header_offset,
+                        compressed_size,
+                        method,
+                    },
+                },
+            );
+        }
+        entries.push((path, member_stat(is_dir, size, mode, mtime)));
+    }
+    if encrypted > 0 {
+        warn!("[Archive] skipped {} encrypted entries", encrypted);
+    }
+    Ok((members, entries))
+}
+
+/// zip의 MS-DOS 날짜/시간 (현지 시간이지만 시간대 정보가 없어 UTC로 봄)
+fn dos_to_unix(date: u16, time: u16) -> u64 {
+    let year = i64::from(date >> 9) + 1980;
+    let month = i64::from((date >> 5) & 0xf).clamp(1, 12);
+    let day = i64::from(date & 0x1f).max(1);
+    // 그레고리력 날짜 → 1970-01-01부터의 일 수
+    let (y, m) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * m + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    let seconds = i64::from(time >> 11) * 3600
+        + i64::from((time >> 5) & 0x3f) * 60
+        + i64::from(time & 0x1f) * 2;
+    (days * 86_400 + seconds).max(0) as u64
+}
+
+fn le_u16(data: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([data[at], data[at + 1]])
+}
+
+fn le_u32(data: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(data[at..at + 4].try_into().unwrap_or_default())
+}
+
+fn le_u64(data: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(data[at..at + 8].try_into().unwrap_or_default())
+}
@@ -0,0 +1,104 @@
+//! 북마크 모듈 - 연결 안에서 자주 쓰는 원격 경로에 이름을 붙여 저장
+//!
+//! 북마크는 연결 ID와 서버의 절대 경로로 이루어진다. 연결의 원격 경로 대신 북마크 경로를 루트로
+//! 마운트하거나 ([`MountManager::mount_bookmark`](crate::mount::MountManager::mount_bookmark))
+//! 그 폴더에서 터미널을 열 때 쓰며, 연결을 완전히 삭제하면 그 연결의 북마크도 지운다.
+
+use crate::storage;
+use crate::types::Bookmark;
+use parking_lot::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+/// 북마크 목록을 읽고 고쳐 쓰는 동안 잡는 잠금
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// 북마크 목록 (`connection_id`가 있으면 그 연결의 북마크만)
+pub fn list(connection_id: Option<&str>) -> Result<Vec<Bookmark>, String> {
+    let mut bookmarks = storage::load_bookmarks()?;
+    if let Some(id) = connection_id {
+        bookmarks.retain(|b| b.connection_id == id);
+    }
+    Ok(bookmarks)
+}
+
+/// 북마크 찾기
+pub fn get(id: &str) -> Result<Bookmark, String> {
+    storage::load_bookmarks()?
+        .into_iter()
+        .find(|b| b.id == id)
+        .ok_or_else(|| "북마크를 찾을 수 없습니다.".to_string())
+}
+
+/// 북마크 추가 (이름을 비우면 경로의 마지막 이름)
+pub fn add(connection_id: &str, name: &str, path: &str) -> Result<Bookmark, String> {
+    if storage::get_connection_by_id(connection_id)?.is_none() {
+        return Err("연결을 찾을 수 없습니다.".to_string());
+    }
+    let path = normalize(path)?;
+    let _guard = LOCK.lock();
+    let mut bookmarks = storage::load_bookmarks()?;
+    if let Some(existing) = bookmarks
+        .iter()
+        .find(|b| b.connection_id == connection_id && b.path == path)
+    {
+        return Err(format!(
+            "같은 경로의 북마크가 이미 있습니다: {}",
+            existing.name
+        ));
+    }
+
+    let name = match name.trim() {
+        "" => path.rsplit('/').find(|s| !s.is_empty()).unwrap_or("/"),
+        trimmed => trimmed,
+    };
+    let bookmark = Bookmark {
+        id: Uuid::new_v4().to_string(),
+        connection_id: connection_id.to_string(),
+        name: name.to_string(),
+        path,
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    };
+    bookmarks.push(bookmark.clone());
+    storage::save_bookmarks(&bookmarks)?;
+    Ok(bookmark)
+}
+
+/// 북마크 삭제
+pub fn remove(id: &str) -> Result<(), String> {
+    let _guard = LOCK.lock();
+    let mut bookmarks = storage::load_bookmarks()?;
+    let before = bookmarks.len();
+    bookmarks.retain(|b| b.id != id);
+    if bookmarks.len() == before {
+        return Err("북마크를 찾을 수 없습니다.".to_string());
+    }
+    storage::save_bookmarks(&bookmarks)
+}
+
+/// 삭제된 연결들의 북마크 지우기
+pub(crate) fn remove_for_connections(ids: &[String]) -> Result<(), String> {
+    let _guard = LOCK.lock();
+    let mut bookmarks = storage::load_bookmarks()?;
+    let before = bookmarks.len();
+    bookmarks.retain(|b| !ids.contains(&b.connection_id));
+    if bookmarks.len() == before {
+        return Ok(());
+    }
+    storage::save_bookmarks(&bookmarks)
+}
+
+/// 서버의 절대 경로로 정리 (끝의 `/`는 루트가 아니면 뺌)
+fn normalize(path: &str) -> Result<String, String> {
+    let path = path.trim();
+    if !path.starts_with('/') {
+        return Err("북마크 경로는 /로 시작하는 절대 경로여야 합니다.".to_string());
+    }
+    match path.trim_end_matches('/') {
+        "" => Ok("/".to_string()),
+        trimmed => Ok(trimmed.to_string()),
+    }
+}
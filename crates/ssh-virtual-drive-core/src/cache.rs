@@ -0,0 +1,893 @@
+//! stat/readdir 캐시 모듈 - 크기 제한 LRU와 만료 항목 주기적 정리
+//!
+//! 언마운트 시 디렉토리 목록을 gzip으로 압축한 스냅샷으로 저장하고, 같은 연결을 다시
+//! 마운트하면 불러와 "오래됨" 상태로 채운다. 오래된 항목은 한 번 그대로 쓰이고
+//! 호출자가 백그라운드에서 다시 확인한다.
+//!
+//! 드라이브를 일시 중지하면 서버에 묻지 않고 보관 중인 항목으로만 답한다 (만료 여부 무관).
+//!
+//! 항목마다 유효 시간을 따로 둔다. 디렉토리 목록을 저장할 때 마지막으로 바뀐 시점을 어림해
+//! (처음 보면 항목들의 가장 최근 수정 시각, 다시 읽었을 때 목록이 달라졌으면 지금) 오래
+//! 그대로인 디렉토리는 기준 TTL보다 길게, 방금 바뀐 디렉토리는 짧게 캐시한다.
+//!
+//! 고정 폴더 예열이 읽어 둔 작은 파일 내용도 여기에 둔다. 내용은 읽을 때의 크기와
+//! 수정 시각이 지금 캐시된 stat과 같을 때만 쓰고, 경로를 무효화하면 함께 버린다.
+
+use crate::offline::OfflineStore;
+use crate::profile::MountTuning;
+use crate::snapshot::SnapshotTree;
+use crate::stats::StatsCollector;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use ssh2::FileStat;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// stat 캐시 최대 항목 수
+const MAX_STAT_ENTRIES: usize = 10_000;
+
+/// readdir 캐시 최대 항목 수 (항목마다 하위 목록 전체를 보관하므로 더 작게)
+const MAX_DIR_ENTRIES: usize = 1_000;
+
+/// 없는 경로 캐시 최대 항목 수
+const MAX_MISSING_ENTRIES: usize = 5_000;
+
+/// 만료 항목 정리 주기 (초)
+const PURGE_INTERVAL_SECS: u64 = 30;
+
+/// 마지막 변화 뒤 지난 시간을 이 값으로 나눠 TTL로 씀 (10분 그대로면 1분 캐시)
+const QUIET_PER_TTL: u32 = 10;
+
+/// 조절한 TTL의 상한 (기준 TTL의 배수)
+const MAX_TTL_SCALE: u32 = 30;
+
+/// 조절한 TTL의 절대 상한 (기준 TTL이 이보다 길면 기준 TTL)
+const MAX_ADAPTIVE_TTL: Duration = Duration::from_secs(600);
+
+/// 만료된 항목을 스냅샷용으로 남겨 두는 시간 (초, 조회에는 쓰이지 않음)
+const STALE_RETENTION_SECS: u64 = 600;
+
+/// 스냅샷 형식 버전 (형식이 바뀌면 이전 스냅샷은 무시)
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// 미리 읽은 파일 내용 캐시 최대 크기 (바이트)
+const MAX_CONTENT_BYTES: usize = 64 * 1024 * 1024;
+
+/// 접근 순서를 기록하는 크기 제한 맵 (가장 오래 사용되지 않은 항목부터 축출)
+///
+/// 용량은 항목 무게의 합으로 센다. 기본 무게는 1이라 항목 수 제한이 되고,
+/// 메모리 예산을 쓰면 추정 바이트를 무게로 준다.
+struct LruMap<V> {
+    entries: HashMap<String, (V, u64, usize)>,
+    order: BTreeMap<u64, String>,
+    tick: u64,
+    capacity: usize,
+    // 현재 항목 무게의 합
+    weight: usize,
+    weigh: fn(&str, &V) -> usize,
+}
+
+impl<V> LruMap<V> {
+    fn new(capacity: usize) -> Self {
+        Self::weighted(capacity, |_, _| 1)
+    }
+
+    fn weighted(capacity: usize, weigh: fn(&str, &V) -> usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+            capacity,
+            weight: 0,
+            weigh,
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// 조회 후 최근 사용으로 표시
+    fn get(&mut self, key: &str) -> Option<&V> {
+        let tick = self.next_tick();
+        let (_, used, _) = self.entries.get_mut(key)?;
+        self.order.remove(used);
+        *used = tick;
+        self.order.insert(tick, key.to_string());
+        self.entries.get(key).map(|(value, _, _)| value)
+    }
+
+    /// 삽입 후 용량 초과분을 축출하고 축출 개수를 반환
+    fn insert(&mut self, key: String, value: V) -> u64 {
+        let tick = self.next_tick();
+        let weight = (self.weigh)(&key, &value);
+        self.weight += weight;
+        if let Some((_, used, old)) = self.entries.insert(key.clone(), (value, tick, weight)) {
+            self.order.remove(&used);
+            self.weight -= old;
+        }
+        self.order.insert(tick, key);
+
+        let mut evicted = 0;
+        while self.weight > self.capacity {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            if let Some((_, _, old)) = self.entries.remove(&oldest) {
+                self.weight -= old;
+            }
+            evicted += 1;
+        }
+        evicted
+    }
+
+    /// 사용 순서를 바꾸지 않고 수정
+    fn peek_mut(&mut self, key: &str) -> Option<&mut V> {
+        self.entries.get_mut(key).map(|(value, _, _)| value)
+    }
+
+    /// 사용 순서와 무관한 전체 항목
+    fn values(&self) -> impl Iterator<Item = (&String, &V)> {
+        self.entries.iter().map(|(key, (value, _, _))| (key, value))
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some((_, used, old)) = self.entries.remove(key) {
+            self.order.remove(&used);
+            self.weight -= old;
+        }
+    }
+
+    /// 키가 `prefix`로 시작하는 항목 제거
+    fn remove_prefix(&mut self, prefix: &str) {
+        let keys: Vec<String> = self
+            .entries
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect();
+        for key in keys {
+            self.remove(&key);
+        }
+    }
+
+    /// 조건을 만족하지 않는 항목 제거 후 제거 개수를 반환
+    fn retain(&mut self, mut keep: impl FnMut(&V) -> bool) -> u64 {
+        let before = self.entries.len();
+        let order = &mut self.order;
+        let weight = &mut self.weight;
+        self.entries.retain(|_, (value, used, old)| {
+            let keep = keep(value);
+            if !keep {
+                order.remove(used);
+                *weight -= *old;
+            }
+            keep
+        });
+        (before - self.entries.len()) as u64
+    }
+}
+
+/// 맵 항목 하나의 고정 부담 추정치 (해시/순서 맵 노드, 키 복사본 제외)
+const ENTRY_OVERHEAD_BYTES: usize = 64;
+
+/// 키가 해시 맵과 순서 맵에 한 번씩 들어가므로 두 배로 셈
+fn key_bytes(key: &str) -> usize {
+    key.len() * 2 + ENTRY_OVERHEAD_BYTES
+}
+
+fn weigh_stat(key: &str, _: &CachedStat) -> usize {
+    key_bytes(key) + std::mem::size_of::<CachedStat>()
+}
+
+fn weigh_dir(key: &str, dir: &CachedDir) -> usize {
+    key_bytes(key)
+        + std::mem::size_of::<CachedDir>()
+        + dir
+            .entries
+            .iter()
+            .map(|(name, _)| name.len() + std::mem::size_of::<(String, FileStat)>())
+            .sum::<usize>()
+}
+
+fn weigh_missing(key: &str, _: &Instant) -> usize {
+    key_bytes(key) + std::mem::size_of::<Instant>()
+}
+
+fn weigh_content(key: &str, content: &CachedContent) -> usize {
+    key_bytes(key) + std::mem::size_of::<CachedContent>() + content.data.len()
+}
+
+/// 캐시된 stat 엔트리
+struct CachedStat {
+    stat: FileStat,
+    cached_at: Instant,
+    ttl: Duration,
+    // 스냅샷에서 불러와 아직 서버와 확인하지 않음
+    stale: bool,
+}
+
+impl CachedStat {
+    fn is_fresh(&self) -> bool {
+        !self.stale && self.cached_at.elapsed() < self.ttl
+    }
+}
+
+/// 미리 읽은 파일 내용 (읽을 때의 크기와 수정 시각)
+struct CachedContent {
+    size: Option<u64>,
+    mtime: Option<u64>,
+    data: Arc<Vec<u8>>,
+}
+
+/// readdir 캐시 엔트리
+struct CachedDir {
+    entries: Vec<(String, FileStat)>,
+    cached_at: Instant,
+    ttl: Duration,
+    // 목록이 마지막으로 바뀐 것으로 어림한 시점 (알 수 없으면 None)
+    changed_at: Option<Instant>,
+    stale: bool,
+}
+
+impl CachedDir {
+    fn is_fresh(&self) -> bool {
+        !self.stale && self.cached_at.elapsed() < self.ttl
+    }
+}
+
+/// 스냅샷에 저장하는 stat (ssh2::FileStat은 직렬화할 수 없음)
+#[derive(Serialize, Deserialize)]
+struct SnapshotStat {
+    size: Option<u64>,
+    uid: Option<u32>,
+    gid: Option<u32>,
+    perm: Option<u32>,
+    atime: Option<u64>,
+    mtime: Option<u64>,
+}
+
+impl From<&FileStat> for SnapshotStat {
+    fn from(stat: &FileStat) -> Self {
+        Self {
+            size: stat.size,
+            uid: stat.uid,
+            gid: stat.gid,
+            perm: stat.perm,
+            atime: stat.atime,
+            mtime: stat.mtime,
+        }
+    }
+}
+
+impl From<SnapshotStat> for FileStat {
+    fn from(stat: SnapshotStat) -> Self {
+        Self {
+            size: stat.size,
+            uid: stat.uid,
+            gid: stat.gid,
+            perm: stat.perm,
+            atime: stat.atime,
+            mtime: stat.mtime,
+        }
+    }
+}
+
+/// 디스크에 저장하는 캐시 스냅샷 (디렉토리 목록만, 하위 stat은 불러올 때 다시 채움)
+#[derive(Serialize, Deserialize)]
+struct CacheSnapshot {
+    version: u32,
+    /// 스냅샷을 저장한 계정 (다른 계정의 목록과 권한을 보여주지 않도록)
+    #[serde(default)]
+    identity: Option<String>,
+    dirs: Vec<(String, Vec<(String, SnapshotStat)>)>,
+}
+
+/// SFTP stat 결과 캐시 — 네트워크 호출 횟수를 대폭 줄임
+pub struct StatCache {
+    stats: Mutex<LruMap<CachedStat>>,
+    dirs: Mutex<LruMap<CachedDir>>,
+    // 서버에 없다고 확인된 경로와 확인 시각
+    missing: Mutex<LruMap<Instant>>,
+    // 고정 폴더 예열로 미리 읽은 작은 파일 내용 (메모리 절약 모드에서는 쓰지 않음)
+    contents: Mutex<LruMap<CachedContent>>,
+    last_purge: Mutex<Instant>,
+    ttl: Duration,
+    adaptive_ttl: bool,
+    negative_ttl: Option<Duration>,
+    // 스냅샷 마운트의 고정된 트리 (있으면 서버에 다시 묻지 않음)
+    frozen: Mutex<Option<Arc<SnapshotTree>>>,
+    // 드라이브 일시 중지 중 (캐시로만 답함)
+    paused: AtomicBool,
+    // 오프라인 사용으로 고정한 파일의 로컬 복사본 (고정한 경로가 있을 때만)
+    offline: Mutex<Option<Arc<OfflineStore>>>,
+    // 적중/실패/축출 카운터
+    counters: Arc<StatsCollector>,
+}
+
+impl StatCache {
+    pub fn new(counters: Arc<StatsCollector>, tuning: &MountTuning) -> Self {
+        Self {
+            stats: Mutex::new(match tuning.cache_budget {
+                Some(budget) => LruMap::weighted(budget.stat_bytes, weigh_stat),
+                None => LruMap::new(MAX_STAT_ENTRIES),
+            }),
+            dirs: Mutex::new(match tuning.cache_budget {
+                Some(budget) => LruMap::weighted(budget.dir_bytes, weigh_dir),
+                None => LruMap::new(MAX_DIR_ENTRIES),
+            }),
+            missing: Mutex::new(match tuning.cache_budget {
+                Some(budget) => LruMap::weighted(budget.missing_bytes, weigh_missing),
+                None => LruMap::new(MAX_MISSING_ENTRIES),
+            }),
+            contents: Mutex::new(LruMap::weighted(
+                tuning.cache_budget.map_or(MAX_CONTENT_BYTES, |_| 0),
+                weigh_content,
+            )),
+            last_purge: Mutex::new(Instant::now()),
+            ttl: tuning.stat_ttl,
+            adaptive_ttl: tuning.adaptive_ttl,
+            negative_ttl: tuning.negative_ttl,
+            frozen: Mutex::new(None),
+            paused: AtomicBool::new(false),
+            offline: Mutex::new(None),
+            counters,
+        }
+    }
+
+    /// 스냅샷 트리로 메타데이터 고정 (새로 고칠 때는 통째로 교체)
+    pub(crate) fn freeze(&self, tree: SnapshotTree) {
+        *self.frozen.lock() = Some(Arc::new(tree));
+    }
+
+    /// 고정된 스냅샷 트리 (스냅샷 마운트가 아니면 None)
+    pub(crate) fn frozen(&self) -> Option<Arc<SnapshotTree>> {
+        self.frozen.lock().clone()
+    }
+
+    /// 오프라인 고정 복사본 연결 (마운트할 때 한 번)
+    pub(crate) fn attach_offline(&self, store: Arc<OfflineStore>) {
+        *self.offline.lock() = Some(store);
+    }
+
+    /// 오프라인 고정 복사본 (고정한 경로가 없으면 None)
+    pub(crate) fn offline(&self) -> Option<Arc<OfflineStore>> {
+        self.offline.lock().clone()
+    }
+
+    /// 드라이브 일시 중지/재개 (중지 중에는 서버에 묻지 않음)
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Release);
+    }
+
+    /// 드라이브가 일시 중지 중인지
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Acquire)
+    }
+
+    /// 만료되었거나 확인하지 않은 항목까지 포함한 stat (일시 중지 중 조회용)
+    pub fn get_retained_stat(&self, path: &str) -> Option<FileStat> {
+        let retained = self.stats.lock().get(path).map(|entry| entry.stat.clone());
+        self.record_lookup(retained.is_some());
+        retained
+    }
+
+    /// 만료되었거나 확인하지 않은 항목까지 포함한 readdir 결과 (일시 중지 중 조회용)
+    pub fn get_retained_dir(&self, path: &str) -> Option<Vec<(String, FileStat)>> {
+        let retained = self
+            .dirs
+            .lock()
+            .get(path)
+            .map(|entry| entry.entries.clone());
+        self.record_lookup(retained.is_some());
+        retained
+    }
+
+    /// 캐시된 stat 조회 (TTL 내이고 확인된 항목만 반환, 만료 항목은 스냅샷용으로 남김)
+    pub fn get_stat(&self, path: &str) -> Option<FileStat> {
+        let fresh = self
+            .stats
+            .lock()
+            .get(path)
+            .filter(|entry| entry.is_fresh())
+            .map(|entry| entry.stat.clone());
+        self.record_lookup(fresh.is_some());
+        fresh
+    }
+
+    /// 캐시된 readdir 결과 조회 (TTL 내이고 확인된 항목만 반환)
+    pub fn get_dir(&self, path: &str) -> Option<Vec<(String, FileStat)>> {
+        let fresh = self
+            .dirs
+            .lock()
+            .get(path)
+            .filter(|entry| entry.is_fresh())
+            .map(|entry| entry.entries.clone());
+        self.record_lookup(fresh.is_some());
+        fresh
+    }
+
+    /// 서버에 없다고 알려진 경로인지 (없는 경로 캐시를 켠 경우만)
+    ///
+    /// 직접 기록한 실패 외에, 유효한 상위 디렉토리 목록에 이름이 없는 경우도 없는 것으로 본다.
+    pub fn is_missing(&self, path: &str) -> bool {
+        let Some(negative_ttl) = self.negative_ttl else {
+            return false;
+        };
+        if self
+            .missing
+            .lock()
+            .get(path)
+            .is_some_and(|checked| checked.elapsed() < negative_ttl)
+        {
+            return true;
+        }
+
+        let parent = parent_path(path);
+        if parent == path {
+            return false;
+        }
+        let name = path[parent.len()..].trim_start_matches('/');
+        self.dirs
+            .lock()
+            .peek_mut(parent)
+            .filter(|entry| entry.is_fresh())
+            .is_some_and(|entry| !entry.entries.iter().any(|(n, _)| n == name))
+    }
+
+    /// 서버에 없는 경로 기록 (없는 경로 캐시를 켠 경우만)
+    pub fn insert_missing(&self, path: &str) {
+        if self.negative_ttl.is_some() {
+            let evicted = self.missing.lock().insert(path.to_string(), Instant::now());
+            self.record_evictions(evicted);
+        }
+    }
+
+    /// 유효한 디렉토리 목록이 캐시에 있는지 (적중 통계에 넣지 않음)
+    pub fn has_dir(&self, path: &str) -> bool {
+        self.dirs
+            .lock()
+            .peek_mut(path)
+            .is_some_and(|entry| entry.is_fresh())
+    }
+
+    /// 스냅샷에서 불러온 stat을 한 번 반환 (호출자가 상위 디렉토리를 다시 확인해야 함)
+    pub fn take_stale_stat(&self, path: &str) -> Option<FileStat> {
+        let mut stats = self.stats.lock();
+        let entry = stats.peek_mut(path).filter(|entry| entry.stale)?;
+        entry.stale = false;
+        entry.cached_at = Instant::now();
+        Some(entry.stat.clone())
+    }
+
+    /// 스냅샷에서 불러온 readdir 결과를 한 번 반환 (호출자가 다시 확인해야 함)
+    pub fn take_stale_dir(&self, path: &str) -> Option<Vec<(String, FileStat)>> {
+        let mut dirs = self.dirs.lock();
+        let entry = dirs.peek_mut(path).filter(|entry| entry.stale)?;
+        entry.stale = false;
+        entry.cached_at = Instant::now();
+        Some(entry.entries.clone())
+    }
+
+    /// 파일 내용을 캐시에 둘 수 있는지 (메모리 절약 모드가 아닌 경우)
+    pub(crate) fn keeps_contents(&self) -> bool {
+        self.contents.lock().capacity > 0
+    }
+
+    /// 미리 읽은 파일 내용 (캐시된 stat의 크기와 수정 시각이 읽을 때와 같은 경우만)
+    ///
+    /// stat이 만료되었으면 내용이 바뀌었는지 알 수 없으므로 쓰지 않는다. 일시 중지 중에는
+    /// 보관 중인 stat과 비교한다.
+    pub(crate) fn get_content(&self, path: &str) -> Option<Arc<Vec<u8>>> {
+        let paused = self.is_paused();
+        let (size, mtime) = self
+            .stats
+            .lock()
+            .peek_mut(path)
+            .filter(|entry| paused || entry.is_fresh())
+            .map(|entry| (entry.stat.size, entry.stat.mtime))?;
+        let mut contents = self.contents.lock();
+        let content = contents.get(path)?;
+        (content.size == size && content.mtime == mtime).then(|| content.data.clone())
+    }
+
+    /// 파일 내용 저장 (`stat`은 내용을 읽기 직전의 stat)
+    pub(crate) fn insert_content(&self, path: &str, stat: &FileStat, data: Vec<u8>) {
+        let evicted = self.contents.lock().insert(
+            path.to_string(),
+            CachedContent {
+                size: stat.size,
+                mtime: stat.mtime,
+                data: Arc::new(data),
+            },
+        );
+        self.record_evictions(evicted);
+    }
+
+    /// stat 결과 저장
+    ///
+    /// 상위 디렉토리가 바뀐 지 얼마 안 되었거나 파일 자신이 방금 수정되었으면 짧게 캐시한다.
+    pub fn insert_stat(&self, path: &str, stat: FileStat) {
+        self.purge_expired_if_due();
+        self.missing.lock().remove(path);
+        let dir_quiet = self
+            .dirs
+            .lock()
+            .peek_mut(parent_path(path))
+            .and_then(|dir| dir.changed_at)
+            .map(|changed_at| changed_at.elapsed());
+        let quiet = match (dir_quiet, quiet_since_mtime(stat.mtime)) {
+            (Some(dir), Some(own)) => Some(dir.min(own)),
+            (dir, own) => dir.or(own),
+        };
+        let evicted = self.stats.lock().insert(
+            path.to_string(),
+            CachedStat {
+                stat,
+                cached_at: Instant::now(),
+                ttl: self.ttl_for(quiet),
+                stale: false,
+            },
+        );
+        self.record_evictions(evicted);
+    }
+
+    /// readdir 결과 저장 (하위 항목 stat도 함께 캐시)
+    pub fn insert_dir(&self, path: &str, entries: &[(String, FileStat)]) {
+        self.purge_expired_if_due();
+        self.store_dir(path, entries, false);
+    }
+
+    fn store_dir(&self, path: &str, entries: &[(String, FileStat)], stale: bool) {
+        let now = Instant::now();
+        // 같은 목록을 다시 읽었으면 이전에 어림한 변화 시점을 이어 씀
+        let previous = self
+            .dirs
+            .lock()
+            .peek_mut(path)
+            .map(|dir| (dir.entries == entries, dir.changed_at));
+        let changed_at = match previous {
+            Some((true, changed_at)) => changed_at,
+            Some((false, _)) => Some(now),
+            None => entries
+                .iter()
+                .filter_map(|(_, stat)| quiet_since_mtime(stat.mtime))
+                .min()
+                .map(|quiet| now.checked_sub(quiet).unwrap_or(now)),
+        };
+        let ttl = self.ttl_for(changed_at.map(|changed_at| changed_at.elapsed()));
+        let mut evicted = 0;
+        {
+            let mut stats = self.stats.lock();
+            let mut missing = self.missing.lock();
+            for (name, stat) in entries {
+                let full_path = if path == "/" {
+                    format!("/{}", name)
+                } else {
+                    format!("{}/{}", path, name)
+                };
+                missing.remove(&full_path);
+                evicted += stats.insert(
+                    full_path,
+                    CachedStat {
+                        stat: stat.clone(),
+                        cached_at: now,
+                        ttl,
+                        stale,
+                    },
+                );
+            }
+        }
+        evicted += self.dirs.lock().insert(
+            path.to_string(),
+            CachedDir {
+                entries: entries.to_vec(),
+                cached_at: now,
+                ttl,
+                changed_at,
+                stale,
+            },
+        );
+        self.record_evictions(evicted);
+    }
+
+    /// 경로의 stat과 상위 디렉토리 목록 무효화 (원격 내용이 바뀐 경우)
+    pub fn invalidate(&self, path: &str) {
+        self.stats.lock().remove(path);
+        self.missing.lock().remove(path);
+        self.contents.lock().remove(path);
+        self.dirs.lock().remove(parent_path(path));
+    }
+
+    /// 디렉토리 자신의 목록까지 무효화 (안의 항목이 바뀐 경우)
+    pub fn invalidate_dir(&self, path: &str) {
+        self.invalidate(path);
+        self.dirs.lock().remove(path);
+    }
+
+    /// 디렉토리와 그 아래 모든 항목 무효화 (디렉토리를 옮기거나 지운 경우)
+    pub fn invalidate_tree(&self, path: &str) {
+        self.invalidate_dir(path);
+        let prefix = format!("{}/", path.trim_end_matches('/'));
+        self.stats.lock().remove_prefix(&prefix);
+        self.missing.lock().remove_prefix(&prefix);
+        self.contents.lock().remove_prefix(&prefix);
+        self.dirs.lock().remove_prefix(&prefix);
+    }
+
+    /// 디렉토리 목록을 압축 스냅샷으로 저장 (만료되었지만 보관 중인 항목 포함)
+    pub fn save_snapshot(&self, file: &Path, identity: &str) -> Result<usize, String> {
+        let dirs: Vec<_> = self
+            .dirs
+            .lock()
+            .values()
+            .map(|(path, dir)| {
+                let entries = dir
+                    .entries
+                    .iter()
+                    .map(|(name, stat)| (name.clone(), SnapshotStat::from(stat)))
+                    .collect();
+                (path.clone(), entries)
+            })
+            .collect();
+        let count = dirs.len();
+        let snapshot = CacheSnapshot {
+            version: SNAPSHOT_VERSION,
+            identity: Some(identity.to_string()),
+            dirs,
+        };
+
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("캐시 디렉토리 생성 실패: {}", e))?;
+        }
+        let out = File::create(file).map_err(|e| format!("캐시 스냅샷 생성 실패: {}", e))?;
+        let mut encoder = GzEncoder::new(BufWriter::new(out), Compression::fast());
+        serde_json::to_writer(&mut encoder, &snapshot)
+            .map_err(|e| format!("캐시 스냅샷 저장 실패: {}", e))?;
+        encoder
+            .finish()
+            .map_err(|e| format!("캐시 스냅샷 저장 실패: {}", e))?;
+        Ok(count)
+    }
+
+    /// 압축 스냅샷을 불러와 오래된 항목으로 채움 (불러온 디렉토리 수 반환)
+    ///
+    /// 다른 계정이 저장한 스냅샷은 무시한다.
+    pub fn load_snapshot(&self, file: &Path, identity: &str) -> Result<usize, String> {
+        let input = File::open(file).map_err(|e| format!("캐시 스냅샷 열기 실패: {}", e))?;
+        let snapshot: CacheSnapshot =
+            serde_json::from_reader(GzDecoder::new(BufReader::new(input)))
+                .map_err(|e| format!("캐시 스냅샷 읽기 실패: {}", e))?;
+        if snapshot.version != SNAPSHOT_VERSION
+            || snapshot
+                .identity
+                .as_deref()
+                .is_some_and(|saved| saved != identity)
+        {
+            return Ok(0);
+        }
+
+        let count = snapshot.dirs.len();
+        for (path, entries) in snapshot.dirs {
+            let entries: Vec<_> = entries
+                .into_iter()
+                .map(|(name, stat)| (name, FileStat::from(stat)))
+                .collect();
+            self.store_dir(&path, &entries, true);
+        }
+        Ok(count)
+    }
+
+    /// 주기적으로 만료 항목 일괄 제거 (접근되지 않는 만료 항목이 메모리를 점유하지 않도록)
+    fn purge_expired_if_due(&self) {
+        // 일시 중지 중에는 남은 항목이 유일한 답이므로 지우지 않음
+        if self.is_paused() {
+            return;
+        }
+        {
+            let mut last_purge = self.last_purge.lock();
+            if last_purge.elapsed() < Duration::from_secs(PURGE_INTERVAL_SECS) {
+                return;
+            }
+            *last_purge = Instant::now();
+        }
+
+        // 스냅샷에 쓸 수 있도록 만료 후에도 잠시 보관
+        let retention = Duration::from_secs(STALE_RETENTION_SECS);
+        let purged = self
+            .stats
+            .lock()
+            .retain(|entry| entry.cached_at.elapsed() < entry.ttl + retention)
+            + self
+                .dirs
+                .lock()
+                .retain(|entry| entry.cached_at.elapsed() < entry.ttl + retention);
+        if let Some(negative_ttl) = self.negative_ttl {
+            self.missing
+                .lock()
+                .retain(|checked| checked.elapsed() < negative_ttl);
+        }
+        self.record_evictions(purged);
+    }
+
+    /// 마지막 변화 뒤 지난 시간에 맞춘 유효 시간 (모르면 기준 TTL)
+    fn ttl_for(&self, quiet: Option<Duration>) -> Duration {
+        match quiet.filter(|_| self.adaptive_ttl) {
+            Some(quiet) => {
+                let max = (self.ttl * MAX_TTL_SCALE)
+                    .min(MAX_ADAPTIVE_TTL)
+                    .max(self.ttl);
+                (quiet / QUIET_PER_TTL).clamp(self.ttl / 4, max)
+            }
+            None => self.ttl,
+        }
+    }
+
+    fn record_lookup(&self, hit: bool) {
+        if hit {
+            self.counters.record_cache_hit();
+        } else {
+            self.counters.record_cache_miss();
+        }
+    }
+
+    fn record_evictions(&self, count: u64) {
+        if count > 0 {
+            self.counters.record_cache_evictions(count);
+        }
+    }
+}
+
+/// 서버 수정 시각 뒤 지난 시간 (서버 시계가 앞서 있으면 0)
+fn quiet_since_mtime(mtime: Option<u64>) -> Option<Duration> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    mtime.map(|mtime| Duration::from_secs(now.saturating_sub(mtime)))
+}
+
+/// `from`을 `to`로 옮긴 뒤의 `path` (`from` 자신이거나 그 아래가 아니면 None)
+pub(crate) fn moved_path(path: &str, from: &str, to: &str) -> Option<String> {
+    if path == from {
+        return Some(to.to_string());
+    }
+    path.strip_prefix(from)
+        .filter(|rest| rest.starts_with('/'))
+        .map(|rest| format!("{}{}", to, rest))
+}
+
+/// 원격 경로의 상위 디렉토리 (루트의 상위는 루트)
+pub(crate) fn parent_path(path: &str) -> &str {
+    match path.rfind('/') {
+        Some(0) | None => "/",
+        Some(idx) => &path[..idx],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{MountProfile, SftpCapabilities};
+
+    fn file_stat(size: u64) -> FileStat {
+        FileStat {
+            size: Some(size),
+            uid: None,
+            gid: None,
+            perm: Some(0o100644),
+            atime: None,
+            mtime: None,
+        }
+    }
+
+    fn cache(counters: &Arc<StatsCollector>) -> StatCache {
+        let mut tuning = MountTuning::for_profile(MountProfile::Default);
+        tuning.adaptive_ttl = false;
+        StatCache::new(counters.clone(), &tuning)
+    }
+
+    /// `elapsed` 전에 캐시한 것으로 되돌림
+    fn age_stat(cache: &StatCache, path: &str, elapsed: Duration) {
+        let mut stats = cache.stats.lock();
+        let entry = stats.peek_mut(path).unwrap();
+        entry.cached_at = Instant::now().checked_sub(elapsed).unwrap();
+    }
+
+    #[test]
+    fn lru_evicts_least_recently_used_entry() {
+        let mut map = LruMap::new(2);
+        assert_eq!(map.insert("/a".to_string(), 1), 0);
+        assert_eq!(map.insert("/b".to_string(), 2), 0);
+        // 조회한 항목은 최근 사용으로 올라가 다음 축출 대상에서 빠짐
+        assert_eq!(map.get("/a"), Some(&1));
+        assert_eq!(map.insert("/c".to_string(), 3), 1);
+
+        assert_eq!(map.get("/b"), None);
+        assert_eq!(map.get("/a"), Some(&1));
+        assert_eq!(map.get("/c"), Some(&3));
+    }
+
+    #[test]
+    fn lru_replacing_a_key_does_not_evict() {
+        let mut map = LruMap::new(2);
+        map.insert("/a".to_string(), 1);
+        map.insert("/b".to_string(), 2);
+        assert_eq!(map.insert("/a".to_string(), 10), 0);
+        assert_eq!(map.get("/a"), Some(&10));
+        assert_eq!(map.get("/b"), Some(&2));
+    }
+
+    #[test]
+    fn weighted_lru_evicts_oldest_until_within_budget() {
+        let mut map: LruMap<usize> = LruMap::weighted(10, |_, size| *size);
+        map.insert("/a".to_string(), 4);
+        map.insert("/b".to_string(), 4);
+        map.insert("/c".to_string(), 2);
+        // 8바이트를 더하면 /a와 /b를 내보내야 예산 안에 듦
+        assert_eq!(map.insert("/d".to_string(), 8), 2);
+        assert_eq!(map.weight, 10);
+        assert_eq!(map.get("/a"), None);
+        assert_eq!(map.get("/b"), None);
+        assert_eq!(map.get("/c"), Some(&2));
+        assert_eq!(map.get("/d"), Some(&8));
+    }
+
+    #[test]
+    fn expired_stat_misses_but_is_retained() {
+        let counters = Arc::new(StatsCollector::default());
+        let cache = cache(&counters);
+        cache.insert_stat("/f", file_stat(3));
+        assert_eq!(cache.get_stat("/f").and_then(|s| s.size), Some(3));
+
+        age_stat(&cache, "/f", cache.ttl + Duration::from_secs(1));
+        assert!(cache.get_stat("/f").is_none());
+        assert_eq!(cache.get_retained_stat("/f").and_then(|s| s.size), Some(3));
+    }
+
+    #[test]
+    fn purge_drops_entries_past_retention_and_counts_them() {
+        let counters = Arc::new(StatsCollector::default());
+        let cache = cache(&counters);
+        cache.insert_stat("/old", file_stat(1));
+        cache.insert_stat("/expired", file_stat(2));
+        age_stat(
+            &cache,
+            "/old",
+            cache.ttl + Duration::from_secs(STALE_RETENTION_SECS + 1),
+        );
+        age_stat(&cache, "/expired", cache.ttl + Duration::from_secs(1));
+
+        // 정리 주기가 지나기 전에는 그대로 둠
+        cache.insert_stat("/new", file_stat(3));
+        assert!(cache.get_retained_stat("/old").is_some());
+
+        *cache.last_purge.lock() = Instant::now()
+            .checked_sub(Duration::from_secs(PURGE_INTERVAL_SECS + 1))
+            .unwrap();
+        cache.insert_stat("/newer", file_stat(4));
+        assert!(cache.get_retained_stat("/old").is_none());
+        assert!(cache.get_retained_stat("/expired").is_some());
+        assert!(cache.get_stat("/newer").is_some());
+        let stats = counters.snapshot('Z', "test", SftpCapabilities::default());
+        assert_eq!(stats.cache_evictions, 1);
+    }
+
+    #[test]
+    fn moved_path_follows_renamed_file_and_children() {
+        assert_eq!(moved_path("/d/a", "/d/a", "/e/b"), Some("/e/b".to_string()));
+        assert_eq!(
+            moved_path("/d/a/x/y.txt", "/d/a", "/e/b"),
+            Some("/e/b/x/y.txt".to_string())
+        );
+        assert_eq!(moved_path("/d/ab", "/d/a", "/e/b"), None);
+        assert_eq!(moved_path("/d", "/d/a", "/e/b"), None);
+    }
+}
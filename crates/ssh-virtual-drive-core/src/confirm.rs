@@ -0,0 +1,122 @@
+//! 보호 경로 확인 모듈 - 중요한 원격 경로의 삭제/이름 변경 전에 사용자에게 확인
+//!
+//! 연결의 `protected_paths` 아래 항목을 지우거나 옮기려 하면 파일시스템 스레드가 확인
+//! 요청을 올리고 답을 기다린다. 앱은 [`take_requests`]로 새 요청을 받아 확인 창을 띄우고
+//! [`answer`]로 결과를 넘긴다. 제한 시간 안에 답이 없으면 거부한다 (확인 창을 띄울 앱이
+//! 없는 백그라운드 서비스 마운트에서는 보호 경로의 삭제/이름 변경이 모두 거부됨).
+//!
+//! 탐색기는 폴더를 지울 때 하위 항목마다 따로 삭제를 요청하므로, 한 번 승인하면 같은
+//! 드라이브의 같은 보호 규칙 아래 작업은 잠시 다시 묻지 않는다.
+
+use crate::types::{ProtectedOp, ProtectedRequest};
+use log::{info, warn};
+use parking_lot::{Condvar, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// 사용자 답을 기다리는 시간 (넘으면 거부)
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 승인 후 같은 규칙 아래 작업을 다시 묻지 않는 시간
+const APPROVAL_WINDOW: Duration = Duration::from_secs(30);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 답을 기다리는 요청 (요청 ID, 받은 답)
+static PENDING: Mutex<Vec<(u64, Option<bool>)>> = Mutex::new(Vec::new());
+
+/// 답이 도착하면 기다리는 스레드를 깨움
+static ANSWERED: Condvar = Condvar::new();
+
+/// 아직 앱에 알리지 않은 요청
+static UNREPORTED: Mutex<Vec<ProtectedRequest>> = Mutex::new(Vec::new());
+
+/// 최근 승인 (드라이브 문자, 보호 규칙, 승인 시각)
+static APPROVALS: Mutex<Vec<(char, String, Instant)>> = Mutex::new(Vec::new());
+
+/// 사용자 확인을 받을 때까지 대기 (승인하면 true, 거부하거나 답이 없으면 false)
+pub(crate) fn confirm(
+    drive_letter: char,
+    operation: ProtectedOp,
+    rule: &str,
+    path: &str,
+    target: Option<&str>,
+) -> bool {
+    if is_approved(drive_letter, rule) {
+        return true;
+    }
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let mut pending = PENDING.lock();
+    pending.push((id, None));
+    UNREPORTED.lock().push(ProtectedRequest {
+        id,
+        drive_letter,
+        operation,
+        path: path.to_string(),
+        target: target.map(str::to_string),
+        rule: rule.to_string(),
+        timeout_secs: CONFIRM_TIMEOUT.as_secs(),
+    });
+    info!(
+        "[Protect] {:?} of '{}' on {}: waiting for confirmation",
+        operation, path, drive_letter
+    );
+
+    let deadline = Instant::now() + CONFIRM_TIMEOUT;
+    let approved = loop {
+        let answer = pending
+            .iter()
+            .find(|(pending_id, _)| *pending_id == id)
+            .and_then(|(_, answer)| *answer);
+        if let Some(answer) = answer {
+            break answer;
+        }
+        if ANSWERED.wait_until(&mut pending, deadline).timed_out() {
+            warn!("[Protect] confirmation for '{}' timed out", path);
+            break false;
+        }
+    };
+    pending.retain(|(pending_id, _)| *pending_id != id);
+    drop(pending);
+    UNREPORTED.lock().retain(|request| request.id != id);
+
+    if approved {
+        APPROVALS
+            .lock()
+            .push((drive_letter, rule.to_string(), Instant::now()));
+    }
+    info!(
+        "[Protect] {:?} of '{}' {}",
+        operation,
+        path,
+        if approved { "approved" } else { "denied" }
+    );
+    approved
+}
+
+/// 같은 드라이브의 같은 규칙을 최근에 승인했는지 (만료된 승인은 정리)
+fn is_approved(drive_letter: char, rule: &str) -> bool {
+    let mut approvals = APPROVALS.lock();
+    approvals.retain(|(_, _, at)| at.elapsed() < APPROVAL_WINDOW);
+    approvals
+        .iter()
+        .any(|(letter, approved, _)| *letter == drive_letter && approved == rule)
+}
+
+/// 지난 호출 이후 올라온 확인 요청
+pub fn take_requests() -> Vec<ProtectedRequest> {
+    std::mem::take(&mut *UNREPORTED.lock())
+}
+
+/// 확인 요청에 답함 (이미 제한 시간이 지난 요청이면 오류)
+pub fn answer(id: u64, approved: bool) -> Result<(), String> {
+    let mut pending = PENDING.lock();
+    let slot = pending
+        .iter_mut()
+        .find(|(pending_id, _)| *pending_id == id)
+        .ok_or_else(|| "확인 요청이 이미 만료되어 작업이 거부되었습니다.".to_string())?;
+    slot.1 = Some(approved);
+    ANSWERED.notify_all();
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use keyring::Entry;
+
+const SERVICE_NAME: &str = "ssh-virtual-drive";
+
+/// 연결의 프록시 비밀번호를 저장하는 자격 증명 이름
+pub fn proxy_credential_key(connection_id: &str) -> String {
+    format!("{}:proxy", connection_id)
+}
+
+/// 연결의 TOTP 비밀 키를 저장하는 자격 증명 이름
+pub fn totp_credential_key(connection_id: &str) -> String {
+    format!("{}:totp", connection_id)
+}
+
+/// 연결 프로필 하나에 속한 자격 증명 이름 (비밀번호, 프록시 비밀번호, TOTP 비밀 키)
+pub fn connection_credential_keys(connection_id: &str) -> [String; 3] {
+    [
+        connection_id.to_string(),
+        proxy_credential_key(connection_id),
+        totp_credential_key(connection_id),
+    ]
+}
+
+/// 키 저장소의 보호된 키를 암호화한 비밀 값을 저장하는 자격 증명 이름
+pub fn keystore_credential_key(key_id: &str) -> String {
+    format!("keystore:{}", key_id)
+}
+
+/// 연결 프로필 동기화 파일의 암호를 저장하는 자격 증명 이름
+pub const PROFILE_SYNC_CREDENTIAL_KEY: &str = "profile-sync";
+
+/// Windows Credential Manager에 비밀번호 저장
+pub fn save_password(connection_id: &str, password: &str) -> Result<(), String> {
+    let entry = Entry::new(SERVICE_NAME, connection_id)
+        .map_err(|e| format!("자격 증명 항목 생성 실패: {}", e))?;
+
+    entry
+        .set_password(password)
+        .map_err(|e| format!("비밀번호 저장 실패: {}", e))
+}
+
+/// Windows Credential Manager에서 비밀번호 가져오기
+pub fn get_password(connection_id: &str) -> Result<Option<String>, String> {
+    let entry = Entry::new(SERVICE_NAME, connection_id)
+        .map_err(|e| format!("자격 증명 항목 접근 실패: {}", e))?;
+
+    match entry.get_password() {
+        Ok(password) => Ok(Some(password)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("비밀번호 가져오기 실패: {}", e)),
+    }
+}
+
+/// Windows Credential Manager에서 비밀번호 삭제
+pub fn delete_password(connection_id: &str) -> Result<(), String> {
+    let entry = Entry::new(SERVICE_NAME, connection_id)
+        .map_err(|e| format!("자격 증명 항목 접근 실패: {}", e))?;
+
+    match entry.delete_password() {
+        Ok(_) => Ok(()),
+        Err(keyring::Error::NoEntry) => Ok(()), // 이미 없으면 OK
+        Err(e) => Err(format!("비밀번호 삭제 실패: {}", e)),
+    }
+}
+
+/// 삭제한 연결 프로필들의 자격 증명을 모두 삭제
+///
+/// 한 항목이 실패해도 나머지는 계속 지우고, 실패한 항목을 모아 돌려준다.
+pub fn delete_connection_credentials(connection_ids: &[String]) -> Result<(), String> {
+    let failures: Vec<String> = connection_ids
+        .iter()
+        .flat_map(|id| connection_credential_keys(id))
+        .filter_map(|key| {
+            delete_password(&key)
+                .err()
+                .map(|e| format!("{}: {}", key, e))
+        })
+        .collect();
+    match failures.is_empty() {
+        true => Ok(()),
+        false => Err(failures.join("\n")),
+    }
+}
@@ -0,0 +1,348 @@
+//! 실행 환경 점검 모듈 - 마운트 문제를 진단할 수 있게 Windows 환경을 조사
+//!
+//! 사전 요구사항 확인([`crate::mount::check_prerequisites`])에 함께 담는다. Windows 버전,
+//! 개발자 모드와 심볼릭 링크 권한, WinFsp 런처 서비스 상태, 앱과 WinFsp DLL의 아키텍처,
+//! 설치된 백신을 확인하고, 문제가 될 만한 점은 사용자에게 보여 줄 설명(`hints`)으로 정리한다.
+//! 느린 확인(백신 조회, 링크 생성 시험, 서비스 조회)은 동시에 실행한다.
+//!
+//! WinFsp 런처 서비스가 멈춰 있으면 마운트가 알기 어려운 오류로 실패하므로
+//! [`start_winfsp_launcher`]로 앱에서 바로 시작할 수 있게 한다.
+
+use crate::types::EnvironmentReport;
+use std::ffi::c_void;
+use std::os::windows::process::CommandExt;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use winreg::enums::HKEY_LOCAL_MACHINE;
+use winreg::RegKey;
+
+/// WinFsp가 설치하는 런처 서비스 이름
+pub(crate) const WINFSP_LAUNCHER_SERVICE: &str = "WinFsp.Launcher";
+
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+const SC_MANAGER_CONNECT: u32 = 0x0001;
+const SERVICE_QUERY_STATUS: u32 = 0x0004;
+const SERVICE_START: u32 = 0x0010;
+const SERVICE_RUNNING: u32 = 0x0000_0004;
+const ERROR_ACCESS_DENIED: i32 = 5;
+const ERROR_SERVICE_ALREADY_RUNNING: i32 = 1056;
+
+/// 서비스를 시작한 뒤 실행 상태가 될 때까지 기다리는 최대 시간
+const SERVICE_START_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[repr(C)]
+#[derive(Default)]
+struct ServiceStatus {
+    service_type: u32,
+    current_state: u32,
+    controls_accepted: u32,
+    win32_exit_code: u32,
+    service_specific_exit_code: u32,
+    check_point: u32,
+    wait_hint: u32,
+}
+
+#[link(name = "advapi32")]
+extern "system" {
+    fn OpenSCManagerW(machine: *const u16, database: *const u16, access: u32) -> *mut c_void;
+    fn OpenServiceW(manager: *mut c_void, name: *const u16, access: u32) -> *mut c_void;
+    fn QueryServiceStatus(service: *mut c_void, status: *mut ServiceStatus) -> i32;
+    fn StartServiceW(service: *mut c_void, argc: u32, argv: *const *const u16) -> i32;
+    fn CloseServiceHandle(handle: *mut c_void) -> i32;
+}
+
+/// 실행 환경 점검 (`winfsp_path`는 찾은 WinFsp DLL 경로)
+pub(crate) fn report(winfsp_path: Option<&str>) -> EnvironmentReport {
+    let (antivirus, symlink_allowed, winfsp_launcher_running) = std::thread::scope(|scope| {
+        let antivirus = scope.spawn(antivirus_products);
+        let symlink = scope.spawn(can_create_symlink);
+        let launcher = scope.spawn(winfsp_launcher_running);
+        (
+            antivirus.join().unwrap_or_default(),
+            symlink.join().unwrap_or(false),
+            launcher.join().unwrap_or(None),
+        )
+    });
+    let (windows_version, windows_build, windows_edition) = windows_version();
+    let app_arch = app_arch().to_string();
+    let winfsp_arch = winfsp_path.and_then(dll_arch).map(str::to_string);
+    let arch_mismatch = winfsp_arch.as_ref().is_some_and(|arch| *arch != app_arch);
+
+    let mut report = EnvironmentReport {
+        windows_version,
+        windows_build,
+        windows_edition,
+        developer_mode: developer_mode(),
+        symlink_allowed,
+        winfsp_launcher_running,
+        app_arch,
+        winfsp_arch,
+        arch_mismatch,
+        antivirus,
+        hints: Vec::new(),
+    };
+    report.hints = hints(&report, winfsp_path.is_some());
+    report
+}
+
+/// 확인 결과에서 사용자에게 알릴 점
+fn hints(report: &EnvironmentReport, winfsp_installed: bool) -> Vec<String> {
+    let mut hints = Vec::new();
+    if winfsp_installed {
+        match report.winfsp_launcher_running {
+            Some(true) => {}
+            Some(false) => hints.push(format!(
+                "WinFsp 런처 서비스({})가 실행 중이 아닙니다. 마운트가 실패하면 서비스를 시작해주세요.",
+                WINFSP_LAUNCHER_SERVICE
+            )),
+            None => hints.push(
+                "WinFsp 런처 서비스를 찾을 수 없습니다. WinFsp를 다시 설치해주세요.".to_string(),
+            ),
+        }
+    }
+    if let (true, Some(winfsp_arch)) = (report.arch_mismatch, &report.winfsp_arch) {
+        hints.push(format!(
+            "앱은 {}용인데 WinFsp {} DLL만 찾았습니다. 앱과 같은 아키텍처를 포함한 WinFsp를 설치해주세요.",
+            report.app_arch, winfsp_arch
+        ));
+    }
+    if !report.symlink_allowed {
+        hints.push(
+            "심볼릭 링크를 만들 권한이 없습니다. 서버의 링크를 Windows 링크로 보이게 하려면 개발자 모드를 켜주세요."
+                .to_string(),
+        );
+    }
+    let third_party: Vec<&str> = report
+        .antivirus
+        .iter()
+        .map(String::as_str)
+        .filter(|name| !name.contains("Windows Defender") && !name.contains("Microsoft Defender"))
+        .collect();
+    if !third_party.is_empty() {
+        hints.push(format!(
+            "{}의 실시간 검사가 드라이브의 파일을 열 때마다 내용을 읽어 느려지거나 파일 작업이 막힐 수 있습니다. 문제가 있으면 드라이브 문자를 검사 예외에 추가해주세요.",
+            third_party.join(", ")
+        ));
+    }
+    hints
+}
+
+/// Windows 버전 (`10.0.22631.4037`), 빌드 번호, 이름 (`Windows 11 Pro 23H2`)
+fn windows_version() -> (Option<String>, Option<u32>, Option<String>) {
+    let Ok(key) = RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(r"SOFTWARE\Microsoft\Windows NT\CurrentVersion")
+    else {
+        return (None, None, None);
+    };
+    let major = key.get_value::<u32, _>("CurrentMajorVersionNumber").ok();
+    let minor = key.get_value::<u32, _>("CurrentMinorVersionNumber").ok();
+    let build = key
+        .get_value::<String, _>("CurrentBuildNumber")
+        .ok()
+        .and_then(|build| build.parse::<u32>().ok());
+    let revision = key.get_value::<u32, _>("UBR").unwrap_or(0);
+    let version = match (major, minor, build) {
+        (Some(major), Some(minor), Some(build)) => {
+            Some(format!("{}.{}.{}.{}", major, minor, build, revision))
+        }
+        _ => None,
+    };
+
+    // Windows 11도 제품 이름은 "Windows 10"으로 기록되어 있어 빌드 번호로 구분
+    let edition = key.get_value::<String, _>("ProductName").ok().map(|name| {
+        let name = match build {
+            Some(build) if build >= 22000 => name.replacen("Windows 10", "Windows 11", 1),
+            _ => name,
+        };
+        match key.get_value::<String, _>("DisplayVersion") {
+            Ok(display) => format!("{} {}", name, display),
+            Err(_) => name,
+        }
+    });
+    (version, build, edition)
+}
+
+/// 개발자 모드 (관리자 권한 없이 심볼릭 링크를 만들 수 있음)
+fn developer_mode() -> bool {
+    RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(r"SOFTWARE\Microsoft\Windows\CurrentVersion\AppModelUnlock")
+        .and_then(|key| key.get_value::<u32, _>("AllowDevelopmentWithoutDevLicense"))
+        .is_ok_and(|value| value == 1)
+}
+
+/// 임시 폴더에 링크를 만들어 봐서 이 프로세스가 심볼릭 링크를 만들 수 있는지 확인
+fn can_create_symlink() -> bool {
+    let link = std::env::temp_dir().join(format!("svd-symlink-check-{}", std::process::id()));
+    let _ = std::fs::remove_file(&link);
+    let created = std::os::windows::fs::symlink_file("svd-symlink-target", &link).is_ok();
+    if created {
+        let _ = std::fs::remove_file(&link);
+    }
+    created
+}
+
+/// WinFsp 런처 서비스 실행 여부 (서비스가 없으면 None)
+pub(crate) fn winfsp_launcher_running() -> Option<bool> {
+    let name: Vec<u16> = WINFSP_LAUNCHER_SERVICE
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    unsafe {
+        let manager = OpenSCManagerW(std::ptr::null(), std::ptr::null(), SC_MANAGER_CONNECT);
+        if manager.is_null() {
+            return None;
+        }
+        let service = OpenServiceW(manager, name.as_ptr(), SERVICE_QUERY_STATUS);
+        let running = if service.is_null() {
+            None
+        } else {
+            let mut status = ServiceStatus::default();
+            let ok = QueryServiceStatus(service, &mut status) != 0;
+            CloseServiceHandle(service);
+            ok.then_some(status.current_state == SERVICE_RUNNING)
+        };
+        CloseServiceHandle(manager);
+        running
+    }
+}
+
+/// WinFsp 런처 서비스 시작 (권한이 없으면 UAC로 관리자 권한을 요청해 `sc.exe`로 시작)
+pub(crate) fn start_winfsp_launcher() -> Result<(), String> {
+    match winfsp_launcher_running() {
+        None => {
+            return Err(
+                "WinFsp 런처 서비스를 찾을 수 없습니다. WinFsp를 다시 설치해주세요.".to_string(),
+            )
+        }
+        Some(true) => return Ok(()),
+        Some(false) => {}
+    }
+    match start_service_directly() {
+        Ok(()) => {}
+        Err(e) if e.raw_os_error() == Some(ERROR_ACCESS_DENIED) => start_service_elevated()?,
+        Err(e) => return Err(format!("WinFsp 런처 서비스 시작 실패: {}", e)),
+    }
+
+    let started = Instant::now();
+    while started.elapsed() < SERVICE_START_TIMEOUT {
+        if winfsp_launcher_running() == Some(true) {
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_millis(300));
+    }
+    Err(
+        "WinFsp 런처 서비스가 시작되지 않았습니다. 이벤트 뷰어의 시스템 로그를 확인해주세요."
+            .to_string(),
+    )
+}
+
+/// 현재 권한으로 서비스 시작 (관리자가 아니면 보통 ERROR_ACCESS_DENIED)
+fn start_service_directly() -> std::io::Result<()> {
+    let name: Vec<u16> = WINFSP_LAUNCHER_SERVICE
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    unsafe {
+        let manager = OpenSCManagerW(std::ptr::null(), std::ptr::null(), SC_MANAGER_CONNECT);
+        if manager.is_null() {
+            return Err(std::io::Error::last_os_error());
+        }
+        let service = OpenServiceW(manager, name.as_ptr(), SERVICE_START | SERVICE_QUERY_STATUS);
+        let result = if service.is_null() {
+            Err(std::io::Error::last_os_error())
+        } else {
+            let started = StartServiceW(service, 0, std::ptr::null()) != 0;
+            let error = std::io::Error::last_os_error();
+            CloseServiceHandle(service);
+            match started || error.raw_os_error() == Some(ERROR_SERVICE_ALREADY_RUNNING) {
+                true => Ok(()),
+                false => Err(error),
+            }
+        };
+        CloseServiceHandle(manager);
+        result
+    }
+}
+
+/// UAC로 관리자 권한을 요청해 서비스 시작 (사용자가 거절하면 오류)
+fn start_service_elevated() -> Result<(), String> {
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command"])
+        .arg(format!(
+            "$p = Start-Process -FilePath sc.exe -ArgumentList 'start','{}' -Verb RunAs \
+             -WindowStyle Hidden -Wait -PassThru -ErrorAction Stop; exit $p.ExitCode",
+            WINFSP_LAUNCHER_SERVICE
+        ))
+        .stdin(Stdio::null())
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| format!("PowerShell 실행 실패: {}", e))?;
+    // sc.exe는 이미 실행 중이면 1056으로 끝남
+    match output.status.code() {
+        Some(0) | Some(ERROR_SERVICE_ALREADY_RUNNING) => Ok(()),
+        code => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let detail = match stderr.trim() {
+                "" => format!("종료 코드 {}", code.unwrap_or(-1)),
+                message => message.to_string(),
+            };
+            Err(format!(
+                "관리자 권한으로 WinFsp 런처 서비스를 시작하지 못했습니다: {}",
+                detail
+            ))
+        }
+    }
+}
+
+/// 보안 센터에 등록된 백신 이름 (서버 Windows처럼 보안 센터가 없으면 빈 목록)
+fn antivirus_products() -> Vec<String> {
+    let output = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "[Console]::OutputEncoding = [Text.Encoding]::UTF8; \
+             Get-CimInstance -Namespace root/SecurityCenter2 -ClassName AntiVirusProduct \
+             -ErrorAction SilentlyContinue | ForEach-Object { $_.displayName }",
+        ])
+        .stdin(Stdio::null())
+        .creation_flags(CREATE_NO_WINDOW)
+        .output();
+    let Ok(output) = output else {
+        return Vec::new();
+    };
+    let mut products: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+    products.dedup();
+    products
+}
+
+/// 앱 실행 파일의 아키텍처
+fn app_arch() -> &'static str {
+    if cfg!(target_arch = "aarch64") {
+        "arm64"
+    } else if cfg!(target_arch = "x86") {
+        "x86"
+    } else {
+        "x64"
+    }
+}
+
+/// WinFsp DLL 파일 이름의 아키텍처 (`winfsp-x64.dll` 등)
+fn dll_arch(path: &str) -> Option<&'static str> {
+    let name = Path::new(path)
+        .file_name()?
+        .to_string_lossy()
+        .to_lowercase();
+    match name.as_str() {
+        "winfsp-x64.dll" => Some("x64"),
+        "winfsp-x86.dll" => Some("x86"),
+        "winfsp-a64.dll" => Some("arm64"),
+        _ => None,
+    }
+}
@@ -0,0 +1,105 @@
+//! 탐색기 표시 모듈 - 마운트한 드라이브의 아이콘과 이름을 "내 PC"에 등록
+//!
+//! 탐색기는 사용자별 `Software\Classes\Applications\Explorer.exe\Drives\<문자>` 키의
+//! `DefaultIcon`/`DefaultLabel` 기본값으로 드라이브 아이콘과 이름을 정한다. 마운트할 때
+//! 연결의 아이콘과 이름을 기록하고 언마운트할 때 지워, 여러 서버를 마운트해도 "내 PC"에서
+//! 구분할 수 있게 한다. 관리자 권한 없이 현재 사용자에게만 적용된다.
+
+use crate::types::SshConnection;
+use log::{debug, warn};
+use std::ffi::c_void;
+use winreg::enums::{HKEY_CURRENT_USER, KEY_ALL_ACCESS};
+use winreg::RegKey;
+
+/// 드라이브별 표시 정보를 두는 키
+const DRIVES_KEY: &str = r"Software\Classes\Applications\Explorer.exe\Drives";
+
+const SHCNE_ASSOCCHANGED: i32 = 0x0800_0000;
+const SHCNF_IDLIST: u32 = 0x0000;
+
+#[link(name = "shell32")]
+extern "system" {
+    fn SHChangeNotify(event_id: i32, flags: u32, item1: *const c_void, item2: *const c_void);
+}
+
+/// 드라이브 아이콘과 이름 등록 (실패해도 마운트는 계속)
+///
+/// 이름은 연결의 `drive_label`, 없으면 연결 이름을 쓴다. 아이콘은 `drive_icon`을 지정한
+/// 경우에만 바꾼다.
+pub(crate) fn register_drive(drive_letter: char, connection: &SshConnection) {
+    // 이전 실행이 비정상 종료하며 남긴 값을 지우고 새로 기록
+    unregister_quietly(drive_letter);
+    let label = connection
+        .drive_label
+        .as_deref()
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .unwrap_or(&connection.name);
+    let icon = connection
+        .drive_icon
+        .as_deref()
+        .map(str::trim)
+        .filter(|icon| !icon.is_empty());
+
+    let result = (|| -> std::io::Result<()> {
+        let (drive, _) = RegKey::predef(HKEY_CURRENT_USER)
+            .create_subkey(format!(r"{}\{}", DRIVES_KEY, drive_letter))?;
+        let (key, _) = drive.create_subkey("DefaultLabel")?;
+        key.set_value("", &label)?;
+        if let Some(icon) = icon {
+            let (key, _) = drive.create_subkey("DefaultIcon")?;
+            key.set_value("", &icon)?;
+        }
+        Ok(())
+    })();
+    match result {
+        Ok(()) => {
+            debug!("[Explorer] {}: registered as '{}'", drive_letter, label);
+            notify_shell();
+        }
+        Err(e) => warn!(
+            "[Explorer] registering {}: icon and label failed: {}",
+            drive_letter, e
+        ),
+    }
+}
+
+/// 드라이브 아이콘과 이름 등록 해제 (언마운트할 때)
+pub(crate) fn unregister_drive(drive_letter: char) {
+    if unregister_quietly(drive_letter) {
+        notify_shell();
+    }
+}
+
+/// 등록된 값을 지움 (지운 값이 있으면 true)
+fn unregister_quietly(drive_letter: char) -> bool {
+    let Ok(drives) =
+        RegKey::predef(HKEY_CURRENT_USER).open_subkey_with_flags(DRIVES_KEY, KEY_ALL_ACCESS)
+    else {
+        return false;
+    };
+    let name = drive_letter.to_string();
+    if drives.open_subkey(&name).is_err() {
+        return false;
+    }
+    if let Err(e) = drives.delete_subkey_all(&name) {
+        warn!(
+            "[Explorer] removing {}: icon and label failed: {}",
+            drive_letter, e
+        );
+        return false;
+    }
+    true
+}
+
+/// 탐색기가 드라이브 아이콘과 이름을 다시 읽게 알림
+fn notify_shell() {
+    unsafe {
+        SHChangeNotify(
+            SHCNE_ASSOCCHANGED,
+            SHCNF_IDLIST,
+            std::ptr::null(),
+            std::ptr::null(),
+        )
+    };
+}
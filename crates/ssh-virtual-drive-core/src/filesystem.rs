@@ -0,0 +1,2466 @@
+//! WinFsp 파일시스템 구현 - SFTP를 가상 드라이브로 마운트
+//! winfsp-rs 0.12 API 사용
+
+use crate::cache::{moved_path, parent_path, StatCache};
+use crate::confirm;
+use crate::handles::{self, HandleIds};
+use crate::listing::{DirPage, FirstPage, Listings};
+use crate::offline::OfflineStore;
+use crate::path_filter::{AccessRules, PathFilter, ProtectedPaths};
+use crate::process::{current_caller, Caller};
+use crate::profile::{self, MountTuning};
+use crate::quota::QuotaTracker;
+use crate::sftp_client::{self as sftp, CancelSource, SharedSftpClient, EXEC_UNSUPPORTED};
+use crate::statbatch::StatBatcher;
+use crate::stats::StatsCollector;
+use crate::streams::{StreamPool, DEFAULT_INFLIGHT_LIMIT_MB};
+use crate::throttle::{PreviewThrottle, PREVIEW_HEADER_BYTES};
+use crate::types::{CreationTimePolicy, FsOp, ProtectedOp, SshConnection, SymlinkPolicy};
+use crate::versions;
+use crate::writeback::{ChangeQueue, DirtyFile, WriteJournal};
+use crate::xattr;
+use log::{debug, info, warn};
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+use winfsp::filesystem::{
+    DirInfo, DirMarker, FileInfo, FileSecurity, FileSystemContext, OpenFileInfo, WideNameInfo,
+};
+use winfsp::host::{FileSystemHost, VolumeParams};
+use winfsp::U16CStr;
+
+#[cfg(debug_assertions)]
+macro_rules! winfsp_debug {
+    ($($arg:tt)*) => (debug!($($arg)*))
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! winfsp_debug {
+    ($($arg:tt)*) => {{}};
+}
+
+/// Cleanup 플래그: 삭제 완료 (FspCleanupDelete)
+const CLEANUP_DELETE: u32 = 0x01;
+
+/// CreateFile 옵션: 디렉토리 생성
+const FILE_DIRECTORY_FILE: u32 = 0x0000_0001;
+
+const FILE_ATTRIBUTE_DIRECTORY: u32 = 0x0000_0010;
+const FILE_ATTRIBUTE_NORMAL: u32 = 0x0000_0080;
+const FILE_ATTRIBUTE_OFFLINE: u32 = 0x0000_1000;
+const FILE_ATTRIBUTE_NOT_CONTENT_INDEXED: u32 = 0x0000_2000;
+const FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS: u32 = 0x0040_0000;
+
+/// 심볼릭 링크 reparse 태그
+const IO_REPARSE_TAG_SYMLINK: u32 = 0xA000_000C;
+
+/// 심볼릭 링크 reparse 플래그: 상대 경로 대상
+const SYMLINK_FLAG_RELATIVE: u32 = 0x0000_0001;
+
+const STATUS_ACCESS_DENIED: i32 = 0xC000_0022_u32 as i32;
+const STATUS_CANCELLED: i32 = 0xC000_0120_u32 as i32;
+const STATUS_DISK_FULL: i32 = 0xC000_007F_u32 as i32;
+/// 일시 중지된 드라이브의 데이터 작업 (ERROR_NOT_READY로 보임)
+const STATUS_DEVICE_NOT_READY: i32 = 0xC000_00A3_u32 as i32;
+/// 쓰기 권한 (FILE_WRITE_DATA | FILE_APPEND_DATA | GENERIC_WRITE | GENERIC_ALL)
+const WRITE_ACCESS_MASK: u32 = 0x0000_0002 | 0x0000_0004 | 0x4000_0000 | 0x1000_0000;
+/// 읽기 권한 (FILE_READ_DATA | GENERIC_READ | GENERIC_ALL)
+const READ_ACCESS_MASK: u32 = 0x0000_0001 | 0x8000_0000 | 0x1000_0000;
+
+/// 재연결 전에 열린 핸들을 다시 열 수 없음 (오래된 핸들, 애플리케이션이 다시 열어야 함)
+const STATUS_FILE_INVALID: i32 = 0xC000_0098_u32 as i32;
+const STATUS_DIRECTORY_NOT_EMPTY: i32 = 0xC000_0101_u32 as i32;
+const STATUS_INVALID_PARAMETER: i32 = 0xC000_000D_u32 as i32;
+const STATUS_IO_REPARSE_TAG_NOT_HANDLED: i32 = 0xC000_0279_u32 as i32;
+
+/// 서버가 시간을 주지 않을 때 쓰는 고정 시간 (2024-01-01)
+const DEFAULT_FILE_TIME: u64 = 1704067200;
+
+/// 볼륨 용량 캐시 TTL (초) - Explorer가 자주 조회하므로 statvfs 호출을 줄임
+const VOLUME_INFO_TTL_SECS: u64 = 30;
+
+/// statvfs를 지원하지 않는 서버의 (전체, 여유) 용량 - 100GB, 50GB (가상)
+const VIRTUAL_VOLUME_SIZE: (u64, u64) = (1024 * 1024 * 1024 * 100, 1024 * 1024 * 1024 * 50);
+
+/// 일시 중지된 드라이브에서 캐시에 없는 항목을 찾을 때의 오류
+pub const DRIVE_PAUSED: &str = "드라이브가 일시 중지되어 서버에 연결하지 않습니다.";
+
+/// 임시 올리기 파일 이름의 표시 (`.이름.svd-upload-xxxxxxxx`)
+const STAGING_MARKER: &str = ".svd-upload-";
+
+/// 이보다 오래된 임시 올리기 파일은 끊긴 올리기가 남긴 것으로 보고 지움
+const STALE_STAGING_SECS: u64 = 24 * 60 * 60;
+
+/// 변경 대기 모드에서 서버에 바로 반영되는 변경을 거부할 때의 이유
+pub const QUEUE_MODE_METADATA: &str =
+    "변경 대기 모드는 기존 파일의 내용만 모아 두므로 만들기, 삭제, 이름 변경, 시간/속성 변경은 할 수 없습니다.";
+
+/// 서버 연결이 끊긴 동안 오프라인 고정 항목이 아닌 경로를 찾을 때의 오류
+pub const DRIVE_OFFLINE: &str = "서버에 연결할 수 없고 오프라인 사용으로 고정한 항목도 아닙니다.";
+
+/// 파일 컨텍스트 - 열린 파일/디렉토리 정보
+pub struct SftpFileContext {
+    pub path: String,
+    pub is_directory: bool,
+    // 아직 원격에 반영되지 않은 쓰기
+    pub dirty: Option<DirtyFile>,
+    // 지금까지 읽은 가장 먼 위치 (미리보기 헤더 읽기 판별용)
+    pub read_end: u64,
+    // 미리보기 제한 판정 결과 (첫 헤더 읽기 때 결정)
+    pub preview_admitted: Option<bool>,
+    // 미반영 쓰기를 올린 뒤 적용할 (atime, mtime) - 서버 기준 Unix 시간
+    pub pending_times: Option<(u64, u64)>,
+    // 열 때 허용된 접근 권한 (재연결 후 다시 열 때 쓰기 가능 여부 확인)
+    pub granted_access: u32,
+    // 마지막 읽기/쓰기가 끝난 위치
+    pub position: u64,
+    // 이 핸들이 유효한 세션 세대 (재연결 후 다시 열면 갱신)
+    pub session: u32,
+    // cleanup 때 진행 중인 읽기 취소
+    pub cancel: CancelSource,
+    // 이 핸들로 덮어쓰기 전에 이전 버전을 남겼음 (여러 번 flush해도 한 번만)
+    pub versioned: bool,
+    // 애플리케이션이 미리 잡아 둔 할당 크기 (파일 크기보다 크면 이 값으로 보고)
+    pub allocation_size: u64,
+    // 임시 이름으로 올리는 중이면 닫을 때 옮길 원래 경로 (path는 임시 경로)
+    pub final_path: Option<String>,
+    // 이 핸들을 연 프로세스 (WinFsp는 열 때만 호출 PID를 알려 줌)
+    pub caller: Caller,
+}
+
+impl SftpFileContext {
+    pub fn new(
+        path: String,
+        is_directory: bool,
+        granted_access: u32,
+        session: u32,
+        caller: Caller,
+    ) -> Self {
+        Self {
+            path,
+            is_directory,
+            dirty: None,
+            read_end: 0,
+            preview_admitted: None,
+            pending_times: None,
+            granted_access,
+            position: 0,
+            session,
+            cancel: CancelSource::default(),
+            versioned: false,
+            allocation_size: 0,
+            final_path: None,
+            caller,
+        }
+    }
+}
+
+/// SFTP 파일시스템 구현
+pub struct SftpFileSystem {
+    client: SharedSftpClient,
+    // 큰 읽기/쓰기용 병렬 세션 묶음 (주 세션은 client와 동일)
+    streams: StreamPool,
+    remote_root: String,
+    drive_letter: char,
+    // 심볼릭 링크 생성 정책
+    symlink_policy: SymlinkPolicy,
+    // 열린 파일 핸들 매핑
+    open_files: RwLock<HashMap<u64, SftpFileContext>>,
+    // 세션 세대가 붙은 핸들 발급기 (재연결 전 핸들 구별)
+    handle_ids: HandleIds,
+    // stat/readdir 캐시 (언마운트 시 스냅샷 저장을 위해 마운트 정보와 공유)
+    cache: Arc<StatCache>,
+    // 형제 항목 stat 폭주를 디렉토리 읽기 한 번으로 묶음
+    stat_batcher: StatBatcher,
+    // 마운트 프로필에서 정해진 캐시/미리 읽기 설정
+    tuning: MountTuning,
+    // 하위 디렉토리 미리 읽기 진행 중 (한 번에 하나만)
+    prefetching: Arc<AtomicBool>,
+    // 백그라운드에서 이어 읽는 큰 디렉토리 목록
+    listings: Listings,
+    // 미반영 쓰기 저널 (생성 실패 시 메모리 버퍼만 사용)
+    journal: Option<WriteJournal>,
+    // 마운트 통계 (업로드 진행 상황 등)
+    stats: Arc<StatsCollector>,
+    // 탐색기에 보이지 않게 할 원격 항목
+    exclude: PathFilter,
+    // 경로별 읽기 전용/숨김 규칙
+    access: AccessRules,
+    // 삭제/이름 변경 전에 사용자 확인을 받을 경로
+    protected: ProtectedPaths,
+    // 덮어쓰기 전에 남길 파일별 이전 버전 수 (None이면 버전 보관 안 함)
+    keep_versions: Option<u32>,
+    // 소프트/서버 쿼터 (설정하지 않으면 None)
+    quota: Option<QuotaTracker>,
+    // 미리보기 헤더 읽기 제한 (metadata_friendly 모드에서만 사용)
+    preview_throttle: Option<PreviewThrottle>,
+    // statvfs 결과 캐시 (조회 시각, (전체, 여유))
+    volume_cache: Mutex<Option<(Instant, (u64, u64))>>,
+    // 서버가 보고하는 시간에 더할 보정값 (초)
+    time_offset_secs: i64,
+    // 생성 시간 합성 방식
+    creation_time_policy: CreationTimePolicy,
+    // 읽기를 거부할 프로세스 이름 (소문자)
+    blocked_processes: Vec<String>,
+    // 색인/백신 검사 제외 힌트 속성 사용
+    indexing_opt_out: bool,
+    // 새 파일을 임시 이름으로 올리고 닫을 때 제 이름으로 바꿈
+    atomic_uploads: bool,
+}
+
+impl SftpFileSystem {
+    pub fn new(
+        client: SharedSftpClient,
+        connection: &SshConnection,
+        drive_letter: char,
+        stats: Arc<StatsCollector>,
+        cache: Arc<StatCache>,
+        journal: Option<WriteJournal>,
+        tuning: MountTuning,
+    ) -> Self {
+        Self {
+            streams: StreamPool::new(client.clone(), drive_letter, connection.transfer_streams, {
+                let configured = connection
+                    .inflight_limit_mb
+                    .unwrap_or(DEFAULT_INFLIGHT_LIMIT_MB);
+                tuning
+                    .inflight_limit_mb
+                    .map_or(configured, |cap| cap.min(configured))
+            }),
+            client: client.clone(),
+            remote_root: connection.remote_path.clone(),
+            drive_letter,
+            symlink_policy: connection.symlink_policy,
+            open_files: RwLock::new(HashMap::new()),
+            handle_ids: HandleIds::default(),
+            cache,
+            journal,
+            stat_batcher: StatBatcher::new(stats.clone()),
+            tuning,
+            prefetching: Arc::new(AtomicBool::new(false)),
+            listings: Listings::default(),
+            stats,
+            exclude: PathFilter::new(&connection.exclude_patterns),
+            access: AccessRules::new(&connection.access_rules),
+            protected: ProtectedPaths::new(&connection.protected_paths),
+            keep_versions: connection.keep_versions,
+            quota: QuotaTracker::new(
+                connection.soft_quota_bytes,
+                connection.server_quota,
+                &connection.remote_path,
+                client,
+            ),
+            preview_throttle: connection.metadata_friendly.then(PreviewThrottle::default),
+            volume_cache: Mutex::new(None),
+            time_offset_secs: i64::from(connection.time_offset_minutes) * 60,
+            creation_time_policy: connection.creation_time_policy,
+            blocked_processes: connection
+                .blocked_processes
+                .iter()
+                .map(|name| name.trim().to_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect(),
+            indexing_opt_out: connection.indexing_opt_out,
+            // 변경 대기 모드는 원래 경로로 쓰기를 모으므로 함께 쓰지 않음
+            atomic_uploads: connection.atomic_uploads && !connection.queue_writes,
+        }
+    }
+
+    /// 핸들의 미반영 쓰기를 원격에 반영
+    fn flush_handle(&self, handle: u64) -> Result<(), String> {
+        let (path, dirty, versioned) = {
+            let mut files = self.open_files.write();
+            match files.get_mut(&handle) {
+                Some(context) => {
+                    let versioned = context.versioned;
+                    context.versioned |= context.dirty.is_some();
+                    (context.path.clone(), context.dirty.take(), versioned)
+                }
+                None => return Ok(()),
+            }
+        }; // open_files lock 해제
+
+        let Some(dirty) = dirty else {
+            return Ok(());
+        };
+
+        // 변경 대기 모드는 올리지 않고 대기열에 보관 (사용자가 올리기 전까지 서버는 그대로)
+        if let Some(queue) = self.queue() {
+            if dirty.buffer.is_empty() {
+                dirty.discard();
+            } else {
+                queue.put(&path, dirty);
+                self.stats.finish_upload(&path);
+            }
+            return Ok(());
+        }
+
+        if !versioned {
+            self.save_version(&path);
+        }
+        let bytes = dirty.buffer.dirty_bytes() as u64;
+        let t0 = Instant::now();
+        match dirty.flush(&self.streams, &path, &self.stats) {
+            Ok(()) => {
+                self.stats.record_op(
+                    &self.caller_of(handle),
+                    &path,
+                    FsOp::Flush,
+                    bytes,
+                    t0.elapsed(),
+                );
+                // 업로드가 mtime을 덮어쓰므로 요청받은 시간은 업로드 뒤에 적용
+                let times = self
+                    .open_files
+                    .write()
+                    .get_mut(&handle)
+                    .and_then(|c| c.pending_times.take());
+                if let Some((atime, mtime)) = times {
+                    if let Err(e) = self.streams.metadata().set_times(&path, atime, mtime) {
+                        warn!("[WinFsp] setting times on '{}' failed: {}", path, e);
+                    }
+                }
+                self.cache.invalidate(&path);
+                winfsp_debug!("[WinFsp] flushed pending writes for '{}'", path);
+                Ok(())
+            }
+            Err((dirty, e)) => {
+                self.stats.record_error(&e);
+                // 실패한 버퍼는 다시 돌려놓아 다음 flush에서 재시도
+                if let Some(context) = self.open_files.write().get_mut(&handle) {
+                    context.dirty = Some(*dirty);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// 임시 이름으로 올린 파일을 원래 경로로 옮김 (미반영 쓰기가 모두 올라간 뒤에만)
+    fn commit_staged(&self, handle: u64) -> Result<(), String> {
+        let (temp, target) = {
+            let files = self.open_files.read();
+            match files.get(&handle) {
+                Some(context) if context.dirty.is_none() => match &context.final_path {
+                    Some(target) => (context.path.clone(), target.clone()),
+                    None => return Ok(()),
+                },
+                _ => return Ok(()),
+            }
+        }; // open_files lock 해제
+
+        let target_exists = self.stat_with_cache(&target).is_ok();
+        if target_exists {
+            self.save_version(&target);
+        }
+        let t0 = Instant::now();
+        let moved = self.streams.metadata().rename(&temp, &target);
+        self.cache.invalidate(&temp);
+        if let Err(e) = moved {
+            // 옮기지 못한 임시 파일은 보이지 않는 채로 남으므로 지움
+            if let Err(remove) = self.streams.metadata().remove_file(&temp) {
+                warn!(
+                    "[WinFsp] removing staged upload '{}' failed: {}",
+                    temp, remove
+                );
+            }
+            if let Some(context) = self.open_files.write().get_mut(&handle) {
+                context.final_path = None;
+            }
+            return Err(e);
+        }
+        self.stats.record_op(
+            &self.caller_of(handle),
+            &target,
+            FsOp::Rename,
+            0,
+            t0.elapsed(),
+        );
+        self.cache.invalidate(&target);
+        // 같은 임시 파일을 연 다른 핸들도 새 경로를 따라감
+        for context in self.open_files.write().values_mut() {
+            if context.path == temp {
+                context.path = target.clone();
+            }
+        }
+        if let Some(context) = self.open_files.write().get_mut(&handle) {
+            context.final_path = None;
+        }
+        info!("[WinFsp] staged upload moved into place: '{}'", target);
+        Ok(())
+    }
+
+    /// 임시 이름으로 올리는 중인 파일의 원래 경로면 임시 경로로 바꿈 (닫기 전에도 제 이름으로 열림)
+    fn resolve_staged(&self, remote_path: String) -> String {
+        if !self.atomic_uploads {
+            return remote_path;
+        }
+        self.open_files
+            .read()
+            .values()
+            .find(|context| context.final_path.as_deref() == Some(remote_path.as_str()))
+            .map_or(remote_path, |context| context.path.clone())
+    }
+
+    /// 목록에 보인 오래된 임시 올리기 파일 정리 (끊겨서 옮기지 못하고 남은 파일)
+    fn sweep_staged(&self, dir: &str, entries: &[(String, ssh2::FileStat)]) {
+        if !self.atomic_uploads || self.is_read_only(dir) || self.queue().is_some() {
+            return;
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let stale: Vec<String> = entries
+            .iter()
+            .filter(|(name, stat)| {
+                is_staging_name(name)
+                    && stat
+                        .mtime
+                        .is_some_and(|mtime| now.saturating_sub(mtime) > STALE_STAGING_SECS)
+            })
+            .map(|(name, _)| format!("{}/{}", dir.trim_end_matches('/'), name))
+            .filter(|path| {
+                !self
+                    .open_files
+                    .read()
+                    .values()
+                    .any(|context| &context.path == path)
+            })
+            .collect();
+        for path in stale {
+            match self.streams.metadata().remove_file(&path) {
+                Ok(()) => info!("[WinFsp] removed stale staged upload '{}'", path),
+                Err(e) => warn!(
+                    "[WinFsp] removing stale staged upload '{}' failed: {}",
+                    path, e
+                ),
+            }
+            self.cache.invalidate(&path);
+        }
+    }
+
+    /// 삭제 표시된 핸들의 원격 파일/디렉토리 삭제 (미반영 쓰기는 버림)
+    fn delete_handle(&self, handle: u64) {
+        let Some((path, is_dir, dirty)) = self
+            .open_files
+            .write()
+            .get_mut(&handle)
+            .map(|c| (c.path.clone(), c.is_directory, c.dirty.take()))
+        else {
+            return;
+        };
+        if let Some(dirty) = dirty {
+            dirty.discard();
+            self.stats.finish_upload(&path);
+        }
+        // FILE_DELETE_ON_CLOSE는 set_delete를 거치지 않으므로 여기서도 규칙 확인
+        if self.is_read_only(&path) {
+            warn!("[WinFsp] delete of '{}' blocked by access rule", path);
+            return;
+        }
+        if self.cache.is_paused() {
+            warn!("[WinFsp] delete of '{}' skipped: drive paused", path);
+            return;
+        }
+        if self.check_not_queued("delete", &path).is_err() {
+            return;
+        }
+        // set_delete에서 승인했으면 같은 규칙의 승인이 남아 있어 다시 묻지 않음
+        if self
+            .confirm_protected(ProtectedOp::Delete, &path, None)
+            .is_err()
+        {
+            warn!("[WinFsp] delete of '{}' not confirmed", path);
+            return;
+        }
+
+        // 쿼터 사용량에서 뺄 크기 (삭제 전에 확인)
+        let freed = match &self.quota {
+            Some(_) if !is_dir => self.stat_with_cache(&path).ok().and_then(|s| s.size),
+            _ => None,
+        };
+        let t0 = Instant::now();
+        let result = {
+            let client = self.streams.metadata();
+            if is_dir {
+                client.remove_dir(&path)
+            } else {
+                client.remove_file(&path)
+            }
+        };
+        match result {
+            Ok(()) => {
+                self.stats.record_op(
+                    &self.caller_of(handle),
+                    &path,
+                    FsOp::Delete,
+                    0,
+                    t0.elapsed(),
+                );
+                if let (Some(quota), Some(freed)) = (&self.quota, freed) {
+                    quota.release(freed);
+                }
+                winfsp_debug!("[WinFsp] deleted '{}'", path)
+            }
+            Err(e) => warn!("[WinFsp] delete of '{}' failed: {}", path, e),
+        }
+        self.cache.invalidate(&path);
+    }
+
+    /// 볼륨 전체/여유 용량 (statvfs 지원 시 실제 값, 아니면 가상 값)
+    fn volume_size(&self) -> (u64, u64) {
+        {
+            let cached = self.volume_cache.lock();
+            if let Some((at, size)) = *cached {
+                if at.elapsed().as_secs() < VOLUME_INFO_TTL_SECS || self.cache.is_paused() {
+                    return size;
+                }
+            }
+        }
+        if self.cache.is_paused() {
+            return VIRTUAL_VOLUME_SIZE;
+        }
+
+        let client = self.streams.metadata();
+        let (total, free) = client
+            .statvfs(&self.remote_root)
+            .unwrap_or(VIRTUAL_VOLUME_SIZE);
+        // 쿼터가 있으면 한도를 전체 용량으로, 남은 한도를 여유 공간으로 표시
+        let size = match self.quota.as_ref().and_then(QuotaTracker::usage) {
+            Some((limit, used)) => (limit, free.min(limit.saturating_sub(used))),
+            None => (total, free),
+        };
+        drop(client);
+        *self.volume_cache.lock() = Some((Instant::now(), size));
+        size
+    }
+
+    /// 미반영 쓰기를 반영한 파일 크기
+    fn effective_size(&self, handle: u64, remote_size: u64) -> u64 {
+        let files = self.open_files.read();
+        let Some(context) = files.get(&handle) else {
+            return remote_size;
+        };
+        match &context.dirty {
+            Some(dirty) => dirty.buffer.end_offset().max(remote_size),
+            None => self.queued_size(&context.path).unwrap_or(remote_size),
+        }
+    }
+
+    /// 핸들로 보고할 할당 크기 (4KB 정렬한 파일 크기와 미리 잡아 둔 크기 중 큰 값)
+    fn allocation_size(&self, handle: u64, file_size: u64) -> u64 {
+        let reserved = self
+            .open_files
+            .read()
+            .get(&handle)
+            .map_or(0, |context| context.allocation_size);
+        ((file_size + 4095) & !4095).max(reserved)
+    }
+
+    /// 덮어쓰기 전에 원격 파일의 현재 내용을 버전으로 남김 (실패해도 쓰기는 계속)
+    fn save_version(&self, path: &str) {
+        let Some(keep) = self.keep_versions else {
+            return;
+        };
+        match versions::save(&self.streams.metadata(), path, keep) {
+            Ok(Some(version)) => self.cache.invalidate_dir(parent_path(&version)),
+            Ok(None) => {}
+            Err(e) => warn!("[WinFsp] keeping a version of '{}' failed: {}", path, e),
+        }
+    }
+
+    /// 변경 대기 모드의 대기열 (일반 모드면 None)
+    fn queue(&self) -> Option<&Arc<ChangeQueue>> {
+        self.journal.as_ref().and_then(WriteJournal::queue)
+    }
+
+    /// 대기 중인 쓰기를 반영한 파일 크기 (대기 중이 아니면 None)
+    fn queued_size(&self, path: &str) -> Option<u64> {
+        self.queue().and_then(|queue| queue.size_of(path))
+    }
+
+    /// SFTP stat 호출 + 캐시 저장
+    fn stat_with_cache(&self, path: &str) -> Result<ssh2::FileStat, String> {
+        // 스냅샷 마운트는 고정된 트리로만 답함
+        if let Some(tree) = self.cache.frozen() {
+            return tree
+                .stat(path)
+                .ok_or_else(|| format!("'{}' 없음 (스냅샷)", path));
+        }
+        // 1. 캐시 확인
+        if let Some(cached) = self.cache.get_stat(path) {
+            return Ok(cached);
+        }
+        // 일시 중지 중에는 만료된 항목이라도 남아 있는 것으로 답함
+        if self.cache.is_paused() {
+            return self
+                .cache
+                .get_retained_stat(path)
+                .or_else(|| self.offline_local(path, OfflineStore::stat))
+                .ok_or_else(|| format!("'{}': {}", path, DRIVE_PAUSED));
+        }
+        if let Some(answer) = self.offline_answer(path, OfflineStore::stat) {
+            return answer;
+        }
+        // 스냅샷에서 불러온 항목은 그대로 쓰고 상위 목록을 백그라운드에서 다시 확인
+        if let Some(stale) = self.cache.take_stale_stat(path) {
+            self.revalidate_dir(parent_path(path));
+            return Ok(stale);
+        }
+        if self.cache.is_missing(path) {
+            return Err(format!("'{}' 없음 (캐시)", path));
+        }
+        // 2. SFTP 호출 (형제 항목 미스가 몰리면 상위 디렉토리를 한 번에 읽음)
+        let t0 = Instant::now();
+        let result = self.stat_batcher.stat(path, &self.cache, &self.streams);
+        let stat = self
+            .offline_fallback(path, result, OfflineStore::stat)
+            .inspect_err(|e| {
+                if sftp::is_not_found(e) {
+                    self.cache.insert_missing(path);
+                } else {
+                    self.stats.record_error(e);
+                }
+            })?;
+        self.stats.record_success(t0.elapsed());
+        // 3. 캐시 저장 (용량 초과 시 LRU 축출)
+        self.cache.insert_stat(path, stat.clone());
+        Ok(stat)
+    }
+
+    /// SFTP readdir 호출 + 캐시 저장
+    fn readdir_with_cache(&self, path: &str) -> Result<Vec<(String, ssh2::FileStat)>, String> {
+        // 1. 캐시 확인
+        if let Some(cached) = self.readdir_from_cache(path) {
+            return cached;
+        }
+        // 2. SFTP 호출
+        let t0 = Instant::now();
+        let client = self.streams.metadata();
+        let result = client.read_dir(path);
+        drop(client);
+        let entries = self
+            .offline_fallback(path, result, OfflineStore::read_dir)
+            .inspect_err(|e| self.stats.record_error(e))?;
+        self.stats.record_success(t0.elapsed());
+        self.sweep_staged(path, &entries);
+        // 3. 캐시 저장 (하위 항목 stat 포함)
+        self.cache.insert_dir(path, &entries);
+        if self.tuning.prefetch_subdirs > 0 {
+            self.prefetch_subdirs(path, &entries);
+        }
+        Ok(entries)
+    }
+
+    /// 캐시로 답할 수 있으면 디렉토리 목록 (스냅샷, 일시 중지, 만료된 항목 포함)
+    fn readdir_from_cache(
+        &self,
+        path: &str,
+    ) -> Option<Result<Vec<(String, ssh2::FileStat)>, String>> {
+        if let Some(tree) = self.cache.frozen() {
+            return Some(
+                tree.read_dir(path)
+                    .ok_or_else(|| format!("'{}' 디렉토리 없음 (스냅샷)", path)),
+            );
+        }
+        if let Some(cached) = self.cache.get_dir(path) {
+            return Some(Ok(cached));
+        }
+        if self.cache.is_paused() {
+            return Some(
+                self.cache
+                    .get_retained_dir(path)
+                    .or_else(|| self.offline_local(path, OfflineStore::read_dir))
+                    .ok_or_else(|| format!("'{}': {}", path, DRIVE_PAUSED)),
+            );
+        }
+        if let Some(answer) = self.offline_answer(path, OfflineStore::read_dir) {
+            return Some(answer);
+        }
+        if let Some(stale) = self.cache.take_stale_dir(path) {
+            self.revalidate_dir(path);
+            return Some(Ok(stale));
+        }
+        None
+    }
+
+    /// read_directory용 목록
+    ///
+    /// 캐시에 없는 큰 디렉토리는 다 읽을 때까지 기다리지 않고 지금까지 읽은 항목 중
+    /// marker 다음 부분만 넘긴다 ([`crate::listing`]).
+    fn readdir_page(&self, path: &str, marker: Option<&str>) -> Result<DirPage, String> {
+        let whole = |entries| DirPage {
+            entries,
+            after_marker: false,
+            complete: true,
+        };
+        if let Some(listing) = self.listings.get(path) {
+            return listing.entries_after(marker);
+        }
+        if let Some(cached) = self.readdir_from_cache(path) {
+            return cached.map(whole);
+        }
+
+        let t0 = Instant::now();
+        let client = self.streams.metadata();
+        let result = self.listings.read(&client, &self.client, &self.cache, path);
+        drop(client);
+        if let Some(store) = self.cache.offline() {
+            store.record_result(&result);
+        }
+        let first = match result {
+            Ok(first) => first,
+            Err(e) if sftp::is_not_found(&e) => {
+                self.stats.record_error(&e);
+                return Err(e);
+            }
+            Err(e) => {
+                self.stats.record_error(&e);
+                // 연결이 끊겼으면 고정 폴더는 로컬 복사본 목록으로 답함
+                return self
+                    .offline_local(path, OfflineStore::read_dir)
+                    .map(whole)
+                    .ok_or(e);
+            }
+        };
+        self.stats.record_success(t0.elapsed());
+        match first {
+            FirstPage::Complete(entries) => {
+                self.cache.insert_dir(path, &entries);
+                if self.tuning.prefetch_subdirs > 0 {
+                    self.prefetch_subdirs(path, &entries);
+                }
+                Ok(whole(entries))
+            }
+            FirstPage::Partial(listing) => listing.entries_after(marker),
+        }
+    }
+
+    /// 하위 디렉토리 목록을 백그라운드에서 미리 읽어 캐시 (빌드 도구의 하위 폴더 탐색 대비)
+    fn prefetch_subdirs(&self, path: &str, entries: &[(String, ssh2::FileStat)]) {
+        let subdirs: Vec<String> = entries
+            .iter()
+            .filter(|(_, stat)| stat.is_dir())
+            .map(|(name, _)| {
+                if path == "/" {
+                    format!("/{}", name)
+                } else {
+                    format!("{}/{}", path, name)
+                }
+            })
+            .filter(|child| {
+                !profile::skip_prefetch(child)
+                    && !self.is_hidden(&self.to_relative_path(child), child)
+                    && !self.cache.has_dir(child)
+            })
+            .take(self.tuning.prefetch_subdirs)
+            .collect();
+        if subdirs.is_empty() || self.prefetching.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let client = self.client.clone();
+        let cache = self.cache.clone();
+        let prefetching = self.prefetching.clone();
+        std::thread::spawn(move || {
+            for dir in &subdirs {
+                let result = client.lock().read_dir(dir);
+                match result {
+                    Ok(entries) => cache.insert_dir(dir, &entries),
+                    Err(e) => {
+                        debug!("[Cache] prefetch '{}' failed: {}", dir, e);
+                        break;
+                    }
+                }
+            }
+            prefetching.store(false, Ordering::Release);
+        });
+    }
+
+    /// 백그라운드에서 디렉토리 목록을 다시 읽어 캐시 갱신 (사라졌으면 무효화)
+    fn revalidate_dir(&self, path: &str) {
+        let client = self.client.clone();
+        let cache = self.cache.clone();
+        let path = path.to_string();
+        std::thread::spawn(move || {
+            let result = client.lock().read_dir(&path);
+            match result {
+                Ok(entries) => cache.insert_dir(&path, &entries),
+                Err(e) => {
+                    debug!("[Cache] revalidate '{}' failed: {}", path, e);
+                    cache.invalidate(&path);
+                }
+            }
+        });
+    }
+
+    /// 상대 경로를 원격 전체 경로로 변환
+    fn to_remote_path(&self, path: &str) -> String {
+        to_remote_path(&self.remote_root, path)
+    }
+
+    /// 원격 전체 경로를 드라이브 기준 상대 경로로 변환 (루트는 빈 문자열)
+    fn to_relative_path(&self, remote_path: &str) -> String {
+        remote_path
+            .strip_prefix(self.remote_root.trim_end_matches('/'))
+            .unwrap_or(remote_path)
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    /// 제외 패턴이나 숨김 규칙에 걸리는 경로인지
+    fn is_hidden(&self, relative_path: &str, remote_path: &str) -> bool {
+        self.exclude.is_excluded(relative_path) || self.access.is_hidden(remote_path)
+    }
+
+    /// 스냅샷 드라이브이거나 접근 규칙상 읽기 전용인 경로인지
+    fn is_read_only(&self, remote_path: &str) -> bool {
+        self.tuning.snapshot || self.access.is_read_only(remote_path)
+    }
+
+    /// 보호 경로면 앱에서 사용자 확인을 받은 뒤에만 진행 (거부하거나 답이 없으면 접근 거부)
+    fn confirm_protected(
+        &self,
+        operation: ProtectedOp,
+        path: &str,
+        target: Option<&str>,
+    ) -> winfsp::Result<()> {
+        let rule = self
+            .protected
+            .rule_for(path)
+            .or_else(|| target.and_then(|target| self.protected.rule_for(target)));
+        let Some(rule) = rule else {
+            return Ok(());
+        };
+        if confirm::confirm(self.drive_letter, operation, rule, path, target) {
+            Ok(())
+        } else {
+            Err(winfsp::FspError::NTSTATUS(STATUS_ACCESS_DENIED))
+        }
+    }
+
+    /// 일시 중지된 드라이브면 서버가 필요한 작업을 거부 (메타데이터는 캐시로 답함)
+    fn check_active(&self) -> winfsp::Result<()> {
+        if self.cache.is_paused() {
+            winfsp_debug!("[WinFsp] rejected: {}", DRIVE_PAUSED);
+            return Err(winfsp::FspError::NTSTATUS(STATUS_DEVICE_NOT_READY));
+        }
+        Ok(())
+    }
+
+    /// 변경 대기 모드면 대기열에 담을 수 없어 서버에 바로 반영될 변경을 거부
+    fn check_not_queued(&self, operation: &str, path: &str) -> winfsp::Result<()> {
+        if self.queue().is_some() {
+            warn!(
+                "[WinFsp] {} of '{}' rejected: {}",
+                operation, path, QUEUE_MODE_METADATA
+            );
+            return Err(winfsp::FspError::NTSTATUS(STATUS_ACCESS_DENIED));
+        }
+        Ok(())
+    }
+
+    /// 서버 연결이 끊긴 상태인지 (오프라인 고정 경로가 없는 드라이브는 항상 false)
+    fn is_offline(&self) -> bool {
+        self.cache
+            .offline()
+            .is_some_and(|store| store.is_disconnected())
+    }
+
+    /// 오프라인 복사본에서 찾음 (고정 경로와 그 상위 폴더만 있음)
+    fn offline_local<T>(
+        &self,
+        path: &str,
+        lookup: impl FnOnce(&OfflineStore, &str) -> Option<T>,
+    ) -> Option<T> {
+        self.cache.offline().and_then(|store| lookup(&store, path))
+    }
+
+    /// 서버 연결이 끊긴 동안은 서버에 묻지 않고 로컬 복사본으로 답함 (연결되어 있으면 None)
+    fn offline_answer<T>(
+        &self,
+        path: &str,
+        lookup: impl FnOnce(&OfflineStore, &str) -> Option<T>,
+    ) -> Option<Result<T, String>> {
+        let store = self
+            .cache
+            .offline()
+            .filter(|store| store.is_disconnected())?;
+        Some(match lookup(&store, path) {
+            Some(found) => Ok(found),
+            None if store.is_pinned(path) => Err(format!("'{}' 없음 (오프라인 복사본)", path)),
+            None => Err(format!("'{}': {}", path, DRIVE_OFFLINE)),
+        })
+    }
+
+    /// 서버 호출 결과를 연결 상태에 반영 (연결 오류면 고정 경로는 로컬 복사본으로 대신 답함)
+    fn offline_fallback<T>(
+        &self,
+        path: &str,
+        result: Result<T, String>,
+        lookup: impl FnOnce(&OfflineStore, &str) -> Option<T>,
+    ) -> Result<T, String> {
+        let Some(store) = self.cache.offline() else {
+            return result;
+        };
+        store.record_result(&result);
+        match result {
+            Err(e) if !sftp::is_not_found(&e) => lookup(&store, path).ok_or(e),
+            result => result,
+        }
+    }
+
+    /// 접근 규칙상 변경할 수 없는 경로면 거부
+    fn check_writable(&self, remote_path: &str) -> winfsp::Result<()> {
+        if self.is_read_only(remote_path) {
+            winfsp_debug!("[WinFsp] '{}' is read-only by access rule", remote_path);
+            return Err(winfsp::FspError::NTSTATUS(STATUS_ACCESS_DENIED));
+        }
+        Ok(())
+    }
+
+    /// 새 파일 핸들 생성 (현재 세션 세대를 상위 비트에 기록)
+    fn create_handle(&self) -> u64 {
+        self.handle_ids.allocate(self.streams.generation())
+    }
+
+    /// 핸들을 연 프로세스 (닫힌 핸들이면 알 수 없음)
+    fn caller_of(&self, handle: u64) -> Caller {
+        self.open_files
+            .read()
+            .get(&handle)
+            .map(|context| context.caller.clone())
+            .unwrap_or_default()
+    }
+
+    /// 차단 목록의 프로세스(색인기, 백신 검사 등)는 파일 내용을 읽는 열기를 거부
+    fn check_caller_may_read(&self, caller: &Caller, granted_access: u32) -> winfsp::Result<()> {
+        if granted_access & READ_ACCESS_MASK == 0
+            || !self.blocked_processes.contains(&caller.name.to_lowercase())
+        {
+            return Ok(());
+        }
+        self.stats.record_blocked_read(caller);
+        winfsp_debug!("[WinFsp] read access denied for process {}", caller.name);
+        Err(winfsp::FspError::NTSTATUS(STATUS_ACCESS_DENIED))
+    }
+
+    /// 재연결 전에 열린 핸들이면 새 세션에서 다시 열기
+    ///
+    /// 원격 작업은 경로로 하므로 다시 열기는 같은 경로가 같은 종류로 남아 있는지 확인하고
+    /// 핸들의 세대를 갱신하는 것이다. 그사이 파일이 사라졌거나 바뀌었으면
+    /// "오래된 핸들" 오류로 거부해 애플리케이션이 파일을 다시 열도록 한다.
+    fn check_handle(&self, handle: u64) -> winfsp::Result<()> {
+        let generation = self.streams.generation();
+        if handles::is_current(handle, generation) {
+            return Ok(());
+        }
+        let (path, is_dir, granted_access, position, has_dirty) = {
+            let files = self.open_files.read();
+            let Some(context) = files.get(&handle) else {
+                return Ok(());
+            };
+            if context.session == generation {
+                return Ok(());
+            }
+            (
+                context.path.clone(),
+                context.is_directory,
+                context.granted_access,
+                context.position,
+                context.dirty.is_some(),
+            )
+        }; // open_files lock 해제
+
+        match self.reopen_check(&path, is_dir, granted_access, position, has_dirty) {
+            Ok(()) => {
+                if let Some(context) = self.open_files.write().get_mut(&handle) {
+                    context.session = generation;
+                }
+                info!(
+                    "[WinFsp] re-opened handle={} '{}' on session {}",
+                    handle, path, generation
+                );
+                Ok(())
+            }
+            Err(reason) => {
+                warn!(
+                    "[WinFsp] stale handle={} '{}' from session {} (current {}), re-open required: {}",
+                    handle,
+                    path,
+                    handles::epoch_of(handle),
+                    generation,
+                    reason
+                );
+                Err(winfsp::FspError::NTSTATUS(STATUS_FILE_INVALID))
+            }
+        }
+    }
+
+    /// 핸들을 새 세션에서 계속 써도 되는지 확인
+    fn reopen_check(
+        &self,
+        path: &str,
+        is_dir: bool,
+        granted_access: u32,
+        position: u64,
+        has_dirty: bool,
+    ) -> Result<(), String> {
+        // 재연결 전 캐시는 믿지 않고 새 세션으로 다시 확인
+        self.cache.invalidate(path);
+        let stat = self.stat_with_cache(path)?;
+        if stat.is_dir() != is_dir {
+            return Err("file type changed".to_string());
+        }
+        // 쓰지 않은 파일이 마지막으로 다룬 위치보다 짧아졌으면 다른 파일로 바뀐 것
+        if !is_dir && !has_dirty && stat.size.unwrap_or(0) < position {
+            return Err("file was truncated or replaced".to_string());
+        }
+        if granted_access & WRITE_ACCESS_MASK != 0 && self.is_read_only(path) {
+            return Err("path is now read-only".to_string());
+        }
+        Ok(())
+    }
+
+    /// ssh2::FileStat을 WinFsp FileInfo로 변환
+    fn stat_to_file_info(&self, stat: &ssh2::FileStat) -> FileInfo {
+        let mut info = FileInfo::default();
+
+        // 파일 크기
+        info.file_size = stat.size.unwrap_or(0);
+        info.allocation_size = (info.file_size + 4095) & !4095; // 4KB 정렬
+
+        // 파일 속성
+        info.file_attributes = self.file_attributes(stat);
+
+        // 시간 정보 (Unix timestamp -> Windows FILETIME)
+        // SFTP v3에는 생성/변경 시간이 없으므로 변경 시간은 mtime, 생성 시간은 정책에 따름
+        let mtime = stat
+            .mtime
+            .map(|t| self.server_to_local_time(t))
+            .unwrap_or(DEFAULT_FILE_TIME);
+        let atime = stat
+            .atime
+            .map(|t| self.server_to_local_time(t))
+            .unwrap_or(mtime);
+        let creation = match self.creation_time_policy {
+            CreationTimePolicy::Modified => mtime,
+            CreationTimePolicy::Fixed => DEFAULT_FILE_TIME,
+        };
+        info.last_write_time = unix_to_windows_time(mtime);
+        info.change_time = unix_to_windows_time(mtime);
+        info.last_access_time = unix_to_windows_time(atime);
+        info.creation_time = unix_to_windows_time(creation);
+
+        info
+    }
+
+    /// 파일 속성 (색인 제외 모드면 색인기/백신이 내용을 읽지 않도록 오프라인 힌트 추가)
+    fn file_attributes(&self, stat: &ssh2::FileStat) -> u32 {
+        match (stat.is_dir(), self.indexing_opt_out) {
+            (true, false) => FILE_ATTRIBUTE_DIRECTORY,
+            (true, true) => FILE_ATTRIBUTE_DIRECTORY | FILE_ATTRIBUTE_NOT_CONTENT_INDEXED,
+            (false, false) => FILE_ATTRIBUTE_NORMAL,
+            // FILE_ATTRIBUTE_NORMAL은 다른 속성과 함께 쓸 수 없음
+            (false, true) => {
+                FILE_ATTRIBUTE_OFFLINE
+                    | FILE_ATTRIBUTE_NOT_CONTENT_INDEXED
+                    | FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS
+            }
+        }
+    }
+
+    /// 서버 시간 → 실제 UTC (연결의 시간 보정 적용)
+    fn server_to_local_time(&self, unix_time: u64) -> u64 {
+        unix_time.saturating_add_signed(self.time_offset_secs)
+    }
+
+    /// 실제 UTC → 서버에 기록할 시간
+    fn local_to_server_time(&self, unix_time: u64) -> u64 {
+        unix_time.saturating_add_signed(-self.time_offset_secs)
+    }
+
+    /// 디렉토리 stat 기본값 생성
+    fn default_dir_stat() -> ssh2::FileStat {
+        ssh2::FileStat {
+            size: Some(0),
+            uid: None,
+            gid: None,
+            perm: Some(0o040755),
+            atime: None,
+            mtime: Some(DEFAULT_FILE_TIME),
+        }
+    }
+}
+
+/// 드라이브 기준 상대 경로를 원격 전체 경로로 변환
+pub fn to_remote_path(remote_root: &str, path: &str) -> String {
+    let path = path.replace('\\', "/");
+    if path == "/" || path.is_empty() {
+        remote_root.to_string()
+    } else if path.starts_with('/') {
+        format!("{}{}", remote_root.trim_end_matches('/'), path)
+    } else {
+        format!("{}/{}", remote_root.trim_end_matches('/'), path)
+    }
+}
+
+/// 임시 이름 올리기에 쓸 같은 폴더의 숨김 경로 (`.이름.svd-upload-xxxxxxxx`)
+fn staging_path(remote_path: &str) -> String {
+    let (parent, name) = remote_path.rsplit_once('/').unwrap_or(("", remote_path));
+    let suffix = Uuid::new_v4().simple().to_string();
+    format!("{}/.{}{}{}", parent, name, STAGING_MARKER, &suffix[..8])
+}
+
+/// [`staging_path`]로 만든 임시 올리기 파일 이름인지
+fn is_staging_name(name: &str) -> bool {
+    name.starts_with('.')
+        && name.rsplit_once(STAGING_MARKER).is_some_and(|(_, suffix)| {
+            suffix.len() == 8 && suffix.bytes().all(|b| b.is_ascii_hexdigit())
+        })
+}
+
+/// REPARSE_DATA_BUFFER(심볼릭 링크)에서 원격 링크 대상 추출
+///
+/// 상대 경로는 그대로 변환하고, 절대 경로는 같은 드라이브를 가리킬 때만 원격 경로로 바꾼다.
+fn parse_symlink_target(
+    buffer: &[u8],
+    drive_letter: char,
+    remote_root: &str,
+) -> Result<String, i32> {
+    let u16_at = |at: usize| {
+        buffer
+            .get(at..at + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+    };
+    let u32_at = |at: usize| {
+        buffer
+            .get(at..at + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    };
+
+    let tag = u32_at(0).ok_or(STATUS_INVALID_PARAMETER)?;
+    if tag != IO_REPARSE_TAG_SYMLINK {
+        return Err(STATUS_IO_REPARSE_TAG_NOT_HANDLED);
+    }
+
+    // ReparseTag(4) + ReparseDataLength(2) + Reserved(2) 다음이 SymbolicLinkReparseBuffer
+    let (Some(sub_offset), Some(sub_len), Some(print_offset), Some(print_len), Some(flags)) =
+        (u16_at(8), u16_at(10), u16_at(12), u16_at(14), u32_at(16))
+    else {
+        return Err(STATUS_INVALID_PARAMETER);
+    };
+    let name_at = |offset: usize, len: usize| {
+        let bytes = buffer.get(20 + offset..20 + offset + len)?;
+        let wide: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+            .collect();
+        Some(String::from_utf16_lossy(&wide))
+    };
+
+    let name = match name_at(print_offset, print_len).filter(|n| !n.is_empty()) {
+        Some(name) => name,
+        None => name_at(sub_offset, sub_len).ok_or(STATUS_INVALID_PARAMETER)?,
+    };
+
+    if flags & SYMLINK_FLAG_RELATIVE != 0 {
+        return Ok(name.replace('\\', "/"));
+    }
+
+    // 절대 경로: "\??\S:\dir" 또는 "S:\dir"
+    let name = name.trim_start_matches("\\??\\");
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), Some(':')) if letter.eq_ignore_ascii_case(&drive_letter) => {
+            Ok(to_remote_path(remote_root, chars.as_str()))
+        }
+        _ => Err(STATUS_INVALID_PARAMETER),
+    }
+}
+
+const UNIX_TO_WINDOWS_EPOCH: u64 = 11644473600;
+
+/// Unix timestamp를 Windows FILETIME으로 변환
+fn unix_to_windows_time(unix_time: u64) -> u64 {
+    (unix_time + UNIX_TO_WINDOWS_EPOCH) * 10_000_000
+}
+
+/// Windows FILETIME을 Unix timestamp로 변환 (1970년 이전은 0)
+fn windows_to_unix_time(windows_time: u64) -> u64 {
+    (windows_time / 10_000_000).saturating_sub(UNIX_TO_WINDOWS_EPOCH)
+}
+
+impl FileSystemContext for SftpFileSystem {
+    type FileContext = u64; // 파일 핸들
+
+    fn get_security_by_name(
+        &self,
+        file_name: &U16CStr,
+        _security_descriptor: Option<&mut [c_void]>,
+        _resolve_reparse_points: impl FnOnce(&U16CStr) -> Option<FileSecurity>,
+    ) -> winfsp::Result<FileSecurity> {
+        let path = file_name.to_string_lossy();
+        let remote_path = self.to_remote_path(&path);
+        if self.is_hidden(&path, &remote_path) {
+            return Err(IoError::new(ErrorKind::NotFound, "File not found").into());
+        }
+        let remote_path = self.resolve_staged(remote_path);
+
+        let t0 = Instant::now();
+        // lock_ms는 이제 stat_with_cache 내부의 lock 대기시간을 포함하지 않지만,
+        // 전체 메서드 수행 시간을 측정하는 것으로 변경합니다.
+        let result = self.stat_with_cache(&remote_path);
+        let duration_ms = t0.elapsed().as_millis();
+
+        // 하위 호환성을 위해 lock_ms는 0으로, sftp_ms는 전체 소요 시간으로 표기
+        let lock_ms = 0;
+        let sftp_ms = duration_ms;
+
+        match result {
+            Ok(stat) => {
+                let attrs = self.file_attributes(&stat);
+                winfsp_debug!(
+                    "[WinFsp] get_security_by_name '{}' -> OK [lock={}ms, sftp={}ms]",
+                    remote_path,
+                    lock_ms,
+                    sftp_ms
+                );
+                Ok(FileSecurity {
+                    attributes: attrs,
+                    reparse: false,
+                    sz_security_descriptor: 0,
+                })
+            }
+            Err(_) => {
+                winfsp_debug!(
+                    "[WinFsp] get_security_by_name '{}' -> NotFound [lock={}ms, sftp={}ms]",
+                    remote_path,
+                    lock_ms,
+                    sftp_ms
+                );
+                Err(IoError::new(ErrorKind::NotFound, "File not found").into())
+            }
+        }
+    }
+
+    fn open(
+        &self,
+        file_name: &U16CStr,
+        _create_options: u32,
+        granted_access: u32,
+        file_info: &mut OpenFileInfo,
+    ) -> winfsp::Result<Self::FileContext> {
+        self.stats.record_activity();
+        let path = file_name.to_string_lossy();
+        let remote_path = self.to_remote_path(&path);
+        if self.is_hidden(&path, &remote_path) {
+            return Err(IoError::new(ErrorKind::NotFound, "File not found").into());
+        }
+        let remote_path = self.resolve_staged(remote_path);
+        let caller = current_caller();
+
+        let t0 = Instant::now();
+        let (stat_info, is_dir) = {
+            let stat = self.stat_with_cache(&remote_path).map_err(|e| {
+                winfsp_debug!(
+                    "[WinFsp] open '{}' -> FAIL [duration={}ms]: {}",
+                    remote_path,
+                    t0.elapsed().as_millis(),
+                    e
+                );
+                // 연결이 끊긴 동안 고정하지 않은 경로는 없는 파일이 아니라 쓸 수 없는 장치로 알림
+                if e.ends_with(DRIVE_OFFLINE) {
+                    return winfsp::FspError::NTSTATUS(STATUS_DEVICE_NOT_READY);
+                }
+                IoError::new(ErrorKind::NotFound, "File not found").into()
+            })?;
+
+            self.stats
+                .record_op(&caller, &remote_path, FsOp::Open, 0, t0.elapsed());
+            let duration_ms = t0.elapsed().as_millis();
+            winfsp_debug!(
+                "[WinFsp] open '{}' [duration={}ms]",
+                remote_path,
+                duration_ms
+            );
+
+            let mut info = self.stat_to_file_info(&stat);
+            if let Some(size) = self.queued_size(&remote_path) {
+                info.file_size = size;
+                info.allocation_size = (size + 4095) & !4095;
+            }
+            let is_dir = stat.is_dir();
+            (info, is_dir)
+        };
+
+        if !is_dir {
+            self.check_caller_may_read(&caller, granted_access)?;
+        }
+        *file_info.as_mut() = stat_info;
+
+        let handle = self.create_handle();
+        let context = SftpFileContext::new(
+            remote_path.clone(),
+            is_dir,
+            granted_access,
+            handles::epoch_of(handle),
+            caller,
+        );
+        self.open_files.write().insert(handle, context);
+        if !is_dir {
+            self.stats.record_file_opened();
+        }
+
+        winfsp_debug!("[WinFsp]   -> handle={}, is_dir={}", handle, is_dir);
+        Ok(handle)
+    }
+
+    fn close(&self, file_context: Self::FileContext) {
+        winfsp_debug!("[WinFsp] close: handle={}", file_context);
+        if let Err(e) = self
+            .flush_handle(file_context)
+            .and_then(|()| self.commit_staged(file_context))
+        {
+            // 저널이 남아 있으면 다음 마운트 시 재적용됨 (임시 이름 파일은 그대로 남음)
+            warn!(
+                "[WinFsp] close: flush failed for handle={}: {}",
+                file_context, e
+            );
+        }
+        let closed = self.open_files.write().remove(&file_context);
+        if let Some(context) = closed.filter(|context| !context.is_directory) {
+            // 올리기가 실패했어도 닫힌 핸들의 업로드는 더 진행되지 않음
+            self.stats.finish_upload(&context.path);
+            self.stats.record_file_closed();
+            // 큰 파일을 헤더 범위까지만 읽고 닫았으면 미리보기 처리기의 읽기로 봄
+            if let Some(throttle) = &self.preview_throttle {
+                if context.preview_admitted == Some(true)
+                    && context.read_end <= PREVIEW_HEADER_BYTES
+                {
+                    throttle.record_header_only();
+                }
+            }
+        }
+    }
+
+    fn cleanup(&self, file_context: &Self::FileContext, _file_name: Option<&U16CStr>, flags: u32) {
+        // 애플리케이션이 핸들을 닫았으므로 진행 중인 읽기는 더 받지 않음
+        if let Some(context) = self.open_files.read().get(file_context) {
+            context.cancel.cancel();
+        }
+        if flags & CLEANUP_DELETE != 0 {
+            self.delete_handle(*file_context);
+            return;
+        }
+        if let Err(e) = self
+            .flush_handle(*file_context)
+            .and_then(|()| self.commit_staged(*file_context))
+        {
+            warn!(
+                "[WinFsp] cleanup: flush failed for handle={}: {}",
+                file_context, e
+            );
+        }
+    }
+
+    fn set_delete(
+        &self,
+        file_context: &Self::FileContext,
+        _file_name: &U16CStr,
+        delete_file: bool,
+    ) -> winfsp::Result<()> {
+        self.stats.record_activity();
+        // 삭제 취소는 cleanup에서 아무것도 하지 않으면 되므로 그대로 허용
+        if !delete_file {
+            return Ok(());
+        }
+        self.check_active()?;
+        let (path, is_dir) = {
+            self.check_handle(*file_context)?;
+            let files = self.open_files.read();
+            let context = files
+                .get(file_context)
+                .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "Invalid handle"))?;
+            (context.path.clone(), context.is_directory)
+        }; // open_files lock 해제
+        self.check_writable(&path)?;
+        self.check_not_queued("delete", &path)?;
+
+        if is_dir {
+            let entries = self.readdir_with_cache(&path).map_err(IoError::other)?;
+            if entries.iter().any(|(name, _)| name != "." && name != "..") {
+                return Err(winfsp::FspError::NTSTATUS(STATUS_DIRECTORY_NOT_EMPTY));
+            }
+        }
+        self.confirm_protected(ProtectedOp::Delete, &path, None)
+    }
+
+    fn flush(
+        &self,
+        file_context: Option<&Self::FileContext>,
+        file_info: &mut FileInfo,
+    ) -> winfsp::Result<()> {
+        match file_context {
+            Some(handle) => {
+                self.flush_handle(*handle).map_err(IoError::other)?;
+                self.get_file_info(handle, file_info)
+            }
+            None => {
+                // 볼륨 전체 flush
+                let handles: Vec<u64> = self.open_files.read().keys().copied().collect();
+                for handle in handles {
+                    self.flush_handle(handle).map_err(IoError::other)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn read(
+        &self,
+        file_context: &Self::FileContext,
+        buffer: &mut [u8],
+        offset: u64,
+    ) -> winfsp::Result<u32> {
+        self.stats.record_activity();
+        // 일시 중지했거나 서버 연결이 끊긴 동안에도 오프라인 고정 파일은 로컬 복사본으로 읽음
+        let pinned = self.cache.offline().filter(|store| {
+            self.open_files
+                .read()
+                .get(file_context)
+                .is_some_and(|context| store.is_pinned(&context.path))
+        });
+        let server_down = self.cache.is_paused() || self.is_offline();
+        if pinned.is_none() {
+            self.check_active()?;
+            if server_down {
+                winfsp_debug!("[WinFsp] read rejected: {}", DRIVE_OFFLINE);
+                return Err(winfsp::FspError::NTSTATUS(STATUS_DEVICE_NOT_READY));
+            }
+        }
+        // 헤더 크기보다 큰 파일만 미리보기 대상 (작은 파일은 복사할 때도 통째로 앞부분만 읽힘)
+        let previewable = self.preview_throttle.is_some() && {
+            let path = self
+                .open_files
+                .read()
+                .get(file_context)
+                .map(|context| context.path.clone());
+            path.and_then(|path| self.stat_with_cache(&path).ok())
+                .and_then(|stat| stat.size)
+                .is_some_and(|size| size > PREVIEW_HEADER_BYTES)
+        };
+        let (path, cancel) = {
+            self.check_handle(*file_context)?;
+            let mut files = self.open_files.write();
+            let context = files
+                .get_mut(file_context)
+                .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "Invalid handle"))?;
+
+            // 앞부분만 읽고 닫는 핸들이 몰린 동안에는 미리보기 처리기로 보고 새 헤더 읽기 거부
+            let end = offset + buffer.len() as u64;
+            if let Some(throttle) = self.preview_throttle.as_ref().filter(|_| previewable) {
+                if end.max(context.read_end) <= PREVIEW_HEADER_BYTES {
+                    let admitted = *context
+                        .preview_admitted
+                        .get_or_insert_with(|| throttle.admit_header_read());
+                    if !admitted {
+                        self.stats.record_preview_denied();
+                        winfsp_debug!("[WinFsp] read '{}' denied (preview burst)", context.path);
+                        return Err(winfsp::FspError::NTSTATUS(STATUS_ACCESS_DENIED));
+                    }
+                }
+            }
+            context.read_end = context.read_end.max(end);
+            context.position = end;
+            (context.path.clone(), context.cancel.token())
+        }; // open_files lock 해제
+
+        // 스냅샷 뒤에 커진 파일은 스냅샷 크기까지만 읽음
+        let frozen = self.cache.frozen();
+        let len = match frozen.as_ref().and_then(|tree| tree.stat(&path)) {
+            Some(stat) => {
+                let remaining = stat.size.unwrap_or(0).saturating_sub(offset);
+                buffer
+                    .len()
+                    .min(usize::try_from(remaining).unwrap_or(usize::MAX))
+            }
+            None => buffer.len(),
+        };
+        if len == 0 {
+            return Ok(0);
+        }
+
+        // 큰 요청은 여러 세션에 나눠 병렬로 읽음 (세션 lock은 StreamPool 내부에서 처리)
+        // 고정 폴더 예열이나 오프라인 고정으로 내용을 받아 둔 파일은 서버에 묻지 않음
+        let t0 = Instant::now();
+        let local = pinned.as_ref().and_then(|store| {
+            if server_down {
+                return Some(store.read(&path, offset, len, None).unwrap_or_else(|| {
+                    Err(format!(
+                        "'{}': 오프라인 복사본을 아직 받지 못했습니다.",
+                        path
+                    ))
+                }));
+            }
+            // 연결되어 있으면 서버의 현재 파일과 같은 복사본일 때만 씀
+            store.read(&path, offset, len, Some(&self.cache.get_stat(&path)?))
+        });
+        let warmed = frozen
+            .is_none()
+            .then(|| self.cache.get_content(&path))
+            .flatten();
+        let result = match (
+            local,
+            warmed,
+            frozen.as_ref().and_then(|tree| tree.archive()),
+        ) {
+            (Some(result), _, _) => result,
+            (None, Some(content), _) => {
+                let start = content
+                    .len()
+                    .min(usize::try_from(offset).unwrap_or(usize::MAX));
+                Ok(content[start..content.len().min(start + len)].to_vec())
+            }
+            (None, None, Some(archive)) => archive.read(&self.client, &path, offset, len, &cancel),
+            (None, None, None) => {
+                let result = self.streams.read_range(&path, offset, len, &cancel);
+                if cancel.is_cancelled() {
+                    result
+                } else {
+                    self.offline_fallback(&path, result, |store, path| {
+                        store.read(path, offset, len, None)?.ok()
+                    })
+                }
+            }
+        };
+        let data = match result {
+            Ok(data) => data,
+            Err(_) if cancel.is_cancelled() => {
+                winfsp_debug!("[WinFsp] read '{}' offset={} cancelled", path, offset);
+                return Err(winfsp::FspError::NTSTATUS(STATUS_CANCELLED));
+            }
+            Err(e) => {
+                self.stats.record_error(&e);
+                return Err(IoError::other(e).into());
+            }
+        };
+        let sftp_ms = t0.elapsed().as_millis();
+
+        let mut bytes_read = data.len().min(buffer.len());
+        buffer[..bytes_read].copy_from_slice(&data[..bytes_read]);
+
+        // 아직 반영되지 않은 쓰기 내용을 덮어씀 (닫힌 뒤 대기 중인 쓰기 포함)
+        match self
+            .open_files
+            .read()
+            .get(file_context)
+            .and_then(|c| c.dirty.as_ref())
+        {
+            Some(dirty) => bytes_read = dirty.buffer.overlay(offset, buffer, bytes_read),
+            None => {
+                if let Some(queue) = self.queue() {
+                    bytes_read = queue
+                        .overlay(&path, offset, buffer, bytes_read)
+                        .unwrap_or(bytes_read);
+                }
+            }
+        }
+        self.stats.record_op(
+            &self.caller_of(*file_context),
+            &path,
+            FsOp::Read,
+            bytes_read as u64,
+            t0.elapsed(),
+        );
+
+        winfsp_debug!(
+            "[WinFsp] read '{}' offset={} len={} -> {}B [sftp={}ms]",
+            path,
+            offset,
+            buffer.len(),
+            bytes_read,
+            sftp_ms
+        );
+        Ok(bytes_read as u32)
+    }
+
+    fn write(
+        &self,
+        file_context: &Self::FileContext,
+        buffer: &[u8],
+        offset: u64,
+        write_to_end_of_file: bool,
+        constrained_io: bool,
+        file_info: &mut FileInfo,
+    ) -> winfsp::Result<u32> {
+        self.stats.record_activity();
+        let path = {
+            self.check_handle(*file_context)?;
+            let files = self.open_files.read();
+            let context = files
+                .get(file_context)
+                .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "Invalid handle"))?;
+            context.path.clone()
+        }; // open_files lock 해제
+        self.check_writable(&path)?;
+        self.check_active()?;
+        let t0 = Instant::now();
+
+        // 원격 파일의 현재 상태 (없으면 새 파일)
+        let base = self.stat_with_cache(&path).ok();
+        let remote_size = base.as_ref().and_then(|s| s.size).unwrap_or(0);
+        let file_size = self.effective_size(*file_context, remote_size);
+
+        let offset = if write_to_end_of_file {
+            file_size
+        } else {
+            offset
+        };
+        // constrained I/O는 파일 크기를 늘리지 않음
+        let data = if constrained_io {
+            if offset >= file_size {
+                return Ok(0);
+            }
+            &buffer[..buffer.len().min((file_size - offset) as usize)]
+        } else {
+            buffer
+        };
+
+        // 파일이 커지는 만큼 쿼터 확인 (초과 시 DISK_FULL)
+        let growth = (offset + data.len() as u64).saturating_sub(file_size);
+        if let Some(quota) = &self.quota {
+            if !quota.reserve(growth) {
+                winfsp_debug!("[WinFsp] write '{}' rejected: quota exceeded", path);
+                return Err(winfsp::FspError::NTSTATUS(STATUS_DISK_FULL));
+            }
+        }
+
+        let needs_flush = {
+            self.check_handle(*file_context)?;
+            let mut files = self.open_files.write();
+            let context = files
+                .get_mut(file_context)
+                .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "Invalid handle"))?;
+            // 대기 중인 쓰기가 있으면 이어서 기록
+            let dirty = context.dirty.get_or_insert_with(|| {
+                self.queue()
+                    .and_then(|queue| queue.take(&path))
+                    .unwrap_or_else(|| DirtyFile::new(self.journal.as_ref(), &path, base.as_ref()))
+            });
+            dirty.write(offset, data);
+            let full = dirty.buffer.dirty_bytes() >= self.tuning.dirty_flush_bytes;
+            context.position = offset + data.len() as u64;
+            full
+        }; // open_files lock 해제
+
+        let new_size = file_size.max(offset + data.len() as u64);
+        if needs_flush {
+            self.flush_handle(*file_context).map_err(IoError::other)?;
+        }
+
+        let mut info = base
+            .as_ref()
+            .map(|stat| self.stat_to_file_info(stat))
+            .unwrap_or_default();
+        info.file_size = new_size;
+        info.allocation_size = self.allocation_size(*file_context, new_size);
+        *file_info = info;
+
+        self.stats.record_op(
+            &self.caller_of(*file_context),
+            &path,
+            FsOp::Write,
+            data.len() as u64,
+            t0.elapsed(),
+        );
+        winfsp_debug!(
+            "[WinFsp] write '{}' offset={} len={} -> size={}",
+            path,
+            offset,
+            data.len(),
+            new_size
+        );
+        Ok(data.len() as u32)
+    }
+
+    fn create(
+        &self,
+        file_name: &U16CStr,
+        create_options: u32,
+        granted_access: u32,
+        _file_attributes: u32,
+        _security_descriptor: Option<&[c_void]>,
+        allocation_size: u64,
+        _extra_buffer: Option<&[u8]>,
+        _extra_buffer_is_reparse_point: bool,
+        file_info: &mut OpenFileInfo,
+    ) -> winfsp::Result<Self::FileContext> {
+        self.stats.record_activity();
+        let remote_path = self.to_remote_path(&file_name.to_string_lossy());
+        let is_dir = create_options & FILE_DIRECTORY_FILE != 0;
+        let caller = current_caller();
+        if !is_dir {
+            self.check_caller_may_read(&caller, granted_access)?;
+        }
+        self.check_writable(&remote_path)?;
+        self.check_not_queued("create", &remote_path)?;
+        self.check_active()?;
+
+        // 임시 이름 올리기: 닫을 때까지 같은 폴더의 숨김 파일에 씀
+        let final_path = (!is_dir && self.atomic_uploads).then(|| remote_path.clone());
+        let remote_path = match &final_path {
+            Some(path) => staging_path(path),
+            None => remote_path,
+        };
+
+        let t0 = Instant::now();
+        {
+            let client = self.streams.metadata();
+            if is_dir {
+                client.create_dir(&remote_path)
+            } else {
+                client.write_file(&remote_path, &[])
+            }
+            .map_err(IoError::other)?;
+        }
+        self.cache.invalidate(&remote_path);
+        self.stats
+            .record_op(&caller, &remote_path, FsOp::Create, 0, t0.elapsed());
+        winfsp_debug!(
+            "[WinFsp] create '{}' is_dir={} [duration={}ms]",
+            remote_path,
+            is_dir,
+            t0.elapsed().as_millis()
+        );
+
+        let stat = self.stat_with_cache(&remote_path).map_err(IoError::other)?;
+        *file_info.as_mut() = self.stat_to_file_info(&stat);
+
+        let handle = self.create_handle();
+        let mut context = SftpFileContext::new(
+            remote_path,
+            is_dir,
+            granted_access,
+            handles::epoch_of(handle),
+            caller,
+        );
+        // 임시 파일은 이전 버전을 남기지 않음 (덮어쓸 원래 파일은 옮길 때 남김)
+        context.versioned = final_path.is_some();
+        context.final_path = final_path;
+        // 미리 잡은 할당 크기를 기억해 두고 그대로 보고 (서버에는 아직 0바이트 파일)
+        if !is_dir {
+            context.allocation_size = (allocation_size + 4095) & !4095;
+            let info = file_info.as_mut();
+            info.allocation_size = info.allocation_size.max(context.allocation_size);
+        }
+        self.open_files.write().insert(handle, context);
+        if !is_dir {
+            self.stats.record_file_opened();
+        }
+        Ok(handle)
+    }
+
+    fn set_reparse_point(
+        &self,
+        file_context: &Self::FileContext,
+        _file_name: &U16CStr,
+        buffer: &[u8],
+    ) -> winfsp::Result<()> {
+        let (path, is_dir) = {
+            self.check_handle(*file_context)?;
+            let files = self.open_files.read();
+            let context = files
+                .get(file_context)
+                .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "Invalid handle"))?;
+            (context.path.clone(), context.is_directory)
+        }; // open_files lock 해제
+        self.check_writable(&path)?;
+        self.check_not_queued("set_reparse_point", &path)?;
+        self.check_active()?;
+
+        // mklink는 빈 파일/디렉토리를 먼저 만든 뒤 reparse point를 설정하므로,
+        // 자리표시자를 지우고 같은 경로에 서버 심볼릭 링크를 만든다.
+        // 링크를 만들 수 없으면 자리표시자를 건드리지 않고 거부한다.
+        let target = match self.symlink_policy {
+            SymlinkPolicy::Allow => {
+                parse_symlink_target(buffer, self.drive_letter, &self.remote_root)
+            }
+            SymlinkPolicy::Deny => Err(STATUS_ACCESS_DENIED),
+        };
+        let target = target.map_err(|status| {
+            winfsp_debug!(
+                "[WinFsp] set_reparse_point '{}' rejected (0x{:08X})",
+                path,
+                status
+            );
+            winfsp::FspError::NTSTATUS(status)
+        })?;
+        // 내용이 있는 파일은 자리표시자가 아니므로 지우지 않음
+        if !is_dir {
+            let stat = self.stat_with_cache(&path).map_err(IoError::other)?;
+            if self.effective_size(*file_context, stat.size.unwrap_or(0)) > 0 {
+                winfsp_debug!(
+                    "[WinFsp] set_reparse_point '{}' rejected: file not empty",
+                    path
+                );
+                return Err(winfsp::FspError::NTSTATUS(STATUS_ACCESS_DENIED));
+            }
+        }
+
+        let client = self.streams.metadata();
+        let result = if is_dir {
+            client.remove_dir(&path)
+        } else {
+            client.remove_file(&path)
+        };
+        if let Err(e) = result {
+            warn!(
+                "[WinFsp] set_reparse_point: placeholder removal failed: {}",
+                e
+            );
+        }
+        self.cache.invalidate(&path);
+        client.symlink(&path, &target).map_err(IoError::other)?;
+        winfsp_debug!("[WinFsp] symlink '{}' -> '{}'", path, target);
+        Ok(())
+    }
+
+    fn get_extended_attributes(
+        &self,
+        file_context: &Self::FileContext,
+        buffer: &mut [u8],
+    ) -> winfsp::Result<u32> {
+        let path = {
+            self.check_handle(*file_context)?;
+            let files = self.open_files.read();
+            let context = files
+                .get(file_context)
+                .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "Invalid handle"))?;
+            context.path.clone()
+        }; // open_files lock 해제
+
+        // 일시 중지 중이거나 getfattr가 없는 서버에서는 EA가 없는 것으로 취급
+        if self.cache.is_paused() {
+            return Ok(0);
+        }
+        let entries = xattr::read_xattrs(&self.streams.metadata(), &path).unwrap_or_else(|e| {
+            warn!("[WinFsp] get_extended_attributes '{}': {}", path, e);
+            Vec::new()
+        });
+        let written = xattr::encode_ea_buffer(&entries, buffer);
+        winfsp_debug!(
+            "[WinFsp] get_extended_attributes '{}' -> {} entries, {}B",
+            path,
+            entries.len(),
+            written
+        );
+        Ok(written)
+    }
+
+    fn set_extended_attributes(
+        &self,
+        file_context: &Self::FileContext,
+        buffer: &[u8],
+        file_info: &mut FileInfo,
+    ) -> winfsp::Result<()> {
+        let path = {
+            self.check_handle(*file_context)?;
+            let files = self.open_files.read();
+            let context = files
+                .get(file_context)
+                .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "Invalid handle"))?;
+            context.path.clone()
+        }; // open_files lock 해제
+
+        self.check_writable(&path)?;
+        self.check_not_queued("set_extended_attributes", &path)?;
+        self.check_active()?;
+
+        let entries = xattr::decode_ea_buffer(buffer)
+            .map_err(|_| winfsp::FspError::NTSTATUS(STATUS_INVALID_PARAMETER))?;
+        {
+            let client = self.streams.metadata();
+            for (name, value) in &entries {
+                xattr::write_xattr(&client, &path, name, value).map_err(IoError::other)?;
+            }
+        }
+        winfsp_debug!(
+            "[WinFsp] set_extended_attributes '{}' -> {} entries",
+            path,
+            entries.len()
+        );
+
+        self.cache.invalidate(&path);
+        self.get_file_info(file_context, file_info)
+    }
+
+    fn rename(
+        &self,
+        file_context: &Self::FileContext,
+        file_name: &U16CStr,
+        new_file_name: &U16CStr,
+        replace_if_exists: bool,
+    ) -> winfsp::Result<()> {
+        self.stats.record_activity();
+        self.check_handle(*file_context)?;
+        let from = self.to_remote_path(&file_name.to_string_lossy());
+        let to = self.to_remote_path(&new_file_name.to_string_lossy());
+        self.check_writable(&from)?;
+        self.check_writable(&to)?;
+        self.check_not_queued("rename", &from)?;
+        self.check_active()?;
+
+        // 이동 전에 이 경로(디렉토리면 그 아래)의 모든 핸들의 미반영 쓰기를 원래 경로에 반영
+        // (다른 핸들이 나중에 옛 경로로 올려 파일을 되살리지 않도록)
+        let affected: Vec<u64> = self
+            .open_files
+            .read()
+            .iter()
+            .filter(|(_, context)| moved_path(&context.path, &from, &to).is_some())
+            .map(|(handle, _)| *handle)
+            .collect();
+        for handle in affected.iter().copied().chain([*file_context]) {
+            self.flush_handle(handle).map_err(IoError::other)?;
+        }
+
+        let target = self.stat_with_cache(&to).ok();
+        let target_exists = target.is_some();
+        if target_exists && !replace_if_exists {
+            return Err(IoError::new(ErrorKind::AlreadyExists, "Target exists").into());
+        }
+        self.confirm_protected(ProtectedOp::Rename, &from, Some(&to))?;
+
+        // 임시 이름으로 올리는 중인 파일은 서버에서 옮기지 않고 닫을 때 옮길 경로만 바꿈
+        if let Some(target) = self
+            .open_files
+            .write()
+            .get_mut(file_context)
+            .and_then(|context| context.final_path.as_mut())
+        {
+            *target = to;
+            return Ok(());
+        }
+
+        // 저장할 때 임시 파일을 원본 위로 옮기는 프로그램이 많아 덮어쓸 대상도 버전으로 남김
+        if target_exists {
+            self.save_version(&to);
+        }
+
+        // 같은 볼륨 내 이동은 SFTP rename으로 서버 안에서 처리 (데이터 전송 없음)
+        let t0 = Instant::now();
+        self.streams
+            .metadata()
+            .rename(&from, &to)
+            .map_err(IoError::other)?;
+        self.stats
+            .record_op(&current_caller(), &from, FsOp::Rename, 0, t0.elapsed());
+        // 덮어쓴 대상 파일만큼 쿼터 사용량이 줄어듦
+        if let (Some(quota), Some(target)) = (&self.quota, &target) {
+            if !target.is_dir() {
+                quota.release(target.size.unwrap_or(0));
+            }
+        }
+        winfsp_debug!(
+            "[WinFsp] rename '{}' -> '{}' [duration={}ms]",
+            from,
+            to,
+            t0.elapsed().as_millis()
+        );
+
+        if let Some(queue) = self.queue() {
+            queue.rename(&from, &to);
+        }
+        self.cache.invalidate_tree(&from);
+        self.cache.invalidate_tree(&to);
+        // 이 핸들과 옮긴 디렉토리 아래를 연 다른 핸들이 새 경로를 따라가도록
+        for context in self.open_files.write().values_mut() {
+            if let Some(moved) = moved_path(&context.path, &from, &to) {
+                context.path = moved;
+            }
+            if let Some(moved) = context
+                .final_path
+                .as_deref()
+                .and_then(|path| moved_path(path, &from, &to))
+            {
+                context.final_path = Some(moved);
+            }
+        }
+        if let Some(context) = self.open_files.write().get_mut(file_context) {
+            context.path = to;
+        }
+        Ok(())
+    }
+
+    fn get_file_info(
+        &self,
+        file_context: &Self::FileContext,
+        file_info: &mut FileInfo,
+    ) -> winfsp::Result<()> {
+        let path = {
+            self.check_handle(*file_context)?;
+            let files = self.open_files.read();
+            let context = files
+                .get(file_context)
+                .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "Invalid handle"))?;
+            context.path.clone()
+        }; // open_files lock 해제
+
+        let t0 = Instant::now();
+        let stat = self.stat_with_cache(&path).map_err(IoError::other)?;
+        let duration_ms = t0.elapsed().as_millis();
+        // drop(client) 제거됨 (stat_with_cache가 처리)
+
+        *file_info = self.stat_to_file_info(&stat);
+        file_info.file_size = self.effective_size(*file_context, file_info.file_size);
+        file_info.allocation_size = self.allocation_size(*file_context, file_info.file_size);
+        winfsp_debug!(
+            "[WinFsp] get_file_info '{}' -> size={} [duration={}ms]",
+            path,
+            file_info.file_size,
+            duration_ms
+        );
+        Ok(())
+    }
+
+    fn set_basic_info(
+        &self,
+        file_context: &Self::FileContext,
+        _file_attributes: u32,
+        _creation_time: u64,
+        last_access_time: u64,
+        last_write_time: u64,
+        _last_change_time: u64,
+        file_info: &mut FileInfo,
+    ) -> winfsp::Result<()> {
+        self.stats.record_activity();
+        // 0은 "변경하지 않음" - 시간 변경 요청이 없으면 현재 정보만 돌려줌
+        if last_access_time == 0 && last_write_time == 0 {
+            return self.get_file_info(file_context, file_info);
+        }
+
+        let (path, dirty) = {
+            self.check_handle(*file_context)?;
+            let files = self.open_files.read();
+            let context = files
+                .get(file_context)
+                .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "Invalid handle"))?;
+            (context.path.clone(), context.dirty.is_some())
+        }; // open_files lock 해제
+        self.check_writable(&path)?;
+        // 대기 중인 파일은 올릴 때 mtime이 덮어써지므로 시간만 기록해 둠
+        let dirty = dirty || self.queue().is_some_and(|queue| queue.contains(&path));
+        if !dirty {
+            self.check_not_queued("set_basic_info", &path)?;
+        }
+        self.check_active()?;
+
+        // SFTP는 atime/mtime을 함께 설정하므로 빠진 값은 현재 값으로 채움
+        let current = self.stat_with_cache(&path).map_err(IoError::other)?;
+        let pick = |requested: u64, current: Option<u64>| match requested {
+            0 => current.unwrap_or(0),
+            time => self.local_to_server_time(windows_to_unix_time(time)),
+        };
+        let mtime = pick(last_write_time, current.mtime);
+        let atime = pick(last_access_time, current.atime.or(current.mtime));
+
+        if dirty {
+            if let Some(context) = self.open_files.write().get_mut(file_context) {
+                context.pending_times = Some((atime, mtime));
+            }
+        } else {
+            self.client
+                .lock()
+                .set_times(&path, atime, mtime)
+                .map_err(IoError::other)?;
+            self.cache.invalidate(&path);
+        }
+        winfsp_debug!("[WinFsp] set_basic_info '{}' mtime={}", path, mtime);
+        self.get_file_info(file_context, file_info)
+    }
+
+    fn set_file_size(
+        &self,
+        file_context: &Self::FileContext,
+        new_size: u64,
+        set_allocation_size: bool,
+        file_info: &mut FileInfo,
+    ) -> winfsp::Result<()> {
+        self.stats.record_activity();
+        let (path, is_dir) = {
+            self.check_handle(*file_context)?;
+            let files = self.open_files.read();
+            let context = files
+                .get(file_context)
+                .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "Invalid handle"))?;
+            (context.path.clone(), context.is_directory)
+        }; // open_files lock 해제
+        if is_dir {
+            return Err(winfsp::FspError::NTSTATUS(STATUS_INVALID_PARAMETER));
+        }
+        self.check_writable(&path)?;
+        self.check_active()?;
+
+        let base = self.stat_with_cache(&path).map_err(IoError::other)?;
+        let file_size = self.effective_size(*file_context, base.size.unwrap_or(0));
+
+        // 할당 크기만 늘리면 서버 파일은 그대로 두고 보고할 크기만 기억
+        // (줄여서 파일 크기보다 작아지면 NTFS처럼 파일도 그 크기로 자름)
+        if set_allocation_size {
+            if let Some(context) = self.open_files.write().get_mut(file_context) {
+                context.allocation_size = (new_size + 4095) & !4095;
+            }
+            if new_size >= file_size {
+                winfsp_debug!("[WinFsp] set_file_size '{}' allocation={}", path, new_size);
+                return self.get_file_info(file_context, file_info);
+            }
+        }
+        if new_size == file_size {
+            return self.get_file_info(file_context, file_info);
+        }
+
+        // 변경 대기 모드는 서버를 건드리지 않으므로 늘리기만 쓰기 버퍼에 기록
+        // (버퍼는 자르는 변경을 표현할 수 없음)
+        if self.queue().is_some() && new_size < file_size {
+            winfsp_debug!(
+                "[WinFsp] set_file_size '{}' rejected: truncation while changes are queued",
+                path
+            );
+            return Err(winfsp::FspError::NTSTATUS(STATUS_ACCESS_DENIED));
+        }
+
+        // 파일이 커지는 만큼 쿼터 확인 (초과 시 DISK_FULL, 크기를 바꾸지 못하면 돌려줌)
+        let growth = new_size.saturating_sub(file_size);
+        if let Some(quota) = &self.quota {
+            if !quota.reserve(growth) {
+                winfsp_debug!("[WinFsp] set_file_size '{}' rejected: quota exceeded", path);
+                return Err(winfsp::FspError::NTSTATUS(STATUS_DISK_FULL));
+            }
+        }
+
+        if self.queue().is_some() {
+            {
+                let mut files = self.open_files.write();
+                let Some(context) = files.get_mut(file_context) else {
+                    drop(files);
+                    if let Some(quota) = &self.quota {
+                        quota.release(growth);
+                    }
+                    return Err(IoError::new(ErrorKind::InvalidInput, "Invalid handle").into());
+                };
+                let dirty = context.dirty.get_or_insert_with(|| {
+                    self.queue()
+                        .and_then(|queue| queue.take(&path))
+                        .unwrap_or_else(|| {
+                            DirtyFile::new(self.journal.as_ref(), &path, Some(&base))
+                        })
+                });
+                dirty.write(new_size - 1, &[0]);
+            } // open_files lock 해제
+            winfsp_debug!("[WinFsp] set_file_size '{}' -> {} (queued)", path, new_size);
+            return self.get_file_info(file_context, file_info);
+        }
+
+        // 미반영 쓰기를 먼저 올린 뒤 서버에서 크기 변경
+        let t0 = Instant::now();
+        let result = self.flush_handle(*file_context).and_then(|()| {
+            let versioned = self
+                .open_files
+                .write()
+                .get_mut(file_context)
+                .is_some_and(|context| std::mem::replace(&mut context.versioned, true));
+            if !versioned {
+                self.save_version(&path);
+            }
+            self.streams.metadata().truncate(&path, new_size)
+        });
+        self.cache.invalidate(&path);
+        if let Some(quota) = &self.quota {
+            match result {
+                Ok(()) => quota.release(file_size.saturating_sub(new_size)),
+                Err(_) => quota.release(growth),
+            }
+        }
+        result.map_err(IoError::other)?;
+        self.stats.record_op(
+            &self.caller_of(*file_context),
+            &path,
+            FsOp::Write,
+            0,
+            t0.elapsed(),
+        );
+        winfsp_debug!(
+            "[WinFsp] set_file_size '{}' {} -> {} [duration={}ms]",
+            path,
+            file_size,
+            new_size,
+            t0.elapsed().as_millis()
+        );
+        // 서버가 실제로 반영한 크기로 보고
+        self.get_file_info(file_context, file_info)
+    }
+
+    fn get_volume_info(
+        &self,
+        volume_info: &mut winfsp::filesystem::VolumeInfo,
+    ) -> winfsp::Result<()> {
+        winfsp_debug!("[WinFsp] get_volume_info");
+        let (total, free) = self.volume_size();
+        volume_info.total_size = total;
+        volume_info.free_size = free;
+        volume_info.set_volume_label("SSHFS");
+        Ok(())
+    }
+
+    fn read_directory(
+        &self,
+        file_context: &Self::FileContext,
+        _pattern: Option<&U16CStr>,
+        marker: DirMarker,
+        buffer: &mut [u8],
+    ) -> winfsp::Result<u32> {
+        self.stats.record_activity();
+        // open_files lock을 잡고 경로를 복사한 후 바로 해제
+        let (dir_path, is_dir) = {
+            self.check_handle(*file_context)?;
+            let files = self.open_files.read();
+            let context = files
+                .get(file_context)
+                .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "Invalid handle"))?;
+            (context.path.clone(), context.is_directory)
+        }; // open_files lock 해제
+
+        winfsp_debug!(
+            "[WinFsp] read_directory: handle={}, path='{}', is_dir={}, marker_none={}",
+            file_context,
+            dir_path,
+            is_dir,
+            marker.is_none()
+        );
+
+        if !is_dir {
+            warn!("[WinFsp]   -> Not a directory!");
+            return Err(IoError::other("Not a directory").into());
+        }
+
+        let t0 = Instant::now();
+
+        // 현재 디렉토리의 stat 정보 (. 및 .. 용) - 캐시 사용
+        let dir_stat = self
+            .stat_with_cache(&dir_path)
+            .unwrap_or_else(|_| Self::default_dir_stat());
+
+        // 원격 디렉토리 목록 읽기 - 캐시 사용 (큰 디렉토리는 읽은 만큼만)
+        let marker_str = marker.inner_as_cstr().map(|m| m.to_string_lossy());
+        let DirPage {
+            entries,
+            after_marker,
+            complete,
+        } = self
+            .readdir_page(&dir_path, marker_str.as_deref())
+            .map_err(|e| {
+                winfsp_debug!("[WinFsp]   -> read_dir failed: {}", e);
+                IoError::other(e)
+            })?;
+
+        self.stats.record_op(
+            &self.caller_of(*file_context),
+            &dir_path,
+            FsOp::List,
+            0,
+            t0.elapsed(),
+        );
+        let duration_ms = t0.elapsed().as_millis();
+        winfsp_debug!(
+            "[WinFsp] read_directory '{}' fetched [duration={}ms]",
+            dir_path,
+            duration_ms
+        );
+
+        winfsp_debug!("[WinFsp]   -> {} entries found", entries.len());
+
+        // ".", ".." 및 실제 파일을 하나의 리스트로 구성
+        let mut all_entries: Vec<(String, FileInfo)> = Vec::new();
+
+        // 이어 읽는 부분 목록에는 ".", ".."이 이미 넘어감
+        if !after_marker || marker_str.is_none() {
+            let dir_info_data = self.stat_to_file_info(&dir_stat);
+            all_entries.push((".".to_string(), dir_info_data.clone()));
+            all_entries.push(("..".to_string(), dir_info_data));
+        }
+
+        let relative_dir = self.to_relative_path(&dir_path);
+        for (name, stat) in &entries {
+            if name == "." || name == ".." {
+                continue;
+            }
+            // 임시 올리기 파일은 숨기고, 지금 올리는 중이면 원래 이름으로 보여 줌
+            let name = if is_staging_name(name) {
+                let staged = format!("{}/{}", dir_path.trim_end_matches('/'), name);
+                let final_name = self
+                    .open_files
+                    .read()
+                    .values()
+                    .filter(|context| context.path == staged)
+                    .find_map(|context| {
+                        let (parent, name) = context.final_path.as_deref()?.rsplit_once('/')?;
+                        (parent == dir_path.trim_end_matches('/')).then(|| name.to_string())
+                    });
+                match final_name {
+                    Some(final_name) if !entries.iter().any(|(n, _)| *n == final_name) => {
+                        final_name
+                    }
+                    _ => continue,
+                }
+            } else {
+                name.clone()
+            };
+            let name = &name;
+            if self.is_hidden(
+                &format!("{}/{}", relative_dir, name),
+                &format!("{}/{}", dir_path.trim_end_matches('/'), name),
+            ) {
+                continue;
+            }
+            let mut info = self.stat_to_file_info(stat);
+            if let Some(size) =
+                self.queued_size(&format!("{}/{}", dir_path.trim_end_matches('/'), name))
+            {
+                info.file_size = size;
+                info.allocation_size = (size + 4095) & !4095;
+            }
+            all_entries.push((name.clone(), info));
+        }
+
+        let mut cursor: u32 = 0;
+
+        // DirMarker가 있으면 해당 항목 이후부터 시작 (부분 목록은 이미 marker 다음부터)
+        let mut found_marker = marker_str.is_none() || after_marker;
+
+        for (name, file_info) in &all_entries {
+            // marker가 있는 경우 해당 marker 이름을 찾을 때까지 스킵
+            if !found_marker {
+                if let Some(ref marker_name) = marker_str {
+                    if *name == **marker_name {
+                        found_marker = true;
+                    }
+                    continue; // marker 항목 자체도 스킵
+                }
+            }
+
+            let mut dir_info: DirInfo<255> = DirInfo::new();
+            *dir_info.file_info_mut() = file_info.clone();
+
+            if dir_info.set_name(name).is_err() {
+                winfsp_debug!("[WinFsp]   -> set_name failed for '{}'", name);
+                continue; // 이름이 너무 긴 경우 스킵
+            }
+
+            // 버퍼에 추가 (공간 부족하면 중단)
+            if !dir_info.append_to_buffer(buffer, &mut cursor) {
+                winfsp_debug!("[WinFsp]   -> buffer full at '{}'", name);
+                break;
+            }
+        }
+
+        // 버퍼 종료 마커 추가 (아직 읽는 중이면 붙이지 않아 탐색기가 마지막 항목부터 다시 요청)
+        if complete {
+            DirInfo::<255>::finalize_buffer(buffer, &mut cursor);
+        }
+
+        winfsp_debug!("[WinFsp]   -> returning {} bytes", cursor);
+        Ok(cursor)
+    }
+}
+
+/// 파일시스템 호스트 생성 및 시작 (파일시스템과 상태를 공유하는 쿼터 추적기도 돌려줌)
+pub fn create_filesystem_host(
+    client: SharedSftpClient,
+    connection: &SshConnection,
+    drive_letter: char,
+    stats: Arc<StatsCollector>,
+    cache: Arc<StatCache>,
+    journal: Option<WriteJournal>,
+    tuning: MountTuning,
+) -> Result<(FileSystemHost<SftpFileSystem>, Option<QuotaTracker>), String> {
+    // WinFsp 초기화
+    winfsp::winfsp_init_or_die();
+
+    // xattr은 getfattr/setfattr로 전달하므로 exec가 막힌 서버에서는 EA를 끔
+    let exec = client.lock().capabilities().exec;
+    if connection.xattr_passthrough && !exec {
+        warn!("[Mount] extended attributes disabled: {}", EXEC_UNSUPPORTED);
+    }
+    let fs = SftpFileSystem::new(
+        client,
+        connection,
+        drive_letter,
+        stats,
+        cache,
+        journal,
+        tuning,
+    );
+
+    // VolumeParams 설정 - 네트워크 파일시스템에 최적화
+    let mut volume_params = VolumeParams::default();
+    volume_params
+        .filesystem_name("SSHFS")
+        .sector_size(4096) // 네트워크 파일시스템에 효율적인 4KB
+        .sectors_per_allocation_unit(64) // 256KB allocation unit - 네트워크 효율성
+        .max_component_length(255)
+        .volume_creation_time(unix_to_windows_time(1704067200))
+        .volume_serial_number(0x53534846) // "SSHF"
+        .file_info_timeout(tuning.file_info_timeout_ms) // 기본 5초, 개발자 프로필은 더 길게
+        .case_sensitive_search(false)
+        .case_preserved_names(true)
+        .unicode_on_disk(true)
+        .read_only_volume(tuning.snapshot)
+        .reparse_points(true) // mklink → 서버 심볼릭 링크 (정책은 set_reparse_point에서 확인)
+        .extended_attributes(connection.xattr_passthrough && exec)
+        .post_cleanup_when_modified_only(true);
+
+    let quota = fs.quota.clone();
+    let mut host = FileSystemHost::new(volume_params, fs).map_err(|e| {
+        let err_str = format!("{:?}", e);
+        if err_str.contains("0xD000000D") || err_str.contains("0xC000000D") {
+            format!(
+                "WinFsp 파일시스템 생성 실패.\n\
+                원인: WinFsp 드라이버에 접근할 수 없습니다.\n\
+                1. WinFsp가 설치되어 있는지 확인해주세요: https://winfsp.dev/rel/\n\
+                2. 이미 설치했다면 컴퓨터를 재시작해주세요.\n\
+                3. 관리자 권한으로 앱을 실행해 보세요.\n\
+                (에러 코드: {})",
+                err_str
+            )
+        } else {
+            format!("파일시스템 호스트 생성 실패: {}", err_str)
+        }
+    })?;
+
+    // 드라이브 문자로 마운트 (`\\.\X:`는 세션별이 아닌 전역 네임스페이스에 드라이브 생성)
+    let mount_point = if connection.global_mount {
+        format!("\\\\.\\{}:", drive_letter)
+    } else {
+        format!("{}:", drive_letter)
+    };
+    host.mount(&mount_point).map_err(|e| {
+        let err_str = format!("{:?}", e);
+        if connection.global_mount && err_str.contains("0xC0000022") {
+            format!(
+                "전역 드라이브 마운트 실패 ({}:): 관리자 권한으로 앱을 실행해야 합니다.",
+                drive_letter
+            )
+        } else {
+            format!("마운트 실패 ({}:): {}", drive_letter, err_str)
+        }
+    })?;
+
+    host.start_with_threads(tuning.worker_threads)
+        .map_err(|e| format!("파일시스템 시작 실패: {:?}", e))?;
+
+    Ok((host, quota))
+}
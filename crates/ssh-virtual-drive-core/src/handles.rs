@@ -0,0 +1,42 @@
+//! 핸들 모듈 - 세대(epoch)가 붙은 파일 핸들 ID
+//!
+//! 상위 비트에 세대를 넣어 재연결 전에 열린 핸들을 새 핸들과 구별한다.
+//! 세대가 다른 핸들은 "오래된 핸들, 다시 열기" 오류로 거부할 수 있다.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// 세대 아래에 둘 일련번호 비트 수 (세대는 남은 상위 24비트)
+const COUNTER_BITS: u32 = 40;
+const COUNTER_MASK: u64 = (1 << COUNTER_BITS) - 1;
+const EPOCH_MASK: u32 = (1 << (64 - COUNTER_BITS)) - 1;
+
+/// 세대별 핸들 ID 발급기
+pub struct HandleIds {
+    next: AtomicU64,
+}
+
+impl Default for HandleIds {
+    fn default() -> Self {
+        Self {
+            next: AtomicU64::new(1),
+        }
+    }
+}
+
+impl HandleIds {
+    /// 주어진 세대로 새 핸들 발급 (일련번호는 세대가 바뀌어도 이어서 증가)
+    pub fn allocate(&self, epoch: u32) -> u64 {
+        let counter = self.next.fetch_add(1, Ordering::Relaxed) & COUNTER_MASK;
+        (u64::from(epoch & EPOCH_MASK) << COUNTER_BITS) | counter
+    }
+}
+
+/// 핸들이 발급된 세대
+pub fn epoch_of(handle: u64) -> u32 {
+    (handle >> COUNTER_BITS) as u32
+}
+
+/// 핸들이 현재 세대에서 발급되었는지
+pub fn is_current(handle: u64, epoch: u32) -> bool {
+    epoch_of(handle) == epoch & EPOCH_MASK
+}
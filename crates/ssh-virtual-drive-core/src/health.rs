@@ -0,0 +1,103 @@
+//! 연결 상태 기록 모듈 - 마운트 중 주기적으로 잰 서버 응답 시간을 연결별로 보관
+//!
+//! [`MountManager::probe_health`](crate::mount::MountManager::probe_health)가 마운트된
+//! 드라이브마다 원격 루트의 정보를 읽어 응답 시간을 재고, 여기서 연결별로 최근
+//! [`HISTORY_LIMIT`]개만 남긴다 ([`PROBE_INTERVAL`] 간격이면 하루치). 응답이 없었던 측정은
+//! 빈 값으로 남겨 끊겼던 시간도 보이게 한다. 기록은 `health.json`에 저장해 앱을 다시 시작해도
+//! 유지되며, UI는 이 기록으로 연결의 안정성을 보여 주고 미러 서버끼리 비교할 수 있게 한다.
+
+use crate::storage;
+use crate::types::HealthSample;
+use log::warn;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 응답 시간 측정 간격
+pub const PROBE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 연결별로 남길 측정값 수
+pub const HISTORY_LIMIT: usize = 1440;
+
+type History = HashMap<String, VecDeque<HealthSample>>;
+
+/// 연결별 기록 (처음 쓸 때 파일에서 읽음)
+static HISTORY: Mutex<Option<History>> = Mutex::new(None);
+
+/// 연결의 상태 기록 (오래된 것부터)
+pub fn history(connection_id: &str) -> Vec<HealthSample> {
+    with_history(|history| {
+        history
+            .get(connection_id)
+            .map(|samples| samples.iter().cloned().collect())
+            .unwrap_or_default()
+    })
+}
+
+/// 연결들의 상태 기록 삭제 (연결 프로필을 지울 때)
+pub fn remove(connection_ids: &[String]) {
+    with_history(|history| {
+        let before = history.len();
+        history.retain(|id, _| !connection_ids.contains(id));
+        if history.len() != before {
+            save_quietly(history);
+        }
+    });
+}
+
+/// 측정 결과 추가 후 저장 (연결 ID, 응답 시간 - 응답이 없었으면 None)
+pub(crate) fn record(results: Vec<(String, Option<Duration>)>) {
+    if results.is_empty() {
+        return;
+    }
+    let at = unix_now();
+    with_history(|history| {
+        for (connection_id, latency) in results {
+            let samples = history.entry(connection_id).or_default();
+            samples.push_back(HealthSample {
+                at,
+                latency_ms: latency.map(|d| d.as_secs_f64() * 1000.0),
+            });
+            while samples.len() > HISTORY_LIMIT {
+                samples.pop_front();
+            }
+        }
+        save_quietly(history);
+    });
+}
+
+fn with_history<T>(f: impl FnOnce(&mut History) -> T) -> T {
+    let mut history = HISTORY.lock();
+    f(history.get_or_insert_with(load))
+}
+
+fn load() -> History {
+    storage::get_health_history_file()
+        .and_then(|file| fs::read_to_string(file).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_quietly(history: &History) {
+    let result = (|| -> Result<(), String> {
+        let file = storage::get_health_history_file()
+            .ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("디렉토리 생성 실패: {}", e))?;
+        }
+        let content =
+            serde_json::to_string(history).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+        fs::write(&file, content).map_err(|e| format!("파일 저장 실패: {}", e))
+    })();
+    if let Err(e) = result {
+        warn!("[Health] saving history failed: {}", e);
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,89 @@
+//! 이벤트 훅 모듈 - 마운트/언마운트/연결 끊김 시 연결별 로컬 명령 실행
+//!
+//! 명령은 `cmd /C`로 실행되며(PowerShell은 `powershell -File ...` 형태로 지정),
+//! 드라이브 정보는 환경 변수로 전달한다. 완료를 기다리지 않으므로 느린 훅이
+//! 마운트를 지연시키지 않는다.
+
+use crate::types::SshConnection;
+use log::{info, warn};
+use std::os::windows::process::CommandExt;
+use std::process::{Command, Stdio};
+
+/// 콘솔 창을 띄우지 않고 실행
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// 훅을 실행하는 시점
+#[derive(Debug, Clone, Copy)]
+pub enum HookEvent {
+    Mount,
+    Unmount,
+    ConnectionLost,
+}
+
+impl HookEvent {
+    fn name(self) -> &'static str {
+        match self {
+            HookEvent::Mount => "mount",
+            HookEvent::Unmount => "unmount",
+            HookEvent::ConnectionLost => "connection_lost",
+        }
+    }
+}
+
+/// 연결에 설정된 훅 실행 (설정이 없으면 아무것도 하지 않음)
+pub fn run_hook(connection: &SshConnection, drive_letter: Option<char>, event: HookEvent) {
+    let hooks = &connection.hooks;
+    let command = match event {
+        HookEvent::Mount => hooks.on_mount.as_deref(),
+        HookEvent::Unmount => hooks.on_unmount.as_deref(),
+        HookEvent::ConnectionLost => hooks.on_connection_lost.as_deref(),
+    };
+    let Some(command) = command.filter(|c| !c.trim().is_empty()) else {
+        return;
+    };
+
+    let drive = drive_letter
+        .or(connection.drive_letter)
+        .map(|letter| format!("{}:", letter))
+        .unwrap_or_default();
+    let spawned = Command::new("cmd")
+        .arg("/C")
+        .raw_arg(command)
+        .env("SVD_EVENT", event.name())
+        .env("SVD_DRIVE", &drive)
+        .env("SVD_CONNECTION_ID", &connection.id)
+        .env("SVD_CONNECTION_NAME", &connection.name)
+        .env("SVD_HOST", &connection.host)
+        .env("SVD_REMOTE_PATH", &connection.remote_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .creation_flags(CREATE_NO_WINDOW)
+        .spawn();
+
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            warn!(
+                "[Hooks] {} hook for '{}' failed to start: {}",
+                event.name(),
+                connection.name,
+                e
+            );
+            return;
+        }
+    };
+    info!(
+        "[Hooks] {} hook started for '{}'",
+        event.name(),
+        connection.name
+    );
+
+    // 종료 코드만 기록 (프로세스 회수)
+    let label = format!("{} hook for '{}'", event.name(), connection.name);
+    std::thread::spawn(move || match child.wait() {
+        Ok(status) if !status.success() => warn!("[Hooks] {} exited with {}", label, status),
+        Ok(_) => {}
+        Err(e) => warn!("[Hooks] {} wait failed: {}", label, e),
+    });
+}
@@ -0,0 +1,468 @@
+//! 로컬 IPC 모듈 - 이름 있는 파이프 위의 JSON-RPC 2.0
+//!
+//! 요청과 응답은 한 줄에 하나의 JSON 객체로 주고받는다 (줄바꿈으로 구분). 한 연결에서
+//! 여러 요청을 차례로 보낼 수 있고, 연결마다 스레드 하나가 처리한다.
+//!
+//! 파이프 접근 권한은 SDDL 문자열로 정한다. 서버 쪽 메서드 구현은 호출자가 넘기는
+//! 처리 함수가 맡고, 이 모듈은 전송과 JSON-RPC 형식만 다룬다.
+
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::ffi::{c_void, OsStr};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::iter::once;
+use std::os::windows::ffi::OsStrExt;
+use std::os::windows::io::{AsRawHandle, FromRawHandle, RawHandle};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 백그라운드 서비스가 여는 파이프
+pub const SERVICE_PIPE: &str = r"\\.\pipe\ssh-virtual-drive-service";
+
+/// 앱이 사용자별로 여는 제어 파이프 이름의 앞부분 (뒤에 사용자 SID와 세션 번호)
+pub const APP_PIPE_PREFIX: &str = r"\\.\pipe\ssh-virtual-drive-app-";
+
+/// JSON-RPC 오류 코드: 요청을 JSON으로 읽을 수 없음
+pub const PARSE_ERROR: i32 = -32700;
+/// JSON-RPC 오류 코드: 없는 메서드
+pub const METHOD_NOT_FOUND: i32 = -32601;
+/// JSON-RPC 오류 코드: 매개변수가 올바르지 않음
+pub const INVALID_PARAMS: i32 = -32602;
+/// JSON-RPC 오류 코드: 메서드 실행 실패 (앱 오류 메시지를 그대로 전달)
+pub const SERVER_ERROR: i32 = -32000;
+
+/// 서버가 다른 연결을 받는 중이면 클라이언트가 다시 시도하는 시간
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+const PIPE_BUFFER_SIZE: u32 = 64 * 1024;
+const PIPE_ACCESS_DUPLEX: u32 = 0x0000_0003;
+const PIPE_TYPE_BYTE: u32 = 0x0000_0000;
+const PIPE_WAIT: u32 = 0x0000_0000;
+const PIPE_REJECT_REMOTE_CLIENTS: u32 = 0x0000_0008;
+const PIPE_UNLIMITED_INSTANCES: u32 = 255;
+const FILE_FLAG_FIRST_PIPE_INSTANCE: u32 = 0x0008_0000;
+const ERROR_ACCESS_DENIED: i32 = 5;
+const TOKEN_QUERY: u32 = 0x0008;
+const TOKEN_USER_CLASS: u32 = 1;
+const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+/// LocalSystem 계정 SID
+const SYSTEM_SID: &str = "S-1-5-18";
+const ERROR_PIPE_BUSY: i32 = 231;
+const ERROR_PIPE_CONNECTED: i32 = 535;
+const SDDL_REVISION_1: u32 = 1;
+
+#[repr(C)]
+struct SecurityAttributes {
+    length: u32,
+    security_descriptor: *mut c_void,
+    inherit_handle: i32,
+}
+
+/// TOKEN_USER (SID_AND_ATTRIBUTES)
+#[repr(C)]
+struct TokenUser {
+    sid: *mut c_void,
+    attributes: u32,
+}
+
+#[link(name = "kernel32")]
+extern "system" {
+    fn GetCurrentProcess() -> *mut c_void;
+    fn OpenProcess(access: u32, inherit_handle: i32, process_id: u32) -> *mut c_void;
+    fn GetNamedPipeServerProcessId(pipe: *mut c_void, process_id: *mut u32) -> i32;
+    fn ProcessIdToSessionId(process_id: u32, session_id: *mut u32) -> i32;
+    fn LocalFree(memory: *mut c_void) -> *mut c_void;
+    fn CreateNamedPipeW(
+        name: *const u16,
+        open_mode: u32,
+        pipe_mode: u32,
+        max_instances: u32,
+        out_buffer_size: u32,
+        in_buffer_size: u32,
+        default_timeout: u32,
+        security_attributes: *const SecurityAttributes,
+    ) -> *mut c_void;
+    fn ConnectNamedPipe(pipe: *mut c_void, overlapped: *mut c_void) -> i32;
+    fn CloseHandle(handle: *mut c_void) -> i32;
+}
+
+#[link(name = "advapi32")]
+extern "system" {
+    fn ConvertStringSecurityDescriptorToSecurityDescriptorW(
+        sddl: *const u16,
+        revision: u32,
+        security_descriptor: *mut *mut c_void,
+        size: *mut u32,
+    ) -> i32;
+    fn OpenProcessToken(process: *mut c_void, access: u32, token: *mut *mut c_void) -> i32;
+    fn GetTokenInformation(
+        token: *mut c_void,
+        class: u32,
+        information: *mut c_void,
+        length: u32,
+        return_length: *mut u32,
+    ) -> i32;
+    fn ConvertSidToStringSidW(sid: *mut c_void, string_sid: *mut *mut u16) -> i32;
+}
+
+/// JSON-RPC 요청
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RpcRequest {
+    pub jsonrpc: String,
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// JSON-RPC 응답
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RpcResponse {
+    pub jsonrpc: String,
+    pub id: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+/// JSON-RPC 오류
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: i32,
+    pub message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// 메서드 실행 실패 (`Result<_, String>` 오류를 그대로 전달)
+    pub fn server(message: impl Into<String>) -> Self {
+        Self::new(SERVER_ERROR, message)
+    }
+}
+
+/// 메서드 이름과 매개변수를 받아 결과를 돌려주는 서버 쪽 처리 함수
+pub type RpcHandler = dyn Fn(&str, Value) -> Result<Value, RpcError> + Send + Sync;
+
+/// 매개변수를 메서드별 구조체로 읽음 (실패하면 `INVALID_PARAMS`)
+pub fn parse_params<T: for<'de> Deserialize<'de>>(params: Value) -> Result<T, RpcError> {
+    // 매개변수 없는 호출도 기본값이 있는 구조체로 받을 수 있도록 null은 빈 객체로 봄
+    let params = if params.is_null() {
+        Value::Object(Default::default())
+    } else {
+        params
+    };
+    serde_json::from_value(params)
+        .map_err(|e| RpcError::new(INVALID_PARAMS, format!("매개변수 오류: {}", e)))
+}
+
+/// 결과 값을 JSON으로 변환
+pub fn to_result<T: Serialize>(value: T) -> Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|e| RpcError::server(format!("JSON 직렬화 실패: {}", e)))
+}
+
+/// 현재 사용자의 앱 제어 파이프 (예: `\\.\pipe\ssh-virtual-drive-app-S-1-5-21-…-1001-1`)
+///
+/// 사용자 이름은 다른 계정(도메인/로컬 동명이인)과 겹칠 수 있어 SID를 쓰고, 같은 사용자의
+/// 다른 로그온 세션과도 섞이지 않도록 세션 번호를 붙인다.
+pub fn app_pipe_name() -> Result<String, String> {
+    let sid = current_user_sid()?;
+    let mut session = 0u32;
+    if unsafe { ProcessIdToSessionId(std::process::id(), &mut session) } == 0 {
+        return Err(format!(
+            "세션 번호 확인 실패: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    Ok(format!("{}{}-{}", APP_PIPE_PREFIX, sid, session))
+}
+
+/// 현재 프로세스 사용자의 SID 문자열 (예: `S-1-5-21-…-1001`)
+fn current_user_sid() -> Result<String, String> {
+    process_user_sid(unsafe { GetCurrentProcess() })
+}
+
+fn process_user_sid(process: *mut c_void) -> Result<String, String> {
+    let mut token: *mut c_void = std::ptr::null_mut();
+    if unsafe { OpenProcessToken(process, TOKEN_QUERY, &mut token) } == 0 {
+        return Err(format!(
+            "프로세스 토큰 열기 실패: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    let sid = token_user_sid(token);
+    unsafe { CloseHandle(token) };
+    sid
+}
+
+fn token_user_sid(token: *mut c_void) -> Result<String, String> {
+    let mut size = 0u32;
+    // 첫 호출은 필요한 크기만 받음 (버퍼가 없어 항상 실패)
+    unsafe { GetTokenInformation(token, TOKEN_USER_CLASS, std::ptr::null_mut(), 0, &mut size) };
+    // TOKEN_USER는 포인터로 시작하므로 8바이트 정렬 버퍼 사용
+    let mut buffer = vec![0u64; (size as usize).div_ceil(8)];
+    let queried = unsafe {
+        GetTokenInformation(
+            token,
+            TOKEN_USER_CLASS,
+            buffer.as_mut_ptr().cast(),
+            size,
+            &mut size,
+        )
+    };
+    if queried == 0 {
+        return Err(format!(
+            "사용자 SID 확인 실패: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    let user = unsafe { &*buffer.as_ptr().cast::<TokenUser>() };
+    let mut text: *mut u16 = std::ptr::null_mut();
+    if unsafe { ConvertSidToStringSidW(user.sid, &mut text) } == 0 {
+        return Err(format!(
+            "사용자 SID 변환 실패: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    let sid = unsafe {
+        let len = (0..).take_while(|&i| *text.add(i) != 0).count();
+        String::from_utf16_lossy(std::slice::from_raw_parts(text, len))
+    };
+    unsafe { LocalFree(text.cast()) };
+    Ok(sid)
+}
+
+/// 파이프 서버 실행 (돌아오지 않음, 연결마다 스레드에서 `handler` 호출)
+///
+/// `sddl`은 파이프 접근 권한이다 (예: `D:(A;;GA;;;SY)(A;;GA;;;BA)`는 SYSTEM과 관리자만).
+/// 원격 컴퓨터에서의 연결은 항상 거부한다. 다른 프로세스가 같은 이름의 파이프를 먼저 만들어
+/// 두었으면 요청을 가로챌 수 있으므로 시작하지 않고 실패를 돌려준다.
+pub fn serve(pipe_name: &str, sddl: &str, handler: Arc<RpcHandler>) -> Result<(), String> {
+    let name = to_wide(pipe_name);
+    let sddl = to_wide(sddl);
+    let mut descriptor: *mut c_void = std::ptr::null_mut();
+    // 보안 설명자는 서버가 도는 동안 계속 쓰므로 해제하지 않음
+    let converted = unsafe {
+        ConvertStringSecurityDescriptorToSecurityDescriptorW(
+            sddl.as_ptr(),
+            SDDL_REVISION_1,
+            &mut descriptor,
+            std::ptr::null_mut(),
+        )
+    };
+    if converted == 0 {
+        return Err(format!(
+            "파이프 권한 설정 실패: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    let attributes = SecurityAttributes {
+        length: std::mem::size_of::<SecurityAttributes>() as u32,
+        security_descriptor: descriptor,
+        inherit_handle: 0,
+    };
+
+    let create = |first: bool| {
+        let open_mode = if first {
+            PIPE_ACCESS_DUPLEX | FILE_FLAG_FIRST_PIPE_INSTANCE
+        } else {
+            PIPE_ACCESS_DUPLEX
+        };
+        let pipe = unsafe {
+            CreateNamedPipeW(
+                name.as_ptr(),
+                open_mode,
+                PIPE_TYPE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                PIPE_UNLIMITED_INSTANCES,
+                PIPE_BUFFER_SIZE,
+                PIPE_BUFFER_SIZE,
+                0,
+                &attributes,
+            )
+        };
+        if !pipe.is_null() && pipe as isize != -1 {
+            return Ok(pipe);
+        }
+        let error = std::io::Error::last_os_error();
+        if first && error.raw_os_error() == Some(ERROR_ACCESS_DENIED) {
+            return Err(format!(
+                "파이프 '{}'를 다른 프로세스가 이미 사용 중입니다.",
+                pipe_name
+            ));
+        }
+        Err(format!("파이프 생성 실패 ({}): {}", pipe_name, error))
+    };
+
+    let mut pipe = create(true)?;
+    loop {
+        let connected = unsafe { ConnectNamedPipe(pipe, std::ptr::null_mut()) } != 0
+            || std::io::Error::last_os_error().raw_os_error() == Some(ERROR_PIPE_CONNECTED);
+        let connect_error = std::io::Error::last_os_error();
+        // 지금 인스턴스를 닫기 전에 다음 인스턴스를 만들어 이름이 비는 틈을 두지 않음
+        let next = match create(false) {
+            Ok(next) => next,
+            Err(e) => {
+                unsafe { CloseHandle(pipe) };
+                return Err(e);
+            }
+        };
+        if !connected {
+            warn!("[IPC] client connect failed: {}", connect_error);
+            unsafe { CloseHandle(pipe) };
+            pipe = next;
+            continue;
+        }
+
+        // File이 drop 되면 핸들이 닫히고 클라이언트 연결이 끊김
+        let stream = unsafe { File::from_raw_handle(pipe as RawHandle) };
+        let handler = handler.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = handle_client(stream, handler.as_ref()) {
+                debug!("[IPC] client disconnected: {}", e);
+            }
+        });
+        pipe = next;
+    }
+}
+
+fn handle_client(stream: File, handler: &RpcHandler) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<RpcRequest>(&line) {
+            Ok(request) => {
+                debug!("[IPC] {}", request.method);
+                let (result, error) = match handler(&request.method, request.params) {
+                    Ok(result) => (Some(result), None),
+                    Err(error) => (None, Some(error)),
+                };
+                RpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: request.id,
+                    result,
+                    error,
+                }
+            }
+            Err(e) => RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: Value::Null,
+                result: None,
+                error: Some(RpcError::new(PARSE_ERROR, format!("요청 해석 실패: {}", e))),
+            },
+        };
+
+        let mut text = serde_json::to_string(&response)
+            .unwrap_or_else(|_| r#"{"jsonrpc":"2.0","id":null}"#.to_string());
+        text.push('\n');
+        writer.write_all(text.as_bytes())?;
+        writer.flush()?;
+    }
+}
+
+/// 파이프 서버에 요청 하나를 보내고 결과를 받음
+pub fn call(pipe_name: &str, method: &str, params: Value) -> Result<Value, String> {
+    request(&open_pipe(pipe_name)?, method, params)
+}
+
+/// 파이프 서버가 LocalSystem 계정으로 실행 중인지 확인한 뒤 요청
+///
+/// 서비스가 멈춘 동안에는 아무 프로세스나 같은 이름의 파이프를 만들 수 있으므로
+/// 비밀 값을 보내는 요청은 이 함수로 보낸다.
+pub fn call_system(pipe_name: &str, method: &str, params: Value) -> Result<Value, String> {
+    let stream = open_pipe(pipe_name)?;
+    let sid = server_user_sid(&stream)
+        .map_err(|e| format!("파이프 '{}'의 서버 확인 실패: {}", pipe_name, e))?;
+    if sid != SYSTEM_SID {
+        return Err(format!(
+            "파이프 '{}'를 서비스가 아닌 프로세스({})가 열었습니다.",
+            pipe_name, sid
+        ));
+    }
+    request(&stream, method, params)
+}
+
+/// 파이프 서버 프로세스 사용자의 SID
+fn server_user_sid(stream: &File) -> Result<String, String> {
+    let mut process_id = 0u32;
+    if unsafe { GetNamedPipeServerProcessId(stream.as_raw_handle(), &mut process_id) } == 0 {
+        return Err(format!(
+            "서버 프로세스 확인 실패: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    let process = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, process_id) };
+    if process.is_null() {
+        return Err(format!(
+            "서버 프로세스 열기 실패: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    let sid = process_user_sid(process);
+    unsafe { CloseHandle(process) };
+    sid
+}
+
+fn request(stream: &File, method: &str, params: Value) -> Result<Value, String> {
+    let request = RpcRequest {
+        jsonrpc: "2.0".to_string(),
+        id: Value::from(1),
+        method: method.to_string(),
+        params,
+    };
+    let mut text =
+        serde_json::to_string(&request).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+    text.push('\n');
+    (&*stream)
+        .write_all(text.as_bytes())
+        .map_err(|e| format!("요청 전송 실패: {}", e))?;
+
+    let mut line = String::new();
+    BufReader::new(stream)
+        .read_line(&mut line)
+        .map_err(|e| format!("응답 읽기 실패: {}", e))?;
+    let response: RpcResponse =
+        serde_json::from_str(&line).map_err(|e| format!("응답 해석 실패: {}", e))?;
+    match response.error {
+        Some(error) => Err(error.message),
+        None => Ok(response.result.unwrap_or(Value::Null)),
+    }
+}
+
+/// 파이프 열기 (모든 인스턴스가 사용 중이면 잠시 다시 시도)
+fn open_pipe(pipe_name: &str) -> Result<File, String> {
+    let started = Instant::now();
+    loop {
+        match OpenOptions::new().read(true).write(true).open(pipe_name) {
+            Ok(file) => return Ok(file),
+            Err(e)
+                if e.raw_os_error() == Some(ERROR_PIPE_BUSY)
+                    && started.elapsed() < CONNECT_TIMEOUT =>
+            {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            Err(e) => return Err(format!("파이프 연결 실패 ({}): {}", pipe_name, e)),
+        }
+    }
+}
+
+fn to_wide(value: &str) -> Vec<u16> {
+    OsStr::new(value).encode_wide().chain(once(0)).collect()
+}
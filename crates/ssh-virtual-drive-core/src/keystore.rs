@@ -0,0 +1,294 @@
+//! 키 저장소 모듈 - 앱 데이터 디렉토리에서 관리하는 SSH 개인 키
+//!
+//! 연결은 임의의 파일 경로 대신 `key_id`로 저장소의 키를 참조할 수 있다.
+//! 키 파일은 현재 사용자만 접근하도록 권한을 좁힌 `keys/store` 아래에 OpenSSH 형식으로
+//! 보관하며, 보호를 선택하면 자격 증명 관리자에 둔 임의 키로 암호화(AES-256-CBC +
+//! HMAC-SHA256)한다. Windows용 libssh2는 메모리의 키를 받지 못하므로 보호된 키는
+//! 인증하는 동안만 같은 디렉토리에 임시 파일로 풀어 둔다.
+
+use crate::credentials;
+use crate::keyfile;
+use crate::storage;
+use crate::types::{KeyFormat, StoredKey};
+use crate::xattr::{from_hex, to_hex};
+use aes::Aes256;
+use cbc::cipher::block_padding::Pkcs7;
+use cbc::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use hmac::{Hmac, Mac};
+use log::warn;
+use sha2::Sha256;
+use std::fs;
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+/// 콘솔 창을 띄우지 않고 실행
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// 암호화 키 길이 (AES-256 키 32바이트 + HMAC 키 32바이트)
+pub(crate) const SECRET_LEN: usize = 64;
+
+const IV_LEN: usize = 16;
+const MAC_LEN: usize = 32;
+
+/// 인증에 쓸 키 파일 (임시로 풀어 둔 파일이면 Drop 시 삭제)
+pub struct KeyFileGuard {
+    path: PathBuf,
+    temporary: bool,
+}
+
+impl KeyFileGuard {
+    /// 사용자가 지정한 기존 키 파일
+    pub fn existing(path: PathBuf) -> Self {
+        Self {
+            path,
+            temporary: false,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for KeyFileGuard {
+    fn drop(&mut self) {
+        if self.temporary {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// 저장소의 키 목록
+pub fn list() -> Result<Vec<StoredKey>, String> {
+    storage::load_stored_keys()
+}
+
+/// 기존 키 파일을 저장소로 가져오기 (PPK는 OpenSSH 형식으로 변환)
+pub fn import(path: &str, name: &str, protect: bool) -> Result<StoredKey, String> {
+    let info = keyfile::validate_key_file(path)?;
+    match info.format {
+        KeyFormat::OpenSsh | KeyFormat::Pem | KeyFormat::Ppk => {}
+        KeyFormat::Public => {
+            return Err("공개 키는 가져올 수 없습니다. 개인 키 파일을 선택해주세요.".to_string())
+        }
+        KeyFormat::Unknown => return Err("SSH 개인 키 형식을 인식할 수 없습니다.".to_string()),
+    }
+    if info.encrypted {
+        return Err(
+            "암호로 보호된 키는 가져올 수 없습니다. 암호를 제거한 뒤 다시 시도해주세요."
+                .to_string(),
+        );
+    }
+
+    let text = fs::read_to_string(path).map_err(|e| format!("키 파일 읽기 실패: {}", e))?;
+    let material = if info.format == KeyFormat::Ppk {
+        keyfile::ppk_to_openssh(&text)?
+    } else {
+        text
+    };
+    store(name, &material, info.key_type, info.fingerprint, protect)
+}
+
+/// 새 ed25519 키 생성 (Windows에 포함된 OpenSSH `ssh-keygen` 사용)
+pub fn generate(name: &str, protect: bool) -> Result<StoredKey, String> {
+    let dir = store_dir()?;
+    let temp = dir.join(format!("{}.tmp", Uuid::new_v4()));
+    let output = Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-C", name, "-f"])
+        .arg(&temp)
+        .stdin(Stdio::null())
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| format!("ssh-keygen 실행 실패 (OpenSSH 클라이언트 설치 필요): {}", e))?;
+
+    let public = temp.with_extension("tmp.pub");
+    let result = if output.status.success() {
+        keyfile::validate_key_file(&temp.to_string_lossy()).and_then(|info| {
+            let material =
+                fs::read_to_string(&temp).map_err(|e| format!("생성한 키 읽기 실패: {}", e))?;
+            store(name, &material, info.key_type, info.fingerprint, protect)
+        })
+    } else {
+        Err(format!(
+            "키 생성 실패: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    };
+    let _ = fs::remove_file(&temp);
+    let _ = fs::remove_file(&public);
+    result
+}
+
+/// 저장소에서 키 삭제 (사용 중인 연결이 있으면 실패)
+pub fn delete(id: &str) -> Result<(), String> {
+    let users: Vec<String> = storage::load_connections()?
+        .into_iter()
+        .filter(|c| c.key_id.as_deref() == Some(id))
+        .map(|c| c.name)
+        .collect();
+    if !users.is_empty() {
+        return Err(format!(
+            "이 키를 사용하는 연결이 있습니다: {}",
+            users.join(", ")
+        ));
+    }
+
+    let mut keys = storage::load_stored_keys()?;
+    keys.retain(|k| k.id != id);
+    storage::save_stored_keys(&keys)?;
+
+    let dir = store_dir()?;
+    let _ = fs::remove_file(dir.join(format!("{}.key", id)));
+    let _ = fs::remove_file(dir.join(format!("{}.key.enc", id)));
+    let _ = credentials::delete_password(&credentials::keystore_credential_key(id));
+    Ok(())
+}
+
+/// 인증에 넘길 키 파일 (보호된 키는 임시 파일로 복호화)
+pub fn auth_key_file(id: &str) -> Result<KeyFileGuard, String> {
+    let key = storage::load_stored_keys()?
+        .into_iter()
+        .find(|k| k.id == id)
+        .ok_or_else(|| "저장소에서 SSH 키를 찾을 수 없습니다.".to_string())?;
+    let dir = store_dir()?;
+    if !key.protected {
+        return Ok(KeyFileGuard::existing(dir.join(format!("{}.key", id))));
+    }
+
+    let secret = load_secret(id)?;
+    let data = fs::read(dir.join(format!("{}.key.enc", id)))
+        .map_err(|e| format!("저장된 키 읽기 실패: {}", e))?;
+    let material = decrypt(&data, &secret)?;
+    temporary_key_file(&material)
+}
+
+/// 키 내용을 저장소 디렉토리의 임시 파일로 풀어 둠 (가드가 Drop되면 삭제)
+pub(crate) fn temporary_key_file(material: &[u8]) -> Result<KeyFileGuard, String> {
+    let guard = KeyFileGuard {
+        path: store_dir()?.join(format!("{}.tmp", Uuid::new_v4())),
+        temporary: true,
+    };
+    fs::write(&guard.path, material).map_err(|e| format!("임시 키 파일 생성 실패: {}", e))?;
+    Ok(guard)
+}
+
+fn store(
+    name: &str,
+    material: &str,
+    key_type: Option<String>,
+    fingerprint: Option<String>,
+    protect: bool,
+) -> Result<StoredKey, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("키 이름을 입력해주세요.".to_string());
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let dir = store_dir()?;
+    if protect {
+        let mut secret = [0u8; SECRET_LEN];
+        getrandom::getrandom(&mut secret).map_err(|e| format!("난수 생성 실패: {}", e))?;
+        let encrypted = encrypt(material.as_bytes(), &secret)?;
+        credentials::save_password(&credentials::keystore_credential_key(&id), &to_hex(&secret))?;
+        fs::write(dir.join(format!("{}.key.enc", id)), encrypted)
+            .map_err(|e| format!("키 저장 실패: {}", e))?;
+    } else {
+        fs::write(dir.join(format!("{}.key", id)), material)
+            .map_err(|e| format!("키 저장 실패: {}", e))?;
+    }
+
+    let key = StoredKey {
+        id,
+        name: name.to_string(),
+        key_type,
+        fingerprint,
+        protected: protect,
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    };
+    let mut keys = storage::load_stored_keys()?;
+    keys.push(key.clone());
+    storage::save_stored_keys(&keys)?;
+    Ok(key)
+}
+
+/// 키 파일 디렉토리 (처음 만들 때 상속 권한을 끊고 현재 사용자에게만 권한 부여)
+fn store_dir() -> Result<PathBuf, String> {
+    let dir = storage::get_data_dir()
+        .ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?
+        .join("keys")
+        .join("store");
+    if dir.exists() {
+        return Ok(dir);
+    }
+    fs::create_dir_all(&dir).map_err(|e| format!("키 디렉토리 생성 실패: {}", e))?;
+
+    let user = std::env::var("USERNAME").unwrap_or_default();
+    if !user.is_empty() {
+        let restricted = Command::new("icacls")
+            .arg(&dir)
+            .args(["/inheritance:r", "/grant:r"])
+            .arg(format!("{}:(OI)(CI)F", user))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .creation_flags(CREATE_NO_WINDOW)
+            .status();
+        if !restricted.is_ok_and(|status| status.success()) {
+            warn!("[KeyStore] could not restrict permissions of {:?}", dir);
+        }
+    }
+    Ok(dir)
+}
+
+fn load_secret(id: &str) -> Result<[u8; SECRET_LEN], String> {
+    let hex = credentials::get_password(&credentials::keystore_credential_key(id))?
+        .ok_or_else(|| "자격 증명 관리자에 키 암호화 정보가 없습니다.".to_string())?;
+    let bytes = from_hex(&hex).ok_or_else(|| "키 암호화 정보가 손상되었습니다.".to_string())?;
+    bytes
+        .try_into()
+        .map_err(|_| "키 암호화 정보가 손상되었습니다.".to_string())
+}
+
+/// IV + 암호문 + HMAC (암호화 후 인증)
+pub(crate) fn encrypt(plain: &[u8], secret: &[u8; SECRET_LEN]) -> Result<Vec<u8>, String> {
+    let (enc_key, mac_key) = secret.split_at(32);
+    let mut iv = [0u8; IV_LEN];
+    getrandom::getrandom(&mut iv).map_err(|e| format!("난수 생성 실패: {}", e))?;
+
+    let ciphertext = cbc::Encryptor::<Aes256>::new(enc_key.into(), &iv.into())
+        .encrypt_padded_vec_mut::<Pkcs7>(plain);
+    let mut out = iv.to_vec();
+    out.extend_from_slice(&ciphertext);
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(mac_key)
+        .map_err(|e| format!("키 암호화 초기화 실패: {}", e))?;
+    mac.update(&out);
+    out.extend_from_slice(&mac.finalize().into_bytes());
+    Ok(out)
+}
+
+pub(crate) fn decrypt(data: &[u8], secret: &[u8; SECRET_LEN]) -> Result<Vec<u8>, String> {
+    let corrupted = || "저장된 키가 손상되었습니다.".to_string();
+    if data.len() < IV_LEN + MAC_LEN {
+        return Err(corrupted());
+    }
+    let (enc_key, mac_key) = secret.split_at(32);
+    let (body, tag) = data.split_at(data.len() - MAC_LEN);
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(mac_key)
+        .map_err(|e| format!("키 복호화 초기화 실패: {}", e))?;
+    mac.update(body);
+    mac.verify_slice(tag).map_err(|_| corrupted())?;
+
+    let (iv, ciphertext) = body.split_at(IV_LEN);
+    cbc::Decryptor::<Aes256>::new(enc_key.into(), iv.into())
+        .decrypt_padded_vec_mut::<Pkcs7>(ciphertext)
+        .map_err(|_| corrupted())
+}
@@ -0,0 +1,74 @@
+//! SSH 가상 드라이브 엔진 - SFTP 클라이언트와 WinFsp 파일시스템
+//!
+//! Tauri 앱과 분리된 마운트 엔진이다. CLI나 서비스 같은 다른 도구도 같은 연결 프로필,
+//! 자격 증명, 캐시, 쓰기 지연 저널을 그대로 쓰며 드라이브를 마운트할 수 있다.
+//!
+//! - [`mount::MountManager`]: 드라이브 마운트/언마운트와 상태 조회
+//! - [`sftp_client::SftpClient`]: 마운트 없이 원격 파일에 접근하는 SFTP 세션
+//! - [`filesystem`]: WinFsp 호스트를 직접 구성할 때 쓰는 파일시스템 구현
+//! - [`storage`], [`credentials`]: 앱과 공유하는 연결 프로필과 자격 증명 저장소
+//! - [`ipc`]: 백그라운드 서비스와 앱 사이의 이름 있는 파이프 JSON-RPC
+//!
+//! 마운트하기 전에 [`mount::setup_winfsp_path`]로 WinFsp DLL을 찾을 수 있게 해야 한다
+//! (실행 파일은 WinFsp DLL을 지연 로딩하도록 링크해야 함).
+//!
+//! ```no_run
+//! use ssh_virtual_drive_core::{logging, mount};
+//!
+//! logging::init();
+//! mount::setup_winfsp_path();
+//!
+//! let manager = mount::MountManager::default();
+//! let status = manager.mount_saved("connection-id", 'Z')?;
+//! println!("{}: {:?}", status.drive_letter, status.status);
+//! manager.unmount('Z')?;
+//! # Ok::<(), String>(())
+//! ```
+
+mod archive;
+pub mod bookmarks;
+pub mod cache;
+pub mod confirm;
+pub mod credentials;
+mod environment;
+mod explorer;
+pub mod filesystem;
+mod handles;
+pub mod health;
+mod hooks;
+pub mod ipc;
+pub mod keyfile;
+pub mod keystore;
+pub mod limits;
+mod listing;
+pub mod logging;
+pub mod memory;
+pub mod mount;
+mod names;
+mod network;
+mod offline;
+pub mod onboarding;
+pub mod path_filter;
+mod process;
+pub mod profile;
+pub mod profile_sync;
+mod quota;
+pub mod reauth;
+mod reconnect;
+pub mod sftp_client;
+pub mod site_import;
+mod snapshot;
+mod statbatch;
+pub mod stats;
+pub mod storage;
+mod streams;
+pub mod templates;
+mod throttle;
+pub mod totp;
+pub mod traffic;
+pub mod trash;
+pub mod types;
+mod versions;
+mod warm;
+pub mod writeback;
+pub mod xattr;
@@ -0,0 +1,146 @@
+//! 자원 한도 모듈 - 여러 서버를 한꺼번에 마운트해도 핸들과 메모리가 바닥나지 않도록 제한
+//!
+//! 동시에 마운트할 드라이브 수, 모든 드라이브의 WinFsp 작업 스레드 합계, 메타데이터 캐시
+//! 메모리 합계에 한도를 둔다. [`MountManager`](crate::mount::MountManager)는 마운트할 때마다
+//! 이미 마운트된 드라이브가 쓰는 양을 빼고 남은 만큼만 새 드라이브에 나눠 주고, 남은 양이
+//! 드라이브 하나에 필요한 최소량보다 적으면 마운트를 거부한다. 설정은 다음 마운트부터 적용된다.
+
+use crate::memory::{self, CacheBudget};
+use crate::storage;
+use crate::types::ResourceLimits;
+use std::fs;
+
+/// 드라이브 하나에 필요한 최소 작업 스레드 수 (WinFsp 기본값의 하한과 같음)
+pub const MIN_WORKER_THREADS: u32 = 2;
+
+/// 드라이브 하나에 필요한 최소 캐시 메모리 (MB)
+pub const MIN_CACHE_MB: u32 = 4;
+
+/// 캐시 메모리 한도가 있을 때 드라이브 하나에 나눠 주는 기본 캐시 메모리 (MB)
+pub const DEFAULT_CACHE_SHARE_MB: u32 = 64;
+
+/// 드라이브 하나가 쓰는 자원
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Allocation {
+    pub worker_threads: u32,
+    /// 캐시 메모리 예산 (MB, None이면 항목 수로만 제한)
+    pub cache_mb: Option<u32>,
+}
+
+/// 저장된 자원 한도 (없으면 제한 없음)
+pub fn settings() -> ResourceLimits {
+    storage::get_resource_limits_file()
+        .and_then(|file| fs::read_to_string(file).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// 자원 한도 저장 (다음 마운트부터 적용)
+pub fn save_settings(limits: &ResourceLimits) -> Result<(), String> {
+    if limits.max_mounts == Some(0) {
+        return Err("동시에 마운트할 드라이브 수는 1 이상이어야 합니다.".to_string());
+    }
+    if limits
+        .max_worker_threads
+        .is_some_and(|n| n < MIN_WORKER_THREADS)
+    {
+        return Err(format!(
+            "작업 스레드 한도는 {}개 이상이어야 합니다.",
+            MIN_WORKER_THREADS
+        ));
+    }
+    if limits.max_cache_mb.is_some_and(|mb| mb < MIN_CACHE_MB) {
+        return Err(format!(
+            "캐시 메모리 한도는 {}MB 이상이어야 합니다.",
+            MIN_CACHE_MB
+        ));
+    }
+    let file = storage::get_resource_limits_file()
+        .ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("디렉토리 생성 실패: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(limits).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+    fs::write(&file, content).map_err(|e| format!("파일 저장 실패: {}", e))
+}
+
+/// 새 드라이브에 나눠 줄 자원 (`used`는 이미 마운트했거나 마운트 중인 드라이브의 자원)
+///
+/// `cache_budget`은 프로필과 메모리 설정으로 정해진 캐시 예산이며, 캐시 메모리 한도가 있으면
+/// 남은 양 안으로 줄인다 (예산이 없던 드라이브는 [`DEFAULT_CACHE_SHARE_MB`]를 쓰는 것으로 셈).
+pub(crate) fn allocate(
+    limits: &ResourceLimits,
+    used: &[Allocation],
+    cache_budget: Option<CacheBudget>,
+) -> Result<Allocation, String> {
+    if let Some(max) = limits.max_mounts {
+        if used.len() >= max as usize {
+            return Err(format!(
+                "동시에 마운트할 수 있는 드라이브 수({}개)에 닿았습니다. 다른 드라이브를 연결 해제하거나 자원 한도를 늘려주세요.",
+                max
+            ));
+        }
+    }
+
+    let default_threads = default_worker_threads();
+    let worker_threads = match limits.max_worker_threads {
+        None => default_threads,
+        Some(max) => {
+            let in_use: u32 = used.iter().map(|a| a.worker_threads).sum();
+            let left = max.saturating_sub(in_use);
+            if left < MIN_WORKER_THREADS {
+                return Err(format!(
+                    "작업 스레드 한도({}개) 중 {}개를 마운트된 드라이브가 쓰고 있어 새 드라이브에 필요한 {}개를 나눌 수 없습니다. 다른 드라이브를 연결 해제하거나 자원 한도를 늘려주세요.",
+                    max, in_use, MIN_WORKER_THREADS
+                ));
+            }
+            default_threads.min(left)
+        }
+    };
+
+    let own_cache_mb = cache_budget.map(|budget| (budget.total() / (1024 * 1024)) as u32);
+    let cache_mb = match limits.max_cache_mb {
+        None => own_cache_mb,
+        Some(max) => {
+            let in_use: u32 = used
+                .iter()
+                .map(|a| a.cache_mb.unwrap_or(DEFAULT_CACHE_SHARE_MB))
+                .sum();
+            let left = max.saturating_sub(in_use);
+            if left < MIN_CACHE_MB {
+                return Err(format!(
+                    "캐시 메모리 한도({}MB) 중 {}MB를 마운트된 드라이브가 쓰고 있어 새 드라이브에 필요한 {}MB를 나눌 수 없습니다. 다른 드라이브를 연결 해제하거나 자원 한도를 늘려주세요.",
+                    max, in_use, MIN_CACHE_MB
+                ));
+            }
+            Some(own_cache_mb.unwrap_or(DEFAULT_CACHE_SHARE_MB).min(left))
+        }
+    };
+
+    Ok(Allocation {
+        worker_threads,
+        cache_mb,
+    })
+}
+
+/// 나눠 받은 캐시 메모리로 만든 캐시 예산 (원래 예산보다 크게 늘리지 않음)
+pub(crate) fn cache_budget(
+    allocation: &Allocation,
+    own: Option<CacheBudget>,
+) -> Option<CacheBudget> {
+    let mb = allocation.cache_mb?;
+    let total = mb as usize * 1024 * 1024;
+    match own {
+        Some(budget) if budget.total() <= total => Some(budget),
+        _ => Some(memory::cache_budget(total)),
+    }
+}
+
+/// 드라이브 하나의 기본 작업 스레드 수 (WinFsp 기본값처럼 CPU 수, 최소 2)
+fn default_worker_threads() -> u32 {
+    std::thread::available_parallelism()
+        .map(|n| n.get() as u32)
+        .unwrap_or(MIN_WORKER_THREADS)
+        .max(MIN_WORKER_THREADS)
+}
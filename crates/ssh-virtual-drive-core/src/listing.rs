@@ -0,0 +1,188 @@
+//! 큰 디렉토리 목록 모듈 - 목록을 다 읽기 전에 앞부분부터 탐색기에 보여줌
+//!
+//! 캐시에 없는 디렉토리는 첫 묶음만 먼저 읽는다. 다 읽지 못했으면 나머지는 백그라운드에서
+//! 묶음 단위로 이어 읽고, 그동안 read_directory는 지금까지 읽은 항목으로 답하되 끝 표시를
+//! 하지 않는다. 탐색기는 마지막으로 받은 이름을 marker로 다음 부분을 다시 요청하므로, 수십만
+//! 항목 디렉토리도 전체 목록을 기다리지 않고 바로 내용이 보인다. 다 읽으면 보통 목록처럼
+//! 캐시에 넣는다.
+
+use crate::cache::StatCache;
+use crate::sftp_client::{SftpClient, SharedSftpClient};
+use log::{debug, info};
+use parking_lot::{Condvar, Mutex};
+use ssh2::FileStat;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 처음에 바로 읽는 항목 수 (이보다 작은 디렉토리는 한 번에 다 읽음)
+const FIRST_PAGE: usize = 2000;
+
+/// 백그라운드에서 한 번에 읽는 항목 수 (묶음 사이에 다른 요청이 세션을 씀)
+const BATCH: usize = 2000;
+
+/// 한 번의 read_directory에 넘기는 최대 항목 수 (버퍼에 들어가는 만큼보다 넉넉하게)
+const PAGE: usize = 1024;
+
+/// 다음 묶음을 기다리는 최대 시간
+const WAIT_FOR_MORE: Duration = Duration::from_secs(30);
+
+/// read_directory 한 번에 넘길 목록
+pub(crate) struct DirPage {
+    pub entries: Vec<(String, FileStat)>,
+    // marker 다음 항목부터인지 (아니면 처음부터 전체)
+    pub after_marker: bool,
+    // 목록 끝까지 넘겼는지 (아니면 탐색기가 이어서 요청)
+    pub complete: bool,
+}
+
+#[derive(Default)]
+struct State {
+    entries: Vec<(String, FileStat)>,
+    done: bool,
+    error: Option<String>,
+}
+
+/// 읽는 중인 디렉토리 목록
+#[derive(Default)]
+pub(crate) struct PartialListing {
+    state: Mutex<State>,
+    grown: Condvar,
+}
+
+impl PartialListing {
+    /// marker 다음 항목 (새 항목이 없으면 다음 묶음이나 끝까지 대기)
+    ///
+    /// marker가 목록에 없으면 처음부터 넘긴다.
+    pub(crate) fn entries_after(&self, marker: Option<&str>) -> Result<DirPage, String> {
+        let deadline = Instant::now() + WAIT_FOR_MORE;
+        let mut state = self.state.lock();
+        loop {
+            let start = marker
+                .and_then(|m| state.entries.iter().rposition(|(name, _)| name == m))
+                .map_or(0, |index| index + 1);
+            if start < state.entries.len() || state.done {
+                let end = state.entries.len().min(start + PAGE);
+                return Ok(DirPage {
+                    entries: state.entries[start..end].to_vec(),
+                    after_marker: true,
+                    complete: state.done && end == state.entries.len(),
+                });
+            }
+            if let Some(e) = &state.error {
+                return Err(e.clone());
+            }
+            if self.grown.wait_until(&mut state, deadline).timed_out() {
+                return Err("디렉토리 목록을 읽는 데 시간이 너무 오래 걸립니다.".to_string());
+            }
+        }
+    }
+
+    fn extend(&self, batch: Vec<(String, FileStat)>, done: bool) {
+        let mut state = self.state.lock();
+        state.entries.extend(batch);
+        state.done = done;
+        self.grown.notify_all();
+    }
+
+    fn fail(&self, error: String) {
+        self.state.lock().error = Some(error);
+        self.grown.notify_all();
+    }
+}
+
+/// 첫 묶음을 읽은 결과
+pub(crate) enum FirstPage {
+    /// 한 번에 다 읽음 (보통 목록처럼 처리)
+    Complete(Vec<(String, FileStat)>),
+    /// 나머지는 백그라운드에서 읽는 중
+    Partial(Arc<PartialListing>),
+}
+
+/// 마운트의 읽는 중인 디렉토리 목록 (원격 경로별)
+#[derive(Default)]
+pub(crate) struct Listings {
+    active: Arc<Mutex<HashMap<String, Arc<PartialListing>>>>,
+}
+
+impl Listings {
+    /// 읽는 중인 목록 (없으면 None)
+    pub(crate) fn get(&self, path: &str) -> Option<Arc<PartialListing>> {
+        self.active.lock().get(path).cloned()
+    }
+
+    /// 첫 묶음을 읽고, 남은 항목이 있으면 백그라운드에서 이어 읽기 시작
+    ///
+    /// 첫 묶음은 호출한 쪽이 잡고 있는 세션(`client`)으로 읽고, 나머지는 `shared`의 lock을
+    /// 묶음마다 잡았다 놓으며 읽는다.
+    pub(crate) fn read(
+        &self,
+        client: &SftpClient,
+        shared: &SharedSftpClient,
+        cache: &Arc<StatCache>,
+        path: &str,
+    ) -> Result<FirstPage, String> {
+        let mut dir = client.open_dir(path)?;
+        let (first, done) = client.read_dir_batch(path, &mut dir, FIRST_PAGE)?;
+        if done {
+            return Ok(FirstPage::Complete(first));
+        }
+
+        for (name, stat) in &first {
+            cache.insert_stat(&child_path(path, name), stat.clone());
+        }
+        let listing = Arc::new(PartialListing::default());
+        listing.extend(first, false);
+        self.active.lock().insert(path.to_string(), listing.clone());
+        info!(
+            "[Listing] '{}' is large, listing the rest in background",
+            path
+        );
+
+        let client = shared.clone();
+        let cache = cache.clone();
+        let active = self.active.clone();
+        let partial = listing.clone();
+        let path = path.to_string();
+        std::thread::spawn(move || {
+            loop {
+                let result = client.lock().read_dir_batch(&path, &mut dir, BATCH);
+                match result {
+                    Ok((batch, done)) => {
+                        // 탐색기가 보이는 항목을 바로 열 수 있도록 stat은 먼저 캐시
+                        for (name, stat) in &batch {
+                            cache.insert_stat(&child_path(&path, name), stat.clone());
+                        }
+                        partial.extend(batch, done);
+                        if done {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        debug!("[Listing] '{}' failed: {}", path, e);
+                        partial.fail(e);
+                        cache.invalidate(&path);
+                        active.lock().remove(&path);
+                        return;
+                    }
+                }
+            }
+            {
+                let state = partial.state.lock();
+                info!(
+                    "[Listing] '{}' finished with {} entries",
+                    path,
+                    state.entries.len()
+                );
+                cache.insert_dir(&path, &state.entries);
+            }
+            // 이후 요청은 캐시의 전체 목록으로 답함
+            active.lock().remove(&path);
+        });
+        Ok(FirstPage::Partial(listing))
+    }
+}
+
+fn child_path(dir: &str, name: &str) -> String {
+    format!("{}/{}", dir.trim_end_matches('/'), name)
+}
@@ -0,0 +1,237 @@
+//! 로그 모듈 - `log` 매크로 출력을 표준 오류로 기록하고 필요하면 민감한 정보를 가림
+//!
+//! 가림 모드에서는 경로의 각 구성 요소와 호스트 이름/IP를 실행마다 새로 만든 salt로
+//! 해시한 짧은 값으로 바꾼다. 같은 실행 안에서는 같은 경로가 같은 값이 되므로 로그를
+//! 따라가며 문제를 추적할 수 있고, 소요 시간이나 작업 종류 같은 나머지 내용은 그대로 남는다.
+//!
+//! 경로는 `'...'`로 감싼 부분과 `/` 또는 `\`가 들어 있는 단어로 찾는다.
+//! 호스트 이름은 연결할 때 [`register_host`]로 등록한 이름을 가린다.
+
+use crate::storage;
+use crate::types::LogSettings;
+use crate::xattr::to_hex;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use parking_lot::RwLock;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fs;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 해시 표기 길이 (16진수 문자 수)
+const HASH_LEN: usize = 8;
+
+struct Logger {
+    level: LevelFilter,
+    redact: AtomicBool,
+    salt: [u8; 16],
+    hosts: RwLock<BTreeSet<String>>,
+}
+
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+/// 로거 설치 (`SVD_LOG`로 수준, `SVD_LOG_REDACT=1`로 가림 모드 강제)
+pub fn init() {
+    let level = std::env::var("SVD_LOG")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(if cfg!(debug_assertions) {
+            LevelFilter::Debug
+        } else {
+            LevelFilter::Info
+        });
+    let forced = std::env::var("SVD_LOG_REDACT").is_ok_and(|value| value == "1");
+    let mut salt = [0u8; 16];
+    let _ = getrandom::getrandom(&mut salt);
+
+    let logger = LOGGER.get_or_init(|| Logger {
+        level,
+        redact: AtomicBool::new(forced || load_settings().redact),
+        salt,
+        hosts: RwLock::new(BTreeSet::new()),
+    });
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+/// 현재 로그 설정
+pub fn settings() -> LogSettings {
+    LogSettings {
+        redact: LOGGER
+            .get()
+            .is_some_and(|logger| logger.redact.load(Ordering::Relaxed)),
+    }
+}
+
+/// 로그 설정 변경 (즉시 적용되고 다음 실행에도 유지)
+pub fn apply_settings(settings: &LogSettings) -> Result<(), String> {
+    if let Some(logger) = LOGGER.get() {
+        logger.redact.store(settings.redact, Ordering::Relaxed);
+    }
+    let file = storage::get_log_settings_file()
+        .ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("디렉토리 생성 실패: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(settings).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+    fs::write(&file, content).map_err(|e| format!("파일 저장 실패: {}", e))
+}
+
+/// 가림 모드에서 숨길 호스트 이름 등록 (연결할 때마다 호출)
+pub fn register_host(host: &str) {
+    let host = host.trim();
+    if host.is_empty() {
+        return;
+    }
+    if let Some(logger) = LOGGER.get() {
+        if !logger.hosts.read().contains(host) {
+            logger.hosts.write().insert(host.to_string());
+        }
+    }
+}
+
+fn load_settings() -> LogSettings {
+    storage::get_log_settings_file()
+        .and_then(|file| fs::read_to_string(file).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let message = record.args().to_string();
+        let message = if self.redact.load(Ordering::Relaxed) {
+            self.redact(&message)
+        } else {
+            message
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        eprintln!(
+            "{}.{:03} {:<5} {}",
+            now.as_secs(),
+            now.subsec_millis(),
+            level_name(record.level()),
+            message
+        );
+    }
+
+    fn flush(&self) {}
+}
+
+fn level_name(level: Level) -> &'static str {
+    match level {
+        Level::Error => "ERROR",
+        Level::Warn => "WARN",
+        Level::Info => "INFO",
+        Level::Debug => "DEBUG",
+        Level::Trace => "TRACE",
+    }
+}
+
+impl Logger {
+    fn redact(&self, message: &str) -> String {
+        // 한 번 훑으며 바꿔야 짧은 이름이 앞서 넣은 해시 값 안에서 다시 바뀌지 않음.
+        // 같은 위치에서는 긴 이름이 우선 (`nas`보다 `nas.example.com`)
+        let hosts = self.hosts.read();
+        let mut hosts: Vec<&String> = hosts.iter().collect();
+        hosts.sort_by_key(|host| std::cmp::Reverse(host.len()));
+        let mut text = String::with_capacity(message.len());
+        let mut rest = message;
+        while let Some(c) = rest.chars().next() {
+            if let Some(host) = hosts.iter().find(|host| rest.starts_with(host.as_str())) {
+                text.push_str(&format!("host-{}", self.hash(host)));
+                rest = &rest[host.len()..];
+            } else {
+                text.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        drop(hosts);
+
+        // 따옴표로 감싼 경로 (공백이 들어 있어도 하나로 처리)
+        let mut quoted = String::with_capacity(text.len());
+        let mut parts = text.split('\'');
+        if let Some(first) = parts.next() {
+            quoted.push_str(&self.redact_words(first));
+        }
+        let rest: Vec<&str> = parts.collect();
+        for (index, part) in rest.iter().enumerate() {
+            quoted.push('\'');
+            // 짝이 맞는 따옴표 안쪽만 경로로 봄 (마지막 홀수 조각은 그대로)
+            if index % 2 == 0 && index + 1 < rest.len() {
+                quoted.push_str(&self.redact_path(part));
+            } else {
+                quoted.push_str(&self.redact_words(part));
+            }
+        }
+        quoted
+    }
+
+    /// 따옴표 밖의 단어 중 경로나 IP 주소처럼 보이는 것만 가림
+    fn redact_words(&self, text: &str) -> String {
+        text.split(' ')
+            .map(|word| {
+                let trimmed = word.trim_matches(|c: char| "\"(),;[]{}<>".contains(c));
+                if trimmed.is_empty() {
+                    return word.to_string();
+                }
+                let replacement =
+                    if trimmed.parse::<IpAddr>().is_ok() || trimmed.parse::<SocketAddr>().is_ok() {
+                        format!("host-{}", self.hash(trimmed))
+                    } else if trimmed.contains('/') || trimmed.contains('\\') {
+                        self.redact_path(trimmed)
+                    } else {
+                        return word.to_string();
+                    };
+                word.replacen(trimmed, &replacement, 1)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// 구분자와 드라이브 문자(`C:`)는 남기고 각 구성 요소를 해시
+    fn redact_path(&self, path: &str) -> String {
+        let mut out = String::with_capacity(path.len());
+        let mut component = String::new();
+        for c in path.chars().chain(std::iter::once('/')) {
+            if c == '/' || c == '\\' {
+                if !component.is_empty() {
+                    let is_drive = component.len() == 2 && component.ends_with(':');
+                    if is_drive || component == "." || component == ".." {
+                        out.push_str(&component);
+                    } else {
+                        out.push_str(&self.hash(&component));
+                    }
+                    component.clear();
+                }
+                out.push(c);
+            } else {
+                component.push(c);
+            }
+        }
+        out.pop();
+        out
+    }
+
+    fn hash(&self, value: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.salt);
+        hasher.update(value.as_bytes());
+        let mut hex = to_hex(&hasher.finalize());
+        hex.truncate(HASH_LEN);
+        hex
+    }
+}
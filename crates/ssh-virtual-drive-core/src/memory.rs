@@ -0,0 +1,79 @@
+//! 메모리 절약 모드 모듈 - 작은 VM이나 씬 클라이언트용 캐시/버퍼 총량 제한
+//!
+//! 켜면 마운트마다 캐시와 버퍼가 쓰는 메모리를 정해진 예산 안으로 나눠 제한한다.
+//! stat/readdir/없는 경로 캐시는 항목 수 대신 추정 바이트로 축출하고, 진행 중인 전송
+//! 버퍼와 파일별 쓰기 지연 버퍼도 예산의 일부만 쓴다. 설정은 다음 마운트부터 적용된다.
+
+use crate::profile::MountTuning;
+use crate::storage;
+use crate::types::MemorySettings;
+use std::fs;
+
+/// 예산 최소값 (MB, 더 작으면 큰 파일 전송이 한 요청씩 줄을 섬)
+pub const MIN_MEMORY_LIMIT_MB: u32 = 16;
+
+/// 저장된 메모리 설정 (없으면 기본값)
+pub fn settings() -> MemorySettings {
+    storage::get_memory_settings_file()
+        .and_then(|file| fs::read_to_string(file).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// 메모리 설정 저장 (다음 마운트부터 적용)
+pub fn save_settings(settings: &MemorySettings) -> Result<(), String> {
+    if settings.low_memory && settings.limit_mb < MIN_MEMORY_LIMIT_MB {
+        return Err(format!(
+            "메모리 예산은 {}MB 이상이어야 합니다.",
+            MIN_MEMORY_LIMIT_MB
+        ));
+    }
+    let file = storage::get_memory_settings_file()
+        .ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("디렉토리 생성 실패: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(settings).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+    fs::write(&file, content).map_err(|e| format!("파일 저장 실패: {}", e))
+}
+
+/// 메모리 절약 모드가 켜져 있으면 마운트 설정을 예산 안으로 줄임
+///
+/// 예산은 캐시 50% (stat 20%, 디렉토리 목록 25%, 없는 경로 5%), 진행 중인 전송 30%,
+/// 파일별 쓰기 지연 버퍼 20%로 나눈다.
+pub fn apply(tuning: &mut MountTuning, settings: &MemorySettings) {
+    if !settings.low_memory {
+        return;
+    }
+    let total = settings.limit_mb.max(MIN_MEMORY_LIMIT_MB) as usize * 1024 * 1024;
+    tuning.cache_budget = Some(cache_budget(total / 2));
+    tuning.inflight_limit_mb = Some((total * 3 / 10 / (1024 * 1024)) as u32);
+    tuning.dirty_flush_bytes = tuning.dirty_flush_bytes.min(total / 5);
+    // 미리 읽은 목록이 예산 안에서 필요한 항목을 밀어내지 않도록 끔
+    tuning.prefetch_subdirs = 0;
+}
+
+/// 캐시 메모리를 종류별로 나눈 예산 (stat 40%, 디렉토리 목록 50%, 없는 경로 10%)
+pub fn cache_budget(cache_bytes: usize) -> CacheBudget {
+    CacheBudget {
+        stat_bytes: cache_bytes * 2 / 5,
+        dir_bytes: cache_bytes / 2,
+        missing_bytes: cache_bytes / 10,
+    }
+}
+
+/// 캐시 종류별 메모리 예산 (바이트)
+#[derive(Debug, Clone, Copy)]
+pub struct CacheBudget {
+    pub stat_bytes: usize,
+    pub dir_bytes: usize,
+    pub missing_bytes: usize,
+}
+
+impl CacheBudget {
+    /// 모든 캐시 예산의 합
+    pub fn total(&self) -> usize {
+        self.stat_bytes + self.dir_bytes + self.missing_bytes
+    }
+}
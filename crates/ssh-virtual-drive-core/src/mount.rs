@@ -0,0 +1,1226 @@
+//! 마운트 관리 모듈 - 드라이브 마운트/언마운트 및 상태 관리
+
+use crate::archive;
+use crate::bookmarks;
+use crate::cache::{parent_path, StatCache};
+use crate::credentials;
+use crate::environment;
+use crate::explorer;
+use crate::filesystem::{
+    create_filesystem_host, to_remote_path, SftpFileSystem, QUEUE_MODE_METADATA,
+};
+use crate::health;
+use crate::hooks::{run_hook, HookEvent};
+use crate::limits::{self, Allocation};
+use crate::offline::{self, OfflineStore};
+use crate::path_filter::{AccessRules, ProtectedPaths};
+use crate::profile::MountTuning;
+use crate::quota::QuotaTracker;
+use crate::reconnect::ReconnectPolicy;
+use crate::sftp_client::{create_shared_client, shell_quote, SftpClient, SharedSftpClient};
+use crate::snapshot::SnapshotTree;
+use crate::stats::StatsCollector;
+use crate::storage;
+use crate::streams::{StreamPool, DEFAULT_INFLIGHT_LIMIT_MB};
+use crate::traffic;
+use crate::types::{
+    AuthType, DriveStatus, DriveStatusType, FileVersion, FsActivity, MountStats, PendingChange,
+    PrerequisiteStatus, PushReport, SftpCapabilities, SshConnection, UnmountCheck,
+};
+use crate::versions;
+use crate::warm;
+use crate::writeback::{ChangeQueue, WriteJournal};
+use log::{info, warn};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use winfsp::host::FileSystemHost;
+
+/// 이 시간 안에 파일시스템 작업이 있었으면 강제로만 언마운트
+const UNMOUNT_QUIET_PERIOD: Duration = Duration::from_secs(30);
+
+/// 열린 파일이 있는 드라이브에서 이 시간 안에 작업이 있었으면 전송 중으로 봄
+const TRANSFER_QUIET_PERIOD: Duration = Duration::from_secs(10);
+
+/// 상태 측정 때 세션을 쓰는 다른 작업을 기다리는 최대 시간 (넘으면 이번 측정은 건너뜀)
+const PROBE_LOCK_WAIT: Duration = Duration::from_secs(2);
+
+/// DefineDosDeviceW: 정의 삭제
+const DDD_REMOVE_DEFINITION: u32 = 0x2;
+
+#[link(name = "kernel32")]
+extern "system" {
+    fn QueryDosDeviceW(device_name: *const u16, target_path: *mut u16, max: u32) -> u32;
+    fn DefineDosDeviceW(flags: u32, device_name: *const u16, target_path: *const u16) -> i32;
+}
+
+/// 마운트된 파일시스템 정보
+#[allow(dead_code)]
+pub struct MountedDrive {
+    pub connection_id: String,
+    pub drive_letter: char,
+    pub remote_root: String,
+    pub connection: SshConnection,
+    pub client: SharedSftpClient,
+    pub stats: Arc<StatsCollector>,
+    pub cache: Arc<StatCache>,
+    // 마운트할 때 확인한 서버 확장 (상태 조회가 바쁜 세션을 기다리지 않도록)
+    capabilities: SftpCapabilities,
+    // 자원 한도에서 나눠 받은 작업 스레드와 캐시 메모리
+    allocation: Allocation,
+    // 재연결 간격과 회로 상태
+    reconnect: Arc<ReconnectPolicy>,
+    // 변경 대기 모드의 대기열 (일반 모드면 None)
+    queue: Option<Arc<ChangeQueue>>,
+    // 파일시스템과 공유하는 쿼터 추적기 (쿼터를 쓰지 않으면 None)
+    quota: Option<QuotaTracker>,
+    // 마운트할 때 적용하지 못한 이전 세션의 쓰기 (마운트해 있는 동안 상태에 계속 보고)
+    journal_conflicts: Vec<String>,
+    // FileSystemHost는 Drop 시 자동으로 정리됨
+    _host: FileSystemHost<SftpFileSystem>,
+}
+
+/// 마운트 중인 드라이브의 연결과 미리 잡아 둔 자원
+struct Reservation {
+    connection_id: String,
+    remote_path: String,
+    allocation: Allocation,
+}
+
+/// 마운트 상태 관리자
+pub struct MountManager {
+    mounted: Mutex<HashMap<char, MountedDrive>>,
+    // 마운트 중인 드라이브가 미리 잡아 둔 자원 (동시에 마운트해도 한도를 넘지 않도록)
+    reserved: Mutex<HashMap<char, Reservation>>,
+    // 별칭 드라이브 문자 → 같은 연결과 경로를 먼저 마운트한 드라이브 문자
+    aliases: Mutex<HashMap<char, char>>,
+}
+
+impl Default for MountManager {
+    fn default() -> Self {
+        Self {
+            mounted: Mutex::new(HashMap::new()),
+            reserved: Mutex::new(HashMap::new()),
+            aliases: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl MountManager {
+    /// 드라이브 마운트 (자원 한도를 넘으면 서버에 접속하기 전에 거부)
+    ///
+    /// 같은 연결과 경로가 이미 마운트되어 있으면 새로 접속하지 않고 그 드라이브를 가리키는
+    /// 별칭 문자를 만든다 (캐시와 세션을 나눠 써서 두 드라이브의 내용이 어긋나지 않음).
+    pub fn mount(
+        &self,
+        connection: &SshConnection,
+        drive_letter: char,
+        password: Option<&str>,
+    ) -> Result<DriveStatus, String> {
+        let mut tuning = MountTuning::for_connection(connection);
+        // 이미 마운트된 드라이브인지 확인
+        let allocation = {
+            let mounted = self.mounted.lock();
+            let mut reserved = self.reserved.lock();
+            if mounted.contains_key(&drive_letter)
+                || reserved.contains_key(&drive_letter)
+                || self.aliases.lock().contains_key(&drive_letter)
+            {
+                return Err(format!("드라이브 {}:는 이미 사용 중입니다.", drive_letter));
+            }
+            // 저널과 캐시를 연결 ID로 저장하므로 한 프로필을 두 드라이브가 나눠 쓰지 않게 함
+            if let Some(other) = mounted.values().find(|d| d.connection_id == connection.id) {
+                if same_remote_path(&other.remote_root, &connection.remote_path) {
+                    let target = other.drive_letter;
+                    drop(reserved);
+                    drop(mounted);
+                    return self.add_alias(drive_letter, target);
+                }
+                return Err(format!(
+                    "'{}' 연결은 이미 {}: 드라이브에 마운트되어 있습니다. 같은 서버를 다른 계정이나 드라이브로 함께 쓰려면 연결 프로필을 따로 만들어주세요.",
+                    connection.name, other.drive_letter
+                ));
+            }
+            // 아직 마운트 중인 드라이브는 별칭으로 가리킬 수 없으므로 끝날 때까지 거부
+            if let Some((letter, pending)) = reserved
+                .iter()
+                .find(|(_, r)| r.connection_id == connection.id)
+            {
+                if same_remote_path(&pending.remote_path, &connection.remote_path) {
+                    return Err(format!(
+                        "'{}' 연결을 {}: 드라이브에 마운트하는 중입니다. 마운트가 끝난 뒤 다시 시도해주세요.",
+                        connection.name, letter
+                    ));
+                }
+                return Err(format!(
+                    "'{}' 연결은 이미 {}: 드라이브에 마운트하는 중입니다. 같은 서버를 다른 계정이나 드라이브로 함께 쓰려면 연결 프로필을 따로 만들어주세요.",
+                    connection.name, letter
+                ));
+            }
+            let used: Vec<Allocation> = mounted
+                .values()
+                .map(|d| d.allocation)
+                .chain(reserved.values().map(|r| r.allocation))
+                .collect();
+            let allocation = limits::allocate(&limits::settings(), &used, tuning.cache_budget)?;
+            reserved.insert(
+                drive_letter,
+                Reservation {
+                    connection_id: connection.id.clone(),
+                    remote_path: connection.remote_path.clone(),
+                    allocation,
+                },
+            );
+            allocation
+        };
+        tuning.worker_threads = allocation.worker_threads;
+        tuning.cache_budget = limits::cache_budget(&allocation, tuning.cache_budget);
+
+        let result = self.mount_reserved(connection, drive_letter, password, tuning, allocation);
+        self.reserved.lock().remove(&drive_letter);
+        result
+    }
+
+    /// 자원을 잡아 둔 드라이브 마운트
+    fn mount_reserved(
+        &self,
+        connection: &SshConnection,
+        drive_letter: char,
+        password: Option<&str>,
+        mut tuning: MountTuning,
+        allocation: Allocation,
+    ) -> Result<DriveStatus, String> {
+        check_letter_free(drive_letter, connection.global_mount)?;
+
+        // SFTP 클라이언트 생성
+        let client = create_shared_client(connection, password)?;
+
+        // 원격 경로가 아카이브 파일이면 프로필과 상관없이 읽기 전용 스냅샷으로 마운트
+        if archive::is_archive_root(&client, &connection.remote_path) {
+            tuning.snapshot = true;
+        }
+
+        // 이전 세션에서 남은 미반영 쓰기 재적용 (읽기 전용 스냅샷 마운트는 다음 일반 마운트로 미룸)
+        let journal = if tuning.snapshot {
+            None
+        } else {
+            match WriteJournal::for_connection(connection) {
+                Ok(journal) => Some(journal),
+                // 변경 대기 모드는 대기 중인 쓰기를 저널에만 보관하므로 저널 없이 마운트하지 않음
+                Err(e) if connection.queue_writes => {
+                    return Err(format!(
+                        "변경 대기 모드에 필요한 쓰기 저널을 열 수 없습니다: {}",
+                        e
+                    ))
+                }
+                Err(e) => {
+                    warn!("[Mount] write journal unavailable: {}", e);
+                    None
+                }
+            }
+        };
+        let journal_conflicts = journal
+            .as_ref()
+            .map(|j| j.recover(&client))
+            .unwrap_or_default();
+        let queue = journal.as_ref().and_then(|j| j.queue().cloned());
+
+        // 파일시스템 호스트 생성 및 시작
+        let stats = Arc::new(StatsCollector::default());
+        let cache = Arc::new(StatCache::new(stats.clone(), &tuning));
+        if tuning.snapshot {
+            // 드라이브가 보이기 전에 전체 목록을 읽어 고정
+            let tree = SnapshotTree::capture(&client, connection)?;
+            info!("[Mount] snapshot of {} entries", tree.entry_count());
+            cache.freeze(tree);
+        } else if let Some(file) = storage::get_cache_snapshot_file(&connection.id) {
+            // 이전 세션의 캐시 스냅샷으로 첫 탐색을 빠르게 (오래된 항목은 사용 시 다시 확인)
+            if file.exists() {
+                match cache.load_snapshot(&file, &connection.identity()) {
+                    Ok(count) => info!("[Mount] loaded {} cached directories", count),
+                    Err(e) => warn!("[Mount] cache snapshot ignored: {}", e),
+                }
+            }
+        }
+        // 오프라인 고정 경로는 이전에 받아 둔 복사본을 바로 쓰고 백그라운드에서 서버와 맞춤
+        if let Some(store) = OfflineStore::open(connection).filter(|_| cache.frozen().is_none()) {
+            cache.attach_offline(store.clone());
+            offline::start_sync(client.clone(), connection, store, &cache, &stats);
+        }
+        let (host, quota) = create_filesystem_host(
+            client.clone(),
+            connection,
+            drive_letter,
+            stats.clone(),
+            cache.clone(),
+            journal,
+            tuning,
+        )?;
+
+        // 마운트 정보 저장
+        let reconnect = client.lock().reconnect_policy();
+        let capabilities = client.lock().capabilities();
+        let pending_changes = queue.as_ref().map(|q| q.len());
+        // 고정 폴더는 드라이브가 보인 뒤 백그라운드에서 미리 읽음
+        warm::start(client.clone(), connection, &cache, &stats);
+        let mounted_drive = MountedDrive {
+            connection_id: connection.id.clone(),
+            drive_letter,
+            remote_root: connection.remote_path.clone(),
+            connection: connection.clone(),
+            capabilities,
+            reconnect,
+            queue,
+            quota,
+            journal_conflicts: journal_conflicts.clone(),
+            client,
+            stats,
+            cache,
+            allocation,
+            _host: host,
+        };
+
+        let same_path_drives = {
+            let mut mounted = self.mounted.lock();
+            mounted.insert(drive_letter, mounted_drive);
+            mounted
+                .get(&drive_letter)
+                .map(|drive| same_path_drives(drive, &mounted))
+                .unwrap_or_default()
+        };
+        if !same_path_drives.is_empty() {
+            warn!(
+                "[Mount] {}: shows the same server path as {:?} through another profile, caches are separate",
+                drive_letter, same_path_drives
+            );
+        }
+        explorer::register_drive(drive_letter, connection);
+        run_hook(connection, Some(drive_letter), HookEvent::Mount);
+
+        Ok(DriveStatus {
+            drive_letter,
+            connection_id: connection.id.clone(),
+            status: DriveStatusType::Connected,
+            error_message: None,
+            journal_conflicts,
+            scope_notes: scope_notes(connection),
+            reconnect: None,
+            session_uptime_secs: Some(0),
+            last_success_at: None,
+            avg_latency_ms: None,
+            last_error: None,
+            paused: false,
+            pending_changes,
+            alias_of: None,
+            same_path_drives,
+        })
+    }
+
+    /// 마운트된 드라이브를 가리키는 별칭 드라이브 문자 추가 (`subst`처럼 이 세션에만 보임)
+    fn add_alias(&self, drive_letter: char, target: char) -> Result<DriveStatus, String> {
+        check_letter_free(drive_letter, false)?;
+        let device = to_wide(&format!("{}:", drive_letter));
+        let path = to_wide(&format!("{}:\\", target));
+        if unsafe { DefineDosDeviceW(0, device.as_ptr(), path.as_ptr()) } == 0 {
+            return Err(format!(
+                "별칭 드라이브 {}: 만들기 실패: {}",
+                drive_letter,
+                std::io::Error::last_os_error()
+            ));
+        }
+
+        let mounted = self.mounted.lock();
+        let Some(drive) = mounted.get(&target) else {
+            remove_alias(drive_letter);
+            return Err(format!("드라이브 {}:가 마운트되어 있지 않습니다.", target));
+        };
+        self.aliases.lock().insert(drive_letter, target);
+        explorer::register_drive(drive_letter, &drive.connection);
+        info!(
+            "[Mount] {}: is an alias of {}: (same connection and path)",
+            drive_letter, target
+        );
+        Ok(DriveStatus {
+            drive_letter,
+            alias_of: Some(target),
+            ..drive_status(target, drive, &mounted)
+        })
+    }
+
+    /// 별칭 문자면 가리키는 드라이브 문자, 아니면 그대로
+    fn resolve(&self, drive_letter: char) -> char {
+        self.aliases
+            .lock()
+            .get(&drive_letter)
+            .copied()
+            .unwrap_or(drive_letter)
+    }
+
+    /// 저장된 연결과 자격 증명으로 마운트
+    pub fn mount_saved(
+        &self,
+        connection_id: &str,
+        drive_letter: char,
+    ) -> Result<DriveStatus, String> {
+        let connection = storage::get_connection_by_id(connection_id)?
+            .ok_or_else(|| "연결을 찾을 수 없습니다.".to_string())?;
+        let password = saved_password(&connection)?;
+        self.mount(&connection, drive_letter, password.as_deref())
+    }
+
+    /// 북마크의 경로를 루트로 저장된 연결 마운트
+    pub fn mount_bookmark(
+        &self,
+        bookmark_id: &str,
+        drive_letter: char,
+    ) -> Result<DriveStatus, String> {
+        let bookmark = bookmarks::get(bookmark_id)?;
+        let mut connection = storage::get_connection_by_id(&bookmark.connection_id)?
+            .ok_or_else(|| "북마크의 연결을 찾을 수 없습니다.".to_string())?;
+        connection.remote_path = bookmark.path;
+        let password = saved_password(&connection)?;
+        self.mount(&connection, drive_letter, password.as_deref())
+    }
+
+    /// 언마운트해도 되는지 확인 (열린 파일 핸들 수와 최근 작업 파일)
+    pub fn check_unmount(&self, drive_letter: char) -> Result<UnmountCheck, String> {
+        // 별칭은 문자만 지우므로 열린 파일이 있어도 그대로 쓸 수 있음
+        if self.aliases.lock().contains_key(&drive_letter) {
+            return Ok(UnmountCheck {
+                drive_letter,
+                open_handles: 0,
+                recent_files: Vec::new(),
+                idle_secs: 0,
+                busy: false,
+            });
+        }
+        let mounted = self.mounted.lock();
+        let drive = mounted
+            .get(&drive_letter)
+            .ok_or_else(|| format!("드라이브 {}:가 마운트되어 있지 않습니다.", drive_letter))?;
+        let open_handles = drive.stats.open_file_handles();
+        let recent_files = drive.stats.recent_files();
+        let idle = drive.stats.idle_for();
+        // 마운트 직후 아무 파일도 다루지 않았으면 유휴 시간이 짧아도 바쁜 것이 아님
+        let busy = open_handles > 0 || (!recent_files.is_empty() && idle < UNMOUNT_QUIET_PERIOD);
+        Ok(UnmountCheck {
+            drive_letter,
+            open_handles,
+            recent_files,
+            idle_secs: idle.as_secs(),
+            busy,
+        })
+    }
+
+    /// 드라이브 언마운트 (`force`가 아니면 열린 파일이나 최근 작업이 있을 때 거부)
+    ///
+    /// 편집기가 저장하지 않은 파일을 연 채로 드라이브가 사라지면 내용을 잃을 수 있다.
+    pub fn unmount_checked(&self, drive_letter: char, force: bool) -> Result<(), String> {
+        if !force {
+            let check = self.check_unmount(drive_letter)?;
+            if check.busy {
+                return Err(format!(
+                    "드라이브 {}:에 열린 파일 {}개가 있거나 {}초 전에 작업이 있었습니다. 파일을 저장하고 닫은 뒤 다시 시도하거나 강제로 연결을 해제하세요.",
+                    drive_letter, check.open_handles, check.idle_secs
+                ));
+            }
+        }
+        self.unmount(drive_letter)
+    }
+
+    /// 드라이브 언마운트
+    pub fn unmount(&self, drive_letter: char) -> Result<(), String> {
+        // 별칭은 드라이브 문자만 지움 (가리키던 드라이브는 그대로)
+        if self.aliases.lock().remove(&drive_letter).is_some() {
+            remove_alias(drive_letter);
+            explorer::unregister_drive(drive_letter);
+            info!("[Mount] removed alias {}:", drive_letter);
+            return Ok(());
+        }
+
+        let mut mounted = self.mounted.lock();
+
+        let drive = mounted
+            .remove(&drive_letter)
+            .ok_or_else(|| format!("드라이브 {}:가 마운트되어 있지 않습니다.", drive_letter))?;
+
+        let stats = drive
+            .stats
+            .snapshot(drive_letter, &drive.connection_id, drive.capabilities);
+        info!(
+            "[Mount] unmount {}: cache hits={} misses={} evictions={}",
+            drive_letter, stats.cache_hits, stats.cache_misses, stats.cache_evictions
+        );
+
+        // 이 드라이브를 가리키던 별칭도 함께 지움
+        let aliases: Vec<char> = {
+            let mut aliases = self.aliases.lock();
+            let letters: Vec<char> = aliases
+                .iter()
+                .filter(|(_, target)| **target == drive_letter)
+                .map(|(alias, _)| *alias)
+                .collect();
+            for alias in &letters {
+                aliases.remove(alias);
+            }
+            letters
+        };
+
+        // FileSystemHost는 Drop 시 자동으로 정리됨
+        drop(mounted);
+        for alias in aliases {
+            remove_alias(alias);
+            explorer::unregister_drive(alias);
+        }
+        explorer::unregister_drive(drive_letter);
+        let connection = drive.connection.clone();
+        let cache = drive.cache.clone();
+        drop(drive);
+
+        // 다음 마운트를 위한 캐시 스냅샷 저장 (실패해도 언마운트는 계속)
+        // 스냅샷 마운트는 캐시를 쓰지 않으므로 일반 마운트의 스냅샷을 덮어쓰지 않음
+        let file =
+            storage::get_cache_snapshot_file(&connection.id).filter(|_| cache.frozen().is_none());
+        if let Some(file) = file {
+            match cache.save_snapshot(&file, &connection.identity()) {
+                Ok(count) => info!("[Mount] saved {} cached directories", count),
+                Err(e) => warn!("[Mount] cache snapshot not saved: {}", e),
+            }
+        }
+        traffic::save();
+        run_hook(&connection, Some(drive_letter), HookEvent::Unmount);
+        Ok(())
+    }
+
+    /// 유휴 시간이 설정값을 넘은 드라이브 언마운트 후 해당 드라이브 문자 반환
+    ///
+    /// 열린 파일이 있거나 올리는 중인 쓰기가 있는 드라이브는 작업이 없어도 건너뛴다.
+    pub fn unmount_idle_drives(&self) -> Vec<char> {
+        let idle: Vec<char> = self
+            .mounted
+            .lock()
+            .values()
+            .filter(|drive| {
+                drive
+                    .connection
+                    .auto_unmount_minutes
+                    .is_some_and(|minutes| {
+                        minutes > 0 && drive.stats.idle_for().as_secs() >= minutes as u64 * 60
+                    })
+            })
+            .filter(|drive| drive.stats.open_file_handles() == 0 && !drive.stats.is_uploading())
+            .map(|drive| drive.drive_letter)
+            .collect();
+
+        idle.into_iter()
+            .filter(|letter| match self.unmount_checked(*letter, false) {
+                Ok(()) => {
+                    info!("[Mount] auto-unmounted idle drive {}:", letter);
+                    true
+                }
+                Err(e) => {
+                    warn!("[Mount] auto-unmount of {}: failed: {}", letter, e);
+                    false
+                }
+            })
+            .collect()
+    }
+
+    /// 파일을 올리거나 열린 파일로 계속 읽고 쓰는 드라이브가 있는지 (절전 방지용)
+    pub fn has_active_transfers(&self) -> bool {
+        self.mounted.lock().values().any(|drive| {
+            drive.stats.is_uploading()
+                || (drive.stats.open_file_handles() > 0
+                    && drive.stats.idle_for() < TRANSFER_QUIET_PERIOD)
+        })
+    }
+
+    /// 마운트된 드라이브마다 서버 응답 시간을 재서 연결 상태 기록에 추가
+    ///
+    /// 일시 중지한 드라이브와 다른 작업이 세션을 쓰고 있는 드라이브는 이번 측정에서 뺀다
+    /// (기다린 시간이 응답 시간에 섞이지 않도록).
+    pub fn probe_health(&self) {
+        let targets: Vec<_> = self
+            .mounted
+            .lock()
+            .values()
+            .filter(|drive| !drive.cache.is_paused())
+            .map(|drive| {
+                (
+                    drive.connection_id.clone(),
+                    drive.remote_root.clone(),
+                    drive.client.clone(),
+                )
+            })
+            .collect();
+        let results = targets
+            .into_iter()
+            .filter_map(|(connection_id, remote_root, client)| {
+                let client = client.try_lock_for(PROBE_LOCK_WAIT)?;
+                let t0 = Instant::now();
+                let latency = client.stat(&remote_root).ok().map(|_| t0.elapsed());
+                Some((connection_id, latency))
+            })
+            .collect();
+        health::record(results);
+    }
+
+    /// 모든 드라이브에서 모아 둔 파일시스템 활동
+    pub fn take_activity(&self) -> Vec<FsActivity> {
+        self.mounted
+            .lock()
+            .values()
+            .flat_map(|drive| drive.stats.take_activity(drive.drive_letter))
+            .collect()
+    }
+
+    /// 마운트된 드라이브 목록
+    ///
+    /// 별칭 드라이브는 가리키는 드라이브의 상태에 `alias_of`를 채워 함께 담는다.
+    pub fn get_mounted_drives(&self) -> Vec<DriveStatus> {
+        let mounted = self.mounted.lock();
+        let mut drives: Vec<DriveStatus> = mounted
+            .iter()
+            .map(|(letter, drive)| drive_status(*letter, drive, &mounted))
+            .collect();
+        drives.extend(self.aliases.lock().iter().filter_map(|(alias, target)| {
+            let drive = mounted.get(target)?;
+            Some(DriveStatus {
+                drive_letter: *alias,
+                alias_of: Some(*target),
+                ..drive_status(*target, drive, &mounted)
+            })
+        }));
+        drives
+    }
+
+    /// 마운트별 통계 조회
+    pub fn get_mount_stats(&self, drive_letter: char) -> Result<MountStats, String> {
+        let target = self.resolve(drive_letter);
+        self.mounted
+            .lock()
+            .get(&target)
+            .map(|drive| {
+                drive
+                    .stats
+                    .snapshot(drive_letter, &drive.connection_id, drive.capabilities)
+            })
+            .ok_or_else(|| format!("드라이브 {}:가 마운트되어 있지 않습니다.", drive_letter))
+    }
+
+    /// 스냅샷 드라이브의 목록을 지금 서버 상태로 다시 읽음 (읽은 항목 수 반환)
+    pub fn refresh_snapshot(&self, drive_letter: char) -> Result<usize, String> {
+        let drive_letter = self.resolve(drive_letter);
+        let (client, connection, cache) = self
+            .mounted
+            .lock()
+            .get(&drive_letter)
+            .map(|drive| {
+                (
+                    drive.client.clone(),
+                    drive.connection.clone(),
+                    drive.cache.clone(),
+                )
+            })
+            .ok_or_else(|| format!("드라이브 {}:가 마운트되어 있지 않습니다.", drive_letter))?;
+        if cache.frozen().is_none() {
+            return Err(format!(
+                "드라이브 {}:는 스냅샷 드라이브가 아닙니다.",
+                drive_letter
+            ));
+        }
+
+        // 목록을 다 읽을 때까지는 이전 스냅샷으로 계속 답함
+        let tree = SnapshotTree::capture(&client, &connection)?;
+        let count = tree.entry_count();
+        cache.freeze(tree);
+        info!(
+            "[Mount] refreshed snapshot of {}: {} entries",
+            drive_letter, count
+        );
+        Ok(count)
+    }
+
+    /// 드라이브 일시 중지 (드라이브 문자는 유지하고 서버 통신을 멈춤)
+    ///
+    /// 중지 중에는 캐시에 남은 메타데이터로만 답하고, 읽기/쓰기처럼 서버가 필요한 작업은
+    /// "장치가 준비되지 않음" 오류로 거부한다. 중지 전에 받은 쓰기는 닫을 때 그대로 올린다.
+    pub fn pause_drive(&self, drive_letter: char) -> Result<(), String> {
+        self.set_paused(drive_letter, true)
+    }
+
+    /// 일시 중지한 드라이브 재개
+    pub fn resume_drive(&self, drive_letter: char) -> Result<(), String> {
+        self.set_paused(drive_letter, false)
+    }
+
+    fn set_paused(&self, drive_letter: char, paused: bool) -> Result<(), String> {
+        let drive_letter = self.resolve(drive_letter);
+        let mounted = self.mounted.lock();
+        let drive = mounted
+            .get(&drive_letter)
+            .ok_or_else(|| format!("드라이브 {}:가 마운트되어 있지 않습니다.", drive_letter))?;
+        if drive.cache.is_paused() == paused {
+            return Err(if paused {
+                format!("드라이브 {}:는 이미 일시 중지되어 있습니다.", drive_letter)
+            } else {
+                format!("드라이브 {}:는 일시 중지되어 있지 않습니다.", drive_letter)
+            });
+        }
+        drive.cache.set_paused(paused);
+        info!(
+            "[Mount] {} {}:",
+            if paused { "paused" } else { "resumed" },
+            drive_letter
+        );
+        Ok(())
+    }
+
+    /// 변경 대기 모드 드라이브에서 올리기를 기다리는 쓰기 목록
+    pub fn list_pending_changes(&self, drive_letter: char) -> Result<Vec<PendingChange>, String> {
+        Ok(self.queue_for(drive_letter)?.list())
+    }
+
+    /// 대기 중인 쓰기를 모두 서버에 올림
+    ///
+    /// 대기열에 넣은 뒤 서버에서 바뀐 파일과 올리기에 실패한 파일은 대기열에 남고
+    /// `errors`에 이유가 담긴다.
+    pub fn push_changes(&self, drive_letter: char) -> Result<PushReport, String> {
+        let drive_letter = self.resolve(drive_letter);
+        let (client, connection, stats, cache) = self
+            .mounted
+            .lock()
+            .get(&drive_letter)
+            .map(|drive| {
+                (
+                    drive.client.clone(),
+                    drive.connection.clone(),
+                    drive.stats.clone(),
+                    drive.cache.clone(),
+                )
+            })
+            .ok_or_else(|| format!("드라이브 {}:가 마운트되어 있지 않습니다.", drive_letter))?;
+        let queue = self.queue_for(drive_letter)?;
+        if cache.is_paused() {
+            return Err(format!(
+                "드라이브 {}:는 일시 중지되어 있습니다.",
+                drive_letter
+            ));
+        }
+
+        let streams = StreamPool::new(
+            client,
+            drive_letter,
+            1,
+            connection
+                .inflight_limit_mb
+                .unwrap_or(DEFAULT_INFLIGHT_LIMIT_MB),
+        );
+        let report = queue.push(&streams, &stats, connection.keep_versions);
+        for path in &report.pushed {
+            cache.invalidate(path);
+            if connection.keep_versions.is_some() {
+                cache.invalidate_dir(&versions::versions_dir(path));
+            }
+        }
+        Ok(report)
+    }
+
+    /// 대기 중인 쓰기 하나를 버림 (경로는 서버 경로)
+    pub fn discard_pending_change(&self, drive_letter: char, path: &str) -> Result<(), String> {
+        if !self.queue_for(drive_letter)?.discard(path) {
+            return Err(format!("'{}'에 대기 중인 변경이 없습니다.", path));
+        }
+        info!("[Mount] discarded pending change of '{}'", path);
+        Ok(())
+    }
+
+    /// 드라이브의 변경 대기열 (변경 대기 모드가 아니면 오류)
+    fn queue_for(&self, drive_letter: char) -> Result<Arc<ChangeQueue>, String> {
+        let drive_letter = self.resolve(drive_letter);
+        let mounted = self.mounted.lock();
+        let drive = mounted
+            .get(&drive_letter)
+            .ok_or_else(|| format!("드라이브 {}:가 마운트되어 있지 않습니다.", drive_letter))?;
+        drive
+            .queue
+            .clone()
+            .ok_or_else(|| format!("드라이브 {}:는 변경 대기 모드가 아닙니다.", drive_letter))
+    }
+
+    /// 앱에서 서버의 트리를 바꾼 뒤 그 연결을 마운트한 드라이브의 캐시 무효화 (원격 전체 경로)
+    pub fn invalidate_remote_tree(&self, connection_id: &str, remote_path: &str) {
+        for drive in self.mounted.lock().values() {
+            if drive.connection_id == connection_id {
+                drive.cache.invalidate_tree(remote_path);
+            }
+        }
+    }
+
+    /// 마운트된 드라이브 안에서 서버 측 복사 (경로는 드라이브 기준)
+    ///
+    /// 탐색기를 거치지 않으므로 파일시스템의 쓰기와 같은 규칙(읽기 전용, 변경 대기 모드,
+    /// 접근/보호 경로 규칙, 쿼터)을 여기서 확인하고, 복사가 끝나면 대상의 캐시를 비운다.
+    pub fn copy_remote(&self, drive_letter: char, from: &str, to: &str) -> Result<(), String> {
+        let drive_letter = self.resolve(drive_letter);
+        let (read_only, paused, queued, connection, cache, quota) = self
+            .mounted
+            .lock()
+            .get(&drive_letter)
+            .map(|drive| {
+                (
+                    drive.cache.frozen().is_some(),
+                    drive.cache.is_paused(),
+                    drive.queue.is_some(),
+                    drive.connection.clone(),
+                    drive.cache.clone(),
+                    drive.quota.clone(),
+                )
+            })
+            .ok_or_else(|| format!("드라이브 {}:가 마운트되어 있지 않습니다.", drive_letter))?;
+        if read_only {
+            return Err(format!(
+                "드라이브 {}:는 읽기 전용 스냅샷입니다.",
+                drive_letter
+            ));
+        }
+        if paused {
+            return Err(format!(
+                "드라이브 {}:는 일시 중지되어 있습니다.",
+                drive_letter
+            ));
+        }
+        if queued {
+            return Err(QUEUE_MODE_METADATA.to_string());
+        }
+        let (client, remote_root) = self.client_for(drive_letter)?;
+        let from = to_remote_path(&remote_root, from);
+        let to = to_remote_path(&remote_root, to);
+
+        let access = AccessRules::new(&connection.access_rules);
+        if access.is_hidden(&from) {
+            return Err(format!("'{}'는 숨김 규칙에 걸려 복사할 수 없습니다.", from));
+        }
+        if access.is_read_only(&to) || access.has_rule_within(&to) {
+            return Err(format!(
+                "'{}'나 그 아래에 읽기 전용/숨김 규칙이 있어 복사할 수 없습니다.",
+                to
+            ));
+        }
+        // 큰 복사 동안 주 세션을 잡고 있으면 드라이브 전체가 멈추므로 따로 연결해서 복사
+        let connector = client.lock().sibling_connector();
+        let sibling = match connector.connect() {
+            Ok(sibling) => Some(sibling),
+            Err(e) => {
+                warn!("[Mount] copy falls back to the main session: {}", e);
+                None
+            }
+        };
+        let primary;
+        let client = match &sibling {
+            Some(sibling) => sibling,
+            None => {
+                primary = client.lock();
+                &*primary
+            }
+        };
+        let target_exists = client.lstat(&to).is_ok();
+        if let Some(rule) = ProtectedPaths::new(&connection.protected_paths)
+            .rule_for(&to)
+            .filter(|_| target_exists)
+        {
+            return Err(format!(
+                "'{}'는 보호 경로 규칙({})에 걸려 덮어쓸 수 없습니다.",
+                to, rule
+            ));
+        }
+
+        // 쿼터가 있으면 원본 크기만큼 미리 잡아 둠 (크기를 모르면 허용)
+        let growth = match &quota {
+            Some(quota) => {
+                let growth = copy_size(client, &from);
+                if !quota.reserve(growth) {
+                    return Err("용량 제한을 넘어 복사할 수 없습니다.".to_string());
+                }
+                growth
+            }
+            None => 0,
+        };
+        let result = client.copy(&from, &to);
+        drop(sibling);
+        match &result {
+            Ok(()) => {
+                cache.invalidate_tree(&to);
+                cache.invalidate_dir(parent_path(&to));
+            }
+            Err(_) => {
+                if let Some(quota) = &quota {
+                    quota.release(growth);
+                }
+            }
+        }
+        result
+    }
+
+    /// 파일의 서버에 보관된 이전 버전 목록 (경로는 드라이브 기준, 최근 것부터)
+    pub fn list_versions(
+        &self,
+        drive_letter: char,
+        path: &str,
+    ) -> Result<Vec<FileVersion>, String> {
+        let (client, remote_root) = self.client_for(drive_letter)?;
+        let path = to_remote_path(&remote_root, path);
+        let result = versions::list(&client.lock(), &path);
+        result
+    }
+
+    /// 보관된 버전으로 파일을 되돌림 (버전 보관이 켜져 있으면 지금 내용도 버전으로 남김)
+    pub fn restore_version(
+        &self,
+        drive_letter: char,
+        path: &str,
+        version: &str,
+    ) -> Result<(), String> {
+        let drive_letter = self.resolve(drive_letter);
+        let (keep, cache) = self
+            .mounted
+            .lock()
+            .get(&drive_letter)
+            .map(|drive| (drive.connection.keep_versions, drive.cache.clone()))
+            .ok_or_else(|| format!("드라이브 {}:가 마운트되어 있지 않습니다.", drive_letter))?;
+        if cache.frozen().is_some() {
+            return Err(format!(
+                "드라이브 {}:는 읽기 전용 스냅샷입니다.",
+                drive_letter
+            ));
+        }
+        if cache.is_paused() {
+            return Err(format!(
+                "드라이브 {}:는 일시 중지되어 있습니다.",
+                drive_letter
+            ));
+        }
+        let (client, remote_root) = self.client_for(drive_letter)?;
+        let path = to_remote_path(&remote_root, path);
+        versions::restore(&client.lock(), &path, version, keep)?;
+        cache.invalidate(&path);
+        cache.invalidate_dir(&versions::versions_dir(&path));
+        Ok(())
+    }
+
+    /// 드라이브의 SFTP 클라이언트와 원격 루트
+    fn client_for(&self, drive_letter: char) -> Result<(SharedSftpClient, String), String> {
+        let drive_letter = self.resolve(drive_letter);
+        self.mounted
+            .lock()
+            .get(&drive_letter)
+            .map(|drive| (drive.client.clone(), drive.remote_root.clone()))
+            .ok_or_else(|| format!("드라이브 {}:가 마운트되어 있지 않습니다.", drive_letter))
+    }
+
+    /// 특정 드라이브가 마운트되어 있는지 확인
+    #[allow(dead_code)]
+    pub fn is_mounted(&self, drive_letter: char) -> bool {
+        self.mounted
+            .lock()
+            .contains_key(&self.resolve(drive_letter))
+    }
+
+    /// 마운트된 드라이브 문자 (별칭 포함, 알파벳 순)
+    pub fn drive_letters(&self) -> Vec<char> {
+        let mut letters: Vec<char> = self.mounted.lock().keys().copied().collect();
+        letters.extend(self.aliases.lock().keys().copied());
+        letters.sort_unstable();
+        letters
+    }
+
+    /// 탐색기 경로(`Z:\dir\file`)의 연결과 서버 경로 (드라이브에 바꿔 보인 이름은 실제 이름으로)
+    pub fn resolve_local_path(&self, local_path: &str) -> Result<(SshConnection, String), String> {
+        let mut chars = local_path.chars();
+        let (Some(letter), Some(':')) = (chars.next(), chars.next()) else {
+            return Err(format!("드라이브 경로가 아닙니다: {}", local_path));
+        };
+        let drive_letter = self.resolve(letter.to_ascii_uppercase());
+        let (connection, client) = self
+            .mounted
+            .lock()
+            .get(&drive_letter)
+            .map(|drive| (drive.connection.clone(), drive.client.clone()))
+            .ok_or_else(|| format!("드라이브 {}:가 마운트되어 있지 않습니다.", drive_letter))?;
+        let path = to_remote_path(&connection.remote_path, chars.as_str());
+        let server_path = client.lock().server_path(&path);
+        Ok((connection, server_path))
+    }
+}
+
+/// 비밀번호 인증이면 자격 증명 관리자에 저장된 비밀번호
+fn saved_password(connection: &SshConnection) -> Result<Option<String>, String> {
+    if connection.auth_type == AuthType::Password {
+        credentials::get_password(&connection.id)
+    } else {
+        Ok(None)
+    }
+}
+
+/// 마운트된 드라이브의 현재 상태
+fn drive_status(
+    letter: char,
+    drive: &MountedDrive,
+    mounted: &HashMap<char, MountedDrive>,
+) -> DriveStatus {
+    DriveStatus {
+        drive_letter: letter,
+        connection_id: drive.connection_id.clone(),
+        status: DriveStatusType::Connected,
+        error_message: None,
+        journal_conflicts: drive.journal_conflicts.clone(),
+        scope_notes: scope_notes(&drive.connection),
+        reconnect: drive.reconnect.status(),
+        session_uptime_secs: Some(drive.reconnect.session_uptime().as_secs()),
+        last_success_at: drive.stats.last_success_at(),
+        avg_latency_ms: drive.stats.average_latency_ms(),
+        last_error: drive.stats.last_error(),
+        paused: drive.cache.is_paused(),
+        pending_changes: drive.queue.as_ref().map(|q| q.len()),
+        alias_of: None,
+        same_path_drives: same_path_drives(drive, mounted),
+    }
+}
+
+/// 서버 측 복사로 늘어날 크기 (폴더는 `du`, 알 수 없으면 0)
+fn copy_size(client: &SftpClient, from: &str) -> u64 {
+    let stat = match client.lstat(from) {
+        Ok(stat) => stat,
+        Err(e) => {
+            warn!("[Mount] copy size of '{}' unknown: {}", from, e);
+            return 0;
+        }
+    };
+    if !stat.is_dir() {
+        return stat.size.unwrap_or(0);
+    }
+    let output = client.exec(&format!(
+        "du -sk -- {}",
+        shell_quote(&client.server_path(from))
+    ));
+    match output.map(|output| {
+        output
+            .split_whitespace()
+            .next()
+            .and_then(|kb| kb.parse::<u64>().ok())
+    }) {
+        Ok(Some(kb)) => kb * 1024,
+        Ok(None) | Err(_) => {
+            warn!(
+                "[Mount] copy size of '{}' unknown, quota not enforced",
+                from
+            );
+            0
+        }
+    }
+}
+
+/// 다른 연결 프로필로 같은 서버 계정과 경로를 마운트한 드라이브 (알파벳 순)
+fn same_path_drives(drive: &MountedDrive, mounted: &HashMap<char, MountedDrive>) -> Vec<char> {
+    let identity = drive.connection.identity();
+    let mut letters: Vec<char> = mounted
+        .values()
+        .filter(|other| {
+            other.connection_id != drive.connection_id
+                && other.connection.identity() == identity
+                && same_remote_path(&other.remote_root, &drive.remote_root)
+        })
+        .map(|other| other.drive_letter)
+        .collect();
+    letters.sort_unstable();
+    letters
+}
+
+/// 끝의 `/` 차이를 무시하고 같은 원격 경로인지
+fn same_remote_path(a: &str, b: &str) -> bool {
+    a.trim_end_matches('/') == b.trim_end_matches('/')
+}
+
+/// 별칭 드라이브 문자 정의 삭제 (가장 최근 정의, 별칭을 만들 때 문자가 비어 있었음을 확인함)
+fn remove_alias(letter: char) {
+    let device = to_wide(&format!("{}:", letter));
+    if unsafe { DefineDosDeviceW(DDD_REMOVE_DEFINITION, device.as_ptr(), std::ptr::null()) } == 0 {
+        warn!(
+            "[Mount] removing alias {}: failed: {}",
+            letter,
+            std::io::Error::last_os_error()
+        );
+    }
+}
+
+fn to_wide(value: &str) -> Vec<u16> {
+    value.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// 마운트 범위에 따른 주의 사항 (DriveStatus에 담아 UI에 표시)
+fn scope_notes(connection: &SshConnection) -> Vec<String> {
+    if !connection.global_mount {
+        return Vec::new();
+    }
+    vec![
+        "전역 드라이브입니다. 관리자 권한 프로세스와 이 컴퓨터의 다른 사용자 세션에도 보입니다."
+            .to_string(),
+        "다른 사용자도 이 연결의 SSH 계정 권한으로 원격 파일에 접근할 수 있습니다.".to_string(),
+        "앱을 종료하거나 언마운트하면 모든 세션에서 함께 사라집니다.".to_string(),
+        "다른 사용자 세션이 같은 문자를 이미 쓰고 있으면 그 세션에서는 이 드라이브가 보이지 않습니다."
+            .to_string(),
+    ]
+}
+
+/// 드라이브 문자가 어느 DOS 장치 네임스페이스에 정의되어 있는지
+///
+/// 원격 데스크톱 서버에서는 사용자 세션마다 네임스페이스가 따로 있고, 없는 이름은
+/// 전역(`Global\`) 네임스페이스에서 찾는다. 다른 세션에만 정의된 문자는 보이지 않으므로
+/// 세션별 마운트가 서로 충돌하지 않는다.
+struct LetterUsage {
+    /// 현재 세션에서만 정의됨 (전역 정의를 가리는 경우 포함)
+    session: bool,
+    /// 전역 네임스페이스에 정의됨 (시스템 드라이브나 다른 세션의 전역 마운트)
+    global: bool,
+}
+
+fn letter_usage(letter: char) -> LetterUsage {
+    let visible = query_dos_device(&format!("{}:", letter));
+    let global = query_dos_device(&format!("Global\\{}:", letter));
+    LetterUsage {
+        session: visible.is_some() && visible != global,
+        global: global.is_some(),
+    }
+}
+
+fn query_dos_device(name: &str) -> Option<String> {
+    let wide = to_wide(name);
+    let mut buffer = [0u16; 1024];
+    let len = unsafe { QueryDosDeviceW(wide.as_ptr(), buffer.as_mut_ptr(), buffer.len() as u32) };
+    if len == 0 {
+        return None;
+    }
+    // 결과는 NUL로 구분된 목록이며 첫 항목이 현재 대상
+    let target = buffer[..len as usize]
+        .split(|&c| c == 0)
+        .next()
+        .unwrap_or(&[]);
+    Some(String::from_utf16_lossy(target))
+}
+
+/// 마운트 전에 드라이브 문자 충돌 확인
+fn check_letter_free(letter: char, global_mount: bool) -> Result<(), String> {
+    let usage = letter_usage(letter);
+    if usage.session {
+        return Err(format!(
+            "드라이브 {}:는 이 세션에서 이미 사용 중입니다.",
+            letter
+        ));
+    }
+    if usage.global {
+        return Err(if global_mount {
+            format!(
+                "드라이브 {}:는 이미 전역 드라이브로 사용 중입니다 (시스템 또는 다른 사용자 세션). 다른 문자를 선택해주세요.",
+                letter
+            )
+        } else {
+            format!(
+                "드라이브 {}:는 모든 세션에 보이는 전역 드라이브(시스템 또는 다른 사용자 세션)가 사용 중입니다. 다른 문자를 선택해주세요.",
+                letter
+            )
+        });
+    }
+    Ok(())
+}
+
+/// WinFsp bin 디렉토리를 PATH에 추가 (지연 로딩되는 WinFsp DLL을 찾을 수 있도록 마운트 전에 호출)
+pub fn setup_winfsp_path() {
+    if let Some(dll_path) = find_winfsp_path() {
+        if let Some(bin_dir) = std::path::Path::new(&dll_path).parent() {
+            if let Ok(current_path) = env::var("PATH") {
+                let bin_str = bin_dir.to_string_lossy().to_string();
+                if !current_path.contains(&bin_str) {
+                    let new_path = format!("{};{}", current_path, bin_str);
+                    env::set_var("PATH", new_path);
+                    println!("WinFsp path added to PATH: {}", bin_str);
+                }
+            }
+        }
+    } else {
+        println!("Warning: Could not find WinFsp installation");
+    }
+}
+
+/// WinFsp 설치 확인
+pub fn find_winfsp_path() -> Option<String> {
+    // 1. 먼저 레지스트리에서 설치 경로 확인
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let registry_keys = [r"SOFTWARE\WOW6432Node\WinFsp", r"SOFTWARE\WinFsp"];
+
+    for key_path in registry_keys {
+        if let Ok(key) = hklm.open_subkey(key_path) {
+            if let Ok(install_dir) = key.get_value::<String, _>("InstallDir") {
+                let bin_path = std::path::PathBuf::from(&install_dir).join("bin");
+                let dll_path = bin_path.join("winfsp-x64.dll");
+                if dll_path.exists() {
+                    return Some(dll_path.to_string_lossy().to_string());
+                }
+                // x86 DLL도 확인
+                let dll_path_x86 = bin_path.join("winfsp-x86.dll");
+                if dll_path_x86.exists() {
+                    return Some(dll_path_x86.to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+
+    // 2. 일반적인 경로에서 찾기
+    let possible_paths = [
+        r"C:\Program Files (x86)\WinFsp\bin\winfsp-x64.dll",
+        r"C:\Program Files\WinFsp\bin\winfsp-x64.dll",
+        r"C:\Program Files (x86)\WinFsp\bin\winfsp-x86.dll",
+        r"C:\Program Files\WinFsp\bin\winfsp-x86.dll",
+    ];
+
+    for path in possible_paths {
+        if std::path::Path::new(path).exists() {
+            return Some(path.to_string());
+        }
+    }
+
+    None
+}
+
+/// 사전 요구사항 확인 (WinFsp만 확인, SSHFS-Win 불필요)와 실행 환경 점검
+pub fn check_prerequisites() -> PrerequisiteStatus {
+    let winfsp_path = find_winfsp_path();
+    let environment = environment::report(winfsp_path.as_deref());
+
+    PrerequisiteStatus {
+        winfsp_installed: winfsp_path.is_some(),
+        sshfs_installed: true, // 더 이상 SSHFS-Win 불필요
+        winfsp_path,
+        sshfs_path: Some("Rust Native (builtin)".to_string()),
+        environment,
+    }
+}
+
+/// WinFsp 런처 서비스 시작 (필요하면 UAC로 관리자 권한 요청)
+pub fn start_winfsp_service() -> Result<(), String> {
+    environment::start_winfsp_launcher()
+}
+
+/// 사용 가능한 드라이브 문자 목록 (현재 세션과 전역 네임스페이스 모두 비어 있는 문자)
+pub fn get_available_drive_letters() -> Vec<char> {
+    ('D'..='Z')
+        .filter(|&letter| {
+            let usage = letter_usage(letter);
+            !usage.session && !usage.global
+        })
+        .collect()
+}
@@ -0,0 +1,466 @@
+//! 원격 이름 정규화 모듈 - 유니코드 정규화 형태만 다른 이름을 같은 원격 항목으로 맞춤
+//!
+//! macOS에서 만든 파일은 이름이 NFD(한글 자모, 악센트를 분리한 형태)로 저장되어, NFC 이름을
+//! 쓰는 Windows에서는 같은 이름이 두 번 보이거나 열리지 않는다. 서버에서 받은 이름은 NFC로
+//! 바꿔 보여주고, 서버로 보내는 경로는 목록에서 본 실제 이름으로 되돌린다. 목록에서 보지 못한
+//! 이름(새로 만드는 파일 등)은 연결의 정규화 정책에 따른 형태로 보낸다. 한 목록에 로컬
+//! 이름이 같아지는 이름이 함께 있으면 (NFC와 NFD 등) 서버 이름을 바꾸지 않은 항목만 보여준다.
+//!
+//! Windows가 장치로 여기는 이름(`con`, `aux`, `nul`, `com1` 등)이나 점/공백으로 끝나는 이름은
+//! 탐색기에서 열 수 없으므로 뒤에 `~`를 붙여 보여주고, 서버로 보낼 때 떼어 낸다. 원래 `~`로
+//! 끝나던 이런 이름에는 `~`를 하나 더 붙여 두 방향 변환이 항상 서로를 되돌리게 한다.
+//!
+//! 이름에 Windows에서 쓸 수 없는 문자(`:`, `*`, `?`, `"`, `<`, `>`, `|`, `\`, 제어 문자)가 있으면
+//! 목록이 깨지거나 열리지 않으므로, 사설 영역 문자(U+F000 + 코드, WSL/Cygwin과 같은 방식)나
+//! 전각 문자로 바꿔 보여준다. 사설 영역 문자는 서버로 보낼 때 항상 원래 문자로 되돌리고,
+//! 전각 문자는 목록에서 본 이름만 되돌린다 (사용자가 직접 입력한 전각 문자는 그대로 보냄).
+//! 서버 이름에 원래 그 범위의 사설 영역 문자가 있으면 앞에 U+F000을 붙여 바꾼 문자와 구별한다.
+
+use crate::types::{IllegalCharEscape, NameNormalization, ReservedNameEscape, SshConnection};
+use icu_normalizer::{ComposingNormalizerBorrowed, DecomposingNormalizerBorrowed};
+use log::debug;
+use parking_lot::Mutex;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// 기억할 최대 이름 수 (정규화 형태가 다른 이름만 기억하므로 보통 훨씬 적음)
+const MAX_KNOWN_NAMES: usize = 100_000;
+
+/// Windows에서 쓸 수 없는 이름 뒤에 붙이는 표시
+const ESCAPE_MARK: char = '~';
+
+/// 쓸 수 없는 문자를 옮겨 놓는 사설 영역의 시작 (U+F000 + 원래 문자 코드)
+const PRIVATE_BASE: u32 = 0xF000;
+
+/// 서버 이름에 원래 있던 사설 영역 문자 앞에 붙이는 표시 (NUL 자리라 바꾼 문자와 겹치지 않음)
+const PRIVATE_ESCAPE: char = '\u{F000}';
+
+/// 전각 문자와 ASCII 문자의 코드 차이 (`:` U+003A → `：` U+FF1A)
+const FULLWIDTH_OFFSET: u32 = 0xFEE0;
+
+const NFC: ComposingNormalizerBorrowed<'static> = ComposingNormalizerBorrowed::new_nfc();
+const NFD: DecomposingNormalizerBorrowed<'static> = DecomposingNormalizerBorrowed::new_nfd();
+
+/// 연결 하나의 로컬 이름 ↔ 서버 이름 대응 (재연결하거나 세션을 더 열어도 공유)
+pub(crate) struct NameMapper {
+    policy: NameNormalization,
+    reserved: ReservedNameEscape,
+    illegal: IllegalCharEscape,
+    // 로컬 형태로 바꾼 전체 경로 → 서버의 실제 이름 (그대로 되돌릴 수 없는 이름만)
+    known: Mutex<HashMap<String, String>>,
+}
+
+impl NameMapper {
+    pub(crate) fn new(connection: &SshConnection) -> Self {
+        Self {
+            policy: connection.name_normalization,
+            reserved: connection.reserved_names,
+            illegal: connection.illegal_chars,
+            known: Mutex::default(),
+        }
+    }
+
+    /// 서버에서 받은 이름을 로컬 형태(NFC, Windows에서 쓸 수 있는 이름)로 바꿈
+    /// (정규화 형태가 다르거나 전각 문자로 바꿨으면 실제 이름을 기억)
+    pub(crate) fn to_local(&self, dir: &str, name: String) -> String {
+        let normalized = match self.normalize(&name) {
+            Cow::Borrowed(_) => None,
+            Cow::Owned(local) => Some(local),
+        };
+        let replaced = replace_illegal(normalized.as_deref().unwrap_or(&name), self.illegal);
+        let remember = normalized.is_some()
+            || (replaced.is_some() && self.illegal == IllegalCharEscape::Fullwidth);
+        let local = match replaced.or(normalized) {
+            Some(local) if remember => self.escape(local),
+            Some(local) => return self.escape(local),
+            None => return self.escape(name),
+        };
+        self.remember_name(dir, &local, name);
+        local
+    }
+
+    /// 디렉토리 목록의 이름을 로컬 형태로 바꿈
+    ///
+    /// 로컬 이름이 겹치는 항목(정규화 형태만 다른 이름 등)은 탐색기가 구별하지 못하므로
+    /// 서버 이름을 바꾸지 않고 보여준 항목을 남기고, 그 이름으로 열면 그 항목을 찾게 한다.
+    pub(crate) fn to_local_entries<T>(
+        &self,
+        dir: &str,
+        entries: Vec<(String, T)>,
+    ) -> Vec<(String, T)> {
+        let mut mapped: Vec<(String, T)> = Vec::with_capacity(entries.len());
+        // 겹칠 수 있는 로컬 이름 → (목록 위치, 바꾸지 않고 보여준 항목이면 서버 이름)
+        let mut seen: HashMap<String, (usize, Option<String>)> = HashMap::new();
+        for (name, value) in entries {
+            // ASCII 이름은 쓸 수 없는 문자를 바꿀 때만 ASCII가 아닌 로컬 이름과 겹칠 수 있음
+            let original =
+                (!name.is_ascii() || name.contains(is_illegal_char)).then(|| name.clone());
+            let local = self.to_local(dir, name);
+            let Some(original) = original else {
+                mapped.push((local, value));
+                continue;
+            };
+            let unchanged = matches!(self.normalize(&original), Cow::Borrowed(_))
+                && replace_illegal(&original, self.illegal).is_none();
+            let original = unchanged.then_some(original);
+            match seen.get(&local) {
+                None => {
+                    seen.insert(local.clone(), (mapped.len(), original));
+                    mapped.push((local, value));
+                }
+                Some(&(index, ref kept)) => {
+                    debug!(
+                        "[Names] '{}/{}' shown once for several server names",
+                        dir.trim_end_matches('/'),
+                        local
+                    );
+                    if kept.is_none() && original.is_some() {
+                        seen.insert(local.clone(), (index, original));
+                        mapped[index] = (local, value);
+                    }
+                }
+            }
+        }
+        // 바꾸지 않은 이름을 남겼으면 다른 항목이 기억시킨 서버 이름 대신 그 이름으로 보냄
+        for (local, (_, original)) in seen {
+            if let Some(original) = original {
+                if self.known.lock().contains_key(&self.known_key(dir, &local)) {
+                    self.remember_name(dir, &local, original);
+                }
+            }
+        }
+        mapped
+    }
+
+    /// 서버로 보낼 경로 (목록에서 본 이름은 실제 이름으로, 처음 보는 이름은 정책 형태로)
+    pub(crate) fn to_remote<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        let escaped = (self.reserved != ReservedNameEscape::Off && path.contains(ESCAPE_MARK))
+            || (self.illegal != IllegalCharEscape::Off && !path.is_ascii());
+        if (self.policy == NameNormalization::Off || path.is_ascii()) && !escaped {
+            return Cow::Borrowed(path);
+        }
+        let local = self.normalize(path);
+        let known = self.known.lock();
+        let mut prefix = String::with_capacity(local.len());
+        let mut remote = String::with_capacity(local.len());
+        for (i, component) in local.split('/').enumerate() {
+            if i > 0 {
+                prefix.push('/');
+                remote.push('/');
+            }
+            prefix.push_str(component);
+            match known.get(&prefix) {
+                Some(actual) => remote.push_str(actual),
+                None => {
+                    let component = match escaped {
+                        true => self.unescape(component),
+                        false => Cow::Borrowed(component),
+                    };
+                    match self.policy {
+                        NameNormalization::Nfd => remote.push_str(&NFD.normalize(&component)),
+                        _ => remote.push_str(&component),
+                    }
+                }
+            }
+        }
+        Cow::Owned(remote)
+    }
+
+    /// 정책과 반대 형태의 경로 (서버에 다른 형태로 있을 수 있을 때 다시 찾아볼 경로)
+    pub(crate) fn alternate(&self, path: &str) -> Option<String> {
+        if self.policy == NameNormalization::Off || path.is_ascii() {
+            return None;
+        }
+        let remote = self.to_remote(path);
+        let alternate = match self.policy {
+            NameNormalization::Nfd => NFC.normalize(&remote).into_owned(),
+            _ => NFD.normalize(&remote).into_owned(),
+        };
+        (alternate != remote).then_some(alternate)
+    }
+
+    /// 다른 형태로 찾은 경로의 실제 이름을 기억
+    pub(crate) fn remember(&self, path: &str, remote: &str) {
+        let local = self.normalize(path);
+        let mut known = self.known.lock();
+        let mut prefix = String::with_capacity(local.len());
+        for (i, (component, actual)) in local.split('/').zip(remote.split('/')).enumerate() {
+            if i > 0 {
+                prefix.push('/');
+            }
+            prefix.push_str(component);
+            if component != actual && known.len() < MAX_KNOWN_NAMES {
+                known.insert(prefix.clone(), actual.to_string());
+            }
+        }
+    }
+
+    fn known_key(&self, dir: &str, local: &str) -> String {
+        format!("{}/{}", self.normalize(dir.trim_end_matches('/')), local)
+    }
+
+    /// 로컬 이름의 서버 이름을 기억
+    fn remember_name(&self, dir: &str, local: &str, remote: String) {
+        let key = self.known_key(dir, local);
+        let mut known = self.known.lock();
+        if known.len() < MAX_KNOWN_NAMES || known.contains_key(&key) {
+            known.insert(key, remote);
+        } else {
+            debug!("[Names] too many mapped names, '{}' not remembered", key);
+        }
+    }
+
+    /// 정책에 따라 NFC로 바꾼 경로 (정규화하지 않으면 그대로)
+    fn normalize<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        if self.policy == NameNormalization::Off || path.is_ascii() {
+            return Cow::Borrowed(path);
+        }
+        NFC.normalize(path)
+    }
+
+    fn escape(&self, name: String) -> String {
+        match self.reserved {
+            ReservedNameEscape::Suffix => escape_reserved(name),
+            ReservedNameEscape::Off => name,
+        }
+    }
+
+    /// [`escape`](Self::escape)와 문자 바꾸기를 되돌린 이름 (바꾼 순서의 반대로)
+    fn unescape<'a>(&self, component: &'a str) -> Cow<'a, str> {
+        let component = match self.reserved {
+            ReservedNameEscape::Suffix => unescape_reserved(component),
+            ReservedNameEscape::Off => Cow::Borrowed(component),
+        };
+        match self.illegal {
+            IllegalCharEscape::Off => component,
+            _ => restore_illegal(component),
+        }
+    }
+}
+
+/// Windows 이름에 쓸 수 없는 문자인지
+fn is_illegal_char(c: char) -> bool {
+    matches!(c, ':' | '*' | '?' | '"' | '<' | '>' | '|' | '\\') || c.is_ascii_control()
+}
+
+/// 사설 영역으로 옮겨 놓은 문자라면 원래 문자 ([`PRIVATE_ESCAPE`]는 NUL)
+fn private_original(c: char) -> Option<char> {
+    (c as u32)
+        .checked_sub(PRIVATE_BASE)
+        .and_then(char::from_u32)
+        .filter(|&c| is_illegal_char(c))
+}
+
+/// 쓸 수 없는 문자를 정한 방식으로 바꿈 (바꿀 문자가 없으면 None)
+///
+/// 전각 문자가 없는 제어 문자는 전각 방식에서도 사설 영역 문자로 바꾼다. 바꾼 문자로
+/// 읽힐 사설 영역 문자가 원래 이름에 있으면 앞에 [`PRIVATE_ESCAPE`]를 붙인다.
+fn replace_illegal(name: &str, escape: IllegalCharEscape) -> Option<String> {
+    if escape == IllegalCharEscape::Off
+        || !name.contains(|c| is_illegal_char(c) || private_original(c).is_some())
+    {
+        return None;
+    }
+    let offset = |c: char| match escape {
+        IllegalCharEscape::Fullwidth if !c.is_ascii_control() => FULLWIDTH_OFFSET,
+        _ => PRIVATE_BASE,
+    };
+    let mut replaced = String::with_capacity(name.len() + 2);
+    for c in name.chars() {
+        if is_illegal_char(c) {
+            replaced.push(char::from_u32(c as u32 + offset(c)).unwrap_or(c));
+            continue;
+        }
+        if private_original(c).is_some() {
+            replaced.push(PRIVATE_ESCAPE);
+        }
+        replaced.push(c);
+    }
+    Some(replaced)
+}
+
+/// 사설 영역으로 옮겨 놓은 문자를 원래 문자로 되돌림 ([`PRIVATE_ESCAPE`] 다음 문자는 그대로)
+fn restore_illegal(component: Cow<'_, str>) -> Cow<'_, str> {
+    if !component.chars().any(|c| private_original(c).is_some()) {
+        return component;
+    }
+    let mut restored = String::with_capacity(component.len());
+    let mut chars = component.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            PRIVATE_ESCAPE => restored.push(chars.next().unwrap_or(PRIVATE_ESCAPE)),
+            _ => restored.push(private_original(c).unwrap_or(c)),
+        }
+    }
+    Cow::Owned(restored)
+}
+
+/// Windows가 장치로 여기는 이름인지 (대소문자 무시, `con.txt`처럼 확장자가 붙어도 해당)
+fn is_device_name(stem: &str) -> bool {
+    let upper = stem.to_ascii_uppercase();
+    matches!(
+        upper.as_bytes(),
+        b"CON" | b"PRN" | b"AUX" | b"NUL" | b"CONIN$" | b"CONOUT$"
+    ) || matches!(
+        upper.as_bytes(),
+        [b'C', b'O', b'M', b'1'..=b'9'] | [b'L', b'P', b'T', b'1'..=b'9']
+    )
+}
+
+/// Windows에서 쓸 수 없는 이름에 표시를 붙임
+///
+/// 장치 이름은 확장자 앞에, 점/공백으로 끝나는 이름은 맨 뒤에 붙인다. 표시만 더 붙은
+/// 이름에도 하나 더 붙여 [`unescape_reserved`]가 항상 원래 이름으로 되돌릴 수 있게 한다.
+fn escape_reserved(mut name: String) -> String {
+    if name == "." || name == ".." {
+        return name;
+    }
+    let stem_len = name.find('.').unwrap_or(name.len());
+    if is_device_name(name[..stem_len].trim_end_matches(ESCAPE_MARK)) {
+        name.insert(stem_len, ESCAPE_MARK);
+    }
+    if name.trim_end_matches(ESCAPE_MARK).ends_with(['.', ' ']) {
+        name.push(ESCAPE_MARK);
+    }
+    name
+}
+
+/// [`escape_reserved`]로 붙인 표시를 뗌 (붙인 순서의 반대로)
+fn unescape_reserved(component: &str) -> Cow<'_, str> {
+    if !component.contains(ESCAPE_MARK) {
+        return Cow::Borrowed(component);
+    }
+    let mut name = component.to_string();
+    if name.ends_with(ESCAPE_MARK) && name.trim_end_matches(ESCAPE_MARK).ends_with(['.', ' ']) {
+        name.pop();
+    }
+    let stem_len = name.find('.').unwrap_or(name.len());
+    let stem = &name[..stem_len];
+    if stem.ends_with(ESCAPE_MARK) && is_device_name(stem.trim_end_matches(ESCAPE_MARK)) {
+        name.remove(stem_len - 1);
+    }
+    Cow::Owned(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapper(policy: NameNormalization, illegal: IllegalCharEscape) -> NameMapper {
+        NameMapper {
+            policy,
+            reserved: ReservedNameEscape::Suffix,
+            illegal,
+            known: Mutex::default(),
+        }
+    }
+
+    /// 목록에서 본 서버 이름이 로컬 이름을 거쳐 그대로 돌아오는지 확인하고 로컬 이름을 돌려줌
+    fn round_trip(names: &NameMapper, remote: &str) -> String {
+        let local = names.to_local("/d", remote.to_string());
+        assert_eq!(
+            names.to_remote(&format!("/d/{}", local)),
+            format!("/d/{}", remote),
+            "{:?} -> {:?}",
+            remote,
+            local
+        );
+        local
+    }
+
+    /// 목록에서 보지 않은 로컬 이름이 서버 이름을 거쳐 그대로 돌아오는지
+    fn reverse_trip(names: &NameMapper, local: &str) {
+        let remote = names.to_remote(&format!("/d/{}", local)).into_owned();
+        let remote = remote.strip_prefix("/d/").unwrap().to_string();
+        assert_eq!(names.to_local("/d", remote), local);
+    }
+
+    #[test]
+    fn reserved_device_names_round_trip() {
+        let names = mapper(NameNormalization::Nfc, IllegalCharEscape::Unicode);
+        let cases = [
+            ("CON", "CON~"),
+            ("aux.txt", "aux~.txt"),
+            ("COM1", "COM1~"),
+            // 이미 `~`로 끝나는 장치 이름은 하나 더 붙음
+            ("CON~", "CON~~"),
+            ("aux~.txt", "aux~~.txt"),
+            ("COM1~~", "COM1~~~"),
+            // 장치 이름이 아니면 `~`로 끝나도 그대로
+            ("notes~", "notes~"),
+            ("console", "console"),
+            ("COM10", "COM10"),
+        ];
+        for (remote, local) in cases {
+            assert_eq!(round_trip(&names, remote), local);
+            reverse_trip(&names, local);
+        }
+    }
+
+    #[test]
+    fn trailing_dots_and_illegal_chars_round_trip() {
+        let names = mapper(NameNormalization::Nfc, IllegalCharEscape::Unicode);
+        let cases = [
+            ("a.", "a.~"),
+            ("a ", "a ~"),
+            ("a.~", "a.~~"),
+            ("COM1.", "COM1~.~"),
+            ("a<b>", "a\u{F03C}b\u{F03E}"),
+            ("a:b", "a\u{F03A}b"),
+            // 원래 있던 사설 영역 문자는 바꾼 문자와 구별되도록 표시가 붙음
+            ("a\u{F03A}b", "a\u{F000}\u{F03A}b"),
+            ("a\u{F000}b", "a\u{F000}\u{F000}b"),
+            ("a\u{F000}:b", "a\u{F000}\u{F000}\u{F03A}b"),
+        ];
+        let mut seen = std::collections::HashSet::new();
+        for (remote, local) in cases {
+            assert_eq!(round_trip(&names, remote), local);
+            reverse_trip(&names, local);
+            assert!(seen.insert(local), "{:?} mapped twice", local);
+        }
+    }
+
+    #[test]
+    fn fullwidth_restores_listed_names_only() {
+        let names = mapper(NameNormalization::Nfc, IllegalCharEscape::Fullwidth);
+        assert_eq!(round_trip(&names, "a<b>"), "a\u{FF1C}b\u{FF1E}");
+        assert_eq!(round_trip(&names, "a:b."), "a\u{FF1A}b.~");
+        // 전각 문자가 없는 제어 문자는 사설 영역 문자로
+        assert_eq!(round_trip(&names, "a\u{1}b"), "a\u{F001}b");
+        // 직접 입력한 전각 문자는 그대로 보냄
+        assert_eq!(names.to_remote("/d/x\u{FF1A}y"), "/d/x\u{FF1A}y");
+    }
+
+    const NFC_NAME: &str = "caf\u{E9}.txt";
+    const NFD_NAME: &str = "cafe\u{301}.txt";
+
+    #[test]
+    fn listed_nfd_name_reopens_with_exact_remote_bytes() {
+        for policy in [NameNormalization::Nfc, NameNormalization::Nfd] {
+            let names = mapper(policy, IllegalCharEscape::Unicode);
+            let listed = names.to_local_entries("/d", vec![(NFD_NAME.to_string(), ())]);
+            assert_eq!(listed, vec![(NFC_NAME.to_string(), ())]);
+            assert_eq!(
+                names.to_remote(&format!("/d/{}", NFC_NAME)),
+                format!("/d/{}", NFD_NAME)
+            );
+        }
+    }
+
+    #[test]
+    fn nfc_and_nfd_variants_in_one_directory_keep_the_nfc_entry() {
+        for order in [[NFD_NAME, NFC_NAME], [NFC_NAME, NFD_NAME]] {
+            let names = mapper(NameNormalization::Nfc, IllegalCharEscape::Unicode);
+            let entries = order.iter().map(|name| (name.to_string(), *name)).collect();
+            // 탐색기에는 한 번만 보이고, 그 이름은 서버의 NFC 항목을 가리킴
+            assert_eq!(
+                names.to_local_entries("/d", entries),
+                vec![(NFC_NAME.to_string(), NFC_NAME)]
+            );
+            assert_eq!(
+                names.to_remote(&format!("/d/{}", NFC_NAME)),
+                format!("/d/{}", NFC_NAME)
+            );
+        }
+    }
+}
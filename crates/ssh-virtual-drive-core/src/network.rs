@@ -0,0 +1,426 @@
+//! 네트워크 연결 모듈 - SSH 서버까지의 TCP 스트림 생성
+//!
+//! 연결에 프록시가 설정되어 있으면 SOCKS5(RFC 1928/1929) 또는 HTTP CONNECT로
+//! 터널을 연 뒤 그 스트림을 SSH 세션에 넘긴다. 대상 호스트 이름은 프록시가 해석한다.
+//!
+//! 호스트 이름이 여러 주소로 해석되면 IPv6/IPv4를 번갈아 가며 조금씩 늦춰 시도하고
+//! (Happy Eyeballs, RFC 8305) 가장 먼저 연결된 주소를 사용한다.
+//!
+//! 연결의 `host_overrides`에 있는 이름은 DNS 대신 지정한 IP로 연결한다.
+//! Known Hosts 확인은 원래 호스트 이름으로 하므로 키는 이름 기준으로 유지된다.
+//!
+//! 스트림을 SSH 세션에 넘기기 전에 연결의 TCP 옵션(nodelay, keepalive, DSCP)을 적용한다.
+//! DSCP는 Windows 그룹 정책이 허용하지 않으면 패킷에 실제로 표시되지 않을 수 있다.
+
+use crate::credentials;
+use crate::types::{ProxyKind, ProxySettings, SshConnection};
+use log::warn;
+use std::io::{self, Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::os::windows::io::AsRawSocket;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// 다음 주소 시도 전 대기 시간
+const ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// 주소 하나당 연결 타임아웃
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 프록시 핸드셰이크(SOCKS5/HTTP CONNECT) 응답 대기 시간
+const PROXY_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// keepalive 응답이 없을 때 다시 보내는 간격
+const KEEPALIVE_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+const SOL_SOCKET: i32 = 0xffff;
+const SO_KEEPALIVE: i32 = 0x0008;
+const IPPROTO_IP: i32 = 0;
+const IP_TOS: i32 = 3;
+const IPPROTO_TCP: i32 = 6;
+const TCP_KEEPIDLE: i32 = 3;
+const TCP_KEEPINTVL: i32 = 17;
+const IPPROTO_IPV6: i32 = 41;
+const IPV6_TCLASS: i32 = 39;
+
+#[link(name = "ws2_32")]
+extern "system" {
+    fn setsockopt(socket: usize, level: i32, name: i32, value: *const i32, len: i32) -> i32;
+}
+
+/// 서버(또는 프록시를 거친 서버)에 연결된 TCP 스트림
+pub fn open_stream(connection: &SshConnection) -> Result<TcpStream, String> {
+    let stream = open_tunnel(connection)?;
+    apply_tcp_options(&stream, connection);
+    Ok(stream)
+}
+
+fn open_tunnel(connection: &SshConnection) -> Result<TcpStream, String> {
+    let target = resolve_override(connection, &connection.host)?;
+    let Some(proxy) = connection.proxy.as_ref() else {
+        return connect_any(&target, connection.port).map_err(|e| format!("TCP 연결 실패: {}", e));
+    };
+
+    let proxy_host = resolve_override(connection, &proxy.host)?;
+    let mut stream =
+        connect_any(&proxy_host, proxy.port).map_err(|e| format!("프록시 연결 실패: {}", e))?;
+    let password = match proxy.username {
+        Some(_) => credentials::get_password(&credentials::proxy_credential_key(&connection.id))?,
+        None => None,
+    };
+
+    // 응답하지 않는 프록시에서 멈추지 않도록 핸드셰이크 동안만 타임아웃 적용
+    set_io_timeout(&stream, Some(PROXY_HANDSHAKE_TIMEOUT))?;
+    match proxy.kind {
+        ProxyKind::Socks5 => socks5_connect(
+            &mut stream,
+            proxy,
+            password.as_deref(),
+            &target,
+            connection.port,
+        )?,
+        ProxyKind::Http => http_connect(
+            &mut stream,
+            proxy,
+            password.as_deref(),
+            &target,
+            connection.port,
+        )?,
+    }
+    set_io_timeout(&stream, None)?;
+    Ok(stream)
+}
+
+fn set_io_timeout(stream: &TcpStream, timeout: Option<Duration>) -> Result<(), String> {
+    stream
+        .set_read_timeout(timeout)
+        .and_then(|_| stream.set_write_timeout(timeout))
+        .map_err(|e| format!("프록시 타임아웃 설정 실패: {}", e))
+}
+
+/// 연결의 TCP 옵션 적용 (실패해도 연결은 계속)
+fn apply_tcp_options(stream: &TcpStream, connection: &SshConnection) {
+    if let Err(e) = stream.set_nodelay(connection.tcp_nodelay) {
+        warn!("[Network] TCP_NODELAY not applied: {}", e);
+    }
+    if let Some(secs) = connection.tcp_keepalive_secs.filter(|&secs| secs > 0) {
+        let interval = KEEPALIVE_RETRY_INTERVAL.as_secs() as i32;
+        let result = set_option(stream, SOL_SOCKET, SO_KEEPALIVE, 1)
+            .and_then(|_| set_option(stream, IPPROTO_TCP, TCP_KEEPIDLE, secs as i32))
+            .and_then(|_| set_option(stream, IPPROTO_TCP, TCP_KEEPINTVL, interval));
+        if let Err(e) = result {
+            warn!("[Network] TCP keepalive not applied: {}", e);
+        }
+    }
+    if let Some(dscp) = connection.dscp {
+        // DSCP는 TOS/Traffic Class 바이트의 상위 6비트
+        let value = (dscp.min(63) as i32) << 2;
+        let result = match stream.peer_addr() {
+            Ok(addr) if addr.is_ipv6() => set_option(stream, IPPROTO_IPV6, IPV6_TCLASS, value),
+            _ => set_option(stream, IPPROTO_IP, IP_TOS, value),
+        };
+        if let Err(e) = result {
+            warn!("[Network] DSCP {} not applied: {}", dscp, e);
+        }
+    }
+}
+
+fn set_option(stream: &TcpStream, level: i32, name: i32, value: i32) -> io::Result<()> {
+    let result = unsafe {
+        setsockopt(
+            stream.as_raw_socket() as usize,
+            level,
+            name,
+            &value,
+            std::mem::size_of::<i32>() as i32,
+        )
+    };
+    match result {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+/// 연결에 지정된 IP가 있으면 그 주소, 없으면 원래 호스트 이름 (대소문자 구분 없음)
+fn resolve_override(connection: &SshConnection, host: &str) -> Result<String, String> {
+    let Some((name, address)) = connection
+        .host_overrides
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(host))
+    else {
+        return Ok(host.to_string());
+    };
+    let address = address.trim();
+    address
+        .parse::<IpAddr>()
+        .map(|ip| ip.to_string())
+        .map_err(|_| format!("'{}'에 지정한 주소가 IP가 아닙니다: {}", name, address))
+}
+
+/// 해석된 모든 주소로 시차를 두고 연결을 시도해 먼저 성공한 스트림 반환
+fn connect_any(host: &str, port: u16) -> io::Result<TcpStream> {
+    let addrs = interleave_families((host, port).to_socket_addrs()?.collect());
+    if addrs.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "호스트 주소를 찾을 수 없습니다",
+        ));
+    }
+    if addrs.len() == 1 {
+        return TcpStream::connect_timeout(&addrs[0], CONNECT_TIMEOUT);
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let mut pending = 0;
+    let mut last_error = None;
+    let mut record = |result: io::Result<TcpStream>, pending: &mut usize| match result {
+        Ok(stream) => Some(stream),
+        Err(e) => {
+            *pending -= 1;
+            last_error = Some(e);
+            None
+        }
+    };
+
+    for addr in addrs {
+        let tx = tx.clone();
+        // 늦게 성공한 연결은 받는 쪽이 없으므로 그대로 닫힘
+        std::thread::spawn(move || {
+            let _ = tx.send(TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT));
+        });
+        pending += 1;
+
+        // 앞선 시도가 빨리 실패하면 기다리지 않고 다음 주소로
+        if let Ok(result) = rx.recv_timeout(ATTEMPT_DELAY) {
+            if let Some(stream) = record(result, &mut pending) {
+                return Ok(stream);
+            }
+        }
+    }
+    drop(tx);
+
+    while pending > 0 {
+        let Ok(result) = rx.recv() else {
+            break;
+        };
+        if let Some(stream) = record(result, &mut pending) {
+            return Ok(stream);
+        }
+    }
+    Err(last_error.unwrap_or_else(|| io::Error::from(io::ErrorKind::TimedOut)))
+}
+
+/// 첫 주소의 종류부터 IPv6/IPv4를 번갈아 배치 (한쪽 경로가 막혀도 다른 쪽이 곧 시도되도록)
+fn interleave_families(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let prefer_v6 = addrs.first().is_some_and(SocketAddr::is_ipv6);
+    let (mut first, mut second): (Vec<_>, Vec<_>) = addrs
+        .into_iter()
+        .partition(|addr| addr.is_ipv6() == prefer_v6);
+    first.reverse();
+    second.reverse();
+
+    let mut ordered = Vec::with_capacity(first.len() + second.len());
+    loop {
+        match (first.pop(), second.pop()) {
+            (None, None) => break,
+            (a, b) => ordered.extend(a.into_iter().chain(b)),
+        }
+    }
+    ordered
+}
+
+/// SOCKS5 핸드셰이크 후 대상 서버로 CONNECT
+fn socks5_connect(
+    stream: &mut TcpStream,
+    proxy: &ProxySettings,
+    password: Option<&str>,
+    host: &str,
+    port: u16,
+) -> Result<(), String> {
+    let io_err = |e: std::io::Error| format!("SOCKS5 프록시 통신 실패: {}", e);
+
+    // 인증 방식 협상 (0: 인증 없음, 2: 사용자 이름/비밀번호)
+    let greeting: &[u8] = if proxy.username.is_some() {
+        &[5, 2, 0, 2]
+    } else {
+        &[5, 1, 0]
+    };
+    stream.write_all(greeting).map_err(io_err)?;
+    let mut reply = [0u8; 2];
+    stream.read_exact(&mut reply).map_err(io_err)?;
+    match reply {
+        [5, 0] => {}
+        [5, 2] => {
+            let username = proxy.username.as_deref().unwrap_or_default();
+            let password = password.unwrap_or_default();
+            if username.len() > 255 || password.len() > 255 {
+                return Err("프록시 사용자 이름/비밀번호가 너무 깁니다.".to_string());
+            }
+            let mut auth = vec![1, username.len() as u8];
+            auth.extend_from_slice(username.as_bytes());
+            auth.push(password.len() as u8);
+            auth.extend_from_slice(password.as_bytes());
+            stream.write_all(&auth).map_err(io_err)?;
+            let mut status = [0u8; 2];
+            stream.read_exact(&mut status).map_err(io_err)?;
+            if status[1] != 0 {
+                return Err("SOCKS5 프록시 인증 실패".to_string());
+            }
+        }
+        [5, 0xFF] => return Err("SOCKS5 프록시가 인증 방식을 거부했습니다.".to_string()),
+        _ => return Err("SOCKS5 프록시 응답이 올바르지 않습니다.".to_string()),
+    }
+
+    // CONNECT 요청 (IP가 아니면 도메인 주소 형식으로 보내 이름 해석은 프록시가 수행)
+    let mut request = vec![5, 1, 0];
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            request.push(1);
+            request.extend_from_slice(&ip.octets());
+        }
+        Ok(IpAddr::V6(ip)) => {
+            request.push(4);
+            request.extend_from_slice(&ip.octets());
+        }
+        Err(_) => {
+            if host.len() > 255 {
+                return Err("호스트 이름이 너무 깁니다.".to_string());
+            }
+            request.extend_from_slice(&[3, host.len() as u8]);
+            request.extend_from_slice(host.as_bytes());
+        }
+    }
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request).map_err(io_err)?;
+
+    let mut header = [0u8; 4];
+    stream.read_exact(&mut header).map_err(io_err)?;
+    if header[1] != 0 {
+        return Err(format!(
+            "SOCKS5 프록시 연결 실패: {}",
+            socks5_reply_message(header[1])
+        ));
+    }
+    // 바인드 주소는 쓰지 않지만 스트림에서 읽어 버려야 SSH 데이터가 시작됨
+    let addr_len = match header[3] {
+        1 => 4,
+        4 => 16,
+        3 => {
+            let mut len = [0u8; 1];
+            stream.read_exact(&mut len).map_err(io_err)?;
+            len[0] as usize
+        }
+        _ => return Err("SOCKS5 프록시 응답이 올바르지 않습니다.".to_string()),
+    };
+    let mut bound = vec![0u8; addr_len + 2];
+    stream.read_exact(&mut bound).map_err(io_err)?;
+    Ok(())
+}
+
+fn socks5_reply_message(code: u8) -> &'static str {
+    match code {
+        1 => "프록시 서버 오류",
+        2 => "프록시 규칙에 의해 거부됨",
+        3 => "네트워크에 연결할 수 없음",
+        4 => "호스트에 연결할 수 없음",
+        5 => "연결이 거부됨",
+        6 => "TTL 만료",
+        7 => "지원하지 않는 명령",
+        8 => "지원하지 않는 주소 형식",
+        _ => "알 수 없는 오류",
+    }
+}
+
+/// HTTP CONNECT 터널 요청
+fn http_connect(
+    stream: &mut TcpStream,
+    proxy: &ProxySettings,
+    password: Option<&str>,
+    host: &str,
+    port: u16,
+) -> Result<(), String> {
+    let io_err = |e: std::io::Error| format!("HTTP 프록시 통신 실패: {}", e);
+
+    let target = if host.contains(':') {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
+    };
+    let mut request = format!("CONNECT {0} HTTP/1.1\r\nHost: {0}\r\n", target);
+    if let Some(username) = proxy.username.as_deref() {
+        let credentials = format!("{}:{}", username, password.unwrap_or_default());
+        request.push_str(&format!(
+            "Proxy-Authorization: Basic {}\r\n",
+            base64_encode(credentials.as_bytes())
+        ));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).map_err(io_err)?;
+
+    // 응답 헤더 끝까지 한 바이트씩 읽음 (뒤따르는 SSH 배너를 소비하지 않도록)
+    let mut response = Vec::new();
+    let mut byte = [0u8; 1];
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() > 16 * 1024 {
+            return Err("HTTP 프록시 응답이 너무 깁니다.".to_string());
+        }
+        stream.read_exact(&mut byte).map_err(io_err)?;
+        response.push(byte[0]);
+    }
+
+    let response = String::from_utf8_lossy(&response);
+    let status_line = response.lines().next().unwrap_or_default();
+    match status_line.split_whitespace().nth(1) {
+        Some(code) if code.starts_with('2') => Ok(()),
+        Some("407") => Err("HTTP 프록시 인증 실패".to_string()),
+        _ => Err(format!("HTTP 프록시 연결 실패: {}", status_line.trim())),
+    }
+}
+
+/// base64 인코딩 (Basic 인증 헤더, 키 지문 표시용)
+pub(crate) fn base64_encode(data: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(TABLE[(n >> (18 - 6 * i)) as usize & 0x3F] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// base64 디코딩 (키 파일, 가져온 사이트의 비밀번호용, 잘못된 문자가 있으면 None)
+pub(crate) fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() / 4 * 3);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in text.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            b'\r' | b'\n' | b' ' | b'\t' => continue,
+            _ => return None,
+        };
+        buffer = buffer << 6 | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
@@ -0,0 +1,486 @@
+//! 오프라인 고정 모듈 - 고른 파일/폴더를 로컬에 내려받아 연결이 끊겨도 읽을 수 있게 함
+//!
+//! 연결에 `offline_paths`를 지정하면 마운트한 뒤 그 경로 아래 파일을 모두 데이터
+//! 디렉토리(`offline/<연결 ID>/`)에 내려받고, 주기적으로 서버와 크기/수정 시간을 비교해
+//! 바뀐 파일만 다시 받는다. 내려받은 목록은 `index.json`에 남아 다음 마운트에도 쓴다.
+//!
+//! 서버 호출이 연결 오류로 실패하면 드라이브를 "연결 끊김"으로 표시하고, 그동안 고정한
+//! 경로는 로컬 복사본으로 답한다. 고정하지 않은 경로는 "장치가 준비되지 않음" 오류로
+//! 거부한다. 백그라운드 동기화가 다시 서버에 닿으면 표시를 지운다. 일시 중지한 드라이브도
+//! 고정한 파일은 로컬 복사본으로 읽을 수 있다.
+
+use crate::cache::{parent_path, StatCache};
+use crate::path_filter::{AccessRules, PathFilter};
+use crate::sftp_client::{self as sftp, SharedSftpClient};
+use crate::stats::StatsCollector;
+use crate::storage;
+use crate::types::SshConnection;
+use log::{debug, info, warn};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use ssh2::FileStat;
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+/// 동기화가 끝난 뒤 다음 동기화까지 기다리는 시간
+const SYNC_INTERVAL: Duration = Duration::from_secs(300);
+
+/// 연결이 끊긴 동안 서버를 다시 확인하는 간격
+const PROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 고정 경로마다 내려받을 최대 항목 수 (실수로 아주 큰 트리를 고정한 경우 대비)
+const MAX_ENTRIES_PER_PIN: usize = 20_000;
+
+/// 이 시간 안에 드라이브 작업이 있었으면 쉬었다가 이어 받음
+const YIELD_WHILE_ACTIVE: Duration = Duration::from_secs(2);
+
+/// 대기 중 드라이브가 언마운트되었는지 확인하는 간격
+const POLL: Duration = Duration::from_millis(500);
+
+/// 내려받은 항목 하나의 메타데이터
+#[derive(Clone, Serialize, Deserialize)]
+struct OfflineEntry {
+    is_dir: bool,
+    size: u64,
+    #[serde(default)]
+    mtime: Option<u64>,
+    #[serde(default)]
+    perm: Option<u32>,
+}
+
+impl OfflineEntry {
+    fn from_stat(stat: &FileStat) -> Self {
+        Self {
+            is_dir: stat.is_dir(),
+            size: stat.size.unwrap_or(0),
+            mtime: stat.mtime,
+            perm: stat.perm,
+        }
+    }
+
+    fn to_stat(&self) -> FileStat {
+        FileStat {
+            size: Some(self.size),
+            uid: None,
+            gid: None,
+            perm: self
+                .perm
+                .or(Some(if self.is_dir { 0o040_755 } else { 0o100_644 })),
+            atime: self.mtime,
+            mtime: self.mtime,
+        }
+    }
+
+    /// 서버의 파일과 같은 내용인지 (크기와 수정 시간으로 판단)
+    fn matches(&self, stat: &FileStat) -> bool {
+        self.size == stat.size.unwrap_or(0) && self.mtime == stat.mtime
+    }
+}
+
+/// 디스크에 저장하는 목록 (다른 서버의 복사본을 잘못 쓰지 않도록 연결 식별자 포함)
+#[derive(Default, Serialize, Deserialize)]
+struct OfflineIndex {
+    identity: String,
+    entries: BTreeMap<String, OfflineEntry>,
+}
+
+/// 연결 하나의 오프라인 복사본
+pub struct OfflineStore {
+    dir: PathBuf,
+    identity: String,
+    // 고정한 원격 절대 경로 (드라이브 루트 아래만)
+    pins: Vec<String>,
+    index: RwLock<OfflineIndex>,
+    // 마지막 서버 호출이 연결 오류로 실패함
+    disconnected: AtomicBool,
+}
+
+impl OfflineStore {
+    /// 연결의 오프라인 복사본 열기 (고정한 경로가 없으면 None)
+    pub(crate) fn open(connection: &SshConnection) -> Option<Arc<Self>> {
+        let root = connection.remote_path.trim_end_matches('/');
+        let pins: Vec<String> = connection
+            .offline_paths
+            .iter()
+            .map(|path| path.trim())
+            .filter(|path| !path.is_empty())
+            .map(|path| match path.trim_end_matches('/') {
+                "" => "/".to_string(),
+                path => path.to_string(),
+            })
+            .filter(|path| {
+                let inside = path == root || path.starts_with(&format!("{}/", root));
+                if !inside {
+                    warn!("[Offline] pinned path '{}' is outside the drive", path);
+                }
+                inside
+            })
+            .collect();
+        if pins.is_empty() {
+            return None;
+        }
+        let dir = storage::get_offline_dir(&connection.id)?;
+        let identity = connection.identity();
+        let index = match fs::read(dir.join("index.json")) {
+            Ok(data) => match serde_json::from_slice::<OfflineIndex>(&data) {
+                Ok(index) if index.identity == identity => index,
+                Ok(_) => {
+                    warn!("[Offline] index belongs to another server, starting over");
+                    OfflineIndex::default()
+                }
+                Err(e) => {
+                    warn!("[Offline] index ignored: {}", e);
+                    OfflineIndex::default()
+                }
+            },
+            Err(_) => OfflineIndex::default(),
+        };
+        info!(
+            "[Offline] {} pinned paths, {} entries available offline",
+            pins.len(),
+            index.entries.len()
+        );
+        Some(Arc::new(Self {
+            dir,
+            identity,
+            pins,
+            index: RwLock::new(index),
+            disconnected: AtomicBool::new(false),
+        }))
+    }
+
+    /// 고정한 경로이거나 그 아래 경로인지
+    pub(crate) fn is_pinned(&self, path: &str) -> bool {
+        self.pins
+            .iter()
+            .any(|pin| path == pin || pin == "/" || path.starts_with(&format!("{}/", pin)))
+    }
+
+    /// 서버에 연결할 수 없는 상태인지
+    pub(crate) fn is_disconnected(&self) -> bool {
+        self.disconnected.load(Ordering::Acquire)
+    }
+
+    /// 서버 호출 결과 기록 ('파일 없음'은 서버에 닿은 것이므로 연결된 것으로 봄)
+    pub(crate) fn record_result<T>(&self, result: &Result<T, String>) {
+        let disconnected = matches!(result, Err(e) if !sftp::is_not_found(e));
+        if self.disconnected.swap(disconnected, Ordering::AcqRel) != disconnected {
+            if disconnected {
+                warn!("[Offline] server unreachable, serving pinned paths locally");
+            } else {
+                info!("[Offline] server reachable again");
+            }
+        }
+    }
+
+    /// 로컬 복사본의 메타데이터 (고정 경로의 상위 폴더 포함)
+    pub(crate) fn stat(&self, path: &str) -> Option<FileStat> {
+        self.index
+            .read()
+            .entries
+            .get(path)
+            .map(OfflineEntry::to_stat)
+    }
+
+    /// 로컬 복사본의 디렉토리 목록 (상위 폴더는 고정 경로로 가는 항목만 보임)
+    pub(crate) fn read_dir(&self, path: &str) -> Option<Vec<(String, FileStat)>> {
+        let index = self.index.read();
+        if !index.entries.get(path)?.is_dir {
+            return None;
+        }
+        let prefix = format!("{}/", path.trim_end_matches('/'));
+        Some(
+            index
+                .entries
+                .range(prefix.clone()..)
+                .take_while(|(child, _)| child.starts_with(&prefix))
+                .filter_map(|(child, entry)| {
+                    let name = &child[prefix.len()..];
+                    (!name.contains('/')).then(|| (name.to_string(), entry.to_stat()))
+                })
+                .collect(),
+        )
+    }
+
+    /// 로컬 복사본에서 읽기 (내려받은 파일이 아니면 None)
+    ///
+    /// `current`를 주면 서버의 현재 상태와 같은 복사본일 때만 읽는다.
+    pub(crate) fn read(
+        &self,
+        path: &str,
+        offset: u64,
+        len: usize,
+        current: Option<&FileStat>,
+    ) -> Option<Result<Vec<u8>, String>> {
+        {
+            let index = self.index.read();
+            let entry = index.entries.get(path)?;
+            if entry.is_dir || current.is_some_and(|stat| !entry.matches(stat)) {
+                return None;
+            }
+        }
+        Some(read_local(&self.file_path(path), offset, len))
+    }
+
+    /// 원격 경로의 로컬 복사본 파일 (경로 해시를 이름으로 씀)
+    fn file_path(&self, remote_path: &str) -> PathBuf {
+        let digest = Sha256::digest(remote_path.as_bytes());
+        let name: String = digest[..16].iter().map(|b| format!("{:02x}", b)).collect();
+        self.dir.join("files").join(name)
+    }
+
+    fn save_index(&self) -> Result<(), String> {
+        let data = serde_json::to_vec(&*self.index.read())
+            .map_err(|e| format!("오프라인 목록 직렬화 실패: {}", e))?;
+        fs::write(self.dir.join("index.json"), data)
+            .map_err(|e| format!("오프라인 목록 저장 실패: {}", e))
+    }
+}
+
+/// 로컬 복사본 파일의 일부 읽기
+fn read_local(file: &Path, offset: u64, len: usize) -> Result<Vec<u8>, String> {
+    let mut file = File::open(file).map_err(|e| format!("오프라인 복사본 열기 실패: {}", e))?;
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| format!("오프라인 복사본 탐색 실패: {}", e))?;
+    let mut data = Vec::with_capacity(len);
+    file.take(len as u64)
+        .read_to_end(&mut data)
+        .map_err(|e| format!("오프라인 복사본 읽기 실패: {}", e))?;
+    Ok(data)
+}
+
+/// 고정한 경로를 백그라운드에서 내려받고 주기적으로 서버와 맞추기 시작
+pub(crate) fn start_sync(
+    client: SharedSftpClient,
+    connection: &SshConnection,
+    store: Arc<OfflineStore>,
+    cache: &Arc<StatCache>,
+    stats: &Arc<StatsCollector>,
+) {
+    let syncer = Syncer {
+        client,
+        store,
+        cache: Arc::downgrade(cache),
+        stats: Arc::downgrade(stats),
+        exclude: PathFilter::new(&connection.exclude_patterns),
+        access: AccessRules::new(&connection.access_rules),
+        root: connection.remote_path.trim_end_matches('/').to_string(),
+    };
+    std::thread::spawn(move || loop {
+        let wait = match syncer.sync() {
+            Some(Ok(files)) => {
+                info!("[Offline] {} pinned files in sync", files);
+                SYNC_INTERVAL
+            }
+            Some(Err(e)) => {
+                debug!("[Offline] sync interrupted: {}", e);
+                PROBE_INTERVAL
+            }
+            // 언마운트됨
+            None => return,
+        };
+        if !syncer.sleep(wait) {
+            return;
+        }
+    });
+}
+
+struct Syncer {
+    client: SharedSftpClient,
+    store: Arc<OfflineStore>,
+    // 드라이브가 언마운트되면 사라짐 (동기화도 멈춤)
+    cache: Weak<StatCache>,
+    stats: Weak<StatsCollector>,
+    exclude: PathFilter,
+    access: AccessRules,
+    root: String,
+}
+
+impl Syncer {
+    /// 고정한 경로를 서버와 한 번 맞춤 (맞춘 파일 수, 언마운트되면 None)
+    ///
+    /// 서버 목록을 다 읽은 뒤에 목록을 바꾸므로 중간에 연결이 끊기면 이전 복사본이 남는다.
+    fn sync(&self) -> Option<Result<usize, String>> {
+        let mut entries = BTreeMap::new();
+        for pin in &self.store.pins {
+            match self.collect(pin, &mut entries)? {
+                Ok(()) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        // 바뀐 파일만 내려받음 (받는 중에 바뀐 파일은 다음 동기화 때 다시 받음)
+        let files_dir = self.store.dir.join("files");
+        if let Err(e) = fs::create_dir_all(&files_dir) {
+            return Some(Err(format!("오프라인 디렉토리 생성 실패: {}", e)));
+        }
+        let mut files = 0;
+        for (path, entry) in &entries {
+            if entry.is_dir {
+                continue;
+            }
+            let local = self.store.file_path(path);
+            let unchanged = self
+                .store
+                .index
+                .read()
+                .entries
+                .get(path)
+                .is_some_and(|old| old.matches(&entry.to_stat()))
+                && local.exists();
+            if !unchanged {
+                self.wait_until_quiet()?;
+                let partial = local.with_extension("part");
+                let result = self
+                    .client
+                    .lock()
+                    .download_file(path, &partial, 0, |_| true);
+                self.store.record_result(&result);
+                let result = result.and_then(|_| {
+                    fs::rename(&partial, &local)
+                        .map_err(|e| format!("오프라인 복사본 저장 실패: {}", e))
+                });
+                if let Err(e) = result {
+                    let _ = fs::remove_file(&partial);
+                    if !sftp::is_not_found(&e) {
+                        return Some(Err(e));
+                    }
+                    debug!("[Offline] '{}' vanished during sync", path);
+                    continue;
+                }
+                // 이전 목록에도 바로 반영해 중간에 끊겨도 받은 파일은 쓸 수 있게 함
+                self.store
+                    .index
+                    .write()
+                    .entries
+                    .insert(path.clone(), entry.clone());
+            }
+            files += 1;
+        }
+
+        // 고정을 풀었거나 서버에서 지운 파일의 복사본 정리
+        let removed: Vec<String> = {
+            let mut index = self.store.index.write();
+            let removed = index
+                .entries
+                .iter()
+                .filter(|(path, entry)| !entry.is_dir && !entries.contains_key(*path))
+                .map(|(path, _)| path.clone())
+                .collect();
+            index.identity = self.store.identity.clone();
+            index.entries = entries;
+            removed
+        };
+        for path in &removed {
+            let _ = fs::remove_file(self.store.file_path(path));
+        }
+        if let Err(e) = self.store.save_index() {
+            warn!("[Offline] {}", e);
+        }
+        Some(Ok(files))
+    }
+
+    /// 고정 경로와 그 상위 폴더, 하위 항목의 서버 메타데이터 수집
+    fn collect(
+        &self,
+        pin: &str,
+        entries: &mut BTreeMap<String, OfflineEntry>,
+    ) -> Option<Result<(), String>> {
+        // 드라이브 루트에서 고정 경로까지의 폴더도 있어야 탐색기에서 찾아 들어갈 수 있음
+        let mut paths = vec![pin.to_string()];
+        let mut path = pin;
+        while path != self.root && path != "/" {
+            path = parent_path(path);
+            paths.push(path.to_string());
+        }
+        for path in paths.iter().rev() {
+            self.wait_until_quiet()?;
+            let result = self.client.lock().stat(path);
+            self.store.record_result(&result);
+            match result {
+                Ok(stat) => {
+                    entries.insert(path.to_string(), OfflineEntry::from_stat(&stat));
+                }
+                Err(e) if sftp::is_not_found(&e) => {
+                    debug!("[Offline] pinned path '{}' not found", path);
+                    return Some(Ok(()));
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        if !entries.get(pin).is_some_and(|entry| entry.is_dir) {
+            return Some(Ok(()));
+        }
+
+        let mut queue = VecDeque::from([pin.to_string()]);
+        let mut count = 0;
+        while let Some(dir) = queue.pop_front() {
+            self.wait_until_quiet()?;
+            let result = self.client.lock().read_dir(&dir);
+            self.store.record_result(&result);
+            let listing = match result {
+                Ok(listing) => listing,
+                Err(e) if sftp::is_not_found(&e) => continue,
+                Err(e) => return Some(Err(e)),
+            };
+            for (name, stat) in listing {
+                let path = format!("{}/{}", dir.trim_end_matches('/'), name);
+                if self.is_hidden(&path) || !(stat.is_dir() || stat.is_file()) {
+                    continue;
+                }
+                count += 1;
+                if count > MAX_ENTRIES_PER_PIN {
+                    warn!(
+                        "[Offline] '{}' has more than {} entries, rest not pinned",
+                        pin, MAX_ENTRIES_PER_PIN
+                    );
+                    return Some(Ok(()));
+                }
+                if stat.is_dir() {
+                    queue.push_back(path.clone());
+                }
+                entries.insert(path, OfflineEntry::from_stat(&stat));
+            }
+        }
+        Some(Ok(()))
+    }
+
+    /// 드라이브 작업이 잠잠해질 때까지 대기 (언마운트되면 None)
+    ///
+    /// 일시 중지한 드라이브는 서버에 연결하지 않으므로 재개할 때까지 기다린다.
+    fn wait_until_quiet(&self) -> Option<()> {
+        loop {
+            let stats = self.stats.upgrade()?;
+            let cache = self.cache.upgrade()?;
+            if !cache.is_paused() && stats.idle_for() >= YIELD_WHILE_ACTIVE {
+                return Some(());
+            }
+            drop((stats, cache));
+            std::thread::sleep(POLL);
+        }
+    }
+
+    /// 다음 동기화까지 대기 (그사이 언마운트되면 false)
+    fn sleep(&self, duration: Duration) -> bool {
+        let mut waited = Duration::ZERO;
+        while waited < duration {
+            if self.cache.strong_count() == 0 {
+                return false;
+            }
+            std::thread::sleep(POLL);
+            waited += POLL;
+        }
+        true
+    }
+
+    fn is_hidden(&self, remote_path: &str) -> bool {
+        let relative = &remote_path[self.root.len()..];
+        self.exclude.is_excluded(relative) || self.access.is_hidden(remote_path)
+    }
+}
@@ -0,0 +1,211 @@
+//! 첫 실행 점검 모듈 - 처음 쓰는 사용자가 마운트까지 가는 데 필요한 항목을 한 번에 점검
+//!
+//! 점검만 하고 아무것도 바꾸지 않는다. 각 항목은 UI가 제안할 작업을 함께 담으므로 마법사는
+//! 목록을 순서대로 보여 주고 작업이 끝나면 다시 점검하면 된다.
+
+use crate::keystore;
+use crate::mount;
+use crate::site_import;
+use crate::storage;
+use crate::types::{
+    ImportSource, OnboardingAction, OnboardingChecklist, OnboardingStatus, OnboardingStep,
+};
+use log::info;
+use std::path::PathBuf;
+
+/// 모든 항목 점검
+pub fn run() -> OnboardingChecklist {
+    let mut steps = Vec::new();
+    prerequisite_steps(&mut steps);
+    steps.push(ssh_dir_step());
+    for (id, title, source) in [
+        ("import_winscp", "WinSCP 사이트", ImportSource::WinScp),
+        (
+            "import_filezilla",
+            "FileZilla 사이트",
+            ImportSource::FileZilla,
+        ),
+        (
+            "import_ssh_config",
+            "SSH 설정 파일의 호스트",
+            ImportSource::SshConfig,
+        ),
+    ] {
+        if let Some(step) = import_step(id, title, source) {
+            steps.push(step);
+        }
+    }
+    steps.push(connections_step());
+
+    let ready = steps
+        .iter()
+        .all(|step| step.status != OnboardingStatus::Required);
+    info!("[Onboarding] {} steps, ready: {}", steps.len(), ready);
+    OnboardingChecklist { steps, ready }
+}
+
+fn step(
+    id: &str,
+    title: &str,
+    status: OnboardingStatus,
+    detail: String,
+    action: Option<OnboardingAction>,
+) -> OnboardingStep {
+    OnboardingStep {
+        id: id.to_string(),
+        title: title.to_string(),
+        status,
+        detail,
+        action,
+        import_source: None,
+    }
+}
+
+/// WinFsp 설치, 런처 서비스, 앱과 DLL 아키텍처
+fn prerequisite_steps(steps: &mut Vec<OnboardingStep>) {
+    let status = mount::check_prerequisites();
+    let Some(path) = status.winfsp_path else {
+        steps.push(step(
+            "winfsp",
+            "WinFsp 설치",
+            OnboardingStatus::Required,
+            "드라이브를 마운트하려면 WinFsp가 필요합니다.".to_string(),
+            Some(OnboardingAction::InstallWinfsp),
+        ));
+        return;
+    };
+    let environment = status.environment;
+    if environment.arch_mismatch {
+        steps.push(step(
+            "winfsp",
+            "WinFsp 설치",
+            OnboardingStatus::Required,
+            format!(
+                "설치된 WinFsp({})가 앱({})과 맞지 않습니다. {}용 WinFsp를 설치해주세요.",
+                environment.winfsp_arch.as_deref().unwrap_or("알 수 없음"),
+                environment.app_arch,
+                environment.app_arch
+            ),
+            Some(OnboardingAction::InstallWinfsp),
+        ));
+    } else {
+        steps.push(step(
+            "winfsp",
+            "WinFsp 설치",
+            OnboardingStatus::Ok,
+            path,
+            None,
+        ));
+    }
+    if environment.winfsp_launcher_running == Some(false) {
+        steps.push(step(
+            "winfsp_service",
+            "WinFsp 서비스",
+            OnboardingStatus::Recommended,
+            "WinFsp 런처 서비스가 멈춰 있습니다. 탐색기에서 드라이브가 보이지 않으면 시작해주세요."
+                .to_string(),
+            Some(OnboardingAction::StartWinfspService),
+        ));
+    }
+}
+
+/// `%USERPROFILE%\.ssh`와 키 저장소의 키
+fn ssh_dir_step() -> OnboardingStep {
+    let stored = keystore::list().map(|keys| keys.len()).unwrap_or(0);
+    let dir = std::env::var_os("USERPROFILE").map(|home| PathBuf::from(home).join(".ssh"));
+    let Some(dir) = dir.filter(|dir| dir.is_dir()) else {
+        let detail = if stored > 0 {
+            format!("키 저장소에 키 {}개가 있습니다.", stored)
+        } else {
+            ".ssh 폴더가 없습니다. 키 인증을 쓰려면 새 키를 만들거나 기존 키를 가져오세요."
+                .to_string()
+        };
+        return step(
+            "ssh_dir",
+            "SSH 키",
+            if stored > 0 {
+                OnboardingStatus::Ok
+            } else {
+                OnboardingStatus::Recommended
+            },
+            detail,
+            (stored == 0).then_some(OnboardingAction::GenerateKey),
+        );
+    };
+
+    let keys = private_keys(&dir);
+    if keys.is_empty() && stored == 0 {
+        return step(
+            "ssh_dir",
+            "SSH 키",
+            OnboardingStatus::Recommended,
+            format!(
+                "{}에 개인 키가 없습니다. 키 인증을 쓰려면 새 키를 만드세요.",
+                dir.display()
+            ),
+            Some(OnboardingAction::GenerateKey),
+        );
+    }
+    let mut detail = format!("{}: 개인 키 {}개", dir.display(), keys.len());
+    if !keys.is_empty() {
+        detail.push_str(&format!(" ({})", keys.join(", ")));
+    }
+    if stored > 0 {
+        detail.push_str(&format!(", 키 저장소: {}개", stored));
+    }
+    step("ssh_dir", "SSH 키", OnboardingStatus::Ok, detail, None)
+}
+
+/// `.ssh` 폴더의 개인 키 파일 이름 (`id_*`에서 `.pub` 제외)
+fn private_keys(dir: &std::path::Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut keys: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.starts_with("id_") && !name.contains('.'))
+        .collect();
+    keys.sort();
+    keys
+}
+
+/// 가져올 사이트가 있을 때만 항목 추가 (프로그램이 없거나 읽지 못하면 생략)
+fn import_step(id: &str, title: &str, source: ImportSource) -> Option<OnboardingStep> {
+    let count = site_import::importable_count(source).ok()?;
+    if count == 0 {
+        return None;
+    }
+    Some(OnboardingStep {
+        import_source: Some(source),
+        ..step(
+            id,
+            title,
+            OnboardingStatus::Recommended,
+            format!("새로 가져올 수 있는 사이트가 {}개 있습니다.", count),
+            Some(OnboardingAction::ImportConnections),
+        )
+    })
+}
+
+/// 저장된 연결
+fn connections_step() -> OnboardingStep {
+    match storage::load_connections() {
+        Ok(connections) if !connections.is_empty() => step(
+            "connections",
+            "연결",
+            OnboardingStatus::Ok,
+            format!("저장된 연결 {}개", connections.len()),
+            None,
+        ),
+        Ok(_) => step(
+            "connections",
+            "연결",
+            OnboardingStatus::Recommended,
+            "저장된 연결이 없습니다. 첫 연결을 추가하세요.".to_string(),
+            Some(OnboardingAction::AddConnection),
+        ),
+        Err(e) => step("connections", "연결", OnboardingStatus::Required, e, None),
+    }
+}
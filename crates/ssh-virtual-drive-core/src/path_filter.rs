@@ -0,0 +1,211 @@
+//! 경로 필터 모듈 - 연결별 제외 패턴으로 원격 항목 숨김
+//!
+//! `/`가 없는 패턴(`node_modules`, `*.tmp`)은 경로의 모든 구성 요소에 대해 비교하고,
+//! `/`가 있는 패턴(`/var/cache/*`)은 드라이브 루트 기준 전체 경로와 비교한다.
+//! Windows 탐색기와 맞추기 위해 대소문자는 구분하지 않는다.
+//!
+//! 접근 규칙(`AccessRules`)은 원격 절대 경로 기준이며 일치한 경로의 하위 전체에 적용된다.
+//! 여러 규칙이 겹치면 더 긴(구체적인) 패턴이 우선한다. 보호 경로(`ProtectedPaths`)도
+//! 같은 방식으로 비교한다.
+
+use crate::types::{AccessMode, AccessRule};
+
+/// 제외 패턴 묶음
+#[derive(Default)]
+pub struct PathFilter {
+    name_patterns: Vec<String>,
+    path_patterns: Vec<String>,
+}
+
+impl PathFilter {
+    pub fn new(patterns: &[String]) -> Self {
+        let mut filter = Self::default();
+        for pattern in patterns {
+            let pattern = pattern.trim().replace('\\', "/").to_lowercase();
+            if pattern.is_empty() {
+                continue;
+            }
+            if pattern.contains('/') {
+                let anchored = format!("/{}", pattern.trim_matches('/'));
+                filter.path_patterns.push(anchored);
+            } else {
+                filter.name_patterns.push(pattern);
+            }
+        }
+        filter
+    }
+
+    /// 드라이브 기준 경로(`/a/b` 또는 `\a\b`)가 제외 대상이거나 제외된 디렉토리 아래에 있는지
+    pub fn is_excluded(&self, relative_path: &str) -> bool {
+        if self.name_patterns.is_empty() && self.path_patterns.is_empty() {
+            return false;
+        }
+
+        let path = relative_path.replace('\\', "/").to_lowercase();
+        let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+
+        if components
+            .iter()
+            .any(|name| self.name_patterns.iter().any(|p| glob_match(p, name)))
+        {
+            return true;
+        }
+
+        // 상위 경로 중 하나라도 일치하면 그 아래 전체를 숨김
+        path_prefixes(&components)
+            .iter()
+            .any(|prefix| self.path_patterns.iter().any(|p| glob_match(p, prefix)))
+    }
+}
+
+/// 경로별 접근 규칙 묶음
+#[derive(Default)]
+pub struct AccessRules {
+    // (정규화된 패턴, 접근 종류) - 긴 패턴부터 정렬
+    rules: Vec<(String, AccessMode)>,
+}
+
+impl AccessRules {
+    pub fn new(rules: &[AccessRule]) -> Self {
+        let mut rules: Vec<(String, AccessMode)> = rules
+            .iter()
+            .map(|rule| {
+                let path = rule.path.trim().replace('\\', "/").to_lowercase();
+                (format!("/{}", path.trim_matches('/')), rule.access)
+            })
+            .collect();
+        rules.sort_by_key(|(pattern, _)| std::cmp::Reverse(pattern.len()));
+        Self { rules }
+    }
+
+    /// 원격 경로에 적용되는 규칙 (가장 구체적인 규칙)
+    pub fn access_for(&self, remote_path: &str) -> Option<AccessMode> {
+        if self.rules.is_empty() {
+            return None;
+        }
+        let path = remote_path.to_lowercase();
+        let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+        let prefixes = path_prefixes(&components);
+        self.rules
+            .iter()
+            .find(|(pattern, _)| {
+                pattern == "/" || prefixes.iter().any(|prefix| glob_match(pattern, prefix))
+            })
+            .map(|(_, access)| *access)
+    }
+
+    /// 원격 경로 아래(경로 자신 제외)에 걸릴 수 있는 규칙이 있는지 (트리 전체를 지우기 전 확인)
+    pub fn has_rule_within(&self, remote_path: &str) -> bool {
+        self.rules
+            .iter()
+            .any(|(pattern, _)| pattern_within(pattern, remote_path))
+    }
+
+    pub fn is_hidden(&self, remote_path: &str) -> bool {
+        self.access_for(remote_path) == Some(AccessMode::Hidden)
+    }
+
+    /// 숨김 경로도 쓰기는 막음 (존재를 드러내지 않도록 호출자가 먼저 숨김 여부를 확인)
+    pub fn is_read_only(&self, remote_path: &str) -> bool {
+        self.access_for(remote_path).is_some()
+    }
+}
+
+/// 삭제/이름 변경 전에 확인할 보호 경로 묶음
+#[derive(Default)]
+pub struct ProtectedPaths {
+    // 정규화된 패턴 - 긴 패턴부터 정렬
+    patterns: Vec<String>,
+}
+
+impl ProtectedPaths {
+    pub fn new(patterns: &[String]) -> Self {
+        let mut patterns: Vec<String> = patterns
+            .iter()
+            .map(|pattern| pattern.trim().replace('\\', "/").to_lowercase())
+            .filter(|pattern| !pattern.is_empty())
+            .map(|pattern| format!("/{}", pattern.trim_matches('/')))
+            .collect();
+        patterns.sort_by_key(|pattern| std::cmp::Reverse(pattern.len()));
+        Self { patterns }
+    }
+
+    /// 원격 경로를 보호하는 규칙 (보호 경로 자체와 그 하위 전체)
+    pub fn rule_for(&self, remote_path: &str) -> Option<&str> {
+        if self.patterns.is_empty() {
+            return None;
+        }
+        let path = remote_path.to_lowercase();
+        let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+        let prefixes = path_prefixes(&components);
+        self.patterns
+            .iter()
+            .find(|pattern| {
+                *pattern == "/" || prefixes.iter().any(|prefix| glob_match(pattern, prefix))
+            })
+            .map(String::as_str)
+    }
+
+    /// 원격 경로 아래(경로 자신 제외)에 걸릴 수 있는 보호 규칙 (트리 전체를 지우기 전 확인)
+    pub fn rule_within(&self, remote_path: &str) -> Option<&str> {
+        self.patterns
+            .iter()
+            .find(|pattern| pattern_within(pattern, remote_path))
+            .map(String::as_str)
+    }
+}
+
+/// 패턴이 경로 아래의 항목에 맞을 수 있는지 (패턴의 앞부분이 경로와 맞고 더 깊음)
+fn pattern_within(pattern: &str, remote_path: &str) -> bool {
+    let path = remote_path.to_lowercase();
+    let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+    let pattern_components: Vec<&str> = pattern.split('/').filter(|c| !c.is_empty()).collect();
+    if pattern_components.len() <= components.len() {
+        return false;
+    }
+    let head = format!("/{}", pattern_components[..components.len()].join("/"));
+    components.is_empty() || glob_match(&head, &path_prefixes(&components)[components.len() - 1])
+}
+
+/// 경로 구성 요소의 누적 접두 경로 ("/a", "/a/b", ...)
+fn path_prefixes(components: &[&str]) -> Vec<String> {
+    components
+        .iter()
+        .scan(String::new(), |prefix, name| {
+            prefix.push('/');
+            prefix.push_str(name);
+            Some(prefix.clone())
+        })
+        .collect()
+}
+
+/// `*`(`/` 제외 임의 문자열)와 `?`(임의 한 글자)를 지원하는 간단한 glob 비교
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // 마지막 `*`의 위치와 그때 대응시킨 text 위치 (되돌아가기용)
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if (c == '?' && text[t] != '/') || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) if text[matched] != '/' => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                _ => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,93 @@
+//! 호출 프로세스 모듈 - WinFsp 요청을 보낸 프로세스 식별
+//!
+//! WinFsp는 Create/Open/Rename 요청에만 호출 PID를 채워 주므로 그 콜백 안에서만
+//! 올바른 값을 얻을 수 있다 (읽기/쓰기/닫기에서는 0). 열 때 얻은 값을 핸들에 보관해 쓴다.
+//! 이미지 이름 조회는 비용이 있어 PID별로 잠시 캐시한다.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// PID → 이름 캐시 유지 시간 (PID 재사용 대비)
+const NAME_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// 캐시 최대 항목 수
+const NAME_CACHE_LIMIT: usize = 512;
+
+const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+
+#[link(name = "kernel32")]
+extern "system" {
+    fn OpenProcess(desired_access: u32, inherit_handle: i32, process_id: u32) -> *mut c_void;
+    fn QueryFullProcessImageNameW(
+        process: *mut c_void,
+        flags: u32,
+        exe_name: *mut u16,
+        size: *mut u32,
+    ) -> i32;
+    fn CloseHandle(handle: *mut c_void) -> i32;
+}
+
+/// 요청을 보낸 프로세스
+#[derive(Debug, Clone, Default)]
+pub struct Caller {
+    pub pid: u32,
+    /// 실행 파일 이름 (예: `SearchIndexer.exe`, 확인할 수 없으면 빈 문자열)
+    pub name: String,
+}
+
+/// 현재 처리 중인 WinFsp 요청의 호출 프로세스 (Create/Open/Rename 콜백 안에서만 유효)
+pub fn current_caller() -> Caller {
+    let pid = unsafe { winfsp_sys::FspFileSystemOperationProcessIdF() };
+    Caller {
+        pid,
+        name: process_name(pid),
+    }
+}
+
+fn process_name(pid: u32) -> String {
+    static CACHE: OnceLock<Mutex<HashMap<u32, (String, Instant)>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(Mutex::default);
+
+    if let Some((name, at)) = cache.lock().get(&pid) {
+        if at.elapsed() < NAME_CACHE_TTL {
+            return name.clone();
+        }
+    }
+
+    let name = query_image_name(pid).unwrap_or_default();
+    let mut cache = cache.lock();
+    if cache.len() >= NAME_CACHE_LIMIT {
+        cache.retain(|_, (_, at)| at.elapsed() < NAME_CACHE_TTL);
+    }
+    cache.insert(pid, (name.clone(), Instant::now()));
+    name
+}
+
+fn query_image_name(pid: u32) -> Option<String> {
+    match pid {
+        0 => return Some("Idle".to_string()),
+        4 => return Some("System".to_string()),
+        _ => {}
+    }
+
+    let mut buffer = [0u16; 1024];
+    let mut size = buffer.len() as u32;
+    let ok = unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process.is_null() {
+            return None;
+        }
+        let ok = QueryFullProcessImageNameW(process, 0, buffer.as_mut_ptr(), &mut size);
+        CloseHandle(process);
+        ok
+    };
+    if ok == 0 {
+        return None;
+    }
+
+    let path = String::from_utf16_lossy(&buffer[..size as usize]);
+    path.rsplit('\\').next().map(str::to_string)
+}
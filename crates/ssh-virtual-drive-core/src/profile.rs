@@ -0,0 +1,98 @@
+//! 마운트 프로필 모듈 - 작업 종류에 맞춘 캐시/메타데이터 설정 묶음
+//!
+//! 마운트한 드라이브에서 바로 빌드하면 git/npm이 수천 개의 작은 stat과 open을 보내고
+//! 대부분은 없는 파일(`package.json`을 상위 폴더마다 찾는 식)이다. 개발자 프로필은
+//! 메타데이터를 오래 캐시하고, 없는 경로도 기억하며, 디렉토리를 읽으면 하위 디렉토리
+//! 목록까지 미리 읽어 둔다.
+//!
+//! 캐시를 오래 유지하는 만큼 다른 곳에서 서버 파일을 바꾸면 늦게 보인다. 이 드라이브를
+//! 거친 변경은 바로 캐시를 무효화하므로 영향이 없다.
+
+use crate::memory::{self, CacheBudget};
+use crate::types::{MountProfile, SshConnection};
+use crate::writeback::MAX_DIRTY_BYTES;
+use std::time::Duration;
+
+/// 미리 읽지 않을 경로 (이름으로 바로 여는 content-addressed 저장소라 목록이 필요 없음)
+const PREFETCH_SKIP: &[&str] = &["/.git/objects"];
+
+/// 프로필에서 정해지는 마운트 설정
+#[derive(Debug, Clone, Copy)]
+pub struct MountTuning {
+    /// stat/readdir 캐시 유효 시간 (변화 빈도에 맞춰 조절할 때는 기준값)
+    pub stat_ttl: Duration,
+    /// 디렉토리가 바뀌는 빈도에 맞춰 캐시 유효 시간을 줄이고 늘림
+    pub adaptive_ttl: bool,
+    /// 없는 경로 기억 시간 (None이면 기억하지 않음)
+    pub negative_ttl: Option<Duration>,
+    /// WinFsp 커널 쪽 파일 정보 캐시 시간 (밀리초)
+    pub file_info_timeout_ms: u32,
+    /// 디렉토리를 읽을 때 미리 읽을 하위 디렉토리 수 (0이면 끔)
+    pub prefetch_subdirs: usize,
+    /// 캐시 메모리 예산 (None이면 항목 수로만 제한)
+    pub cache_budget: Option<CacheBudget>,
+    /// 연결 설정보다 우선하는 진행 중 전송 버퍼 한도 (MB)
+    pub inflight_limit_mb: Option<u32>,
+    /// 파일별 쓰기 지연 버퍼가 이 크기를 넘으면 바로 반영
+    pub dirty_flush_bytes: usize,
+    /// 마운트할 때 전체 목록을 읽어 고정하고 읽기 전용으로 마운트
+    pub snapshot: bool,
+    /// WinFsp 작업 스레드 수 (0이면 WinFsp 기본값, 마운트할 때 자원 한도로 정함)
+    pub worker_threads: u32,
+}
+
+impl MountTuning {
+    /// 연결의 프로필에 앱 메모리 설정을 더한 마운트 설정
+    pub fn for_connection(connection: &SshConnection) -> Self {
+        let mut tuning = Self::for_profile(connection.mount_profile);
+        memory::apply(&mut tuning, &memory::settings());
+        tuning
+    }
+
+    pub fn for_profile(profile: MountProfile) -> Self {
+        match profile {
+            MountProfile::Default => Self {
+                stat_ttl: Duration::from_secs(10),
+                adaptive_ttl: true,
+                negative_ttl: None,
+                file_info_timeout_ms: 5000,
+                prefetch_subdirs: 0,
+                cache_budget: None,
+                inflight_limit_mb: None,
+                dirty_flush_bytes: MAX_DIRTY_BYTES,
+                snapshot: false,
+                worker_threads: 0,
+            },
+            MountProfile::Developer => Self {
+                stat_ttl: Duration::from_secs(120),
+                adaptive_ttl: true,
+                negative_ttl: Some(Duration::from_secs(60)),
+                file_info_timeout_ms: 30_000,
+                prefetch_subdirs: 32,
+                cache_budget: None,
+                inflight_limit_mb: None,
+                dirty_flush_bytes: MAX_DIRTY_BYTES,
+                snapshot: false,
+                worker_threads: 0,
+            },
+            // 메타데이터는 스냅샷 트리에서만 답하므로 TTL과 미리 읽기는 쓰이지 않음
+            MountProfile::Snapshot => Self {
+                stat_ttl: Duration::from_secs(10),
+                adaptive_ttl: false,
+                negative_ttl: None,
+                file_info_timeout_ms: 5000,
+                prefetch_subdirs: 0,
+                cache_budget: None,
+                inflight_limit_mb: None,
+                dirty_flush_bytes: MAX_DIRTY_BYTES,
+                snapshot: true,
+                worker_threads: 0,
+            },
+        }
+    }
+}
+
+/// 미리 읽기에서 뺄 원격 경로인지
+pub fn skip_prefetch(remote_path: &str) -> bool {
+    PREFETCH_SKIP.iter().any(|skip| remote_path.contains(skip))
+}
@@ -0,0 +1,384 @@
+//! 연결 프로필 동기화 모듈 - 연결 목록을 사용자가 고른 SFTP 서버의 파일 하나로 여러 PC에 맞춤
+//!
+//! 동기화 파일은 암호에서 PBKDF2-HMAC-SHA256으로 만든 키로 암호화(AES-256-CBC + HMAC-SHA256,
+//! [`keystore`]와 같은 형식)하며, 암호는 PC마다 자격 증명 관리자에 저장한다. 비밀번호, TOTP
+//! 비밀 키 같은 자격 증명은 파일에 넣지 않는다 (각 PC에서 마운트할 때 입력). 연결 훅도 로컬 명령을
+//! 실행하므로 동기화 파일에 넣지 않고 받은 구성도 무시하며, 각 PC에 설정한 훅을 그대로 둔다.
+//!
+//! 마지막으로 동기화한 목록을 기준으로 삼아 연결마다 어느 쪽이 바뀌었는지 가려낸다. 한쪽만
+//! 바뀌었으면 그쪽을 따르고, 양쪽 모두 고쳤으면 이 PC의 구성을 남기고 다른 PC의 구성은
+//! "(동기화 충돌)" 사본으로 추가해 사용자가 고르게 한다. 한쪽에서 삭제했어도 다른 쪽에서
+//! 고쳤으면 고친 구성을 남기며, 다른 PC에서 삭제한 연결은 바로 지우지 않고 보관함으로 옮긴다.
+
+use crate::keystore::{self, SECRET_LEN};
+use crate::sftp_client::{is_not_found, SftpClient};
+use crate::types::{ConnectionHooks, ProfileSyncReport, ProfileSyncSettings, SshConnection};
+use crate::{credentials, storage, trash};
+use hmac::{Hmac, Mac};
+use log::{info, warn};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+/// 동기화 파일 머리 (형식 버전 포함)
+const MAGIC: &[u8] = b"SVDSYNC1";
+
+const SALT_LEN: usize = 16;
+
+/// 암호에서 키를 만들 때의 PBKDF2 반복 횟수
+const PBKDF2_ROUNDS: u32 = 200_000;
+
+/// 동시에 두 번 동기화하지 않도록 잡는 잠금
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// 동기화 파일 내용
+#[derive(Serialize, Deserialize)]
+struct SyncDocument {
+    /// 마지막으로 올린 시각 (Unix 초)
+    updated_at: u64,
+    connections: Vec<SshConnection>,
+}
+
+/// 저장된 동기화 설정 (없으면 꺼짐)
+pub fn settings() -> ProfileSyncSettings {
+    storage::get_profile_sync_file()
+        .and_then(|file| fs::read_to_string(file).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// 동기화 설정 저장 (`passphrase`가 있으면 자격 증명 관리자의 암호를 바꿈)
+///
+/// 동기화할 서버나 파일이 바뀌면 이전 기준 목록은 맞지 않으므로 지운다.
+pub fn save_settings(
+    settings: &ProfileSyncSettings,
+    passphrase: Option<&str>,
+) -> Result<(), String> {
+    let mut settings = settings.clone();
+    settings.remote_path = settings.remote_path.trim().to_string();
+    if settings.enabled {
+        let connection_id = settings
+            .connection_id
+            .as_deref()
+            .ok_or_else(|| "동기화 파일을 둘 연결을 선택해주세요.".to_string())?;
+        if storage::get_connection_by_id(connection_id)?.is_none() {
+            return Err("동기화에 쓸 연결을 찾을 수 없습니다.".to_string());
+        }
+        if settings.remote_path.is_empty() || settings.remote_path.ends_with('/') {
+            return Err("동기화 파일 경로를 입력해주세요.".to_string());
+        }
+    }
+    match passphrase {
+        Some(passphrase) if passphrase.chars().count() < 8 => {
+            return Err("동기화 암호는 8자 이상이어야 합니다.".to_string())
+        }
+        Some(passphrase) => {
+            credentials::save_password(credentials::PROFILE_SYNC_CREDENTIAL_KEY, passphrase)?
+        }
+        None if settings.enabled
+            && credentials::get_password(credentials::PROFILE_SYNC_CREDENTIAL_KEY)?.is_none() =>
+        {
+            return Err("동기화 암호를 입력해주세요.".to_string())
+        }
+        None => {}
+    }
+
+    let previous = self::settings();
+    if previous.connection_id != settings.connection_id
+        || previous.remote_path != settings.remote_path
+    {
+        if let Some(file) = storage::get_profile_sync_base_file() {
+            let _ = fs::remove_file(file);
+        }
+    }
+
+    let file = storage::get_profile_sync_file()
+        .ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("디렉토리 생성 실패: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(&settings).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+    fs::write(&file, content).map_err(|e| format!("파일 저장 실패: {}", e))
+}
+
+/// 서버의 동기화 파일과 연결 목록을 맞춤 (동기화가 꺼져 있으면 None)
+pub fn sync() -> Result<Option<ProfileSyncReport>, String> {
+    let settings = settings();
+    let Some(connection_id) = settings
+        .connection_id
+        .as_deref()
+        .filter(|_| settings.enabled)
+    else {
+        return Ok(None);
+    };
+    let _guard = LOCK.lock();
+    let passphrase = credentials::get_password(credentials::PROFILE_SYNC_CREDENTIAL_KEY)?
+        .ok_or_else(|| "자격 증명 관리자에 동기화 암호가 없습니다.".to_string())?;
+
+    let client = SftpClient::connect_saved(connection_id)?;
+    let path = settings.remote_path.as_str();
+    let (remote, remote_etag) = match client.read_file(path) {
+        Ok(data) => (
+            Some(
+                open(&data, &passphrase)?
+                    .connections
+                    .iter()
+                    .map(without_hooks)
+                    .collect::<Vec<_>>(),
+            ),
+            etag(&client, path)?,
+        ),
+        Err(e) if is_not_found(&e) => (None, None),
+        Err(e) => return Err(e),
+    };
+    let base = load_base();
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut report = ProfileSyncReport::default();
+    let mut removed = Vec::new();
+    // 서버에 올린 뒤에만 로컬 목록을 바꿔, 다른 PC에 밀려 올리지 못하면 아무것도 바꾸지 않음
+    let merged = storage::update_connections(|connections| {
+        // 훅은 이 PC의 것만 쓰므로 빼고 합친 뒤 같은 ID의 연결에 다시 붙임
+        let hooks: HashMap<String, ConnectionHooks> = connections
+            .iter()
+            .map(|c| (c.id.clone(), c.hooks.clone()))
+            .collect();
+        let local: Vec<SshConnection> = connections.iter().map(without_hooks).collect();
+        let merged = match &remote {
+            Some(remote) => merge(&base, &local, remote, &mut report, &mut removed),
+            None => local,
+        };
+        let kept: Vec<SshConnection> = merged
+            .iter()
+            .filter(|c| !removed.contains(&c.id))
+            .cloned()
+            .collect();
+
+        if remote
+            .as_ref()
+            .is_none_or(|remote| !same_list(remote, &kept))
+        {
+            // 읽은 뒤 다른 PC가 먼저 올렸으면 덮어쓰지 않고 다음 동기화에서 다시 맞춤
+            if etag(&client, path)? != remote_etag {
+                return Err(
+                    "다른 PC가 동시에 동기화해 이번에는 올리지 않았습니다. 잠시 뒤 다시 시도해주세요."
+                        .to_string(),
+                );
+            }
+            let document = SyncDocument {
+                updated_at: now,
+                connections: kept.clone(),
+            };
+            upload(&client, path, &seal(&document, &passphrase)?)?;
+            report.uploaded = true;
+        }
+
+        *connections = merged
+            .iter()
+            .map(|c| SshConnection {
+                hooks: hooks.get(&c.id).cloned().unwrap_or_default(),
+                ..c.clone()
+            })
+            .collect();
+        Ok(kept)
+    })?;
+    // 다른 PC에서 삭제한 연결은 되돌릴 수 있게 보관함으로 옮김
+    for id in &removed {
+        if let Err(e) = trash::delete(id) {
+            warn!("[ProfileSync] moving {} to trash failed: {}", id, e);
+        }
+    }
+    save_base(&merged);
+    report.synced_at = now;
+
+    info!(
+        "[ProfileSync] synced: {} added, {} updated, {} removed, {} conflicts, uploaded: {}",
+        report.added,
+        report.updated,
+        report.removed,
+        report.conflicts.len(),
+        report.uploaded
+    );
+    Ok(Some(report))
+}
+
+/// 기준 목록, 이 PC의 목록, 서버의 목록을 연결 ID별로 합침
+///
+/// 결과는 다른 PC에서 삭제한 연결도 그대로 담고 그 ID는 `removed`에 넣는다.
+fn merge(
+    base: &[SshConnection],
+    local: &[SshConnection],
+    remote: &[SshConnection],
+    report: &mut ProfileSyncReport,
+    removed: &mut Vec<String>,
+) -> Vec<SshConnection> {
+    let base: HashMap<&str, &SshConnection> = base.iter().map(|c| (c.id.as_str(), c)).collect();
+    let mut remote_left: HashMap<&str, &SshConnection> =
+        remote.iter().map(|c| (c.id.as_str(), c)).collect();
+    let mut merged = Vec::with_capacity(local.len().max(remote.len()));
+
+    for ours in local {
+        let previous = base.get(ours.id.as_str()).copied();
+        match (previous, remote_left.remove(ours.id.as_str())) {
+            (_, Some(theirs)) if same(ours, theirs) => merged.push(ours.clone()),
+            (Some(previous), Some(theirs)) if same(ours, previous) => {
+                merged.push(theirs.clone());
+                report.updated += 1;
+            }
+            (Some(previous), Some(theirs)) if same(theirs, previous) => merged.push(ours.clone()),
+            (_, Some(theirs)) => {
+                merged.push(ours.clone());
+                merged.push(conflict_copy(theirs));
+                report.conflicts.push(ours.name.clone());
+            }
+            // 다른 PC에서 삭제함 (이 PC에서 고쳤으면 남김)
+            (Some(previous), None) if same(ours, previous) => {
+                merged.push(ours.clone());
+                removed.push(ours.id.clone());
+                report.removed += 1;
+            }
+            (_, None) => merged.push(ours.clone()),
+        }
+    }
+
+    // 처음 동기화할 때 양쪽에서 따로 만든 같은 연결은 하나로 봄
+    let local_sites: HashSet<(String, String)> = local
+        .iter()
+        .filter(|c| !base.contains_key(c.id.as_str()))
+        .map(|c| (c.identity(), c.remote_path.clone()))
+        .collect();
+    for theirs in remote {
+        if !remote_left.contains_key(theirs.id.as_str()) {
+            continue;
+        }
+        match base.get(theirs.id.as_str()) {
+            // 이 PC에서 삭제함 (다른 PC에서 고쳤으면 되살림)
+            Some(previous) if same(theirs, previous) => continue,
+            None if local_sites.contains(&(theirs.identity(), theirs.remote_path.clone())) => {
+                continue
+            }
+            _ => {
+                merged.push(theirs.clone());
+                report.added += 1;
+            }
+        }
+    }
+    merged
+}
+
+/// 충돌한 다른 PC의 구성을 새 ID의 사본으로
+fn conflict_copy(connection: &SshConnection) -> SshConnection {
+    let mut copy = connection.clone();
+    copy.id = Uuid::new_v4().to_string();
+    copy.name = format!("{} (동기화 충돌)", connection.name);
+    copy
+}
+
+/// 동기화에 넣지 않는 훅을 뺀 구성
+fn without_hooks(connection: &SshConnection) -> SshConnection {
+    SshConnection {
+        hooks: ConnectionHooks::default(),
+        ..connection.clone()
+    }
+}
+
+fn same(a: &SshConnection, b: &SshConnection) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+/// 순서와 관계없이 같은 연결 목록인지 (PC마다 순서가 달라도 다시 올리지 않도록)
+fn same_list(a: &[SshConnection], b: &[SshConnection]) -> bool {
+    let b: HashMap<&str, &SshConnection> = b.iter().map(|c| (c.id.as_str(), c)).collect();
+    a.len() == b.len()
+        && a.iter()
+            .all(|c| b.get(c.id.as_str()).is_some_and(|other| same(c, other)))
+}
+
+/// 서버 파일의 크기와 수정 시각 (없으면 None)
+fn etag(client: &SftpClient, path: &str) -> Result<Option<(u64, u64)>, String> {
+    match client.stat(path) {
+        Ok(stat) => Ok(Some((stat.size.unwrap_or(0), stat.mtime.unwrap_or(0)))),
+        Err(e) if is_not_found(&e) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// 임시 이름으로 올린 뒤 이름을 바꿔, 올리다 끊겨도 다른 PC가 반쪽 파일을 읽지 않게 함
+fn upload(client: &SftpClient, path: &str, data: &[u8]) -> Result<(), String> {
+    let temp = format!("{}.{}.tmp", path, Uuid::new_v4());
+    client.write_file(&temp, data)?;
+    client.rename(&temp, path).inspect_err(|_| {
+        let _ = client.remove_file(&temp);
+    })
+}
+
+fn load_base() -> Vec<SshConnection> {
+    storage::get_profile_sync_base_file()
+        .and_then(|file| fs::read_to_string(file).ok())
+        .and_then(|content| serde_json::from_str::<Vec<SshConnection>>(&content).ok())
+        .map(|connections| connections.iter().map(without_hooks).collect())
+        .unwrap_or_default()
+}
+
+fn save_base(connections: &[SshConnection]) {
+    let Some(file) = storage::get_profile_sync_base_file() else {
+        return;
+    };
+    let written = serde_json::to_string(connections)
+        .map_err(|e| e.to_string())
+        .and_then(|content| fs::write(&file, content).map_err(|e| e.to_string()));
+    if let Err(e) = written {
+        warn!("[ProfileSync] saving sync base failed: {}", e);
+    }
+}
+
+/// 머리 + 솔트 + 암호문
+fn seal(document: &SyncDocument, passphrase: &str) -> Result<Vec<u8>, String> {
+    let plain = serde_json::to_vec(document).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+    let mut salt = [0u8; SALT_LEN];
+    getrandom::getrandom(&mut salt).map_err(|e| format!("난수 생성 실패: {}", e))?;
+    let mut out = MAGIC.to_vec();
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&keystore::encrypt(&plain, &derive_key(passphrase, &salt)?)?);
+    Ok(out)
+}
+
+fn open(data: &[u8], passphrase: &str) -> Result<SyncDocument, String> {
+    let body = data
+        .strip_prefix(MAGIC)
+        .filter(|body| body.len() > SALT_LEN)
+        .ok_or_else(|| "동기화 파일 형식이 아닙니다.".to_string())?;
+    let (salt, sealed) = body.split_at(SALT_LEN);
+    let plain = keystore::decrypt(sealed, &derive_key(passphrase, salt)?).map_err(|_| {
+        "동기화 파일을 열 수 없습니다. 다른 PC와 같은 암호인지 확인해주세요.".to_string()
+    })?;
+    serde_json::from_slice(&plain).map_err(|e| format!("JSON 파싱 실패: {}", e))
+}
+
+/// PBKDF2-HMAC-SHA256으로 암호화 키와 HMAC 키 만들기
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; SECRET_LEN], String> {
+    let prf = Hmac::<Sha256>::new_from_slice(passphrase.as_bytes())
+        .map_err(|e| format!("동기화 키 생성 실패: {}", e))?;
+    let mut key = [0u8; SECRET_LEN];
+    for (index, block) in key.chunks_mut(32).enumerate() {
+        let mut mac = prf.clone();
+        mac.update(salt);
+        mac.update(&(index as u32 + 1).to_be_bytes());
+        let mut u = mac.finalize().into_bytes();
+        let mut t = u;
+        for _ in 1..PBKDF2_ROUNDS {
+            u = prf.clone().chain_update(u).finalize().into_bytes();
+            t.iter_mut().zip(u.iter()).for_each(|(t, u)| *t ^= u);
+        }
+        block.copy_from_slice(&t);
+    }
+    Ok(key)
+}
@@ -0,0 +1,245 @@
+//! 용량 제한 모듈 - 마운트별 소프트 쿼터 또는 서버 사용자 쿼터 반영
+//!
+//! 설정한 소프트 쿼터는 드라이브 루트 아래 사용량(`du`)과 비교하고,
+//! 서버 쿼터 모드는 `quota -w` 결과 중 원격 루트가 있는 파일 시스템(`df -P`)의 값(1KB 블록 단위)을 사용한다.
+//! 조회 사이의 쓰기 증가분과 삭제/자르기로 줄어든 크기는 직접 반영해 두었다가 다음 조회 때
+//! 서버 값으로 맞춘다. 조회는 백그라운드 스레드에서 하고 쓰기는 마지막 값으로 판단하며,
+//! `du`는 큰 트리에서 오래 걸리므로 주 세션을 잡지 않도록 따로 연결한 세션에서 드물게 실행한다.
+
+use crate::sftp_client::{shell_quote, SharedSftpClient};
+use log::warn;
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 서버 쿼터 재조회 주기
+const QUOTA_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 소프트 쿼터 사용량(`du`) 재조회 주기
+const DU_REFRESH_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// 마지막으로 확인한 쿼터 상태
+struct QuotaState {
+    // (한도, 사용량) - 조회에 실패했으면 None (알 수 없음)
+    usage: Option<(u64, u64)>,
+    checked_at: Instant,
+}
+
+/// 마운트별 용량 제한 추적기 (복제본은 같은 상태를 공유)
+#[derive(Clone)]
+pub struct QuotaTracker {
+    soft_limit: Option<u64>,
+    server_quota: bool,
+    remote_root: String,
+    client: SharedSftpClient,
+    state: Arc<Mutex<Option<QuotaState>>>,
+    refreshing: Arc<AtomicBool>,
+}
+
+impl QuotaTracker {
+    /// 쿼터를 쓰지 않는 설정이면 None
+    pub fn new(
+        soft_limit: Option<u64>,
+        server_quota: bool,
+        remote_root: &str,
+        client: SharedSftpClient,
+    ) -> Option<Self> {
+        if soft_limit.is_none() && !server_quota {
+            return None;
+        }
+        Some(Self {
+            soft_limit,
+            server_quota,
+            remote_root: remote_root.to_string(),
+            client,
+            state: Arc::new(Mutex::new(None)),
+            refreshing: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// (한도, 사용량) - 알 수 없으면 None, 오래되었으면 백그라운드에서 다시 조회
+    pub fn usage(&self) -> Option<(u64, u64)> {
+        let interval = if self.server_quota {
+            QUOTA_REFRESH_INTERVAL
+        } else {
+            DU_REFRESH_INTERVAL
+        };
+        let (usage, stale) = match self.state.lock().as_ref() {
+            Some(state) => (state.usage, state.checked_at.elapsed() >= interval),
+            None => (None, true),
+        };
+        if stale {
+            self.refresh();
+        }
+        usage
+    }
+
+    /// 쓰기로 늘어날 크기를 수용할 수 있는지 확인하고 사용량에 반영 (사용량을 모르면 허용)
+    pub fn reserve(&self, growth: u64) -> bool {
+        if growth == 0 {
+            return true;
+        }
+        // 오래된 값이면 백그라운드 조회만 시작
+        self.usage();
+        // 확인과 반영을 한 잠금 안에서 해야 동시에 쓰는 쪽들이 함께 한도를 넘지 않음
+        let mut state = self.state.lock();
+        let Some((limit, used)) = state.as_mut().and_then(|state| state.usage.as_mut()) else {
+            return true;
+        };
+        if used.saturating_add(growth) > *limit {
+            return false;
+        }
+        *used = used.saturating_add(growth);
+        true
+    }
+
+    /// 삭제나 자르기로 줄어든 크기를 사용량에서 뺌
+    pub fn release(&self, freed: u64) {
+        if freed > 0 {
+            self.adjust(|used| used.saturating_sub(freed));
+        }
+    }
+
+    fn adjust(&self, change: impl FnOnce(u64) -> u64) {
+        if let Some((_, used)) = self
+            .state
+            .lock()
+            .as_mut()
+            .and_then(|state| state.usage.as_mut())
+        {
+            *used = change(*used);
+        }
+    }
+
+    /// 백그라운드 조회 시작 (이미 조회 중이면 무시)
+    fn refresh(&self) {
+        if self.refreshing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let soft_limit = self.soft_limit;
+        let server_quota = self.server_quota;
+        let remote_root = self.remote_root.clone();
+        let client = self.client.clone();
+        let state = self.state.clone();
+        let refreshing = self.refreshing.clone();
+        std::thread::spawn(move || {
+            let usage = query(&client, server_quota, soft_limit, &remote_root);
+            *state.lock() = Some(QuotaState {
+                usage,
+                checked_at: Instant::now(),
+            });
+            refreshing.store(false, Ordering::Release);
+        });
+    }
+}
+
+fn query(
+    client: &SharedSftpClient,
+    server_quota: bool,
+    soft_limit: Option<u64>,
+    remote_root: &str,
+) -> Option<(u64, u64)> {
+    if server_quota {
+        let output = {
+            let client = client.lock();
+            client
+                .exec(&format!(
+                    "df -P -- {}",
+                    shell_quote(&client.server_path(remote_root))
+                ))
+                .and_then(|df| {
+                    let filesystem = parse_df(&df)
+                        .ok_or_else(|| format!("df 출력을 해석할 수 없습니다: {}", df.trim()))?;
+                    Ok((filesystem, client.exec("quota -w")?))
+                })
+        };
+        match output {
+            Ok(((device, mount_point), output)) => {
+                if let Some(usage) = parse_quota(&output, &device, &mount_point) {
+                    return Some(usage);
+                }
+            }
+            Err(e) => warn!("[Quota] server quota unavailable: {}", e),
+        }
+    }
+
+    let limit = soft_limit?;
+    // du 동안 주 세션을 잡고 있으면 다른 파일 작업이 멈추므로 따로 연결
+    let connector = client.lock().sibling_connector();
+    let sibling = connector.connect();
+    let used = sibling
+        .and_then(|sibling| {
+            sibling.exec(&format!(
+                "du -sk -- {}",
+                shell_quote(&sibling.server_path(remote_root))
+            ))
+        })
+        .and_then(|output| {
+            output
+                .split_whitespace()
+                .next()
+                .and_then(|kb| kb.parse::<u64>().ok())
+                .ok_or_else(|| format!("du 출력을 해석할 수 없습니다: {}", output.trim()))
+        });
+    match used {
+        Ok(kb) => Some((limit, kb * 1024)),
+        Err(e) => {
+            warn!("[Quota] usage unknown, soft quota not enforced: {}", e);
+            None
+        }
+    }
+}
+
+/// `df -P` 출력에서 원격 루트가 있는 (파일 시스템, 마운트 지점) 추출
+fn parse_df(output: &str) -> Option<(String, String)> {
+    let line = output.lines().nth(1)?;
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    // 마운트 지점에 공백이 있을 수 있으므로 여섯째 칸부터 모두 합침
+    let mount_point = fields.get(5..).filter(|rest| !rest.is_empty())?.join(" ");
+    Some((fields[0].to_string(), mount_point))
+}
+
+/// `quota -w` 출력에서 원격 루트가 있는 파일 시스템의 (한도, 사용량) 추출
+///
+/// "Filesystem blocks quota limit grace files ..." 아래에서 첫 칸이 그 장치나 마운트 지점인 줄의
+/// 1KB 블록 값을 사용하며, 소프트 한도가 0이면 하드 한도를 쓴다. 초과 시 사용량 뒤에 붙는 `*`는 무시한다.
+fn parse_quota(output: &str, device: &str, mount_point: &str) -> Option<(u64, u64)> {
+    output.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let filesystem = *fields.first()?;
+        if filesystem != device && filesystem != mount_point {
+            return None;
+        }
+        let number = |i: usize| fields.get(i)?.trim_end_matches('*').parse::<u64>().ok();
+        let used = number(1)?;
+        let soft = number(2)?;
+        let hard = number(3)?;
+        let limit = if soft > 0 { soft } else { hard };
+        (limit > 0).then_some((limit * 1024, used * 1024))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quota_uses_line_of_root_filesystem() {
+        let df = "Filesystem     1024-blocks    Used Available Capacity Mounted on\n\
+                  /dev/sdb1        102400000 5000000  97400000       5% /srv/my data\n";
+        let (device, mount_point) = parse_df(df).unwrap();
+        assert_eq!(device, "/dev/sdb1");
+        assert_eq!(mount_point, "/srv/my data");
+
+        let quota = "Disk quotas for user alice (uid 1000):\n\
+                     \x20    Filesystem  blocks   quota   limit   grace   files   quota   limit   grace\n\
+                     \x20     /dev/sda1   1000    2000    3000            10       0       0\n\
+                     \x20     /dev/sdb1   4000*   3000    5000   6days    20       0       0\n";
+        assert_eq!(
+            parse_quota(quota, &device, &mount_point),
+            Some((3000 * 1024, 4000 * 1024))
+        );
+        assert_eq!(parse_quota(quota, "/dev/sdc1", "/mnt"), None);
+    }
+}
@@ -0,0 +1,65 @@
+//! 재인증 모듈 - 서버가 거부한 저장 비밀번호로 재연결을 반복하지 않도록 멈춤
+//!
+//! 비밀번호가 만료되거나 바뀌어 서버가 인증을 거부하면 연결 ID를 거부 목록에 올린다.
+//! 목록에 있는 연결은 재연결을 시도하지 않고 바로 실패한다 (잘못된 비밀번호로 반복
+//! 시도하다 계정이 잠기지 않도록). 앱은 [`take_rejected`]로 새로 거부된 연결을 받아
+//! 사용자에게 새 비밀번호를 묻고, [`supply_password`]로 넘기면 다음 재연결부터 새
+//! 비밀번호를 쓴다.
+
+use crate::credentials;
+use log::info;
+use parking_lot::Mutex;
+
+/// 거부 목록에 있는 연결의 재연결이 반환하는 오류
+pub const CREDENTIALS_PAUSED: &str =
+    "서버가 저장된 비밀번호를 거부해 새 비밀번호를 입력할 때까지 재연결을 멈췄습니다.";
+
+/// 새 비밀번호를 기다리는 연결
+static REJECTED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// 아직 앱에 알리지 않은 거부 연결
+static UNREPORTED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// 비밀번호 거부 기록 (저장하지 않은 연결은 제외)
+pub(crate) fn mark_rejected(connection_id: &str) {
+    if connection_id.is_empty() {
+        return;
+    }
+    let mut rejected = REJECTED.lock();
+    if rejected.iter().any(|id| id == connection_id) {
+        return;
+    }
+    info!(
+        "[Auth] stored password of {} rejected, reconnects paused",
+        connection_id
+    );
+    rejected.push(connection_id.to_string());
+    UNREPORTED.lock().push(connection_id.to_string());
+}
+
+/// 새 비밀번호를 기다리는 중인지
+pub fn is_rejected(connection_id: &str) -> bool {
+    REJECTED.lock().iter().any(|id| id == connection_id)
+}
+
+/// 지난 호출 이후 비밀번호가 거부된 연결 ID
+pub fn take_rejected() -> Vec<String> {
+    std::mem::take(&mut *UNREPORTED.lock())
+}
+
+/// 거부 기록 해제 (인증에 성공했거나 새 비밀 값을 받음)
+pub fn clear(connection_id: &str) {
+    REJECTED.lock().retain(|id| id != connection_id);
+    UNREPORTED.lock().retain(|id| id != connection_id);
+}
+
+/// 사용자가 입력한 새 비밀번호를 저장하고 재연결 재개
+pub fn supply_password(connection_id: &str, password: &str) -> Result<(), String> {
+    credentials::save_password(connection_id, password)?;
+    clear(connection_id);
+    info!(
+        "[Auth] new password for {}, reconnects resumed",
+        connection_id
+    );
+    Ok(())
+}
@@ -0,0 +1,144 @@
+//! 재연결 정책 모듈 - 불안정한 네트워크에서 서버에 재연결을 몰아 보내지 않음
+//!
+//! 재연결이 실패할 때마다 다음 시도까지 기다리는 시간을 두 배로 늘리고 (지터 포함),
+//! 연속 실패가 한도에 이르면 회로를 열어 일정 시간 동안 시도 자체를 막는다. 막는 시간이
+//! 지나면 한 번만 시험 삼아 시도하고 (반열림), 성공하면 처음 상태로 돌아간다.
+//! fail2ban처럼 짧은 시간의 접속 실패 횟수로 차단하는 서버에서 차단되지 않도록 한다.
+
+use crate::types::{CircuitState, ReconnectStatus};
+use log::warn;
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+
+/// 첫 재시도 전 대기 시간
+const BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// 재시도 간격 상한
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// 회로를 여는 연속 실패 횟수
+const MAX_CONSECUTIVE_FAILURES: u32 = 6;
+
+/// 회로가 열려 있는 시간
+const OPEN_DURATION: Duration = Duration::from_secs(300);
+
+#[derive(Default)]
+struct State {
+    failures: u32,
+    next_attempt: Option<Instant>,
+    open_until: Option<Instant>,
+    // 연결이 끊긴 뒤 아직 다시 연결하지 못함 (끊김 훅을 한 번만 실행)
+    lost: bool,
+}
+
+/// 연결 하나의 재연결 시도 기록 (재연결해도 유지)
+pub struct ReconnectPolicy {
+    state: Mutex<State>,
+    // 현재 세션이 연결된 시각
+    connected_at: Mutex<Instant>,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            state: Mutex::default(),
+            connected_at: Mutex::new(Instant::now()),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// 현재 세션이 연결된 뒤 지난 시간
+    pub fn session_uptime(&self) -> Duration {
+        self.connected_at.lock().elapsed()
+    }
+
+    /// 재연결해도 되는 시점까지 대기 (회로가 열려 있으면 바로 실패)
+    pub(crate) fn wait_turn(&self) -> Result<(), String> {
+        let wait = {
+            let state = self.state.lock();
+            let now = Instant::now();
+            if let Some(open_until) = state.open_until {
+                if open_until > now {
+                    return Err(format!(
+                        "재연결 실패가 반복되어 {}초 동안 재연결을 멈췄습니다.",
+                        (open_until - now).as_secs().max(1)
+                    ));
+                }
+            }
+            state
+                .next_attempt
+                .map(|at| at.saturating_duration_since(now))
+                .unwrap_or_default()
+        };
+        if !wait.is_zero() {
+            std::thread::sleep(wait);
+        }
+        Ok(())
+    }
+
+    /// 연결 끊김 기록 (재연결에 성공한 뒤 처음 끊긴 것이면 true)
+    pub(crate) fn mark_lost(&self) -> bool {
+        !std::mem::replace(&mut self.state.lock().lost, true)
+    }
+
+    /// 재연결 결과 기록
+    pub(crate) fn record(&self, result: &Result<(), String>) {
+        let mut state = self.state.lock();
+        match result {
+            Ok(()) => {
+                *state = State::default();
+                *self.connected_at.lock() = Instant::now();
+            }
+            Err(e) => {
+                // 반열림 상태의 시험 시도가 실패하면 곧바로 다시 엶
+                state.failures = if state.open_until.is_some() {
+                    MAX_CONSECUTIVE_FAILURES
+                } else {
+                    state.failures + 1
+                };
+                state.next_attempt = Some(Instant::now() + backoff_delay(state.failures));
+                if state.failures >= MAX_CONSECUTIVE_FAILURES {
+                    warn!(
+                        "[Reconnect] {} consecutive failures, pausing for {}s: {}",
+                        state.failures,
+                        OPEN_DURATION.as_secs(),
+                        e
+                    );
+                    state.open_until = Some(Instant::now() + OPEN_DURATION);
+                }
+            }
+        }
+    }
+
+    /// 드라이브 상태에 보일 재연결 상태 (실패가 없으면 None)
+    pub fn status(&self) -> Option<ReconnectStatus> {
+        let state = self.state.lock();
+        if state.failures == 0 {
+            return None;
+        }
+        let now = Instant::now();
+        let (circuit, retry_at) = match state.open_until {
+            Some(open_until) if open_until > now => (CircuitState::Open, Some(open_until)),
+            Some(_) => (CircuitState::HalfOpen, None),
+            None => (CircuitState::Closed, state.next_attempt),
+        };
+        Some(ReconnectStatus {
+            failures: state.failures,
+            circuit,
+            retry_in_secs: retry_at.map(|at| at.saturating_duration_since(now).as_secs()),
+        })
+    }
+}
+
+/// 실패 횟수에 따른 대기 시간 (지수 증가, 절반은 무작위로 흩어 동시 재시도를 분산)
+fn backoff_delay(failures: u32) -> Duration {
+    let exponential = BASE_DELAY
+        .saturating_mul(1 << failures.saturating_sub(1).min(16))
+        .min(MAX_DELAY);
+    let half = exponential / 2;
+    let mut random = [0u8; 4];
+    let _ = getrandom::getrandom(&mut random);
+    let fraction = f64::from(u32::from_le_bytes(random)) / f64::from(u32::MAX);
+    half + half.mul_f64(fraction)
+}
@@ -0,0 +1,1560 @@
+//! SFTP 클라이언트 모듈 - ssh2 crate를 사용한 SFTP 연결 관리
+
+use crate::credentials;
+use crate::keyfile;
+use crate::keystore;
+use crate::logging;
+use crate::names::NameMapper;
+use crate::network;
+use crate::reauth;
+use crate::reconnect::ReconnectPolicy;
+use crate::storage;
+use crate::totp;
+use crate::traffic;
+use crate::types::{AuthType, SftpCapabilities, SshConnection};
+use log::{info, warn};
+use parking_lot::Mutex;
+use ssh2::{
+    ErrorCode, FileStat, KeyboardInteractivePrompt, OpenFlags, OpenType, Prompt, RenameFlags,
+    Session, Sftp,
+};
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+const READ_TIMEOUT_SECS: u64 = 60;
+const WRITE_TIMEOUT_SECS: u64 = 60;
+
+/// exec 지원 확인 명령의 응답 대기 시간
+const EXEC_PROBE_TIMEOUT_MS: u32 = 3000;
+
+/// 취소할 수 있는 원격 명령이 출력을 기다리다 취소를 확인하는 간격
+const CANCEL_POLL_MS: u32 = 500;
+
+/// exec 지원 확인 명령이 출력하는 표식
+const EXEC_PROBE_MARKER: &str = "sshvd-exec-ok";
+
+/// exec 채널을 쓸 수 없는 서버에서 명령 기반 기능이 반환하는 오류
+pub const EXEC_UNSUPPORTED: &str = "서버가 원격 명령 실행을 지원하지 않습니다 (SFTP 전용 서버).";
+
+/// 서버가 비밀번호를 거부했을 때 오류 메시지의 시작 (만료되었거나 바뀐 비밀번호)
+pub const PASSWORD_REJECTED: &str = "서버가 비밀번호를 거부했습니다";
+
+/// libssh2 인증 실패 오류 코드
+const LIBSSH2_ERROR_AUTHENTICATION_FAILED: i32 = -18;
+/// libssh2 비밀번호 만료 오류 코드
+const LIBSSH2_ERROR_PASSWORD_EXPIRED: i32 = -15;
+/// libssh2 디렉토리 끝 (readdir가 더 돌려줄 항목이 없음)
+const LIBSSH2_ERROR_FILE: i32 = -16;
+
+/// 업로드 청크 크기 - 청크 단위로 서버 수락 여부를 확인해 중단 시 이어쓰기 지점으로 사용
+const UPLOAD_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// SFTP 클라이언트 래퍼
+#[allow(dead_code)]
+pub struct SftpClient {
+    session: Session,
+    sftp: Sftp,
+    // 서버가 지원하는 SFTP 확장
+    capabilities: SftpCapabilities,
+    // 재연결용 연결 정보
+    connection: SshConnection,
+    password: Option<String>,
+    // 재연결 간격과 회로 상태 (재연결해도 유지)
+    reconnect: Arc<ReconnectPolicy>,
+    // 로컬 이름과 서버의 실제 이름 대응 (재연결해도 유지)
+    names: Arc<NameMapper>,
+}
+
+impl SftpClient {
+    /// 새 SFTP 연결 생성
+    pub fn connect(connection: &SshConnection, password: Option<&str>) -> Result<Self, String> {
+        let (session, sftp) = Self::open_session(connection, password).inspect_err(|e| {
+            if e.starts_with(PASSWORD_REJECTED) {
+                reauth::mark_rejected(&connection.id);
+            }
+        })?;
+        reauth::clear(&connection.id);
+        let capabilities = Self::detect_capabilities(&session, &sftp);
+
+        Ok(Self {
+            session,
+            sftp,
+            capabilities,
+            connection: connection.clone(),
+            password: password.map(str::to_string),
+            reconnect: Arc::default(),
+            names: Arc::new(NameMapper::new(connection)),
+        })
+    }
+
+    /// 인증된 SSH 세션과 SFTP 세션 생성
+    fn open_session(
+        connection: &SshConnection,
+        password: Option<&str>,
+    ) -> Result<(Session, Sftp), String> {
+        let session = Self::handshake(connection)?;
+
+        // Known Hosts 검증 후 고정한 지문 확인 (인증 정보를 보내기 전에)
+        Self::verify_known_host(&session, &connection.host, connection.port)?;
+        Self::verify_pinned_host_key(&session, connection)?;
+
+        // 인증 (2단계 인증 서버는 1단계가 부분 성공으로 끝나도 이어서 진행)
+        let primary = match connection.auth_type {
+            AuthType::Password => {
+                let pwd = password.ok_or("비밀번호가 필요합니다.")?;
+                session
+                    .userauth_password(&connection.username, pwd)
+                    .map_err(|e| match e.code() {
+                        // 네트워크 오류와 구분해 새 비밀번호를 받을 때까지 재시도하지 않음
+                        ErrorCode::Session(
+                            LIBSSH2_ERROR_AUTHENTICATION_FAILED | LIBSSH2_ERROR_PASSWORD_EXPIRED,
+                        ) => format!("{}: {}", PASSWORD_REJECTED, e),
+                        _ => format!("비밀번호 인증 실패: {}", e),
+                    })
+            }
+            AuthType::Key => {
+                let key_file = match connection.key_id.as_deref() {
+                    Some(key_id) => keystore::auth_key_file(key_id)?,
+                    None => {
+                        let key_path = connection
+                            .key_path
+                            .as_ref()
+                            .ok_or("SSH 키 경로가 필요합니다.")?;
+                        keyfile::auth_key_file(key_path)?
+                    }
+                };
+                session
+                    .userauth_pubkey_file(&connection.username, None, key_file.path(), None)
+                    .map_err(|e| format!("SSH 키 인증 실패: {}", e))
+            }
+            AuthType::Agent => Self::authenticate_agent(&session, &connection.username),
+        };
+        if !session.authenticated() && connection.totp {
+            let secret =
+                credentials::get_password(&credentials::totp_credential_key(&connection.id))?
+                    .ok_or("저장된 TOTP 비밀 키가 없습니다.")?;
+            let mut prompter = OtpPrompter { password, secret };
+            if let Err(e) =
+                session.userauth_keyboard_interactive(&connection.username, &mut prompter)
+            {
+                // 1단계가 실패했으면 그 오류가 원인 (비밀번호 거부는 PASSWORD_REJECTED로 분류되어
+                // 새 비밀번호를 받을 때까지 재시도하지 않음)
+                primary?;
+                return Err(format!("2단계 인증 실패: {}", e));
+            }
+        } else {
+            primary?;
+        }
+
+        if !session.authenticated() {
+            return Err("SSH 인증 실패".to_string());
+        }
+
+        // SFTP 세션 시작
+        let sftp = session
+            .sftp()
+            .map_err(|e| format!("SFTP 세션 시작 실패: {}", e))?;
+
+        Ok((session, sftp))
+    }
+
+    /// SSH 에이전트의 키로 인증
+    ///
+    /// Windows에서는 OpenSSH Authentication Agent 서비스의 파이프에 연결한다. 보안 키는
+    /// 서명할 때마다 에이전트가 터치를 요청하므로, 키가 여러 개면 보안 키를 먼저 시도해
+    /// 조직 정책상 허용되지 않는 일반 키로 먼저 실패하는 일을 줄인다.
+    fn authenticate_agent(session: &Session, username: &str) -> Result<(), String> {
+        let mut agent = session
+            .agent()
+            .map_err(|e| format!("SSH 에이전트 초기화 실패: {}", e))?;
+        agent.connect().map_err(|e| {
+            format!(
+                "SSH 에이전트 연결 실패 (OpenSSH Authentication Agent 서비스가 실행 중인지 확인해주세요): {}",
+                e
+            )
+        })?;
+        agent
+            .list_identities()
+            .map_err(|e| format!("SSH 에이전트 키 목록 조회 실패: {}", e))?;
+        let mut identities = agent
+            .identities()
+            .map_err(|e| format!("SSH 에이전트 키 목록 조회 실패: {}", e))?;
+        if identities.is_empty() {
+            let _ = agent.disconnect();
+            return Err(
+                "SSH 에이전트에 등록된 키가 없습니다. `ssh-add`로 키를 추가해주세요.".to_string(),
+            );
+        }
+        identities.sort_by_key(|identity| !keyfile::is_security_key(identity.blob()));
+
+        let mut last_error = None;
+        for identity in &identities {
+            match agent.userauth(username, identity) {
+                Ok(()) => {
+                    info!(
+                        "[SSH] authenticated with agent key '{}'",
+                        identity.comment()
+                    );
+                    last_error = None;
+                    break;
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        let _ = agent.disconnect();
+        match last_error {
+            None => Ok(()),
+            Some(e) => Err(format!(
+                "SSH 에이전트 인증 실패 (보안 키라면 터치 요청에 응답했는지 확인해주세요): {}",
+                e
+            )),
+        }
+    }
+
+    /// TCP 연결과 SSH 핸드셰이크 (인증 전)
+    fn handshake(connection: &SshConnection) -> Result<Session, String> {
+        // 가림 모드 로그에서 숨길 호스트 이름
+        logging::register_host(&connection.host);
+        if let Some(proxy) = &connection.proxy {
+            logging::register_host(&proxy.host);
+        }
+
+        // TCP 연결 (DNS 해석 포함, 설정된 경우 프록시 경유)
+        let tcp = network::open_stream(connection)?;
+
+        // 읽기/쓰기 타임아웃 설정
+        tcp.set_read_timeout(Some(Duration::from_secs(READ_TIMEOUT_SECS)))
+            .map_err(|e| format!("읽기 타임아웃 설정 실패: {}", e))?;
+        tcp.set_write_timeout(Some(Duration::from_secs(WRITE_TIMEOUT_SECS)))
+            .map_err(|e| format!("쓰기 타임아웃 설정 실패: {}", e))?;
+
+        // SSH 세션 생성
+        let mut session = Session::new().map_err(|e| format!("SSH 세션 생성 실패: {}", e))?;
+        session.set_tcp_stream(tcp);
+        session
+            .handshake()
+            .map_err(|e| format!("SSH 핸드셰이크 실패: {}", e))?;
+        Ok(session)
+    }
+
+    /// 서버의 현재 호스트 키 지문 (인증하지 않음, 지문을 다시 고정하기 전 확인용)
+    pub fn fetch_host_key_fingerprint(connection: &SshConnection) -> Result<String, String> {
+        let session = Self::handshake(connection)?;
+        let fingerprint = Self::host_key_fingerprint(&session)?;
+        let _ = session.disconnect(None, "host key check", None);
+        Ok(fingerprint)
+    }
+
+    /// 저장된 연결 프로필로 연결 (비밀번호는 자격 증명 관리자에서 조회)
+    pub fn connect_saved(connection_id: &str) -> Result<Self, String> {
+        let connection = storage::get_connection_by_id(connection_id)?
+            .ok_or_else(|| "연결을 찾을 수 없습니다.".to_string())?;
+        let password = if connection.auth_type == AuthType::Password {
+            credentials::get_password(connection_id)?
+        } else {
+            None
+        };
+        Self::connect(&connection, password.as_deref())
+    }
+
+    /// SFTP 확장 지원 여부 확인
+    ///
+    /// libssh2는 서버가 SSH_FXP_VERSION에 담아 보낸 확장 목록을 노출하지 않으므로,
+    /// 로그인 디렉토리 핸들에 각 확장 요청을 직접 보내 본다.
+    fn detect_capabilities(session: &Session, sftp: &Sftp) -> SftpCapabilities {
+        let statvfs = sftp
+            .opendir(Path::new("."))
+            .and_then(|mut dir| dir.statvfs())
+            .is_ok();
+
+        SftpCapabilities {
+            statvfs,
+            fsync: Self::probe_fsync(sftp),
+            exec: Self::probe_exec(session),
+        }
+    }
+
+    /// fsync@openssh.com 지원 여부 (성공 응답을 받았을 때만 지원으로 봄)
+    ///
+    /// 디렉토리 핸들은 지원하는 서버도 동기화를 거절하므로, 로그인 디렉토리를 읽기 전용 파일
+    /// 핸들로 열어 요청한다 (OpenSSH는 디렉토리도 파일로 열 수 있음).
+    fn probe_fsync(sftp: &Sftp) -> bool {
+        sftp.open(Path::new("."))
+            .and_then(|mut file| file.fsync())
+            .is_ok()
+    }
+
+    /// exec 채널로 셸 명령을 실행할 수 있는지 확인
+    ///
+    /// `ForceCommand internal-sftp` 서버는 exec 요청을 받아들인 뒤 SFTP 서버를 띄우므로
+    /// 종료를 기다리다 멈추지 않도록 짧은 타임아웃 안에 표식이 출력되는지 본다.
+    fn probe_exec(session: &Session) -> bool {
+        let previous = session.timeout();
+        session.set_timeout(EXEC_PROBE_TIMEOUT_MS);
+        let supported = session.channel_session().is_ok_and(|mut channel| {
+            let mut output = String::new();
+            let echoed = channel.exec(&format!("echo {}", EXEC_PROBE_MARKER)).is_ok()
+                && channel.read_to_string(&mut output).is_ok()
+                && output.contains(EXEC_PROBE_MARKER);
+            let _ = channel.close();
+            echoed
+        });
+        session.set_timeout(previous);
+        if !supported {
+            info!("[SFTP] exec channel unavailable, shell-based features disabled");
+        }
+        supported
+    }
+
+    /// 서버가 지원하는 SFTP 확장
+    pub fn capabilities(&self) -> SftpCapabilities {
+        self.capabilities
+    }
+
+    /// 같은 연결 정보로 별도 세션을 여는 데 필요한 값 (연결은 세션 lock을 놓은 뒤에 함)
+    pub fn sibling_connector(&self) -> SiblingConnector {
+        SiblingConnector {
+            capabilities: self.capabilities,
+            connection: self.connection.clone(),
+            password: self.password.clone(),
+            reconnect: self.reconnect.clone(),
+            names: self.names.clone(),
+        }
+    }
+
+    /// 같은 연결 정보로 세션을 새로 만들어 교체
+    ///
+    /// 서버가 저장된 비밀번호를 거부한 연결은 새 비밀번호를 받을 때까지 시도하지 않는다.
+    pub fn reconnect(&mut self) -> Result<(), String> {
+        let connection = self.connection.clone();
+        if reauth::is_rejected(&connection.id) {
+            return Err(reauth::CREDENTIALS_PAUSED.to_string());
+        }
+        // 사용자가 새로 입력한 비밀번호가 있으면 그것으로 연결
+        let password = match connection.auth_type {
+            AuthType::Password => credentials::get_password(&connection.id)
+                .ok()
+                .flatten()
+                .or_else(|| self.password.clone()),
+            _ => self.password.clone(),
+        };
+        let policy = self.reconnect.clone();
+        let names = self.names.clone();
+        *self = Self::connect(&connection, password.as_deref())?;
+        self.reconnect = policy;
+        self.names = names;
+        Ok(())
+    }
+
+    /// 드라이브에 보이는 이름을 서버의 실제 이름으로 되돌린 경로 (원격 경로를 사용자에게 줄 때)
+    pub fn server_path(&self, path: &str) -> String {
+        self.names.to_remote(path).into_owned()
+    }
+
+    /// 서버 명령(`find`, `du` 등)이 출력한 경로를 드라이브에 보이는 경로로 바꿈
+    ///
+    /// `root`는 명령에 넘긴 경로의 드라이브 쪽 이름이다. 출력 경로가 그 아래가 아니면 그대로 둔다.
+    pub fn local_path(&self, root: &str, server: &str) -> String {
+        let server_root = self.names.to_remote(root);
+        let rest = match server.strip_prefix(server_root.trim_end_matches('/')) {
+            Some(rest) if rest.is_empty() || rest.starts_with('/') => rest,
+            _ => return server.to_string(),
+        };
+        let mut local = root.trim_end_matches('/').to_string();
+        for name in rest.split('/').filter(|name| !name.is_empty()) {
+            let name = self.names.to_local(&local, name.to_string());
+            local.push('/');
+            local.push_str(&name);
+        }
+        if local.is_empty() {
+            local.push('/');
+        }
+        local
+    }
+
+    /// 연결 프로필
+    pub(crate) fn connection(&self) -> &SshConnection {
+        &self.connection
+    }
+
+    /// 재연결 정책 (드라이브 상태 표시와 재연결 간격 조절용)
+    pub(crate) fn reconnect_policy(&self) -> Arc<ReconnectPolicy> {
+        self.reconnect.clone()
+    }
+
+    /// 연결에 고정한 지문과 서버 호스트 키 비교
+    fn verify_pinned_host_key(session: &Session, connection: &SshConnection) -> Result<(), String> {
+        let Some(pinned) = connection
+            .host_key_fingerprint
+            .as_deref()
+            .map(normalize_fingerprint)
+            .filter(|p| !p.is_empty())
+        else {
+            return Ok(());
+        };
+        let actual = Self::host_key_fingerprint(session)?;
+        if normalize_fingerprint(&actual) != pinned {
+            warn!("[SSH] pinned host key mismatch for {}", connection.id);
+            return Err(format!(
+                "호스트 키 고정 검증 실패: 서버 호스트 키가 {}(으)로 바뀌었습니다. 서버 관리자에게 변경을 확인한 뒤 지문을 다시 고정해주세요.",
+                actual
+            ));
+        }
+        Ok(())
+    }
+
+    fn host_key_fingerprint(session: &Session) -> Result<String, String> {
+        let (key, _) = session
+            .host_key()
+            .ok_or("서버 호스트 키를 받지 못했습니다.")?;
+        Ok(keyfile::fingerprint(key))
+    }
+
+    fn verify_known_host(session: &Session, host: &str, port: u16) -> Result<(), String> {
+        let known_hosts_path = std::env::var("USERPROFILE")
+            .or_else(|_| std::env::var("HOME"))
+            .ok()
+            .map(|h| Path::new(&h).join(".ssh").join("known_hosts"))
+            .filter(|p| p.exists());
+
+        if let Some(path) = known_hosts_path {
+            let hostport = format!("[{}]:{}", host, port);
+
+            let mut known_hosts = session
+                .known_hosts()
+                .map_err(|e| format!("known_hosts 초기화 실패: {}", e))?;
+            known_hosts
+                .read_file(&path, ssh2::KnownHostFileKind::OpenSSH)
+                .map_err(|e| format!("known_hosts 파일 로드 실패: {}", e))?;
+
+            let host_key = session.host_key();
+            if let Some((key, _)) = host_key {
+                match known_hosts.check(&hostport, key) {
+                    ssh2::CheckResult::Match => {}
+                    ssh2::CheckResult::Mismatch => {
+                        return Err(format!(
+                            "호스트 키 검증 실패: '{}'의 호스트 키가 known_hosts와 일치하지 않습니다.",
+                            hostport
+                        ));
+                    }
+                    ssh2::CheckResult::NotFound | ssh2::CheckResult::Failure => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 파일 정보 조회 (심볼릭 링크는 따라가지 않고 링크 자신의 정보)
+    pub fn lstat(&self, path: &str) -> Result<FileStat, String> {
+        self.sftp
+            .lstat(Path::new(&*self.names.to_remote(path)))
+            .map_err(|e| format!("파일 정보 읽기 실패: {}", e))
+    }
+
+    /// 디렉토리 목록 읽기
+    pub fn read_dir(&self, path: &str) -> Result<Vec<(String, FileStat)>, String> {
+        let entries = self
+            .sftp
+            .readdir(Path::new(&*self.names.to_remote(path)))
+            .map_err(|e| format!("디렉토리 읽기 실패: {}", e))?;
+
+        let entries = entries
+            .into_iter()
+            .map(|(entry, stat)| {
+                let name = entry
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                (name, stat)
+            })
+            .collect();
+        Ok(self.names.to_local_entries(path, entries))
+    }
+
+    /// 디렉토리를 열어 항목을 나눠 읽기 ([`read_dir_batch`](Self::read_dir_batch)로 이어 읽음)
+    ///
+    /// 반환된 핸들은 같은 세션을 쓰므로 읽을 때마다 클라이언트 lock을 잡아야 한다.
+    pub fn open_dir(&self, path: &str) -> Result<ssh2::File, String> {
+        self.sftp
+            .opendir(Path::new(&*self.names.to_remote(path)))
+            .map_err(|e| format!("디렉토리 읽기 실패: {}", e))
+    }
+
+    /// 연 디렉토리에서 최대 `max`개 항목 읽기 (끝까지 읽었으면 true를 함께 반환)
+    ///
+    /// `path`는 [`open_dir`](Self::open_dir)에 넘긴 경로 (이름의 정규화 형태를 기억하는 데 씀)
+    pub fn read_dir_batch(
+        &self,
+        path: &str,
+        dir: &mut ssh2::File,
+        max: usize,
+    ) -> Result<(Vec<(String, FileStat)>, bool), String> {
+        let mut entries = Vec::new();
+        let complete = loop {
+            if entries.len() >= max {
+                break false;
+            }
+            match dir.readdir() {
+                Ok((name, stat)) => {
+                    let name = name.to_string_lossy().to_string();
+                    if name != "." && name != ".." {
+                        entries.push((name, stat));
+                    }
+                }
+                Err(e) if e.code() == ErrorCode::Session(LIBSSH2_ERROR_FILE) => break true,
+                Err(e) => return Err(format!("디렉토리 읽기 실패: {}", e)),
+            }
+        };
+        // 겹치는 이름은 한 번에 읽은 항목 안에서만 가림
+        Ok((self.names.to_local_entries(path, entries), complete))
+    }
+
+    /// 파일 정보 가져오기 (없는 경로면 [`is_not_found`]가 참인 오류)
+    ///
+    /// 목록을 읽지 않은 폴더의 이름은 정규화 형태를 모르므로, 없다고 하면 다른 형태로 한 번 더
+    /// 찾아보고 찾으면 그 이름을 기억한다.
+    pub fn stat(&self, path: &str) -> Result<FileStat, String> {
+        let result = self
+            .sftp
+            .stat(Path::new(&*self.names.to_remote(path)))
+            .map_err(|e| format!("파일 정보 읽기 실패: {}", e));
+        match (result, self.names.alternate(path)) {
+            (Err(e), Some(alternate)) if is_not_found(&e) => {
+                let stat = self.sftp.stat(Path::new(&alternate)).map_err(|_| e)?;
+                self.names.remember(path, &alternate);
+                Ok(stat)
+            }
+            (result, _) => result,
+        }
+    }
+
+    /// 파일 읽기
+    pub fn read_file(&self, path: &str) -> Result<Vec<u8>, String> {
+        let mut file = self
+            .sftp
+            .open(Path::new(&*self.names.to_remote(path)))
+            .map_err(|e| format!("파일 열기 실패: {}", e))?;
+
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)
+            .map_err(|e| format!("파일 읽기 실패: {}", e))?;
+        traffic::record(&self.connection, contents.len() as u64, 0);
+
+        Ok(contents)
+    }
+
+    /// 파일의 일부 읽기 (offset부터 length만큼)
+    pub fn read_file_range(
+        &self,
+        path: &str,
+        offset: u64,
+        length: usize,
+        cancel: &CancelToken,
+    ) -> Result<Vec<u8>, String> {
+        cancel.check()?;
+        let mut file = self
+            .sftp
+            .open(Path::new(&*self.names.to_remote(path)))
+            .map_err(|e| format!("파일 열기 실패: {}", e))?;
+
+        // seek to offset
+        use std::io::Seek;
+        file.seek(std::io::SeekFrom::Start(offset))
+            .map_err(|e| format!("파일 탐색 실패: {}", e))?;
+
+        let mut buffer = vec![0u8; length];
+        let bytes_read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("파일 읽기 실패: {}", e))?;
+        traffic::record(&self.connection, bytes_read as u64, 0);
+
+        buffer.truncate(bytes_read);
+        Ok(buffer)
+    }
+
+    /// offset부터 차례로 읽을 파일 열기 (아카이브처럼 큰 파일을 흘려 읽을 때)
+    ///
+    /// 반환된 파일은 같은 세션을 쓰므로 다 읽을 때까지 클라이언트 lock을 잡고 있어야 한다.
+    pub fn open_reader(&self, path: &str, offset: u64) -> Result<RemoteReader, String> {
+        use std::io::Seek;
+        let mut file = self
+            .sftp
+            .open(Path::new(&*self.names.to_remote(path)))
+            .map_err(|e| format!("파일 열기 실패: {}", e))?;
+        file.seek(std::io::SeekFrom::Start(offset))
+            .map_err(|e| format!("파일 탐색 실패: {}", e))?;
+        Ok(RemoteReader {
+            file,
+            connection: Box::new(self.connection.clone()),
+            received: 0,
+        })
+    }
+
+    /// 파일의 일부를 EOF 또는 length까지 반복해서 읽기
+    pub fn read_file_range_full(
+        &self,
+        path: &str,
+        offset: u64,
+        length: usize,
+        cancel: &CancelToken,
+    ) -> Result<Vec<u8>, String> {
+        cancel.check()?;
+        let mut file = self
+            .sftp
+            .open(Path::new(&*self.names.to_remote(path)))
+            .map_err(|e| format!("파일 열기 실패: {}", e))?;
+
+        use std::io::Seek;
+        file.seek(std::io::SeekFrom::Start(offset))
+            .map_err(|e| format!("파일 탐색 실패: {}", e))?;
+
+        // 조각 단위로 읽어 취소되면 남은 부분은 받지 않음
+        let mut buffer = Vec::with_capacity(length);
+        while buffer.len() < length {
+            cancel.check()?;
+            let want = (length - buffer.len()).min(READ_CHUNK_SIZE) as u64;
+            let read = (&mut file)
+                .take(want)
+                .read_to_end(&mut buffer)
+                .map_err(|e| format!("파일 읽기 실패: {}", e))?;
+            traffic::record(&self.connection, read as u64, 0);
+            if read == 0 {
+                break;
+            }
+        }
+        Ok(buffer)
+    }
+
+    /// 파일 쓰기
+    pub fn write_file(&self, path: &str, contents: &[u8]) -> Result<(), String> {
+        let mut file = self
+            .sftp
+            .create(Path::new(&*self.names.to_remote(path)))
+            .map_err(|e| format!("파일 생성 실패: {}", e))?;
+
+        file.write_all(contents)
+            .map_err(|e| format!("파일 쓰기 실패: {}", e))?;
+        traffic::record(&self.connection, 0, contents.len() as u64);
+        Ok(())
+    }
+
+    /// 파일의 일부 쓰기 (offset부터, 파일이 없으면 생성하며 기존 내용은 유지)
+    pub fn write_file_range(&self, path: &str, offset: u64, data: &[u8]) -> Result<(), String> {
+        let mut file = self
+            .sftp
+            .open_mode(
+                Path::new(&*self.names.to_remote(path)),
+                OpenFlags::WRITE | OpenFlags::CREATE,
+                0o644,
+                OpenType::File,
+            )
+            .map_err(|e| format!("파일 열기 실패: {}", e))?;
+
+        use std::io::Seek;
+        file.seek(std::io::SeekFrom::Start(offset))
+            .map_err(|e| format!("파일 탐색 실패: {}", e))?;
+
+        file.write_all(data)
+            .map_err(|e| format!("파일 쓰기 실패: {}", e))?;
+        traffic::record(&self.connection, 0, data.len() as u64);
+        Ok(())
+    }
+
+    /// 파일의 일부를 청크 단위로 쓰기
+    ///
+    /// 실패하면 서버가 이미 수락한 바이트 수를 함께 반환해 호출자가 그 지점부터 이어쓸 수 있게 한다.
+    pub fn write_file_range_resumable(
+        &self,
+        path: &str,
+        offset: u64,
+        data: &[u8],
+    ) -> Result<(), (usize, String)> {
+        let mut file = self
+            .sftp
+            .open_mode(
+                Path::new(&*self.names.to_remote(path)),
+                OpenFlags::WRITE | OpenFlags::CREATE,
+                0o644,
+                OpenType::File,
+            )
+            .map_err(|e| (0, format!("파일 열기 실패: {}", e)))?;
+
+        use std::io::Seek;
+        file.seek(std::io::SeekFrom::Start(offset))
+            .map_err(|e| (0, format!("파일 탐색 실패: {}", e)))?;
+
+        let mut written = 0;
+        for chunk in data.chunks(UPLOAD_CHUNK_SIZE) {
+            file.write_all(chunk)
+                .map_err(|e| (written, format!("파일 쓰기 실패: {}", e)))?;
+            traffic::record(&self.connection, 0, chunk.len() as u64);
+            written += chunk.len();
+        }
+
+        // fsync 확장을 지원하면 서버 디스크까지 반영되었음을 확인
+        if self.capabilities.fsync {
+            file.fsync()
+                .map_err(|e| (written, format!("파일 동기화 실패: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    /// 로컬 파일을 offset부터 업로드
+    ///
+    /// 청크마다 `progress(전송된 바이트)`를 호출하고, false를 반환하면 중단한다.
+    /// 끝까지 보냈으면 true, 중단되었으면 false.
+    pub fn upload_file(
+        &self,
+        local_path: &Path,
+        remote_path: &str,
+        offset: u64,
+        mut progress: impl FnMut(u64) -> bool,
+    ) -> Result<bool, String> {
+        use std::io::Seek;
+        let mut local =
+            std::fs::File::open(local_path).map_err(|e| format!("로컬 파일 열기 실패: {}", e))?;
+        local
+            .seek(std::io::SeekFrom::Start(offset))
+            .map_err(|e| format!("로컬 파일 탐색 실패: {}", e))?;
+
+        let flags = if offset == 0 {
+            OpenFlags::WRITE | OpenFlags::CREATE | OpenFlags::TRUNCATE
+        } else {
+            OpenFlags::WRITE | OpenFlags::CREATE
+        };
+        let mut remote = self
+            .sftp
+            .open_mode(
+                Path::new(&*self.names.to_remote(remote_path)),
+                flags,
+                0o644,
+                OpenType::File,
+            )
+            .map_err(|e| format!("파일 열기 실패: {}", e))?;
+        remote
+            .seek(std::io::SeekFrom::Start(offset))
+            .map_err(|e| format!("파일 탐색 실패: {}", e))?;
+
+        let mut transferred = offset;
+        let mut chunk = vec![0u8; UPLOAD_CHUNK_SIZE];
+        loop {
+            let n = local
+                .read(&mut chunk)
+                .map_err(|e| format!("로컬 파일 읽기 실패: {}", e))?;
+            if n == 0 {
+                return Ok(true);
+            }
+            remote
+                .write_all(&chunk[..n])
+                .map_err(|e| format!("파일 쓰기 실패: {}", e))?;
+            traffic::record(&self.connection, 0, n as u64);
+            transferred += n as u64;
+            if !progress(transferred) {
+                return Ok(false);
+            }
+        }
+    }
+
+    /// 원격 파일을 offset부터 로컬로 다운로드 (진행/중단 규칙은 upload_file과 같음)
+    pub fn download_file(
+        &self,
+        remote_path: &str,
+        local_path: &Path,
+        offset: u64,
+        mut progress: impl FnMut(u64) -> bool,
+    ) -> Result<bool, String> {
+        use std::io::Seek;
+        let mut remote = self
+            .sftp
+            .open(Path::new(&*self.names.to_remote(remote_path)))
+            .map_err(|e| format!("파일 열기 실패: {}", e))?;
+        remote
+            .seek(std::io::SeekFrom::Start(offset))
+            .map_err(|e| format!("파일 탐색 실패: {}", e))?;
+
+        let mut local = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(offset == 0)
+            .open(local_path)
+            .map_err(|e| format!("로컬 파일 열기 실패: {}", e))?;
+        local
+            .seek(std::io::SeekFrom::Start(offset))
+            .map_err(|e| format!("로컬 파일 탐색 실패: {}", e))?;
+
+        let mut transferred = offset;
+        let mut chunk = vec![0u8; UPLOAD_CHUNK_SIZE];
+        loop {
+            let n = remote
+                .read(&mut chunk)
+                .map_err(|e| format!("파일 읽기 실패: {}", e))?;
+            if n == 0 {
+                return Ok(true);
+            }
+            traffic::record(&self.connection, n as u64, 0);
+            local
+                .write_all(&chunk[..n])
+                .map_err(|e| format!("로컬 파일 쓰기 실패: {}", e))?;
+            transferred += n as u64;
+            if !progress(transferred) {
+                return Ok(false);
+            }
+        }
+    }
+
+    /// 접근/수정 시간 설정 (Unix 시간)
+    pub fn set_times(&self, path: &str, atime: u64, mtime: u64) -> Result<(), String> {
+        let stat = FileStat {
+            size: None,
+            uid: None,
+            gid: None,
+            perm: None,
+            atime: Some(atime),
+            mtime: Some(mtime),
+        };
+        self.sftp
+            .setstat(Path::new(&*self.names.to_remote(path)), stat)
+            .map_err(|e| format!("파일 시간 설정 실패: {}", e))
+    }
+
+    /// 파일 크기 변경 (늘리면 서버가 0으로 채움)
+    pub fn truncate(&self, path: &str, size: u64) -> Result<(), String> {
+        let stat = FileStat {
+            size: Some(size),
+            uid: None,
+            gid: None,
+            perm: None,
+            atime: None,
+            mtime: None,
+        };
+        self.sftp
+            .setstat(Path::new(&*self.names.to_remote(path)), stat)
+            .map_err(|e| format!("파일 크기 변경 실패: {}", e))
+    }
+
+    /// 파일 삭제
+    pub fn remove_file(&self, path: &str) -> Result<(), String> {
+        self.sftp
+            .unlink(Path::new(&*self.names.to_remote(path)))
+            .map_err(|e| format!("파일 삭제 실패: {}", e))
+    }
+
+    /// 디렉토리 생성
+    pub fn create_dir(&self, path: &str) -> Result<(), String> {
+        self.sftp
+            .mkdir(Path::new(&*self.names.to_remote(path)), 0o755)
+            .map_err(|e| format!("디렉토리 생성 실패: {}", e))
+    }
+
+    /// 디렉토리 삭제
+    pub fn remove_dir(&self, path: &str) -> Result<(), String> {
+        self.sftp
+            .rmdir(Path::new(&*self.names.to_remote(path)))
+            .map_err(|e| format!("디렉토리 삭제 실패: {}", e))
+    }
+
+    /// 심볼릭 링크 생성 (link_path → target)
+    ///
+    /// OpenSSH 서버는 SSH_FXP_SYMLINK 인자 순서를 명세와 반대로 해석하므로 대상 경로를 먼저 보낸다.
+    pub fn symlink(&self, link_path: &str, target: &str) -> Result<(), String> {
+        self.sftp
+            .symlink(
+                Path::new(&*self.names.to_remote(target)),
+                Path::new(&*self.names.to_remote(link_path)),
+            )
+            .map_err(|e| format!("심볼릭 링크 생성 실패: {}", e))
+    }
+
+    /// 파일/디렉토리 이름 변경 (대상 파일이 있으면 덮어씀)
+    ///
+    /// libssh2는 이름 변경 플래그를 SFTP v5 이상 서버에만 보내므로, OpenSSH 같은 v3 서버는
+    /// 대상이 있으면 이름 변경을 거부한다. 그 경우에만 exec 채널을 쓸 수 있는 서버는
+    /// `mv -f`(rename(2), 원자적 교체)로 바꾸고, 그 밖에는 대상을 임시 이름으로 비켜 둔 뒤
+    /// 옮기고 실패하면 되돌린다. 대상이 디렉토리면 덮어쓰지 않고 실패를 그대로 돌려준다.
+    pub fn rename(&self, from: &str, to: &str) -> Result<(), String> {
+        replace_rename(self, from, to)
+    }
+
+    /// 원격 파일시스템 용량 (전체, 여유) - statvfs 확장 필요
+    pub fn statvfs(&self, path: &str) -> Result<(u64, u64), String> {
+        if !self.capabilities.statvfs {
+            return Err("서버가 statvfs 확장을 지원하지 않습니다.".to_string());
+        }
+        let mut dir = self
+            .sftp
+            .opendir(Path::new(&*self.names.to_remote(path)))
+            .map_err(|e| format!("디렉토리 열기 실패: {}", e))?;
+        let vfs = dir
+            .statvfs()
+            .map_err(|e| format!("용량 조회 실패: {}", e))?;
+        let block = if vfs.f_frsize > 0 {
+            vfs.f_frsize
+        } else {
+            vfs.f_bsize
+        };
+        Ok((vfs.f_blocks * block, vfs.f_bavail * block))
+    }
+
+    /// 원격 명령 실행 (exec 채널) - 종료 코드가 0이 아니면 stderr를 담아 실패 반환
+    pub fn exec(&self, command: &str) -> Result<String, String> {
+        if !self.capabilities.exec {
+            return Err(EXEC_UNSUPPORTED.to_string());
+        }
+        let mut channel = self
+            .session
+            .channel_session()
+            .map_err(|e| format!("exec 채널 열기 실패: {}", e))?;
+        channel
+            .exec(command)
+            .map_err(|e| format!("원격 명령 실행 실패: {}", e))?;
+
+        let mut stdout = String::new();
+        channel
+            .read_to_string(&mut stdout)
+            .map_err(|e| format!("원격 명령 출력 읽기 실패: {}", e))?;
+        let mut stderr = String::new();
+        let _ = channel.stderr().read_to_string(&mut stderr);
+
+        channel
+            .wait_close()
+            .map_err(|e| format!("exec 채널 종료 실패: {}", e))?;
+        let status = channel
+            .exit_status()
+            .map_err(|e| format!("종료 코드 확인 실패: {}", e))?;
+
+        if status != 0 {
+            return Err(format!(
+                "원격 명령 실패 (종료 코드 {}): {}",
+                status,
+                stderr.trim()
+            ));
+        }
+        Ok(stdout)
+    }
+
+    /// 원격 명령을 실행하며 표준 출력을 그대로 `out`에 씀 (바이너리 출력용)
+    pub fn exec_to(&self, command: &str, out: &mut dyn Write) -> Result<(), String> {
+        if !self.capabilities.exec {
+            return Err(EXEC_UNSUPPORTED.to_string());
+        }
+        let mut channel = self
+            .session
+            .channel_session()
+            .map_err(|e| format!("exec 채널 열기 실패: {}", e))?;
+        channel
+            .exec(command)
+            .map_err(|e| format!("원격 명령 실행 실패: {}", e))?;
+
+        let received = std::io::copy(&mut channel, out)
+            .map_err(|e| format!("원격 명령 출력 읽기 실패: {}", e))?;
+        traffic::record(&self.connection, received, 0);
+        let mut stderr = String::new();
+        let _ = channel.stderr().read_to_string(&mut stderr);
+
+        channel
+            .wait_close()
+            .map_err(|e| format!("exec 채널 종료 실패: {}", e))?;
+        let status = channel
+            .exit_status()
+            .map_err(|e| format!("종료 코드 확인 실패: {}", e))?;
+        if status != 0 {
+            return Err(format!(
+                "원격 명령 실패 (종료 코드 {}): {}",
+                status,
+                stderr.trim()
+            ));
+        }
+        Ok(())
+    }
+
+    /// 원격 명령을 실행하며 표준 출력을 줄 단위로 전달 (콜백이 false면 중단)
+    ///
+    /// 출력이 큰 명령(`find` 등)을 끝까지 기다리지 않고 처리하기 위해 사용한다.
+    /// 중단하지 않았으면 종료 코드를, 중단했으면 None을 반환한다.
+    pub fn exec_lines(
+        &self,
+        command: &str,
+        on_line: impl FnMut(&str) -> bool,
+    ) -> Result<Option<i32>, String> {
+        self.read_exec_lines(command, None, on_line)
+    }
+
+    /// [`exec_lines`](Self::exec_lines)와 같지만 `cancelled`가 켜지면 출력이 없는 동안에도
+    /// 잠시 뒤 채널을 닫고 None을 반환 (오래 아무것도 출력하지 않는 `find` 취소용)
+    pub fn exec_lines_cancellable(
+        &self,
+        command: &str,
+        cancelled: &AtomicBool,
+        on_line: impl FnMut(&str) -> bool,
+    ) -> Result<Option<i32>, String> {
+        self.read_exec_lines(command, Some(cancelled), on_line)
+    }
+
+    fn read_exec_lines(
+        &self,
+        command: &str,
+        cancelled: Option<&AtomicBool>,
+        mut on_line: impl FnMut(&str) -> bool,
+    ) -> Result<Option<i32>, String> {
+        if !self.capabilities.exec {
+            return Err(EXEC_UNSUPPORTED.to_string());
+        }
+        let mut channel = self
+            .session
+            .channel_session()
+            .map_err(|e| format!("exec 채널 열기 실패: {}", e))?;
+        channel
+            .exec(command)
+            .map_err(|e| format!("원격 명령 실행 실패: {}", e))?;
+
+        // 취소할 수 있으면 출력을 기다리는 동안 짧게 깨어나 취소를 확인
+        let previous = self.session.timeout();
+        if cancelled.is_some() {
+            self.session.set_timeout(CANCEL_POLL_MS);
+        }
+        let finished = read_lines(&mut channel, cancelled, &mut on_line);
+        self.session.set_timeout(previous);
+        if !finished? {
+            let _ = channel.close();
+            return Ok(None);
+        }
+
+        channel
+            .wait_close()
+            .map_err(|e| format!("exec 채널 종료 실패: {}", e))?;
+        channel
+            .exit_status()
+            .map(Some)
+            .map_err(|e| format!("종료 코드 확인 실패: {}", e))
+    }
+
+    /// 서버 측 복사 (`cp -a`) - 데이터를 내려받지 않고 서버 안에서 복사
+    ///
+    /// `-T`로 대상을 항상 복사본 이름으로 다룬다 (대상 디렉토리가 있어도 그 안에 넣지 않음).
+    /// `-T`는 GNU cp에만 있어서 셸 복사가 실패하면(BSD/busybox cp, exec를 쓸 수 없는 서버)
+    /// SFTP로 읽어 다시 쓰는 복사로 대신한다.
+    pub fn copy(&self, from: &str, to: &str) -> Result<(), String> {
+        let copied = self.exec(&format!(
+            "cp -a -T -- {} {}",
+            shell_quote(&self.names.to_remote(from)),
+            shell_quote(&self.names.to_remote(to))
+        ));
+        if let Err(e) = copied {
+            info!("[SFTP] cp failed, copying '{}' over SFTP: {}", from, e);
+            return self
+                .copy_over_sftp(from, to)
+                .map_err(|e| format!("서버 측 복사 실패: {}", e));
+        }
+        Ok(())
+    }
+
+    /// SFTP로 읽어 다시 쓰는 복사 (디렉토리는 하위까지, 링크는 링크로, 권한과 시간 유지)
+    ///
+    /// 디렉토리 권한과 시간은 하위 항목을 모두 복사한 뒤 맞춘다 (읽기 전용 디렉토리도 채울 수 있게).
+    fn copy_over_sftp(&self, from: &str, to: &str) -> Result<(), String> {
+        let mut pending = vec![(from.to_string(), to.to_string(), self.lstat(from)?, false)];
+        while let Some((from, to, stat, filled)) = pending.pop() {
+            if filled {
+                self.copy_attributes(&to, &stat)?;
+                continue;
+            }
+            if stat.file_type().is_symlink() {
+                let target = self
+                    .sftp
+                    .readlink(Path::new(&*self.names.to_remote(&from)))
+                    .map_err(|e| format!("심볼릭 링크 읽기 실패: {}", e))?;
+                // 링크 대상은 서버 이름 그대로 옮김 (인자 순서는 `symlink` 참고)
+                self.sftp
+                    .symlink(&target, Path::new(&*self.names.to_remote(&to)))
+                    .map_err(|e| format!("심볼릭 링크 생성 실패: {}", e))?;
+            } else if stat.is_dir() {
+                // cp -T처럼 이미 있는 대상 디렉토리에는 합쳐 넣음
+                if let Err(e) = self.create_dir(&to) {
+                    if !self.stat(&to).is_ok_and(|existing| existing.is_dir()) {
+                        return Err(e);
+                    }
+                }
+                let entries = self.read_dir(&from)?;
+                pending.push((from.clone(), to.clone(), stat, true));
+                for (name, child) in entries {
+                    if name == "." || name == ".." {
+                        continue;
+                    }
+                    pending.push((
+                        format!("{}/{}", from, name),
+                        format!("{}/{}", to, name),
+                        child,
+                        false,
+                    ));
+                }
+            } else {
+                self.copy_file_over_sftp(&from, &to)?;
+                self.copy_attributes(&to, &stat)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 파일 하나를 메모리에 다 올리지 않고 흘려 보내며 복사 (대상이 있으면 덮어씀)
+    fn copy_file_over_sftp(&self, from: &str, to: &str) -> Result<(), String> {
+        let mut source = self
+            .sftp
+            .open(Path::new(&*self.names.to_remote(from)))
+            .map_err(|e| format!("파일 열기 실패: {}", e))?;
+        let mut target = self
+            .sftp
+            .create(Path::new(&*self.names.to_remote(to)))
+            .map_err(|e| format!("파일 생성 실패: {}", e))?;
+        let copied = std::io::copy(&mut source, &mut target)
+            .map_err(|e| format!("파일 복사 실패: {}", e))?;
+        traffic::record(&self.connection, copied, copied);
+        Ok(())
+    }
+
+    /// 원본의 권한 비트와 접근/수정 시간을 복사본에 적용
+    fn copy_attributes(&self, path: &str, source: &FileStat) -> Result<(), String> {
+        let stat = FileStat {
+            size: None,
+            uid: None,
+            gid: None,
+            perm: source.perm.map(|perm| perm & 0o7777),
+            atime: source.atime,
+            mtime: source.mtime,
+        };
+        self.sftp
+            .setstat(Path::new(&*self.names.to_remote(path)), stat)
+            .map_err(|e| format!("파일 속성 설정 실패: {}", e))
+    }
+
+    /// 연결이 유효한지 확인
+    #[allow(dead_code)]
+    pub fn is_connected(&self) -> bool {
+        self.session.authenticated()
+    }
+}
+
+/// keyboard-interactive 질문에 자동 응답 (비밀번호 질문은 저장된 비밀번호, 나머지는 TOTP 코드)
+struct OtpPrompter<'a> {
+    password: Option<&'a str>,
+    secret: String,
+}
+
+impl KeyboardInteractivePrompt for OtpPrompter<'_> {
+    fn prompt<'b>(
+        &mut self,
+        _username: &str,
+        _instructions: &str,
+        prompts: &[Prompt<'b>],
+    ) -> Vec<String> {
+        prompts
+            .iter()
+            .map(|prompt| {
+                let text = prompt.text.to_lowercase();
+                let asks_code = ["code", "otp", "token", "verification", "인증"]
+                    .iter()
+                    .any(|word| text.contains(word));
+                match self.password {
+                    Some(password) if text.contains("password") && !asks_code => {
+                        password.to_string()
+                    }
+                    _ => totp::current_code(&self.secret).unwrap_or_default(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// 지문 비교용 정규화 (`SHA256:` 접두어와 base64 패딩 생략 허용)
+fn normalize_fingerprint(fingerprint: &str) -> String {
+    let fingerprint = fingerprint.trim();
+    let fingerprint = fingerprint.strip_prefix("SHA256:").unwrap_or(fingerprint);
+    fingerprint.trim_end_matches('=').to_string()
+}
+
+/// 차례로 읽는 원격 파일 ([`SftpClient::open_reader`]가 반환, 닫을 때 받은 양을 전송량에 더함)
+pub struct RemoteReader {
+    file: ssh2::File,
+    connection: Box<SshConnection>,
+    received: u64,
+}
+
+impl Read for RemoteReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.file.read(buf)?;
+        self.received += read as u64;
+        Ok(read)
+    }
+}
+
+impl std::io::Seek for RemoteReader {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        std::io::Seek::seek(&mut self.file, pos)
+    }
+}
+
+impl Drop for RemoteReader {
+    fn drop(&mut self) {
+        traffic::record(&self.connection, self.received, 0);
+    }
+}
+
+/// 보조 세션 연결 정보 (병렬 전송, 긴 서버 명령용)
+pub struct SiblingConnector {
+    capabilities: SftpCapabilities,
+    connection: SshConnection,
+    password: Option<String>,
+    reconnect: Arc<ReconnectPolicy>,
+    names: Arc<NameMapper>,
+}
+
+impl SiblingConnector {
+    /// 별도 세션 생성 (확장 지원 여부는 다시 확인하지 않음)
+    pub fn connect(&self) -> Result<SftpClient, String> {
+        let (session, sftp) = SftpClient::open_session(&self.connection, self.password.as_deref())?;
+        Ok(SftpClient {
+            session,
+            sftp,
+            capabilities: self.capabilities,
+            connection: self.connection.clone(),
+            password: self.password.clone(),
+            reconnect: self.reconnect.clone(),
+            names: self.names.clone(),
+        })
+    }
+}
+
+/// 덮어쓰는 이름 변경에 쓰는 원격 연산
+trait RenameBackend {
+    /// 한 번의 SFTP 이름 변경 (`overwrite`면 덮어쓰기 플래그를 함께 보냄)
+    fn rename_once(&self, from: &str, to: &str, overwrite: bool) -> Result<(), String>;
+    /// 경로 종류 (없으면 None, 디렉토리면 Some(true))
+    fn kind(&self, path: &str) -> Option<bool>;
+    /// 서버 쪽 원자적 교체 (지원하지 않으면 None)
+    fn replace_atomic(&self, from: &str, to: &str) -> Option<Result<(), String>>;
+    fn remove(&self, path: &str) -> Result<(), String>;
+}
+
+impl RenameBackend for SftpClient {
+    fn rename_once(&self, from: &str, to: &str, overwrite: bool) -> Result<(), String> {
+        let flags =
+            overwrite.then_some(RenameFlags::OVERWRITE | RenameFlags::ATOMIC | RenameFlags::NATIVE);
+        self.sftp
+            .rename(
+                Path::new(&*self.names.to_remote(from)),
+                Path::new(&*self.names.to_remote(to)),
+                flags,
+            )
+            .map_err(|e| format!("이름 변경 실패: {}", e))
+    }
+
+    fn kind(&self, path: &str) -> Option<bool> {
+        self.lstat(path).ok().map(|stat| stat.is_dir())
+    }
+
+    fn replace_atomic(&self, from: &str, to: &str) -> Option<Result<(), String>> {
+        if !self.capabilities.exec {
+            return None;
+        }
+        let command = format!(
+            "mv -f -- {} {}",
+            shell_quote(&self.server_path(from)),
+            shell_quote(&self.server_path(to))
+        );
+        Some(self.exec(&command).map(|_| ()))
+    }
+
+    fn remove(&self, path: &str) -> Result<(), String> {
+        self.remove_file(path)
+    }
+}
+
+/// 대상이 있어 거절된 이름 변경만 대상을 교체해 다시 시도
+///
+/// v3 서버는 대상이 있을 때 일반 실패(SSH_FX_FAILURE)를 돌려주므로, 권한 거부나 원본 없음 같은
+/// 다른 오류이거나 원본/대상 상태가 맞지 않으면 처음 오류를 그대로 돌려준다.
+fn replace_rename(backend: &impl RenameBackend, from: &str, to: &str) -> Result<(), String> {
+    let error = match backend.rename_once(from, to, true) {
+        Ok(()) => return Ok(()),
+        Err(e) => e,
+    };
+    let target_exists = error.contains("[SFTP(4)]") || error.contains("[SFTP(11)]");
+    if !target_exists || backend.kind(from).is_none() || backend.kind(to) != Some(false) {
+        return Err(error);
+    }
+
+    match backend.replace_atomic(from, to) {
+        Some(Ok(())) => return Ok(()),
+        Some(Err(e)) => warn!("[SFTP] atomic replace of '{}' failed: {}", to, e),
+        None => {}
+    }
+
+    // 대상을 비켜 두고 옮긴 뒤 지움 (옮기기에 실패하면 대상을 되돌림)
+    let aside = replace_aside_path(to);
+    backend.rename_once(to, &aside, false)?;
+    if let Err(e) = backend.rename_once(from, to, false) {
+        return match backend.rename_once(&aside, to, false) {
+            Ok(()) => Err(e),
+            Err(restore) => {
+                warn!(
+                    "[SFTP] restoring '{}' from '{}' failed: {}",
+                    to, aside, restore
+                );
+                Err(format!("{} (기존 파일은 '{}'에 남아 있습니다)", e, aside))
+            }
+        };
+    }
+    if let Err(e) = backend.remove(&aside) {
+        warn!("[SFTP] removing replaced file '{}' failed: {}", aside, e);
+    }
+    Ok(())
+}
+
+/// 교체하는 동안 기존 대상을 비켜 둘 숨김 이름 (같은 디렉토리)
+fn replace_aside_path(path: &str) -> String {
+    let (parent, name) = path.rsplit_once('/').unwrap_or(("", path));
+    let suffix = Uuid::new_v4().simple().to_string();
+    format!("{}/.{}.svd-replace-{}", parent, name, &suffix[..8])
+}
+
+/// POSIX 셸 인용 (작은따옴표로 감싸고 내부 작은따옴표는 이스케이프)
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// exec 채널 출력을 줄 단위로 전달 (끝까지 읽었으면 true, 콜백이나 취소로 멈췄으면 false)
+fn read_lines(
+    channel: &mut ssh2::Channel,
+    cancelled: Option<&AtomicBool>,
+    on_line: &mut impl FnMut(&str) -> bool,
+) -> Result<bool, String> {
+    let mut reader = BufReader::new(channel);
+    let mut line = Vec::new();
+    loop {
+        if cancelled.is_some_and(|c| c.load(Ordering::Relaxed)) {
+            return Ok(false);
+        }
+        let read = match reader.read_until(b'\n', &mut line) {
+            Ok(read) => read,
+            // 취소를 확인하려고 깨어남 (읽던 줄의 앞부분은 `line`에 남아 있음)
+            Err(e) if cancelled.is_some() && e.kind() == ErrorKind::TimedOut => continue,
+            Err(e) => return Err(format!("원격 명령 출력 읽기 실패: {}", e)),
+        };
+        if read == 0 && line.is_empty() {
+            return Ok(true);
+        }
+        // UTF-8이 아닌 파일 이름도 중단 없이 전달
+        let text = String::from_utf8_lossy(&line);
+        if !on_line(text.trim_end_matches(['\r', '\n'])) {
+            return Ok(false);
+        }
+        line.clear();
+    }
+}
+
+/// 취소된 읽기 오류 메시지
+pub const READ_CANCELLED: &str = "읽기 요청이 취소되었습니다.";
+
+/// SFTP 오류 메시지가 '파일 없음'(SSH_FX_NO_SUCH_FILE)인지
+pub fn is_not_found(error: &str) -> bool {
+    error.contains("[SFTP(2)]")
+}
+
+/// 한 번에 읽는 최대 크기 (이 단위마다 취소 여부 확인)
+const READ_CHUNK_SIZE: usize = 256 * 1024;
+
+/// 핸들별 취소 신호 (cleanup마다 세대를 올려 그 전에 시작된 요청만 취소)
+#[derive(Default)]
+pub struct CancelSource(Arc<AtomicU32>);
+
+impl CancelSource {
+    /// 지금까지 시작된 요청 취소
+    pub fn cancel(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 새 요청용 토큰 (이후 cancel 되면 취소됨)
+    pub fn token(&self) -> CancelToken {
+        CancelToken {
+            counter: Some(self.0.clone()),
+            start: self.0.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// 진행 중인 요청의 취소 여부
+#[derive(Clone, Default)]
+pub struct CancelToken {
+    counter: Option<Arc<AtomicU32>>,
+    start: u32,
+}
+
+impl CancelToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.counter
+            .as_ref()
+            .is_some_and(|counter| counter.load(Ordering::Relaxed) != self.start)
+    }
+
+    /// 취소되었으면 오류
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(READ_CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// 스레드 안전한 SFTP 클라이언트 핸들
+pub type SharedSftpClient = Arc<Mutex<SftpClient>>;
+
+/// 새 공유 SFTP 클라이언트 생성
+pub fn create_shared_client(
+    connection: &SshConnection,
+    password: Option<&str>,
+) -> Result<SharedSftpClient, String> {
+    let client = SftpClient::connect(connection, password)?;
+    Ok(Arc::new(Mutex::new(client)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::BTreeMap;
+
+    /// OpenSSH(v3)처럼 대상이 있으면 이름 변경을 거절하는 메모리 서버
+    #[derive(Default)]
+    struct FakeServer {
+        // 경로 → (디렉토리 여부, 내용)
+        entries: RefCell<BTreeMap<String, (bool, &'static str)>>,
+        // None: posix-rename/exec 없음, Some(false): mv -f 실패
+        atomic: Option<bool>,
+        // 이 원본의 이름 변경은 항상 해당 SFTP 상태 코드로 실패
+        broken_source: Option<(&'static str, u32)>,
+    }
+
+    impl FakeServer {
+        fn with(entries: &[(&str, bool, &'static str)]) -> Self {
+            let server = Self::default();
+            for (path, is_dir, content) in entries {
+                server
+                    .entries
+                    .borrow_mut()
+                    .insert(path.to_string(), (*is_dir, *content));
+            }
+            server
+        }
+
+        fn snapshot(&self) -> Vec<(String, &'static str)> {
+            self.entries
+                .borrow()
+                .iter()
+                .map(|(path, (_, content))| (path.clone(), *content))
+                .collect()
+        }
+    }
+
+    impl RenameBackend for FakeServer {
+        fn rename_once(&self, from: &str, to: &str, _overwrite: bool) -> Result<(), String> {
+            if let Some((source, code)) = self.broken_source {
+                if source == from {
+                    return Err(format!("이름 변경 실패: [SFTP({})] failure", code));
+                }
+            }
+            let mut entries = self.entries.borrow_mut();
+            if entries.contains_key(to) {
+                return Err("이름 변경 실패: [SFTP(4)] failure".to_string());
+            }
+            let Some(entry) = entries.remove(from) else {
+                return Err("이름 변경 실패: [SFTP(2)] no such file".to_string());
+            };
+            entries.insert(to.to_string(), entry);
+            Ok(())
+        }
+
+        fn kind(&self, path: &str) -> Option<bool> {
+            self.entries.borrow().get(path).map(|(is_dir, _)| *is_dir)
+        }
+
+        fn replace_atomic(&self, from: &str, to: &str) -> Option<Result<(), String>> {
+            match self.atomic? {
+                false => Some(Err("mv: cannot move: Permission denied".to_string())),
+                true => {
+                    let mut entries = self.entries.borrow_mut();
+                    let entry = entries.remove(from)?;
+                    entries.insert(to.to_string(), entry);
+                    Some(Ok(()))
+                }
+            }
+        }
+
+        fn remove(&self, path: &str) -> Result<(), String> {
+            self.entries
+                .borrow_mut()
+                .remove(path)
+                .map(|_| ())
+                .ok_or_else(|| "파일 삭제 실패: [SFTP(2)] no such file".to_string())
+        }
+    }
+
+    fn files(new: &'static str, old: &'static str) -> Vec<(String, &'static str)> {
+        vec![("/d/a".to_string(), new), ("/d/b".to_string(), old)]
+    }
+
+    #[test]
+    fn overwrite_moves_target_aside_without_atomic_replace() {
+        let server = FakeServer::with(&[("/d/a", false, "new"), ("/d/b", false, "old")]);
+        assert_eq!(replace_rename(&server, "/d/a", "/d/b"), Ok(()));
+        assert_eq!(server.snapshot(), vec![("/d/b".to_string(), "new")]);
+    }
+
+    #[test]
+    fn overwrite_uses_atomic_replace() {
+        let mut server = FakeServer::with(&[("/d/a", false, "new"), ("/d/b", false, "old")]);
+        server.atomic = Some(true);
+        assert_eq!(replace_rename(&server, "/d/a", "/d/b"), Ok(()));
+        assert_eq!(server.snapshot(), vec![("/d/b".to_string(), "new")]);
+    }
+
+    #[test]
+    fn failed_atomic_replace_falls_back() {
+        let mut server = FakeServer::with(&[("/d/a", false, "new"), ("/d/b", false, "old")]);
+        server.atomic = Some(false);
+        assert_eq!(replace_rename(&server, "/d/a", "/d/b"), Ok(()));
+        assert_eq!(server.snapshot(), vec![("/d/b".to_string(), "new")]);
+    }
+
+    #[test]
+    fn directory_target_is_not_replaced() {
+        let server = FakeServer::with(&[("/d/a", false, "new"), ("/d/b", true, "dir")]);
+        assert!(replace_rename(&server, "/d/a", "/d/b").is_err());
+        assert_eq!(server.snapshot(), files("new", "dir"));
+    }
+
+    #[test]
+    fn failed_move_restores_target() {
+        let mut server = FakeServer::with(&[("/d/a", false, "new"), ("/d/b", false, "old")]);
+        server.broken_source = Some(("/d/a", 4));
+        assert!(replace_rename(&server, "/d/a", "/d/b").is_err());
+        assert_eq!(server.snapshot(), files("new", "old"));
+    }
+
+    #[test]
+    fn other_errors_do_not_touch_target() {
+        let mut server = FakeServer::with(&[("/d/a", false, "new"), ("/d/b", false, "old")]);
+        server.broken_source = Some(("/d/a", 3));
+        server.atomic = Some(true);
+        assert!(replace_rename(&server, "/d/a", "/d/b").is_err());
+        assert_eq!(server.snapshot(), files("new", "old"));
+
+        let server = FakeServer::with(&[("/d/b", false, "old")]);
+        assert!(replace_rename(&server, "/d/a", "/d/b").is_err());
+        assert_eq!(server.snapshot(), vec![("/d/b".to_string(), "old")]);
+    }
+}
@@ -0,0 +1,646 @@
+//! 연결 가져오기 모듈 - WinSCP/FileZilla/OpenSSH에 저장된 사이트를 연결 프로필로 옮김
+//!
+//! WinSCP는 레지스트리(`HKCU\Software\Martin Prikryl\WinSCP 2\Sessions`)나 이식용 설정
+//! 파일(`WinSCP.ini`)에, FileZilla는 `%APPDATA%\FileZilla\sitemanager.xml`에, OpenSSH는
+//! `%USERPROFILE%\.ssh\config`의 `Host` 항목에 사이트를 저장한다. SFTP/SCP 사이트만
+//! 가져오고, 같은 계정과 원격 경로의 연결이 이미 있으면 건너뛴다. 비밀번호는 요청한
+//! 경우에만 자격 증명 관리자로 옮기며, 마스터 암호로 보호된 비밀번호는 풀 수 없어 빼고
+//! 가져온다.
+
+use crate::credentials;
+use crate::network::base64_decode;
+use crate::storage;
+use crate::types::{AuthType, ImportReport, ImportSource, SshConnection};
+use log::info;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// WinSCP 세션 레지스트리 키 (HKCU 아래)
+const WINSCP_SESSIONS_KEY: &str = r"Software\Martin Prikryl\WinSCP 2\Sessions";
+
+/// WinSCP가 새 세션의 기본값을 보관하는 세션 이름
+const WINSCP_DEFAULT_SESSION: &str = "Default Settings";
+
+/// 가져온 사이트 하나
+struct Site {
+    connection: SshConnection,
+    password: Option<String>,
+}
+
+/// 다른 프로그램의 사이트를 읽어 연결 목록에 추가
+///
+/// `path`가 없으면 WinSCP는 레지스트리, FileZilla는 기본 `sitemanager.xml`에서 읽는다.
+pub fn import(
+    source: ImportSource,
+    path: Option<&Path>,
+    with_passwords: bool,
+) -> Result<ImportReport, String> {
+    let sites = sites(source, path)?;
+
+    // 가져오는 동안 다른 명령이 연결 목록을 바꾸지 않도록 목록을 잡고 추가
+    let report = storage::update_connections(|connections| {
+        let mut report = ImportReport::default();
+        for site in sites {
+            let Site {
+                mut connection,
+                password,
+            } = match site {
+                Ok(site) => site,
+                Err(reason) => {
+                    report.skipped.push(reason);
+                    continue;
+                }
+            };
+            if is_duplicate(connections, &connection) {
+                report.skipped.push(format!(
+                    "{}: 같은 계정과 원격 경로의 연결이 이미 있습니다.",
+                    connection.name
+                ));
+                continue;
+            }
+
+            connection.id = Uuid::new_v4().to_string();
+            if with_passwords && connection.auth_type == AuthType::Password {
+                match password {
+                    Some(password) => credentials::save_password(&connection.id, &password)?,
+                    None => report.notes.push(format!(
+                        "{}: 저장된 비밀번호를 풀 수 없어 비밀번호 없이 가져왔습니다.",
+                        connection.name
+                    )),
+                }
+            }
+            connections.push(connection.clone());
+            report.imported.push(connection);
+        }
+        Ok(report)
+    })?;
+    info!(
+        "[Import] {:?}: {} imported, {} skipped",
+        source,
+        report.imported.len(),
+        report.skipped.len()
+    );
+    Ok(report)
+}
+
+/// 기본 위치에서 새로 가져올 수 있는 사이트 수 (이미 있는 연결과 가져올 수 없는 사이트 제외)
+pub fn importable_count(source: ImportSource) -> Result<usize, String> {
+    let connections = storage::load_connections()?;
+    Ok(sites(source, None)?
+        .into_iter()
+        .flatten()
+        .filter(|site| !is_duplicate(&connections, &site.connection))
+        .count())
+}
+
+fn sites(source: ImportSource, path: Option<&Path>) -> Result<Vec<Result<Site, String>>, String> {
+    match (source, path) {
+        (ImportSource::WinScp, Some(path)) => Ok(winscp_ini(&read(path)?)),
+        (ImportSource::WinScp, None) => winscp_registry(),
+        (ImportSource::FileZilla, path) => {
+            let path = path
+                .map(Path::to_path_buf)
+                .or_else(filezilla_default_path)
+                .ok_or("FileZilla 설정 폴더를 찾을 수 없습니다.")?;
+            filezilla(&read(&path)?)
+        }
+        (ImportSource::SshConfig, path) => {
+            let path = path
+                .map(Path::to_path_buf)
+                .or_else(ssh_config_default_path)
+                .ok_or("사용자 폴더를 찾을 수 없습니다.")?;
+            Ok(ssh_config(&read(&path)?))
+        }
+    }
+}
+
+/// 같은 계정과 원격 경로의 연결이 이미 있는지
+fn is_duplicate(connections: &[SshConnection], connection: &SshConnection) -> bool {
+    connections
+        .iter()
+        .any(|c| c.identity() == connection.identity() && c.remote_path == connection.remote_path)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read(path)
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .map_err(|e| format!("'{}' 읽기 실패: {}", path.display(), e))
+}
+
+/// 사이트 정보로 새 연결 프로필 생성 (나머지 설정은 저장 형식의 기본값)
+fn new_connection(
+    name: &str,
+    host: &str,
+    port: u16,
+    username: &str,
+    key_path: Option<&str>,
+    remote_path: &str,
+    notes: &str,
+) -> Result<SshConnection, String> {
+    let auth_type = if key_path.is_some() {
+        "key"
+    } else {
+        "password"
+    };
+    let remote_path = if remote_path.is_empty() {
+        "/"
+    } else {
+        remote_path
+    };
+    serde_json::from_value(serde_json::json!({
+        "id": "",
+        "name": name,
+        "host": host,
+        "port": port,
+        "username": username,
+        "auth_type": auth_type,
+        "key_path": key_path,
+        "remote_path": remote_path,
+        "notes": Some(notes.trim()).filter(|n| !n.is_empty()),
+    }))
+    .map_err(|e| format!("{}: 연결 정보 변환 실패: {}", name, e))
+}
+
+/// 레지스트리의 WinSCP 세션
+fn winscp_registry() -> Result<Vec<Result<Site, String>>, String> {
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+
+    let sessions = RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey(WINSCP_SESSIONS_KEY)
+        .map_err(|e| format!("WinSCP 세션을 찾을 수 없습니다: {}", e))?;
+    let mut sites = Vec::new();
+    for name in sessions.enum_keys().flatten() {
+        let Ok(key) = sessions.open_subkey(&name) else {
+            continue;
+        };
+        let value = |field: &str| {
+            key.get_value::<String, _>(field)
+                .ok()
+                .or_else(|| key.get_value::<u32, _>(field).ok().map(|n| n.to_string()))
+        };
+        if let Some(site) = winscp_site(&name, value) {
+            sites.push(site);
+        }
+    }
+    Ok(sites)
+}
+
+/// `WinSCP.ini`의 `[Sessions\이름]` 섹션
+fn winscp_ini(content: &str) -> Vec<Result<Site, String>> {
+    let mut sections: Vec<(String, HashMap<String, String>)> = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            if let Some(name) = section.strip_prefix(r"Sessions\") {
+                sections.push((name.to_string(), HashMap::new()));
+            } else {
+                sections.push((String::new(), HashMap::new()));
+            }
+        } else if let (Some((key, value)), Some((name, values))) =
+            (line.split_once('='), sections.last_mut())
+        {
+            if !name.is_empty() {
+                values.insert(key.to_string(), value.to_string());
+            }
+        }
+    }
+    sections
+        .into_iter()
+        .filter(|(name, _)| !name.is_empty())
+        .filter_map(|(name, values)| winscp_site(&name, |field| values.get(field).cloned()))
+        .collect()
+}
+
+/// WinSCP 세션 하나 (기본값 세션이면 None)
+///
+/// 세션 이름과 문자열 값은 `%XX`로 인코딩되어 있다.
+fn winscp_site(
+    encoded_name: &str,
+    value: impl Fn(&str) -> Option<String>,
+) -> Option<Result<Site, String>> {
+    let name = percent_decode(encoded_name);
+    if name == WINSCP_DEFAULT_SESSION {
+        return None;
+    }
+    let text = |field: &str| value(field).map(|v| percent_decode(&v)).unwrap_or_default();
+
+    // 0: SCP, 1: SFTP (SCP로 대체 가능), 2: SFTP, 5 이상: FTP/WebDAV/S3
+    let protocol = value("FSProtocol").and_then(|v| v.parse::<u32>().ok());
+    if protocol.is_some_and(|p| p > 2) {
+        return Some(Err(format!("{}: SFTP/SCP 사이트가 아닙니다.", name)));
+    }
+    let host = text("HostName");
+    if host.is_empty() {
+        return Some(Err(format!("{}: 호스트가 없습니다.", name)));
+    }
+    let port = value("PortNumber")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(22);
+    let username = text("UserName");
+    let key_path = Some(text("PublicKeyFile")).filter(|k| !k.is_empty());
+    let password = value("Password").and_then(|p| winscp_password(&host, &username, &p));
+
+    Some(
+        new_connection(
+            &name,
+            &host,
+            port,
+            &username,
+            key_path.as_deref(),
+            &text("RemoteDirectory"),
+            &text("Note"),
+        )
+        .map(|connection| Site {
+            connection,
+            password,
+        }),
+    )
+}
+
+/// WinSCP의 간단한 비밀번호 난독화 풀기 (마스터 암호로 암호화된 값이면 None)
+fn winscp_password(host: &str, username: &str, encrypted: &str) -> Option<String> {
+    const MAGIC: u8 = 0xA3;
+    const FLAG: u8 = 0xFF;
+
+    let nibbles: Vec<u8> = encrypted
+        .chars()
+        .map(|c| c.to_digit(16).map(|d| d as u8))
+        .collect::<Option<_>>()?;
+    let mut bytes = nibbles
+        .chunks_exact(2)
+        .map(|pair| !((pair[0] << 4 | pair[1]) ^ MAGIC));
+    let flag = bytes.next()?;
+    let length = usize::from(if flag == FLAG {
+        bytes.next()?;
+        bytes.next()?
+    } else {
+        flag
+    });
+    let skip = bytes.next()? as usize;
+    let decoded: Vec<u8> = bytes.skip(skip).take(length).collect();
+    if decoded.len() != length {
+        return None;
+    }
+    let decoded = String::from_utf8(decoded).ok()?;
+    // 새 형식은 사용자 이름과 호스트를 앞에 붙여 저장 (다르면 마스터 암호로 암호화된 값)
+    if flag == FLAG {
+        decoded
+            .strip_prefix(&format!("{}{}", username, host))
+            .map(str::to_string)
+    } else {
+        Some(decoded)
+    }
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = text
+            .get(i + 1..i + 3)
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn filezilla_default_path() -> Option<PathBuf> {
+    std::env::var_os("APPDATA").map(|dir| Path::new(&dir).join("FileZilla").join("sitemanager.xml"))
+}
+
+/// `sitemanager.xml`의 사이트 (폴더 안의 사이트는 `폴더/이름`)
+fn filezilla(content: &str) -> Result<Vec<Result<Site, String>>, String> {
+    // 엔티티 앞뒤 공백이 사라지지 않도록 trim_text는 쓰지 않고 폴더 이름만 다듬음
+    let mut reader = Reader::from_str(content);
+
+    let mut sites = Vec::new();
+    let mut folders: Vec<String> = Vec::new();
+    let mut server: Option<HashMap<String, String>> = None;
+    // 지금 읽는 사이트 항목 (요소 이름, 내용)
+    let mut field: Option<(String, String)> = None;
+    let mut in_folder_name = false;
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("sitemanager.xml 해석 실패: {}", e))?;
+        let text = match &event {
+            Event::Start(e) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+                in_folder_name = false;
+                if let Some(values) = &mut server {
+                    if let Ok(Some(encoding)) = e.try_get_attribute("encoding") {
+                        let encoding = encoding.unescape_value().unwrap_or_default();
+                        values.insert(format!("{}@encoding", name), encoding.into_owned());
+                    }
+                    field = Some((name, String::new()));
+                } else if name == "Folder" {
+                    folders.push(String::new());
+                    in_folder_name = true;
+                } else if name == "Server" {
+                    server = Some(HashMap::new());
+                }
+                None
+            }
+            Event::Text(t) => Some(
+                t.decode()
+                    .map_err(|e| format!("sitemanager.xml 해석 실패: {}", e))?
+                    .into_owned(),
+            ),
+            Event::GeneralRef(r) => {
+                let name = r
+                    .decode()
+                    .map_err(|e| format!("sitemanager.xml 해석 실패: {}", e))?;
+                match r.resolve_char_ref() {
+                    Ok(Some(c)) => Some(c.to_string()),
+                    _ => quick_xml::escape::resolve_predefined_entity(&name).map(str::to_string),
+                }
+            }
+            Event::End(e) => {
+                in_folder_name = false;
+                match e.name().as_ref() {
+                    b"Folder" if server.is_none() => {
+                        folders.pop();
+                    }
+                    b"Server" => {
+                        if let Some(values) = server.take() {
+                            sites.push(filezilla_site(&folders, &values));
+                        }
+                    }
+                    _ => {
+                        if let (Some((name, value)), Some(values)) = (field.take(), &mut server) {
+                            values.insert(name, value);
+                        }
+                    }
+                }
+                None
+            }
+            Event::Eof => break,
+            _ => None,
+        };
+        if let Some(text) = text {
+            if let Some((_, value)) = &mut field {
+                value.push_str(&text);
+            } else if let (true, Some(folder)) = (in_folder_name, folders.last_mut()) {
+                folder.push_str(&text);
+            }
+        }
+    }
+    Ok(sites)
+}
+
+/// FileZilla 사이트 하나
+fn filezilla_site(folders: &[String], values: &HashMap<String, String>) -> Result<Site, String> {
+    let get = |field: &str| values.get(field).map(String::as_str).unwrap_or_default();
+    let name = folders
+        .iter()
+        .map(String::as_str)
+        .chain([get("Name")])
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    // 1: SFTP (0: FTP, 3/4: FTPS 등)
+    if get("Protocol") != "1" {
+        return Err(format!("{}: SFTP 사이트가 아닙니다.", name));
+    }
+    let host = get("Host").trim();
+    if host.is_empty() {
+        return Err(format!("{}: 호스트가 없습니다.", name));
+    }
+    let port = get("Port").trim().parse().unwrap_or(22);
+    // 5: 키 파일 로그인
+    let key_path = Some(get("Keyfile")).filter(|k| get("Logontype") == "5" && !k.is_empty());
+    // 마스터 암호로 보호된 비밀번호는 encoding="crypt"
+    let password = match values.get("Pass@encoding").map(String::as_str) {
+        Some("base64") => base64_decode(get("Pass")).and_then(|p| String::from_utf8(p).ok()),
+        None if !get("Pass").is_empty() => Some(get("Pass").to_string()),
+        _ => None,
+    };
+
+    let connection = new_connection(
+        &name,
+        host,
+        port,
+        get("User"),
+        key_path,
+        &filezilla_remote_dir(get("RemoteDir")),
+        get("Comments"),
+    )?;
+    Ok(Site {
+        connection,
+        password,
+    })
+}
+
+/// FileZilla 원격 경로 (`1 0 4 home 4 user` → `/home/user`)
+///
+/// 서버 종류, 접두어 길이와 접두어, 경로 조각마다 길이(글자 수)와 이름이 공백으로 이어진다.
+fn filezilla_remote_dir(encoded: &str) -> String {
+    let mut rest = encoded;
+    let (Some(_server_type), Some(prefix_len)) = (next_number(&mut rest), next_number(&mut rest))
+    else {
+        return String::new();
+    };
+    if prefix_len > 0 && next_part(&mut rest, prefix_len).is_none() {
+        return String::new();
+    }
+
+    let mut path = String::new();
+    while let Some(len) = next_number(&mut rest) {
+        let Some(part) = next_part(&mut rest, len) else {
+            break;
+        };
+        path.push('/');
+        path.push_str(part);
+    }
+    path
+}
+
+fn next_number(rest: &mut &str) -> Option<usize> {
+    let (number, tail) = rest.split_once(' ').unwrap_or((*rest, ""));
+    let number = number.parse().ok()?;
+    *rest = tail;
+    Some(number)
+}
+
+fn next_part<'a>(rest: &mut &'a str, chars: usize) -> Option<&'a str> {
+    let end = match rest.char_indices().nth(chars) {
+        Some((end, _)) => end,
+        None if rest.chars().count() == chars => rest.len(),
+        None => return None,
+    };
+    let (part, tail) = rest.split_at(end);
+    *rest = tail.strip_prefix(' ').unwrap_or(tail);
+    Some(part)
+}
+
+fn ssh_config_default_path() -> Option<PathBuf> {
+    user_profile().map(|home| home.join(".ssh").join("config"))
+}
+
+fn user_profile() -> Option<PathBuf> {
+    std::env::var_os("USERPROFILE").map(PathBuf::from)
+}
+
+/// `ssh_config`의 `Host` 블록 하나 (키는 소문자, 나온 순서대로)
+struct HostBlock {
+    patterns: Vec<String>,
+    values: Vec<(String, String)>,
+}
+
+impl HostBlock {
+    fn new(patterns: Vec<String>) -> Self {
+        Self {
+            patterns,
+            values: Vec::new(),
+        }
+    }
+}
+
+/// `~/.ssh/config`의 `Host` 항목 (와일드카드 패턴은 다른 항목의 기본값으로만 사용)
+///
+/// OpenSSH처럼 처음 나온 값이 이기므로 파일 끝의 `Host *` 기본값은 앞에서 정하지 않은 값만
+/// 채운다. `Match`와 `Include`는 따라가지 않는다.
+fn ssh_config(content: &str) -> Vec<Result<Site, String>> {
+    // 첫 `Host` 앞의 값은 모든 호스트에 적용
+    let mut blocks = vec![HostBlock::new(vec!["*".to_string()])];
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = match line.split_once(|c: char| c == '=' || c.is_whitespace()) {
+            Some((key, value)) => (key, value.trim_start_matches([' ', '\t', '=']).trim()),
+            None => (line, ""),
+        };
+        let key = key.to_ascii_lowercase();
+        match key.as_str() {
+            "host" => blocks.push(HostBlock::new(
+                value.split_whitespace().map(str::to_string).collect(),
+            )),
+            // Match 조건은 평가하지 않으므로 그 블록의 값은 어디에도 적용하지 않음
+            "match" => blocks.push(HostBlock::new(Vec::new())),
+            _ => {
+                if let Some(block) = blocks.last_mut() {
+                    block
+                        .values
+                        .push((key, value.trim_matches('"').to_string()));
+                }
+            }
+        }
+    }
+
+    let aliases: Vec<&String> = blocks
+        .iter()
+        .flat_map(|block| &block.patterns)
+        .filter(|p| !p.contains(['*', '?', '!']))
+        .collect();
+    let mut seen = Vec::new();
+    let mut sites = Vec::new();
+    for alias in aliases {
+        if seen.contains(&alias) {
+            continue;
+        }
+        seen.push(alias);
+        let value = |field: &str| {
+            blocks
+                .iter()
+                .filter(|block| ssh_host_matches(&block.patterns, alias))
+                .flat_map(|block| &block.values)
+                .find(|(key, _)| key == field)
+                .map(|(_, value)| value.as_str())
+        };
+        let host = value("hostname").unwrap_or(alias).replace("%h", alias);
+        let port = match value("port").map(str::parse::<u16>) {
+            Some(Ok(port)) => port,
+            Some(Err(_)) => {
+                sites.push(Err(format!("{}: 포트가 올바르지 않습니다.", alias)));
+                continue;
+            }
+            None => 22,
+        };
+        let key_path = value("identityfile").map(expand_home);
+        sites.push(
+            new_connection(
+                alias,
+                &host,
+                port,
+                value("user").unwrap_or_default(),
+                key_path.as_deref(),
+                "",
+                "",
+            )
+            .map(|connection| Site {
+                connection,
+                password: None,
+            }),
+        );
+    }
+    sites
+}
+
+/// `Host` 패턴 목록이 별칭에 맞는지 (`!`로 시작하는 패턴에 맞으면 제외)
+fn ssh_host_matches(patterns: &[String], alias: &str) -> bool {
+    let mut matched = false;
+    for pattern in patterns {
+        match pattern.strip_prefix('!') {
+            Some(negated) if glob_matches(negated, alias) => return false,
+            Some(_) => {}
+            None => matched |= glob_matches(pattern, alias),
+        }
+    }
+    matched
+}
+
+/// `*`와 `?`만 있는 간단한 패턴 비교
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c.eq_ignore_ascii_case(&text[t]) => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// `~/`로 시작하는 경로를 사용자 폴더 기준으로
+fn expand_home(path: &str) -> String {
+    match (
+        path.strip_prefix("~/").or_else(|| path.strip_prefix("~\\")),
+        user_profile(),
+    ) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().into_owned(),
+        _ => path.to_string(),
+    }
+}
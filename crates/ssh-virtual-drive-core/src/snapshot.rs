@@ -0,0 +1,187 @@
+//! 스냅샷 마운트 모듈 - 한 시점의 원격 트리 메타데이터 고정
+//!
+//! 스냅샷 프로필로 마운트하면 원격 루트 아래 전체 목록을 한 번 읽어 두고, 이후
+//! stat/readdir은 서버에 묻지 않고 이 목록으로만 답한다. 서버에서 파일이 계속 바뀌어도
+//! 탐색기에 보이는 트리는 그대로라 조사용으로 둘러보기 좋다. 드라이브는 읽기 전용이다.
+//!
+//! 파일 내용은 읽을 때 서버에서 가져오므로 스냅샷 뒤에 바뀐 파일은 새 내용이 보일 수
+//! 있다 (읽기는 스냅샷 크기까지로 자름). 사용자가 새로 고치면 목록을 다시 읽어 통째로
+//! 교체한다.
+//!
+//! 원격 경로가 아카이브 파일이면 ([`crate::archive`]) 서버 디렉토리 대신 아카이브 목록으로
+//! 트리를 만들고, 내용도 아카이브에서 읽는다.
+
+use crate::archive::{self, ArchiveIndex};
+use crate::path_filter::{AccessRules, PathFilter};
+use crate::sftp_client::SharedSftpClient;
+use crate::types::SshConnection;
+use ssh2::FileStat;
+use std::collections::{HashMap, VecDeque};
+
+/// 스냅샷 최대 항목 수 (넘으면 원격 경로를 좁히도록 안내)
+const MAX_SNAPSHOT_ENTRIES: usize = 500_000;
+
+/// 한 시점의 원격 트리 (경로별 stat과 디렉토리별 하위 이름)
+pub struct SnapshotTree {
+    stats: HashMap<String, FileStat>,
+    dirs: HashMap<String, Vec<String>>,
+    // 아카이브 마운트면 내용을 읽을 아카이브 색인
+    archive: Option<ArchiveIndex>,
+}
+
+impl SnapshotTree {
+    /// 원격 루트 아래 전체 목록 읽기 (제외 패턴과 숨김 규칙에 걸리는 하위 트리는 건너뜀)
+    pub fn capture(client: &SharedSftpClient, connection: &SshConnection) -> Result<Self, String> {
+        let root = connection.remote_path.clone();
+        let exclude = PathFilter::new(&connection.exclude_patterns);
+        let access = AccessRules::new(&connection.access_rules);
+
+        let root_stat = client.lock().stat(&root)?;
+        if !root_stat.is_dir() && archive::is_archive_root(client, &root) {
+            return Self::from_archive(client, connection, root_stat);
+        }
+        let mut stats = HashMap::from([(root.clone(), root_stat)]);
+        let mut dirs = HashMap::new();
+        let mut pending = VecDeque::from([root.clone()]);
+
+        while let Some(dir) = pending.pop_front() {
+            // 목록을 읽는 동안 다른 작업도 세션을 쓸 수 있도록 디렉토리마다 lock
+            let entries = client.lock().read_dir(&dir)?;
+            let mut names = Vec::with_capacity(entries.len());
+            for (name, stat) in entries {
+                if name == "." || name == ".." {
+                    continue;
+                }
+                let path = format!("{}/{}", dir.trim_end_matches('/'), name);
+                let relative = path
+                    .strip_prefix(root.trim_end_matches('/'))
+                    .unwrap_or(&path);
+                if exclude.is_excluded(relative) || access.is_hidden(&path) {
+                    continue;
+                }
+                // readdir 속성은 링크를 따라가지 않으므로 링크된 디렉토리로 순환하지 않음
+                if stat.is_dir() {
+                    pending.push_back(path.clone());
+                }
+                stats.insert(path, stat);
+                names.push(name);
+            }
+            dirs.insert(dir, names);
+
+            if stats.len() > MAX_SNAPSHOT_ENTRIES {
+                return Err(format!(
+                    "스냅샷 항목이 너무 많습니다 ({}개 초과). 원격 경로를 좁히거나 제외 패턴을 추가해주세요.",
+                    MAX_SNAPSHOT_ENTRIES
+                ));
+            }
+        }
+
+        Ok(Self {
+            stats,
+            dirs,
+            archive: None,
+        })
+    }
+
+    /// 아카이브 목록으로 트리 구성 (목록에 없는 상위 디렉토리는 만들어 채움)
+    fn from_archive(
+        client: &SharedSftpClient,
+        connection: &SshConnection,
+        archive_stat: FileStat,
+    ) -> Result<Self, String> {
+        let root = connection.remote_path.clone();
+        let exclude = PathFilter::new(&connection.exclude_patterns);
+        let listing = archive::index(client, &root)?;
+        if listing.entries.len() > MAX_SNAPSHOT_ENTRIES {
+            return Err(format!(
+                "아카이브 항목이 너무 많습니다 ({}개 초과).",
+                MAX_SNAPSHOT_ENTRIES
+            ));
+        }
+
+        // 루트는 아카이브 파일 시간을 가진 디렉토리로 보임
+        let dir_stat = |mtime: Option<u64>| FileStat {
+            size: Some(0),
+            uid: None,
+            gid: None,
+            perm: Some(0o040_555),
+            atime: mtime,
+            mtime,
+        };
+        let mut stats = HashMap::from([(root.clone(), dir_stat(archive_stat.mtime))]);
+        let mut dirs: HashMap<String, Vec<String>> = HashMap::from([(root.clone(), Vec::new())]);
+        let base = root.trim_end_matches('/');
+        for (path, stat) in listing.entries {
+            let relative = path.strip_prefix(base).unwrap_or(&path);
+            if exclude.is_excluded(relative) {
+                continue;
+            }
+            if stat.is_dir() {
+                dirs.entry(path.clone()).or_default();
+            }
+            // 같은 이름이 두 번 나오면 뒤의 항목이 이김 (tar에 덧붙인 경우)
+            if stats.insert(path.clone(), stat).is_some() {
+                continue;
+            }
+
+            // 상위 디렉토리를 거슬러 올라가며 목록에 이름 추가
+            let mut child = path;
+            while let Some((parent, name)) = child.rsplit_once('/') {
+                let parent = if parent.len() <= base.len() {
+                    root.clone()
+                } else {
+                    parent.to_string()
+                };
+                let created = !dirs.contains_key(&parent);
+                dirs.entry(parent.clone())
+                    .or_default()
+                    .push(name.to_string());
+                if created {
+                    stats
+                        .entry(parent.clone())
+                        .or_insert_with(|| dir_stat(archive_stat.mtime));
+                }
+                if !created || parent == root {
+                    break;
+                }
+                child = parent;
+            }
+        }
+
+        Ok(Self {
+            stats,
+            dirs,
+            archive: Some(listing.index),
+        })
+    }
+
+    /// 스냅샷 시점의 stat (없으면 그때 없던 경로)
+    pub fn stat(&self, path: &str) -> Option<FileStat> {
+        self.stats.get(path).cloned()
+    }
+
+    /// 스냅샷 시점의 디렉토리 목록
+    pub fn read_dir(&self, path: &str) -> Option<Vec<(String, FileStat)>> {
+        let names = self.dirs.get(path)?;
+        let prefix = path.trim_end_matches('/');
+        Some(
+            names
+                .iter()
+                .filter_map(|name| {
+                    let stat = self.stats.get(&format!("{}/{}", prefix, name))?;
+                    Some((name.clone(), stat.clone()))
+                })
+                .collect(),
+        )
+    }
+
+    /// 아카이브 마운트면 내용을 읽을 아카이브 색인
+    pub fn archive(&self) -> Option<&ArchiveIndex> {
+        self.archive.as_ref()
+    }
+
+    /// 항목 수 (루트 포함)
+    pub fn entry_count(&self) -> usize {
+        self.stats.len()
+    }
+}
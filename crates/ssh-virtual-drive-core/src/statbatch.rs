@@ -0,0 +1,124 @@
+//! stat 묶음 처리 모듈 - 같은 디렉토리 항목의 연속된 stat을 readdir 한 번으로 처리
+//!
+//! 속성 창을 열거나 크기로 정렬하면 탐색기가 형제 항목마다 stat을 보내고, 단일 SFTP
+//! 세션에서는 이것들이 차례로 왕복한다. 짧은 시간 안에 같은 상위 디렉토리에서 캐시
+//! 미스가 몰리면 그 디렉토리를 한 번 읽어 하위 항목 stat을 모두 캐시에 채우고,
+//! 그동안 도착한 같은 디렉토리의 stat은 목록이 올 때까지 기다렸다가 캐시에서 답한다.
+
+use crate::cache::{parent_path, StatCache};
+use crate::stats::StatsCollector;
+use crate::streams::StreamPool;
+use log::debug;
+use parking_lot::{Condvar, Mutex};
+use ssh2::FileStat;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 미스를 같은 묶음으로 셀 시간 범위
+const BATCH_WINDOW: Duration = Duration::from_millis(250);
+
+/// 이 수만큼 형제 항목 미스가 몰리면 디렉토리 전체를 읽음
+const BATCH_THRESHOLD: u32 = 4;
+
+/// 추적할 최대 디렉토리 수 (넘으면 지난 기록 정리)
+const MAX_TRACKED_DIRS: usize = 256;
+
+#[derive(Default)]
+struct DirState {
+    window_start: Option<Instant>,
+    misses: u32,
+    loading: bool,
+}
+
+/// 디렉토리별 stat 미스 기록과 진행 중인 묶음 읽기
+pub struct StatBatcher {
+    dirs: Mutex<HashMap<String, DirState>>,
+    loaded: Condvar,
+    stats: Arc<StatsCollector>,
+}
+
+impl StatBatcher {
+    pub fn new(stats: Arc<StatsCollector>) -> Self {
+        Self {
+            dirs: Mutex::new(HashMap::new()),
+            loaded: Condvar::new(),
+            stats,
+        }
+    }
+
+    /// 캐시에 없는 경로의 stat (필요하면 상위 디렉토리를 한 번에 읽음)
+    pub fn stat(
+        &self,
+        path: &str,
+        cache: &StatCache,
+        streams: &StreamPool,
+    ) -> Result<FileStat, String> {
+        let parent = parent_path(path);
+        if parent == path {
+            return streams.metadata().stat(path);
+        }
+
+        let mut dirs = self.dirs.lock();
+        let state = dirs.entry(parent.to_string()).or_default();
+        if state.loading {
+            // 다른 요청이 이미 목록을 읽는 중이면 끝날 때까지 기다렸다가 캐시에서 답함
+            while dirs.get(parent).is_some_and(|s| s.loading) {
+                self.loaded.wait(&mut dirs);
+            }
+            drop(dirs);
+            if let Some(stat) = cache.get_stat(path) {
+                self.stats.record_batched_stat();
+                return Ok(stat);
+            }
+            return streams.metadata().stat(path);
+        }
+
+        let now = Instant::now();
+        match state.window_start {
+            Some(start) if now.duration_since(start) <= BATCH_WINDOW => state.misses += 1,
+            _ => {
+                state.window_start = Some(now);
+                state.misses = 1;
+            }
+        }
+        if state.misses < BATCH_THRESHOLD {
+            if dirs.len() > MAX_TRACKED_DIRS {
+                dirs.retain(|_, s| {
+                    s.loading || s.window_start.is_some_and(|t| t.elapsed() <= BATCH_WINDOW)
+                });
+            }
+            drop(dirs);
+            return streams.metadata().stat(path);
+        }
+
+        // 이 요청이 디렉토리 전체를 읽음
+        state.loading = true;
+        drop(dirs);
+        let t0 = Instant::now();
+        let listed = streams.metadata().read_dir(parent);
+        if let Ok(entries) = &listed {
+            cache.insert_dir(parent, entries);
+            debug!(
+                "[StatBatch] '{}' listed for stat burst ({} entries, {}ms)",
+                parent,
+                entries.len(),
+                t0.elapsed().as_millis()
+            );
+        }
+        self.dirs.lock().remove(parent);
+        self.loaded.notify_all();
+
+        let name = path[parent.len()..].trim_start_matches('/');
+        match listed {
+            Ok(entries) => match entries.into_iter().find(|(n, _)| n == name) {
+                Some((_, stat)) => {
+                    self.stats.record_batched_stat();
+                    Ok(stat)
+                }
+                None => streams.metadata().stat(path),
+            },
+            Err(_) => streams.metadata().stat(path),
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! 마운트 통계 수집 모듈 - 캐시 적중률 등 파일시스템 동작 지표
+
+use crate::process::Caller;
+use crate::types::{FsActivity, FsOp, MountStats, ProcessStats, SftpCapabilities, UploadProgress};
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// 지연 시간 이동 평균에서 새 측정값의 비중 (1/N)
+const LATENCY_SMOOTHING: u64 = 8;
+
+/// 전달 전에 모아 둘 최대 활동 수 (넘으면 오래된 것부터 버림)
+const ACTIVITY_BUFFER_LIMIT: usize = 200;
+
+/// 언마운트 확인에 보여 줄 최근 작업 파일 수
+const RECENT_FILES_LIMIT: usize = 10;
+
+/// 마운트별 통계 수집기 (파일시스템과 MountManager가 공유)
+pub struct StatsCollector {
+    // 마지막 파일시스템 작업 시각 (생성 시각 기준 밀리초)
+    created_at: Instant,
+    last_activity_ms: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    cache_evictions: AtomicU64,
+    batched_stats: AtomicU64,
+    preview_reads_denied: AtomicU64,
+    uploads: Mutex<HashMap<String, UploadProgress>>,
+    // 아직 프론트엔드에 보내지 않은 작업 (drive_letter는 꺼낼 때 채움)
+    activity: Mutex<VecDeque<FsActivity>>,
+    // 최근에 작업한 파일 경로 (최근 것부터, 활동 전달과 무관하게 유지)
+    recent_files: Mutex<VecDeque<String>>,
+    // 애플리케이션이 열어 둔 파일 핸들 수 (디렉토리 제외)
+    open_file_handles: AtomicU64,
+    // 실행 파일 이름별 접근 통계
+    processes: Mutex<HashMap<String, ProcessStats>>,
+    // 마지막으로 성공한 원격 작업 시각 (유닉스 시간 초, 0이면 없음)
+    last_success_at: AtomicU64,
+    // 원격 작업 지연 시간 이동 평균 (마이크로초, 0이면 측정 전)
+    latency_avg_us: AtomicU64,
+    last_error: Mutex<Option<String>>,
+}
+
+impl Default for StatsCollector {
+    fn default() -> Self {
+        Self {
+            created_at: Instant::now(),
+            last_activity_ms: AtomicU64::new(0),
+            cache_hits: AtomicU64::default(),
+            cache_misses: AtomicU64::default(),
+            cache_evictions: AtomicU64::default(),
+            batched_stats: AtomicU64::default(),
+            preview_reads_denied: AtomicU64::default(),
+            uploads: Mutex::default(),
+            activity: Mutex::default(),
+            recent_files: Mutex::default(),
+            open_file_handles: AtomicU64::default(),
+            processes: Mutex::default(),
+            last_success_at: AtomicU64::default(),
+            latency_avg_us: AtomicU64::default(),
+            last_error: Mutex::default(),
+        }
+    }
+}
+
+impl StatsCollector {
+    /// 사용자 파일시스템 작업 기록 (자동 언마운트 판단용)
+    pub fn record_activity(&self) {
+        let elapsed = self.created_at.elapsed().as_millis() as u64;
+        self.last_activity_ms.fetch_max(elapsed, Ordering::Relaxed);
+    }
+
+    /// 마지막 작업 이후 경과 시간
+    pub fn idle_for(&self) -> Duration {
+        let last = Duration::from_millis(self.last_activity_ms.load(Ordering::Relaxed));
+        self.created_at.elapsed().saturating_sub(last)
+    }
+
+    /// 파일 핸들 열림/닫힘 기록 (언마운트 전 확인용)
+    pub fn record_file_opened(&self) {
+        self.open_file_handles.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_file_closed(&self) {
+        let _ = self
+            .open_file_handles
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+    }
+
+    /// 열려 있는 파일 핸들 수
+    pub fn open_file_handles(&self) -> u64 {
+        self.open_file_handles.load(Ordering::Relaxed)
+    }
+
+    /// 최근에 작업한 파일 (최근 것부터)
+    pub fn recent_files(&self) -> Vec<String> {
+        self.recent_files.lock().iter().cloned().collect()
+    }
+
+    /// 원격 작업 성공 기록 (지연 시간 평균 갱신)
+    pub fn record_success(&self, duration: Duration) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        self.last_success_at.store(now, Ordering::Relaxed);
+
+        let sample = (duration.as_micros() as u64).max(1);
+        let _ = self
+            .latency_avg_us
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |avg| {
+                Some(if avg == 0 {
+                    sample
+                } else {
+                    avg - avg / LATENCY_SMOOTHING + sample / LATENCY_SMOOTHING
+                })
+            });
+    }
+
+    /// 원격 작업 실패 기록 (드라이브 상태에 마지막 오류로 표시)
+    pub fn record_error(&self, error: &str) {
+        *self.last_error.lock() = Some(error.to_string());
+    }
+
+    /// 마지막 성공 시각 (유닉스 시간 초)
+    pub fn last_success_at(&self) -> Option<u64> {
+        Some(self.last_success_at.load(Ordering::Relaxed)).filter(|&t| t > 0)
+    }
+
+    /// 원격 작업 지연 시간 이동 평균 (밀리초)
+    pub fn average_latency_ms(&self) -> Option<f64> {
+        let avg = self.latency_avg_us.load(Ordering::Relaxed);
+        (avg > 0).then(|| avg as f64 / 1000.0)
+    }
+
+    /// 마지막 원격 작업 오류
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error.lock().clone()
+    }
+
+    pub fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_cache_miss(&self) {
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_cache_evictions(&self, count: u64) {
+        self.cache_evictions.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn record_batched_stat(&self) {
+        self.batched_stats.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_preview_denied(&self) {
+        self.preview_reads_denied.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_upload_progress(&self, path: &str, confirmed_bytes: u64, pending_bytes: u64) {
+        self.uploads.lock().insert(
+            path.to_string(),
+            UploadProgress {
+                path: path.to_string(),
+                confirmed_bytes,
+                pending_bytes,
+            },
+        );
+    }
+
+    pub fn finish_upload(&self, path: &str) {
+        self.uploads.lock().remove(path);
+    }
+
+    /// 서버에 올리는 중인 쓰기가 있는지
+    pub fn is_uploading(&self) -> bool {
+        !self.uploads.lock().is_empty()
+    }
+
+    /// 파일시스템 작업 기록 (직전 항목과 경로/종류/프로세스가 같으면 합침)
+    ///
+    /// `caller`는 핸들을 열 때 확인한 프로세스 (읽기/쓰기 중에는 WinFsp가 알려 주지 않음)
+    pub fn record_op(&self, caller: &Caller, path: &str, op: FsOp, bytes: u64, duration: Duration) {
+        self.record_success(duration);
+        {
+            let mut processes = self.processes.lock();
+            let stats = Self::process_entry(&mut processes, caller);
+            stats.operations += 1;
+            match op {
+                FsOp::Read => stats.bytes_read += bytes,
+                FsOp::Write => stats.bytes_written += bytes,
+                _ => {}
+            }
+        }
+
+        // 디렉토리 목록은 탐색만으로도 생기므로 최근 파일에 넣지 않음
+        if op != FsOp::List {
+            let mut recent = self.recent_files.lock();
+            recent.retain(|p| p != path);
+            recent.push_front(path.to_string());
+            recent.truncate(RECENT_FILES_LIMIT);
+        }
+
+        let duration_ms = duration.as_millis() as u64;
+        let mut activity = self.activity.lock();
+        if let Some(last) = activity.back_mut() {
+            if last.op == op && last.path == path && last.pid == caller.pid {
+                last.bytes += bytes;
+                last.duration_ms += duration_ms;
+                last.count += 1;
+                return;
+            }
+        }
+        if activity.len() >= ACTIVITY_BUFFER_LIMIT {
+            activity.pop_front();
+        }
+        activity.push_back(FsActivity {
+            drive_letter: '\0',
+            path: path.to_string(),
+            op,
+            pid: caller.pid,
+            process: caller.name.clone(),
+            bytes,
+            duration_ms,
+            count: 1,
+        });
+    }
+
+    /// 차단 목록 때문에 거부한 읽기 기록
+    pub fn record_blocked_read(&self, caller: &Caller) {
+        let mut processes = self.processes.lock();
+        Self::process_entry(&mut processes, caller).blocked_reads += 1;
+    }
+
+    fn process_entry<'a>(
+        processes: &'a mut HashMap<String, ProcessStats>,
+        caller: &Caller,
+    ) -> &'a mut ProcessStats {
+        let name = if caller.name.is_empty() {
+            format!("PID {}", caller.pid)
+        } else {
+            caller.name.clone()
+        };
+        processes
+            .entry(name.clone())
+            .or_insert_with(|| ProcessStats {
+                name,
+                ..Default::default()
+            })
+    }
+
+    /// 모아 둔 작업을 꺼냄
+    pub fn take_activity(&self, drive_letter: char) -> Vec<FsActivity> {
+        let mut activity: Vec<FsActivity> = self.activity.lock().drain(..).collect();
+        for entry in activity.iter_mut() {
+            entry.drive_letter = drive_letter;
+        }
+        activity
+    }
+
+    /// 현재 카운터 값을 프론트엔드 전달용 구조체로 복사
+    pub fn snapshot(
+        &self,
+        drive_letter: char,
+        connection_id: &str,
+        capabilities: SftpCapabilities,
+    ) -> MountStats {
+        MountStats {
+            drive_letter,
+            connection_id: connection_id.to_string(),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            cache_misses: self.cache_misses.load(Ordering::Relaxed),
+            cache_evictions: self.cache_evictions.load(Ordering::Relaxed),
+            batched_stats: self.batched_stats.load(Ordering::Relaxed),
+            preview_reads_denied: self.preview_reads_denied.load(Ordering::Relaxed),
+            uploads: self.uploads.lock().values().cloned().collect(),
+            capabilities,
+            processes: {
+                let mut processes: Vec<ProcessStats> =
+                    self.processes.lock().values().cloned().collect();
+                processes.sort_by_key(|p| std::cmp::Reverse(p.operations));
+                processes
+            },
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use crate::types::{
+    Bookmark, ConnectionTemplate, DeletedConnection, SshConnection, StoredKey, TransferItem,
+};
+use directories::ProjectDirs;
+use parking_lot::Mutex;
+use std::fs;
+use std::path::PathBuf;
+
+const APP_NAME: &str = "ssh-virtual-drive";
+
+/// 연결 목록을 읽고 고쳐 쓰는 동안 잡는 잠금 (명령이 동시에 실행되어도 변경을 잃지 않도록)
+static CONNECTIONS_LOCK: Mutex<()> = Mutex::new(());
+
+/// 앱 데이터 디렉토리 경로 반환
+pub fn get_data_dir() -> Option<PathBuf> {
+    ProjectDirs::from("com", "sshvirtualdrive", APP_NAME).map(|dirs| dirs.data_dir().to_path_buf())
+}
+
+/// 연결 목록 파일 경로
+fn get_connections_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("connections.json"))
+}
+
+/// 저장된 연결 목록 로드
+pub fn load_connections() -> Result<Vec<SshConnection>, String> {
+    let file_path =
+        get_connections_file().ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+
+    if !file_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&file_path).map_err(|e| format!("파일 읽기 실패: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("JSON 파싱 실패: {}", e))
+}
+
+/// 연결 목록 저장
+pub fn save_connections(connections: &[SshConnection]) -> Result<(), String> {
+    let file_path =
+        get_connections_file().ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+
+    // 디렉토리가 없으면 생성
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("디렉토리 생성 실패: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(connections)
+        .map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+
+    fs::write(&file_path, content).map_err(|e| format!("파일 저장 실패: {}", e))
+}
+
+/// 연결 목록을 잠근 채 읽어 고친 뒤 저장 (`update`가 실패하면 저장하지 않음)
+pub fn update_connections<T>(
+    update: impl FnOnce(&mut Vec<SshConnection>) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = CONNECTIONS_LOCK.lock();
+    let mut connections = load_connections()?;
+    let result = update(&mut connections)?;
+    save_connections(&connections)?;
+    Ok(result)
+}
+
+/// ID로 특정 연결 찾기
+pub fn get_connection_by_id(id: &str) -> Result<Option<SshConnection>, String> {
+    let connections = load_connections()?;
+    Ok(connections.into_iter().find(|c| c.id == id))
+}
+
+/// 삭제한 연결 보관 목록 파일 경로
+fn get_deleted_connections_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("deleted_connections.json"))
+}
+
+/// 삭제한 연결 보관 목록 로드
+pub fn load_deleted_connections() -> Result<Vec<DeletedConnection>, String> {
+    let file_path = get_deleted_connections_file()
+        .ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+
+    if !file_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&file_path).map_err(|e| format!("파일 읽기 실패: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("JSON 파싱 실패: {}", e))
+}
+
+/// 삭제한 연결 보관 목록 저장
+pub fn save_deleted_connections(deleted: &[DeletedConnection]) -> Result<(), String> {
+    let file_path = get_deleted_connections_file()
+        .ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("디렉토리 생성 실패: {}", e))?;
+    }
+
+    let content =
+        serde_json::to_string_pretty(deleted).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+
+    fs::write(&file_path, content).map_err(|e| format!("파일 저장 실패: {}", e))
+}
+
+/// 전송 대기열 파일 경로
+fn get_transfers_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("transfers.json"))
+}
+
+/// 저장된 전송 대기열 로드
+pub fn load_transfers() -> Result<Vec<TransferItem>, String> {
+    let file_path =
+        get_transfers_file().ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+
+    if !file_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&file_path).map_err(|e| format!("파일 읽기 실패: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("JSON 파싱 실패: {}", e))
+}
+
+/// 전송 대기열 저장
+pub fn save_transfers(transfers: &[TransferItem]) -> Result<(), String> {
+    let file_path =
+        get_transfers_file().ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("디렉토리 생성 실패: {}", e))?;
+    }
+
+    let content =
+        serde_json::to_string_pretty(transfers).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+
+    fs::write(&file_path, content).map_err(|e| format!("파일 저장 실패: {}", e))
+}
+
+/// 키 저장소 목록 파일 경로
+fn get_stored_keys_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("keys").join("keys.json"))
+}
+
+/// 키 저장소 목록 로드
+pub fn load_stored_keys() -> Result<Vec<StoredKey>, String> {
+    let file_path =
+        get_stored_keys_file().ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+
+    if !file_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&file_path).map_err(|e| format!("파일 읽기 실패: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("JSON 파싱 실패: {}", e))
+}
+
+/// 키 저장소 목록 저장
+pub fn save_stored_keys(keys: &[StoredKey]) -> Result<(), String> {
+    let file_path =
+        get_stored_keys_file().ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("디렉토리 생성 실패: {}", e))?;
+    }
+
+    let content =
+        serde_json::to_string_pretty(keys).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+
+    fs::write(&file_path, content).map_err(|e| format!("파일 저장 실패: {}", e))
+}
+
+/// 연결 템플릿 파일 경로
+fn get_templates_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("templates.json"))
+}
+
+/// 저장된 연결 템플릿 로드
+pub fn load_templates() -> Result<Vec<ConnectionTemplate>, String> {
+    let file_path =
+        get_templates_file().ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+
+    if !file_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&file_path).map_err(|e| format!("파일 읽기 실패: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("JSON 파싱 실패: {}", e))
+}
+
+/// 연결 템플릿 저장
+pub fn save_templates(templates: &[ConnectionTemplate]) -> Result<(), String> {
+    let file_path =
+        get_templates_file().ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("디렉토리 생성 실패: {}", e))?;
+    }
+
+    let content =
+        serde_json::to_string_pretty(templates).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+
+    fs::write(&file_path, content).map_err(|e| format!("파일 저장 실패: {}", e))
+}
+
+/// 원격 경로 북마크 파일 경로
+fn get_bookmarks_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("bookmarks.json"))
+}
+
+/// 저장된 북마크 로드
+pub fn load_bookmarks() -> Result<Vec<Bookmark>, String> {
+    let file_path =
+        get_bookmarks_file().ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+
+    if !file_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&file_path).map_err(|e| format!("파일 읽기 실패: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("JSON 파싱 실패: {}", e))
+}
+
+/// 북마크 저장
+pub fn save_bookmarks(bookmarks: &[Bookmark]) -> Result<(), String> {
+    let file_path =
+        get_bookmarks_file().ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("디렉토리 생성 실패: {}", e))?;
+    }
+
+    let content =
+        serde_json::to_string_pretty(bookmarks).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+
+    fs::write(&file_path, content).map_err(|e| format!("파일 저장 실패: {}", e))
+}
+
+/// 연결별 메타데이터 캐시 스냅샷 파일 경로
+pub fn get_cache_snapshot_file(connection_id: &str) -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("cache").join(format!("{}.json.gz", connection_id)))
+}
+
+/// 연결별 오프라인 고정 파일 복사본 디렉토리 경로
+pub fn get_offline_dir(connection_id: &str) -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("offline").join(connection_id))
+}
+
+/// 업데이트 설치 후 다시 마운트할 드라이브 목록 파일 경로
+pub fn get_pending_mounts_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("pending_mounts.json"))
+}
+
+/// 로그 설정 파일 경로
+pub fn get_log_settings_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("logging.json"))
+}
+
+/// 백그라운드 서비스 드라이브 목록 파일 경로 (서비스 계정의 데이터 디렉토리)
+pub fn get_service_mounts_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("service_mounts.json"))
+}
+
+/// 메모리 설정 파일 경로
+pub fn get_memory_settings_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("memory.json"))
+}
+
+/// 자원 한도 설정 파일 경로
+pub fn get_resource_limits_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("limits.json"))
+}
+
+/// 앱 설정 파일 경로
+pub fn get_app_settings_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("settings.json"))
+}
+
+/// 연결별 상태 기록(지연 시간 측정) 파일 경로
+pub fn get_health_history_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("health.json"))
+}
+
+/// 연결 프로필 동기화 설정 파일 경로
+pub fn get_profile_sync_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("profile_sync.json"))
+}
+
+/// 마지막으로 동기화한 연결 목록 파일 경로 (양쪽 변경을 가려내는 기준)
+pub fn get_profile_sync_base_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("profile_sync_base.json"))
+}
+
+/// 연결별 전송량 기록 파일 경로
+pub fn get_traffic_file() -> Option<PathBuf> {
+    get_data_dir().map(|dir| dir.join("traffic.json"))
+}
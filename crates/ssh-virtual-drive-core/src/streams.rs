@@ -0,0 +1,397 @@
+//! 다중 스트림 전송 모듈 - 큰 읽기/쓰기를 여러 SFTP 세션에 나눠 병렬 처리
+//!
+//! 지연 시간이 큰 링크에서는 단일 SFTP 채널의 처리량이 회선 속도에 못 미친다.
+//! lftp의 pget처럼 큰 범위를 여러 세션에 나눠 동시에 전송한 뒤 다시 합친다.
+//!
+//! 메타데이터 작업(stat/readdir/open 등)은 대량 데이터 전송보다 먼저 처리한다.
+//! 보조 세션을 쓸 수 있으면 작은 데이터 요청도 보조 세션으로 보내 주 세션을 메타데이터에
+//! 비워 두고, 주 세션을 함께 써야 하면 대기 중인 메타데이터 작업이 끝날 때까지 양보한다.
+//!
+//! 마운트마다 진행 중인 데이터 요청의 버퍼 크기 합에 한도를 둔다. 한도를 넘는 요청은
+//! 앞선 요청이 끝날 때까지 줄을 서서 기다리므로, 큰 병렬 읽기가 몰려도 메모리가
+//! 요청 수만큼 늘어나지 않는다. 취소된 읽기는 기다리던 중이라도 바로 빠진다.
+
+use crate::hooks::{run_hook, HookEvent};
+use crate::sftp_client::{CancelToken, SftpClient, SharedSftpClient};
+use log::{debug, warn};
+use parking_lot::{Condvar, Mutex, MutexGuard};
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 이 크기 이상인 요청만 병렬로 나눔
+const PARALLEL_THRESHOLD: usize = 1024 * 1024;
+
+/// 스트림 하나가 맡는 최소 크기
+const MIN_PART_SIZE: usize = 256 * 1024;
+
+/// 연결당 최대 스트림 수
+pub const MAX_TRANSFER_STREAMS: u8 = 8;
+
+/// 데이터 전송이 메타데이터 작업에 양보하는 최대 시간 (계속 밀리지 않도록)
+const MAX_METADATA_YIELD: Duration = Duration::from_millis(200);
+
+/// 진행 중인 전송 버퍼 기본 한도 (MB)
+pub const DEFAULT_INFLIGHT_LIMIT_MB: u32 = 64;
+
+/// 한도를 기다리는 동안 취소 여부를 확인하는 간격
+const BUDGET_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 보조 세션 연결에 실패한 뒤 다시 시도하기까지 기다리는 시간 (그동안은 주 세션만 사용)
+const SIBLING_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// 진행 중인 전송 버퍼 크기 한도
+struct ByteBudget {
+    limit: usize,
+    in_use: Mutex<usize>,
+    released: Condvar,
+}
+
+/// 예약한 버퍼 크기 (drop 되면 반환)
+struct BudgetGuard<'a> {
+    budget: &'a ByteBudget,
+    bytes: usize,
+}
+
+impl ByteBudget {
+    fn new(limit_mb: u32) -> Self {
+        Self {
+            limit: (limit_mb.max(1) as usize) * 1024 * 1024,
+            in_use: Mutex::new(0),
+            released: Condvar::new(),
+        }
+    }
+
+    /// 자리가 날 때까지 기다렸다가 예약 (한도보다 큰 요청은 혼자 진행할 수 있을 때 시작)
+    fn acquire(&self, bytes: usize, cancel: &CancelToken) -> Result<BudgetGuard<'_>, String> {
+        let bytes = bytes.min(self.limit);
+        let started = Instant::now();
+        let mut in_use = self.in_use.lock();
+        while *in_use + bytes > self.limit {
+            cancel.check()?;
+            self.released.wait_for(&mut in_use, BUDGET_POLL_INTERVAL);
+        }
+        *in_use += bytes;
+        drop(in_use);
+
+        let waited = started.elapsed();
+        if waited >= BUDGET_POLL_INTERVAL {
+            debug!(
+                "[Streams] waited {}ms for {} bytes of transfer budget",
+                waited.as_millis(),
+                bytes
+            );
+        }
+        Ok(BudgetGuard {
+            budget: self,
+            bytes,
+        })
+    }
+}
+
+impl Drop for BudgetGuard<'_> {
+    fn drop(&mut self) {
+        *self.budget.in_use.lock() -= self.bytes;
+        self.budget.released.notify_all();
+    }
+}
+
+/// 주 세션 + 필요할 때 여는 보조 세션 묶음
+pub struct StreamPool {
+    primary: SharedSftpClient,
+    extra: Mutex<Vec<SharedSftpClient>>,
+    // 마지막으로 보조 세션 연결에 실패한 시각
+    extra_failed_at: Mutex<Option<Instant>>,
+    stream_count: usize,
+    // 주 세션을 새로 연결한 횟수 (파일 핸들 세대로 사용)
+    generation: AtomicU32,
+    // 주 세션을 기다리거나 쓰는 중인 메타데이터 작업 수
+    metadata_waiting: AtomicUsize,
+    metadata_lock: Mutex<()>,
+    metadata_idle: Condvar,
+    // 진행 중인 읽기/쓰기 버퍼 크기 한도
+    budget: ByteBudget,
+    // 연결 끊김 훅에 넘길 드라이브 문자
+    drive_letter: char,
+}
+
+/// 메타데이터 작업용 주 세션 (잡고 있는 동안 데이터 전송이 주 세션을 양보함)
+pub struct MetadataGuard<'a> {
+    pool: &'a StreamPool,
+    client: MutexGuard<'a, SftpClient>,
+}
+
+impl Deref for MetadataGuard<'_> {
+    type Target = SftpClient;
+
+    fn deref(&self) -> &SftpClient {
+        &self.client
+    }
+}
+
+impl Drop for MetadataGuard<'_> {
+    fn drop(&mut self) {
+        self.pool.metadata_waiting.fetch_sub(1, Ordering::Relaxed);
+        let _lock = self.pool.metadata_lock.lock();
+        self.pool.metadata_idle.notify_all();
+    }
+}
+
+impl StreamPool {
+    pub fn new(
+        primary: SharedSftpClient,
+        drive_letter: char,
+        stream_count: u8,
+        inflight_limit_mb: u32,
+    ) -> Self {
+        Self {
+            primary,
+            extra: Mutex::new(Vec::new()),
+            extra_failed_at: Mutex::new(None),
+            stream_count: stream_count.clamp(1, MAX_TRANSFER_STREAMS) as usize,
+            generation: AtomicU32::new(0),
+            metadata_waiting: AtomicUsize::new(0),
+            metadata_lock: Mutex::new(()),
+            metadata_idle: Condvar::new(),
+            budget: ByteBudget::new(inflight_limit_mb),
+            drive_letter,
+        }
+    }
+
+    /// 메타데이터 작업용으로 주 세션 잡기 (대량 전송보다 우선)
+    pub fn metadata(&self) -> MetadataGuard<'_> {
+        self.metadata_waiting.fetch_add(1, Ordering::Relaxed);
+        MetadataGuard {
+            pool: self,
+            client: self.primary.lock(),
+        }
+    }
+
+    /// 대기 중인 메타데이터 작업이 있으면 끝날 때까지 잠시 양보
+    fn yield_to_metadata(&self) {
+        let deadline = Instant::now() + MAX_METADATA_YIELD;
+        let mut lock = self.metadata_lock.lock();
+        while self.metadata_waiting.load(Ordering::Relaxed) > 0 {
+            if self
+                .metadata_idle
+                .wait_until(&mut lock, deadline)
+                .timed_out()
+            {
+                break;
+            }
+        }
+    }
+
+    /// 나누지 않는 데이터 요청 (보조 세션이 있으면 그쪽, 없으면 양보 후 주 세션)
+    fn bulk<T>(&self, op: impl FnOnce(&SftpClient) -> T) -> T {
+        if self.stream_count > 1 {
+            if let Some(session) = self.sessions(2).get(1) {
+                return op(&session.lock());
+            }
+        }
+        self.yield_to_metadata();
+        op(&self.primary.lock())
+    }
+
+    /// 사용할 세션 목록 (보조 세션은 처음 필요할 때 연결, 실패하면 있는 만큼만 사용)
+    ///
+    /// 연결은 세션 lock 없이 하므로 그동안에도 메타데이터 작업이 진행된다. 연결에 실패하면
+    /// 잠시 다시 시도하지 않고 주 세션으로만 전송한다.
+    fn sessions(&self, wanted: usize) -> Vec<SharedSftpClient> {
+        let mut sessions = vec![self.primary.clone()];
+        if wanted <= 1 {
+            return sessions;
+        }
+
+        let missing = (wanted - 1).saturating_sub(self.extra.lock().len());
+        let retry_blocked = self
+            .extra_failed_at
+            .lock()
+            .is_some_and(|at| at.elapsed() < SIBLING_RETRY_DELAY);
+        if missing > 0 && !retry_blocked {
+            let generation = self.generation();
+            let connector = self.primary.lock().sibling_connector();
+            let mut opened = Vec::new();
+            for _ in 0..missing {
+                match connector.connect() {
+                    Ok(client) => opened.push(Arc::new(Mutex::new(client))),
+                    Err(e) => {
+                        warn!("[Streams] extra SFTP session unavailable: {}", e);
+                        *self.extra_failed_at.lock() = Some(Instant::now());
+                        break;
+                    }
+                }
+            }
+            let mut extra = self.extra.lock();
+            // 연결하는 동안 재연결이 있었으면 버림 (보조 세션은 새 세대에 다시 연결)
+            if self.generation() == generation {
+                extra.extend(opened);
+                extra.truncate(self.stream_count - 1);
+            }
+        }
+        sessions.extend(self.extra.lock().iter().take(wanted - 1).cloned());
+        sessions
+    }
+
+    /// 요청 길이에 맞는 분할 개수
+    fn parts_for(&self, len: usize) -> usize {
+        if self.stream_count <= 1 || len < PARALLEL_THRESHOLD {
+            1
+        } else {
+            self.stream_count.min(len / MIN_PART_SIZE).max(1)
+        }
+    }
+
+    /// 범위 읽기 (크면 여러 세션에 나눠 읽고 이어붙임)
+    ///
+    /// 취소되면 아직 받지 않은 조각은 요청하지 않고 `READ_CANCELLED` 오류를 반환한다.
+    pub fn read_range(
+        &self,
+        path: &str,
+        offset: u64,
+        len: usize,
+        cancel: &CancelToken,
+    ) -> Result<Vec<u8>, String> {
+        let _budget = self.budget.acquire(len, cancel)?;
+        let parts = self.parts_for(len);
+        if parts == 1 {
+            return self.bulk(|client| client.read_file_range(path, offset, len, cancel));
+        }
+
+        self.yield_to_metadata();
+        let sessions = self.sessions(parts);
+        let ranges = split_range(offset, len, sessions.len());
+        let results: Vec<Result<Vec<u8>, String>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = sessions
+                .iter()
+                .zip(&ranges)
+                .map(|(session, &(part_offset, part_len))| {
+                    scope.spawn(move || {
+                        session
+                            .lock()
+                            .read_file_range_full(path, part_offset, part_len, cancel)
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| {
+                    h.join()
+                        .unwrap_or_else(|_| Err("전송 스레드 실패".to_string()))
+                })
+                .collect()
+        });
+
+        // 앞 조각이 짧으면 그 지점이 EOF이므로 뒤 조각은 버림
+        let mut data = Vec::with_capacity(len);
+        for ((_, part_len), result) in ranges.iter().zip(results) {
+            let part = result?;
+            let short = part.len() < *part_len;
+            data.extend_from_slice(&part);
+            if short {
+                break;
+            }
+        }
+        Ok(data)
+    }
+
+    /// 범위 쓰기 (크면 여러 세션에 나눠 씀)
+    ///
+    /// 실패하면 앞에서부터 연속으로 확인된 바이트 수를 반환해 이어쓰기 지점으로 쓰게 한다.
+    pub fn write_range(&self, path: &str, offset: u64, data: &[u8]) -> Result<(), (usize, String)> {
+        let _budget = self
+            .budget
+            .acquire(data.len(), &CancelToken::default())
+            .map_err(|e| (0, e))?;
+        let parts = self.parts_for(data.len());
+        if parts == 1 {
+            return self.bulk(|client| client.write_file_range_resumable(path, offset, data));
+        }
+
+        self.yield_to_metadata();
+        let sessions = self.sessions(parts);
+        let ranges = split_range(offset, data.len(), sessions.len());
+        let results: Vec<Result<(), (usize, String)>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = sessions
+                .iter()
+                .zip(&ranges)
+                .map(|(session, &(part_offset, part_len))| {
+                    let start = (part_offset - offset) as usize;
+                    let part = &data[start..start + part_len];
+                    scope.spawn(move || {
+                        session
+                            .lock()
+                            .write_file_range_resumable(path, part_offset, part)
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| {
+                    h.join()
+                        .unwrap_or_else(|_| Err((0, "전송 스레드 실패".to_string())))
+                })
+                .collect()
+        });
+
+        let mut confirmed = 0;
+        for ((_, part_len), result) in ranges.iter().zip(results) {
+            match result {
+                Ok(()) => confirmed += part_len,
+                Err((written, e)) => return Err((confirmed + written, e)),
+            }
+        }
+        Ok(())
+    }
+
+    /// 주 세션 재연결 (보조 세션은 버리고 다음 병렬 전송 때 다시 연결)
+    ///
+    /// 재연결 정책이 정한 간격만큼 세션 lock 없이 기다린 뒤 시도한다. 연결 끊김 훅은
+    /// 끊길 때 한 번만 실행하고, 재연결에 성공하면 다음 끊김에 다시 실행한다.
+    /// 같은 끊김으로 여러 작업이 함께 재연결하면 먼저 성공한 작업의 세션을 그대로 쓴다.
+    pub fn reconnect(&self) -> Result<(), String> {
+        let seen = self.generation();
+        let policy = self.primary.lock().reconnect_policy();
+        if policy.mark_lost() {
+            let connection = self.primary.lock().connection().clone();
+            run_hook(
+                &connection,
+                Some(self.drive_letter),
+                HookEvent::ConnectionLost,
+            );
+        }
+        policy.wait_turn()?;
+        let mut primary = self.primary.lock();
+        if self.generation() != seen {
+            // 기다리는 동안 다른 작업이 이미 다시 연결함
+            return Ok(());
+        }
+        self.extra.lock().clear();
+        let result = primary.reconnect();
+        policy.record(&result);
+        result?;
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        *self.extra_failed_at.lock() = None;
+        Ok(())
+    }
+
+    /// 현재 세션 세대 (재연결할 때마다 증가)
+    pub fn generation(&self) -> u32 {
+        self.generation.load(Ordering::Relaxed)
+    }
+}
+
+/// 범위를 n개의 연속 조각으로 나눔 (offset, len)
+fn split_range(offset: u64, len: usize, n: usize) -> Vec<(u64, usize)> {
+    let base = len / n;
+    let remainder = len % n;
+    let mut ranges = Vec::with_capacity(n);
+    let mut cursor = offset;
+    for i in 0..n {
+        let part_len = base + usize::from(i < remainder);
+        ranges.push((cursor, part_len));
+        cursor += part_len as u64;
+    }
+    ranges
+}
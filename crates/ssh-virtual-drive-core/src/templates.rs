@@ -0,0 +1,166 @@
+//! 연결 템플릿 모듈 - `{변수}` 자리 표시로 호스트나 사용자만 다른 연결을 찍어 냄
+//!
+//! 템플릿은 연결 구성 하나를 그대로 담고, 이름, 호스트, 사용자, 원격 경로, 키 파일 경로, 메모,
+//! 고정/오프라인 폴더, 프록시 호스트와 사용자에 `{host}`, `{user}` 같은 자리 표시를 쓸 수 있다.
+//! [`instantiate`]는 변수 값을 채운 새 연결을 만든다 (ID와 서버 호스트 키 지문은 비우고,
+//! 비밀번호 같은 자격 증명은 옮기지 않음).
+
+use crate::storage;
+use crate::types::{ConnectionTemplate, SshConnection};
+use parking_lot::Mutex;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// 템플릿 목록을 읽고 고쳐 쓰는 동안 잡는 잠금
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// 템플릿 목록
+pub fn list() -> Result<Vec<ConnectionTemplate>, String> {
+    storage::load_templates()
+}
+
+/// 템플릿 저장 (ID가 비어 있으면 새 템플릿)
+pub fn save(mut template: ConnectionTemplate) -> Result<ConnectionTemplate, String> {
+    template.name = template.name.trim().to_string();
+    if template.name.is_empty() {
+        return Err("템플릿 이름을 입력해주세요.".to_string());
+    }
+    template.connection.id = String::new();
+    template.variables = variables(&template.connection);
+
+    let _guard = LOCK.lock();
+    let mut templates = storage::load_templates()?;
+    if template.id.is_empty() {
+        template.id = Uuid::new_v4().to_string();
+        templates.push(template.clone());
+    } else {
+        let existing = templates
+            .iter_mut()
+            .find(|t| t.id == template.id)
+            .ok_or_else(|| "템플릿을 찾을 수 없습니다.".to_string())?;
+        *existing = template.clone();
+    }
+    storage::save_templates(&templates)?;
+    Ok(template)
+}
+
+/// 템플릿 삭제
+pub fn delete(id: &str) -> Result<(), String> {
+    let _guard = LOCK.lock();
+    let mut templates = storage::load_templates()?;
+    let before = templates.len();
+    templates.retain(|t| t.id != id);
+    if templates.len() == before {
+        return Err("템플릿을 찾을 수 없습니다.".to_string());
+    }
+    storage::save_templates(&templates)
+}
+
+/// 변수 값을 채운 새 연결 구성 (저장은 하지 않음, 빠진 변수가 있으면 실패)
+pub fn instantiate(
+    template_id: &str,
+    vars: &BTreeMap<String, String>,
+) -> Result<SshConnection, String> {
+    let template = storage::load_templates()?
+        .into_iter()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| "템플릿을 찾을 수 없습니다.".to_string())?;
+
+    let missing: Vec<String> = variables(&template.connection)
+        .into_iter()
+        .filter(|name| vars.get(name).is_none_or(|value| value.trim().is_empty()))
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("변수 값을 입력해주세요: {}", missing.join(", ")));
+    }
+
+    let mut connection = template.connection;
+    connection.id = String::new();
+    // 다른 서버의 지문이므로 새 연결에 옮기지 않음
+    connection.host_key_fingerprint = None;
+    for field in fields(&mut connection) {
+        *field = substitute(field, vars);
+    }
+    Ok(connection)
+}
+
+/// 자리 표시를 쓸 수 있는 문자열 필드
+fn fields(connection: &mut SshConnection) -> Vec<&mut String> {
+    let mut fields = vec![
+        &mut connection.name,
+        &mut connection.host,
+        &mut connection.username,
+        &mut connection.remote_path,
+    ];
+    fields.extend(connection.key_path.as_mut());
+    fields.extend(connection.notes.as_mut());
+    fields.extend(connection.pinned_folders.iter_mut());
+    fields.extend(connection.offline_paths.iter_mut());
+    if let Some(proxy) = connection.proxy.as_mut() {
+        fields.push(&mut proxy.host);
+        fields.extend(proxy.username.as_mut());
+    }
+    fields
+}
+
+/// 구성에 쓰인 변수 이름 (처음 나온 순서, 중복 없음)
+fn variables(connection: &SshConnection) -> Vec<String> {
+    let mut connection = connection.clone();
+    let mut names = Vec::new();
+    for field in fields(&mut connection) {
+        for name in placeholders(field) {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// 문자열의 `{이름}` 자리 표시 (이름은 영문, 숫자, `_`만 - 다른 중괄호는 그대로 둠)
+fn placeholders(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        if let Some(end) = rest.find('}') {
+            let name = &rest[..end];
+            if is_variable_name(name) {
+                names.push(name);
+                rest = &rest[end + 1..];
+            }
+        }
+    }
+    names
+}
+
+/// 자리 표시를 변수 값으로 바꿈 (한 번에 훑어서 값 안의 중괄호는 다시 바꾸지 않음)
+fn substitute(text: &str, vars: &BTreeMap<String, String>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let variable = after
+            .find('}')
+            .map(|end| &after[..end])
+            .filter(|name| is_variable_name(name))
+            .and_then(|name| vars.get(name).map(|value| (name, value)));
+        match variable {
+            Some((name, value)) => {
+                result.push_str(value.trim());
+                rest = &after[name.len() + 1..];
+            }
+            None => {
+                result.push('{');
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,46 @@
+//! 미리보기 읽기 제한 모듈 - Explorer 썸네일 추출로 인한 헤더 읽기 폭주 차단
+//!
+//! 썸네일/미리보기 처리기는 수백 개 파일을 연달아 열어 앞부분만 읽고 닫는다.
+//! 짧은 시간에 앞부분만 읽고 닫은 핸들이 몰리면 새 핸들의 헤더 읽기를 거부해
+//! 폴더 탐색에 필요한 메타데이터 요청이 밀리지 않게 한다. 복사처럼 끝까지 읽는 핸들과
+//! 구분하려고 헤더 범위보다 큰 파일을 그 범위 안에서만 읽고 닫은 핸들만 센다.
+
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// 이보다 큰 파일을 이 범위 안에서만 읽은 핸들을 헤더 읽기로 취급
+/// (이보다 작은 파일은 복사할 때도 전체가 이 범위 안이라 세지 않음)
+pub const PREVIEW_HEADER_BYTES: u64 = 256 * 1024;
+
+/// 폭주 판단 구간
+const BURST_WINDOW: Duration = Duration::from_secs(2);
+
+/// 구간 안에서 헤더만 읽고 닫은 핸들 수
+const BURST_LIMIT: usize = 16;
+
+/// 최근 헤더만 읽고 닫은 시각 기록
+#[derive(Default)]
+pub struct PreviewThrottle {
+    recent: Mutex<VecDeque<Instant>>,
+}
+
+impl PreviewThrottle {
+    /// 새 핸들의 첫 헤더 읽기를 허용할지 판단
+    pub fn admit_header_read(&self) -> bool {
+        let now = Instant::now();
+        let mut recent = self.recent.lock();
+        while recent
+            .front()
+            .is_some_and(|at| now.duration_since(*at) > BURST_WINDOW)
+        {
+            recent.pop_front();
+        }
+        recent.len() < BURST_LIMIT
+    }
+
+    /// 헤더만 읽고 닫은 핸들 기록
+    pub fn record_header_only(&self) {
+        self.recent.lock().push_back(Instant::now());
+    }
+}
@@ -0,0 +1,204 @@
+//! TOTP 모듈 - 2단계 인증 코드 생성 (RFC 6238, 기본 HMAC-SHA1, 30초, 6자리)
+//!
+//! 비밀 키는 인증 앱에 등록할 때 쓰는 base32 문자열 또는 `otpauth://` URI로 받으며
+//! 자격 증명 관리자에만 저장한다. URI의 `algorithm`(SHA1/SHA256/SHA512), `digits`(6~8),
+//! `period` 값을 따르고 지원하지 않는 값이면 저장 전에 거부한다.
+
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 기본 코드 갱신 주기 (초)
+const TIME_STEP_SECS: u64 = 30;
+
+/// 기본 코드 자릿수
+const DIGITS: u32 = 6;
+
+/// HMAC 해시 알고리즘
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Algorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+/// 코드 생성에 쓸 비밀 키와 매개변수
+#[derive(Debug)]
+struct TotpParams {
+    key: Vec<u8>,
+    algorithm: Algorithm,
+    digits: u32,
+    period: u64,
+}
+
+/// 현재 시각의 TOTP 코드
+pub fn current_code(secret: &str) -> Result<String, String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("시스템 시간 확인 실패: {}", e))?
+        .as_secs();
+    code_at(secret, now)
+}
+
+/// 저장 전 비밀 키 형식 확인
+pub fn validate_secret(secret: &str) -> Result<(), String> {
+    parse_secret(secret).map(|_| ())
+}
+
+fn code_at(secret: &str, unix_time: u64) -> Result<String, String> {
+    let params = parse_secret(secret)?;
+    let counter = (unix_time / params.period).to_be_bytes();
+
+    let hash = match params.algorithm {
+        Algorithm::Sha1 => sign::<Hmac<Sha1>>(&params.key, &counter)?,
+        Algorithm::Sha256 => sign::<Hmac<Sha256>>(&params.key, &counter)?,
+        Algorithm::Sha512 => sign::<Hmac<Sha512>>(&params.key, &counter)?,
+    };
+
+    // 동적 잘라내기 (RFC 4226 5.3)
+    let offset = (hash[hash.len() - 1] & 0x0F) as usize;
+    let value = u32::from_be_bytes([
+        hash[offset] & 0x7F,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+    Ok(format!(
+        "{:0width$}",
+        value % 10u32.pow(params.digits),
+        width = params.digits as usize
+    ))
+}
+
+fn sign<M: Mac + KeyInit>(key: &[u8], message: &[u8]) -> Result<Vec<u8>, String> {
+    let mut mac =
+        <M as Mac>::new_from_slice(key).map_err(|e| format!("TOTP 키 초기화 실패: {}", e))?;
+    mac.update(message);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+/// base32 비밀 키 또는 `otpauth://...?secret=...` URI 해석
+fn parse_secret(secret: &str) -> Result<TotpParams, String> {
+    let secret = secret.trim();
+    let mut params = TotpParams {
+        key: Vec::new(),
+        algorithm: Algorithm::Sha1,
+        digits: DIGITS,
+        period: TIME_STEP_SECS,
+    };
+    let Some(uri) = secret.strip_prefix("otpauth://") else {
+        params.key = decode_base32(secret)?;
+        return Ok(params);
+    };
+
+    let query = uri.split_once('?').map(|(_, query)| query).unwrap_or("");
+    let mut encoded = None;
+    for (name, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+        match name.to_ascii_lowercase().as_str() {
+            "secret" => encoded = Some(value),
+            "algorithm" => {
+                params.algorithm = match value.to_ascii_uppercase().as_str() {
+                    "SHA1" => Algorithm::Sha1,
+                    "SHA256" => Algorithm::Sha256,
+                    "SHA512" => Algorithm::Sha512,
+                    _ => return Err(format!("지원하지 않는 TOTP 알고리즘입니다: {}", value)),
+                }
+            }
+            "digits" => {
+                params.digits = value
+                    .parse()
+                    .ok()
+                    .filter(|digits| (6..=8).contains(digits))
+                    .ok_or_else(|| format!("지원하지 않는 TOTP 자릿수입니다: {}", value))?
+            }
+            "period" => {
+                params.period = value
+                    .parse()
+                    .ok()
+                    .filter(|period| *period > 0)
+                    .ok_or_else(|| format!("TOTP 주기가 올바르지 않습니다: {}", value))?
+            }
+            _ => {}
+        }
+    }
+    let encoded = encoded.ok_or_else(|| "otpauth URI에 secret 값이 없습니다.".to_string())?;
+    params.key = decode_base32(encoded)?;
+    Ok(params)
+}
+
+/// base32 문자열을 바이트로 변환 (공백, `-`, `=` 무시)
+fn decode_base32(encoded: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in encoded.chars().filter(|c| !matches!(c, ' ' | '-' | '=')) {
+        let value = match c.to_ascii_uppercase() {
+            c @ 'A'..='Z' => c as u32 - 'A' as u32,
+            c @ '2'..='7' => c as u32 - '2' as u32 + 26,
+            _ => return Err("TOTP 비밀 키 형식이 올바르지 않습니다 (base32).".to_string()),
+        };
+        buffer = ((buffer << 5) | value) & 0xFFFF;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    if bytes.is_empty() {
+        return Err("TOTP 비밀 키가 비어 있습니다.".to_string());
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 6238 부록 B의 시험 벡터 (T = 59)
+    #[test]
+    fn uri_parameters_select_algorithm_and_digits() {
+        let cases = [
+            ("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ", "SHA1", "94287082"),
+            (
+                "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZA====",
+                "SHA256",
+                "46119246",
+            ),
+            (
+                "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNA=",
+                "SHA512",
+                "90693936",
+            ),
+        ];
+        for (secret, algorithm, expected) in cases {
+            let uri = format!(
+                "otpauth://totp/test?secret={}&algorithm={}&digits=8&period=30",
+                secret, algorithm
+            );
+            assert_eq!(code_at(&uri, 59).unwrap(), expected, "{}", algorithm);
+        }
+        // 기본값은 SHA1, 30초, 6자리
+        assert_eq!(
+            code_at("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ", 59).unwrap(),
+            "287082"
+        );
+        // period=60이면 T = 118에서 같은 카운터
+        assert_eq!(
+            code_at(
+                "otpauth://totp/test?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&period=60",
+                118
+            )
+            .unwrap(),
+            "287082"
+        );
+    }
+
+    #[test]
+    fn unsupported_uri_parameters_are_rejected() {
+        let base = "otpauth://totp/test?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+        assert!(validate_secret(&format!("{}&algorithm=MD5", base)).is_err());
+        assert!(validate_secret(&format!("{}&digits=10", base)).is_err());
+        assert!(validate_secret(&format!("{}&period=0", base)).is_err());
+    }
+}
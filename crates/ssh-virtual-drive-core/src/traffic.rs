@@ -0,0 +1,214 @@
+//! 전송량 기록 모듈 - 연결별로 서버와 주고받은 파일 데이터 양을 누적
+//!
+//! 클라우드 서버는 나가는 트래픽에 요금을 매기므로 연결별 누적 전송량과 월별 전송량을
+//! 보관한다. [`SftpClient`](crate::sftp_client::SftpClient)가 파일 내용과 원격 명령 출력을
+//! 주고받을 때마다 기록하며 (목록/정보 조회 같은 메타데이터와 SSH 프로토콜 부담은 제외),
+//! 월은 UTC 기준으로 나눈다. 기록은 메모리에 모았다가 [`save`]로 `traffic.json`에 저장한다.
+//!
+//! 연결에 월 전송량 한도(`monthly_transfer_cap_bytes`)를 정하면 이번 달 전송량이 한도에
+//! 닿을 때 한 번 경고를 남기고, 앱은 [`take_cap_warnings`]로 받아 사용자에게 알린다.
+//! 한도는 경고용이며 전송을 막지는 않는다.
+
+use crate::storage;
+use crate::types::{ConnectionUsage, MonthlyUsage, SshConnection, UsageCapWarning};
+use log::{info, warn};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 연결별로 남길 월 수
+pub const MONTHS_LIMIT: usize = 24;
+
+/// 연결 하나의 전송량 기록
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct UsageRecord {
+    bytes_received: u64,
+    bytes_sent: u64,
+    // 처음 기록한 시각 (Unix 시간, 초)
+    since: u64,
+    // 월별 전송량 (오래된 것부터)
+    months: Vec<MonthlyUsage>,
+    // 한도 경고를 마지막으로 남긴 달 (같은 달에 다시 경고하지 않음)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    warned_month: Option<String>,
+}
+
+#[derive(Default)]
+struct State {
+    records: HashMap<String, UsageRecord>,
+    // 마지막 저장 이후 바뀌었는지
+    dirty: bool,
+}
+
+/// 연결별 기록 (처음 쓸 때 파일에서 읽음)
+static USAGE: Mutex<Option<State>> = Mutex::new(None);
+
+/// 아직 앱에 알리지 않은 한도 경고
+static WARNINGS: Mutex<Vec<UsageCapWarning>> = Mutex::new(Vec::new());
+
+/// 연결의 누적/월별 전송량 (기록이 없으면 0)
+pub fn usage(connection: &SshConnection) -> ConnectionUsage {
+    let month = current_month();
+    with_state(|state| {
+        let record = state
+            .records
+            .get(&connection.id)
+            .cloned()
+            .unwrap_or_default();
+        let current_month = record
+            .months
+            .iter()
+            .find(|m| m.month == month)
+            .cloned()
+            .unwrap_or_else(|| empty_month(&month));
+        ConnectionUsage {
+            connection_id: connection.id.clone(),
+            bytes_received: record.bytes_received,
+            bytes_sent: record.bytes_sent,
+            since: (record.since != 0).then_some(record.since),
+            current_month,
+            months: record.months,
+            monthly_transfer_cap_bytes: connection.monthly_transfer_cap_bytes,
+        }
+    })
+}
+
+/// 연결들의 전송량 기록 삭제 (연결 프로필을 지울 때)
+pub fn remove(connection_ids: &[String]) {
+    with_state(|state| {
+        let before = state.records.len();
+        state.records.retain(|id, _| !connection_ids.contains(id));
+        if state.records.len() != before {
+            state.dirty = true;
+        }
+    });
+    save();
+}
+
+/// 지난 호출 이후 월 전송량 한도에 닿은 연결
+pub fn take_cap_warnings() -> Vec<UsageCapWarning> {
+    std::mem::take(&mut *WARNINGS.lock())
+}
+
+/// 바뀐 기록이 있으면 저장
+pub fn save() {
+    with_state(|state| {
+        if !state.dirty {
+            return;
+        }
+        match write(&state.records) {
+            Ok(()) => state.dirty = false,
+            Err(e) => warn!("[Traffic] saving usage failed: {}", e),
+        }
+    });
+}
+
+/// 전송량 추가 (받은 바이트, 보낸 바이트 - 저장하지 않은 연결은 제외)
+pub(crate) fn record(connection: &SshConnection, received: u64, sent: u64) {
+    if connection.id.is_empty() || (received == 0 && sent == 0) {
+        return;
+    }
+    let month = current_month();
+    with_state(|state| {
+        state.dirty = true;
+        let record = state.records.entry(connection.id.clone()).or_default();
+        if record.since == 0 {
+            record.since = unix_now();
+        }
+        record.bytes_received += received;
+        record.bytes_sent += sent;
+
+        if record.months.last().is_none_or(|m| m.month != month) {
+            record.months.push(empty_month(&month));
+            if record.months.len() > MONTHS_LIMIT {
+                record.months.remove(0);
+            }
+        }
+        let Some(current) = record.months.last_mut() else {
+            return;
+        };
+        current.bytes_received += received;
+        current.bytes_sent += sent;
+
+        let used = current.bytes_received + current.bytes_sent;
+        let Some(cap) = connection.monthly_transfer_cap_bytes else {
+            return;
+        };
+        if used < cap || record.warned_month.as_deref() == Some(month.as_str()) {
+            return;
+        }
+        record.warned_month = Some(month.clone());
+        info!(
+            "[Traffic] {} reached its monthly transfer cap ({} of {} bytes)",
+            connection.id, used, cap
+        );
+        WARNINGS.lock().push(UsageCapWarning {
+            connection_id: connection.id.clone(),
+            connection_name: connection.name.clone(),
+            month,
+            used_bytes: used,
+            cap_bytes: cap,
+        });
+    });
+}
+
+fn with_state<T>(f: impl FnOnce(&mut State) -> T) -> T {
+    let mut state = USAGE.lock();
+    f(state.get_or_insert_with(|| State {
+        records: load(),
+        dirty: false,
+    }))
+}
+
+fn load() -> HashMap<String, UsageRecord> {
+    storage::get_traffic_file()
+        .and_then(|file| fs::read_to_string(file).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write(records: &HashMap<String, UsageRecord>) -> Result<(), String> {
+    let file = storage::get_traffic_file()
+        .ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("디렉토리 생성 실패: {}", e))?;
+    }
+    let content = serde_json::to_string(records).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+    fs::write(&file, content).map_err(|e| format!("파일 저장 실패: {}", e))
+}
+
+fn empty_month(month: &str) -> MonthlyUsage {
+    MonthlyUsage {
+        month: month.to_string(),
+        bytes_received: 0,
+        bytes_sent: 0,
+    }
+}
+
+/// 현재 UTC 기준 달 (`YYYY-MM`)
+fn current_month() -> String {
+    // 1970-01-01부터 지난 날 수를 그레고리력 연/월로 변환 (3월 시작 연도로 계산)
+    let days = (unix_now() / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}", year, month)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,126 @@
+//! 연결 보관함 모듈 - 삭제한 연결 프로필을 일정 기간 보관해 되돌릴 수 있게 함
+//!
+//! 연결을 삭제하면 목록에서만 빼고 프로필을 보관 목록(`deleted_connections.json`)에
+//! 옮긴다. 보관 기간([`RETENTION_DAYS`]) 안에는 [`restore`]로 그대로 되돌릴 수 있고,
+//! 기간이 지나면 [`purge_expired`]가 자격 증명 관리자의 비밀 값과 메타데이터 캐시,
+//! 오프라인 사본, 북마크까지 지운다.
+
+use crate::types::{DeletedConnection, SshConnection};
+use crate::{bookmarks, credentials, health, storage, traffic};
+use log::{info, warn};
+use parking_lot::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 삭제한 연결을 보관하는 기간 (일)
+pub const RETENTION_DAYS: u64 = 7;
+
+/// 보관 목록을 읽고 고쳐 쓰는 동안 잡는 잠금
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// 연결을 목록에서 빼고 보관 목록으로 옮김
+///
+/// 보관 목록에 먼저 기록한 뒤 목록에서 뺀다. 순서가 반대면 보관 목록 저장에 실패했을 때
+/// 프로필이 어디에도 남지 않는다.
+pub fn delete(id: &str) -> Result<(), String> {
+    let _guard = LOCK.lock();
+    let connection = storage::load_connections()?
+        .into_iter()
+        .find(|c| c.id == id)
+        .ok_or_else(|| "연결을 찾을 수 없습니다.".to_string())?;
+
+    let previous = storage::load_deleted_connections()?;
+    let mut deleted = previous.clone();
+    deleted.retain(|d| d.connection.id != id);
+    deleted.push(DeletedConnection {
+        connection,
+        deleted_at: unix_now(),
+    });
+    storage::save_deleted_connections(&deleted)?;
+
+    let removed = storage::update_connections(|connections| {
+        connections.retain(|c| c.id != id);
+        Ok(())
+    });
+    if let Err(e) = removed {
+        // 목록에 남은 연결의 자격 증명을 보관 기간이 지나 지우지 않도록 기록을 되돌림
+        if let Err(rollback) = storage::save_deleted_connections(&previous) {
+            warn!(
+                "[Trash] rolling back the archive entry of {} failed: {}",
+                id, rollback
+            );
+        }
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// 보관 중인 연결을 연결 목록으로 되돌림
+pub fn restore(id: &str) -> Result<SshConnection, String> {
+    let _guard = LOCK.lock();
+    let mut deleted = storage::load_deleted_connections()?;
+    let idx = deleted
+        .iter()
+        .position(|d| d.connection.id == id)
+        .ok_or_else(|| {
+            "되돌릴 연결을 찾을 수 없습니다. 보관 기간이 지났을 수 있습니다.".to_string()
+        })?;
+    let connection = deleted[idx].connection.clone();
+
+    storage::update_connections(|connections| {
+        if !connections.iter().any(|c| c.id == connection.id) {
+            connections.push(connection.clone());
+        }
+        Ok(())
+    })?;
+    deleted.remove(idx);
+    storage::save_deleted_connections(&deleted)?;
+    Ok(connection)
+}
+
+/// 보관 중인 연결 목록
+pub fn list() -> Vec<DeletedConnection> {
+    storage::load_deleted_connections().unwrap_or_default()
+}
+
+/// 보관 기간이 지난 연결의 자격 증명과 로컬 데이터 삭제
+pub fn purge_expired() {
+    let _guard = LOCK.lock();
+    let Ok(mut deleted) = storage::load_deleted_connections() else {
+        return;
+    };
+    let cutoff = unix_now().saturating_sub(RETENTION_DAYS * 24 * 60 * 60);
+    let (expired, kept): (Vec<_>, Vec<_>) = deleted.drain(..).partition(|d| d.deleted_at < cutoff);
+    if expired.is_empty() {
+        return;
+    }
+
+    let ids: Vec<String> = expired.into_iter().map(|d| d.connection.id).collect();
+    if let Err(e) = credentials::delete_connection_credentials(&ids) {
+        warn!("[Trash] removing credentials failed: {}", e);
+    }
+    health::remove(&ids);
+    traffic::remove(&ids);
+    if let Err(e) = bookmarks::remove_for_connections(&ids) {
+        warn!("[Trash] removing bookmarks failed: {}", e);
+    }
+    for id in &ids {
+        // 메타데이터 캐시 스냅샷과 오프라인 사본 삭제 (원격 목록이 로컬에 남지 않도록)
+        if let Some(file) = storage::get_cache_snapshot_file(id) {
+            let _ = std::fs::remove_file(file);
+        }
+        if let Some(dir) = storage::get_offline_dir(id) {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+    match storage::save_deleted_connections(&kept) {
+        Ok(()) => info!("[Trash] {} deleted connection(s) purged", ids.len()),
+        Err(e) => warn!("[Trash] saving deleted connections failed: {}", e),
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
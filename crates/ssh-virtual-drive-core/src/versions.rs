@@ -0,0 +1,191 @@
+//! 파일 버전 모듈 - 드라이브로 덮어쓰기 전에 원격 파일을 서버 안에서 백업
+//!
+//! 연결에 `keep_versions`를 설정하면 파일 내용이 바뀌기 직전에 같은 폴더의
+//! `.versions/<이름>.<시각>`으로 서버 측 복사(`cp -a`)를 하고 파일마다 최근 N개만 남긴다.
+//! 시각은 UTC `YYYYMMDD-HHMMSS` 형식이다. 원격 명령을 실행할 수 없는 SFTP 전용 서버에서는
+//! 버전을 남기지 못하며, 이때도 쓰기 자체는 막지 않는다.
+
+use crate::cache::parent_path;
+use crate::sftp_client::{is_not_found, SftpClient};
+use crate::types::FileVersion;
+use log::{info, warn};
+use std::cmp::Reverse;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 버전을 보관하는 폴더 이름 (원본과 같은 폴더 아래)
+pub(crate) const VERSIONS_DIR: &str = ".versions";
+
+/// 덮어쓰기 전에 현재 내용을 버전으로 복사 (새로 만든 버전 경로, 남길 내용이 없으면 None)
+pub(crate) fn save(client: &SftpClient, path: &str, keep: u32) -> Result<Option<String>, String> {
+    let (versions_dir, name) = split(path);
+    // 버전 폴더 안의 파일이나 빈 파일은 남기지 않음
+    if keep == 0 || parent_path(path).ends_with(&format!("/{}", VERSIONS_DIR)) {
+        return Ok(None);
+    }
+    let stat = match client.stat(path) {
+        Ok(stat) => stat,
+        Err(e) if is_not_found(&e) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    if !stat.is_file() || stat.size.unwrap_or(0) == 0 {
+        return Ok(None);
+    }
+
+    if let Err(e) = client.stat(&versions_dir) {
+        if !is_not_found(&e) {
+            return Err(e);
+        }
+        client.create_dir(&versions_dir)?;
+    }
+    let version = format!("{}/{}.{}", versions_dir, name, format_stamp(unix_now()));
+    // 같은 초에 이미 남긴 버전이 있으면 그대로 둠 (한 번 저장에 여러 번 flush)
+    if client.stat(&version).is_ok() {
+        return Ok(None);
+    }
+    client.copy(path, &version)?;
+    info!("[Versions] kept '{}'", version);
+
+    prune(client, path, keep);
+    Ok(Some(version))
+}
+
+/// 파일의 보관된 버전 목록 (최근 것부터)
+pub(crate) fn list(client: &SftpClient, path: &str) -> Result<Vec<FileVersion>, String> {
+    let (versions_dir, name) = split(path);
+    let entries = match client.read_dir(&versions_dir) {
+        Ok(entries) => entries,
+        Err(e) if is_not_found(&e) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let prefix = format!("{}.", name);
+    let mut versions: Vec<FileVersion> = entries
+        .into_iter()
+        .filter_map(|(entry, stat)| {
+            let saved_at = parse_stamp(entry.strip_prefix(&prefix)?)?;
+            Some(FileVersion {
+                name: entry,
+                saved_at,
+                size: stat.size.unwrap_or(0),
+            })
+        })
+        .collect();
+    versions.sort_by_key(|v| Reverse(v.saved_at));
+    Ok(versions)
+}
+
+/// 버전을 원래 파일에 되돌림 (켜져 있으면 되돌리기 전 내용도 버전으로 남김)
+pub(crate) fn restore(
+    client: &SftpClient,
+    path: &str,
+    version: &str,
+    keep: Option<u32>,
+) -> Result<(), String> {
+    let (versions_dir, name) = split(path);
+    let valid = version
+        .strip_prefix(&format!("{}.", name))
+        .and_then(parse_stamp)
+        .is_some();
+    if !valid || version.contains('/') {
+        return Err(format!("'{}'는 '{}'의 버전이 아닙니다.", version, name));
+    }
+    let source = format!("{}/{}", versions_dir, version);
+    client.stat(&source)?;
+
+    if let Some(keep) = keep {
+        save(client, path, keep)?;
+    }
+    client.copy(&source, path)?;
+    info!("[Versions] restored '{}' from '{}'", path, version);
+    Ok(())
+}
+
+/// 최근 `keep`개를 넘는 오래된 버전 삭제 (실패해도 다음 저장 때 다시 시도)
+fn prune(client: &SftpClient, path: &str, keep: u32) {
+    let versions = match list(client, path) {
+        Ok(versions) => versions,
+        Err(e) => {
+            warn!("[Versions] listing versions of '{}' failed: {}", path, e);
+            return;
+        }
+    };
+    let (versions_dir, _) = split(path);
+    for version in versions.iter().skip(keep as usize) {
+        if let Err(e) = client.remove_file(&format!("{}/{}", versions_dir, version.name)) {
+            warn!("[Versions] removing '{}' failed: {}", version.name, e);
+        }
+    }
+}
+
+/// 파일의 버전을 보관하는 폴더 경로
+pub(crate) fn versions_dir(path: &str) -> String {
+    split(path).0
+}
+
+/// 원격 경로 → (버전 폴더, 파일 이름)
+fn split(path: &str) -> (String, &str) {
+    let parent = parent_path(path);
+    let name = path[parent.len()..].trim_start_matches('/');
+    (
+        format!("{}/{}", parent.trim_end_matches('/'), VERSIONS_DIR),
+        name,
+    )
+}
+
+/// Unix 시간 → `YYYYMMDD-HHMMSS` (UTC)
+fn format_stamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    // 1970-01-01부터의 일 수 → 그레고리력 날짜
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem / 60 % 60,
+        rem % 60
+    )
+}
+
+/// `YYYYMMDD-HHMMSS` (UTC) → Unix 시간
+fn parse_stamp(stamp: &str) -> Option<u64> {
+    let (date, time) = stamp.split_once('-')?;
+    if date.len() != 8
+        || time.len() != 6
+        || !date.chars().chain(time.chars()).all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let number = |s: &str| s.parse::<i64>().ok();
+    let year = number(&date[..4])?;
+    let month = number(&date[4..6])?;
+    let day = number(&date[6..])?;
+    let (y, m) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * m + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    let seconds = number(&time[..2])? * 3600 + number(&time[2..4])? * 60 + number(&time[4..])?;
+    u64::try_from(days * 86_400 + seconds).ok()
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,171 @@
+//! 캐시 예열 모듈 - 고정한 폴더를 마운트 직후 백그라운드에서 미리 읽음
+//!
+//! 연결에 `pinned_folders`를 지정하면 마운트한 뒤 그 폴더 아래 디렉토리 목록을 차례로 읽어
+//! stat/readdir 캐시를 채운다. 자주 쓰는 프로젝트 폴더는 처음 열어도 서버를 기다리지 않는다.
+//! `warm_file_max_kb`를 지정하면 그 크기 이하 파일의 내용도 읽어 캐시에 둔다.
+//!
+//! 탐색기 작업을 방해하지 않도록 항목마다 세션 lock을 잡았다 놓고, 드라이브에 작업이 있는
+//! 동안에는 쉬었다가 이어 읽는다. 드라이브를 언마운트하면 멈춘다.
+
+use crate::cache::StatCache;
+use crate::path_filter::{AccessRules, PathFilter};
+use crate::profile;
+use crate::sftp_client::SharedSftpClient;
+use crate::stats::StatsCollector;
+use crate::types::SshConnection;
+use log::{debug, info, warn};
+use std::collections::VecDeque;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+/// 고정 폴더마다 미리 읽을 최대 디렉토리 수 (아주 큰 트리 전체를 읽지 않도록)
+const MAX_DIRS_PER_FOLDER: usize = 2000;
+
+/// 이 시간 안에 드라이브 작업이 있었으면 쉬었다가 이어 읽음
+const YIELD_WHILE_ACTIVE: Duration = Duration::from_secs(2);
+
+/// 드라이브 작업이 끝나기를 기다리는 간격
+const ACTIVE_POLL: Duration = Duration::from_millis(500);
+
+/// 마운트한 드라이브의 고정 폴더를 백그라운드에서 미리 읽기 시작 (고정 폴더가 없으면 무시)
+pub(crate) fn start(
+    client: SharedSftpClient,
+    connection: &SshConnection,
+    cache: &Arc<StatCache>,
+    stats: &Arc<StatsCollector>,
+) {
+    let root = connection.remote_path.trim_end_matches('/').to_string();
+    let folders: Vec<String> = connection
+        .pinned_folders
+        .iter()
+        .map(|folder| folder.trim())
+        .filter(|folder| !folder.is_empty())
+        .map(|folder| match folder.trim_end_matches('/') {
+            "" => "/".to_string(),
+            folder => folder.to_string(),
+        })
+        .filter(|folder| {
+            let inside =
+                folder.trim_end_matches('/') == root || folder.starts_with(&format!("{}/", root));
+            if !inside {
+                warn!("[Warm] pinned folder '{}' is outside the drive", folder);
+            }
+            inside
+        })
+        .collect();
+    if folders.is_empty() || cache.frozen().is_some() {
+        return;
+    }
+
+    let warmer = Warmer {
+        client,
+        cache: Arc::downgrade(cache),
+        stats: Arc::downgrade(stats),
+        exclude: PathFilter::new(&connection.exclude_patterns),
+        access: AccessRules::new(&connection.access_rules),
+        root,
+        max_file_bytes: connection
+            .warm_file_max_kb
+            .filter(|_| cache.keeps_contents())
+            .map(|kb| u64::from(kb) * 1024),
+    };
+    std::thread::spawn(move || {
+        for folder in &folders {
+            match warmer.warm(folder) {
+                Some((dirs, files)) => info!(
+                    "[Warm] '{}' warmed: {} directories, {} files",
+                    folder, dirs, files
+                ),
+                // 언마운트되었거나 일시 중지됨
+                None => return,
+            }
+        }
+    });
+}
+
+struct Warmer {
+    client: SharedSftpClient,
+    // 드라이브가 언마운트되면 사라짐 (예열도 멈춤)
+    cache: Weak<StatCache>,
+    stats: Weak<StatsCollector>,
+    exclude: PathFilter,
+    access: AccessRules,
+    root: String,
+    max_file_bytes: Option<u64>,
+}
+
+impl Warmer {
+    /// 폴더 아래를 너비 우선으로 미리 읽음 (읽은 (디렉토리 수, 파일 수), 중단되면 None)
+    fn warm(&self, folder: &str) -> Option<(usize, usize)> {
+        let mut queue = VecDeque::from([folder.to_string()]);
+        let (mut dirs, mut files) = (0, 0);
+        while let Some(dir) = queue.pop_front() {
+            if dirs >= MAX_DIRS_PER_FOLDER {
+                debug!("[Warm] '{}' has more than {} directories", folder, dirs);
+                break;
+            }
+            let cache = self.wait_until_quiet()?;
+            if cache.has_dir(&dir) {
+                continue;
+            }
+            let result = self.client.lock().read_dir(&dir);
+            let entries = match result {
+                Ok(entries) => entries,
+                Err(e) => {
+                    debug!("[Warm] listing '{}' failed: {}", dir, e);
+                    continue;
+                }
+            };
+            cache.insert_dir(&dir, &entries);
+            dirs += 1;
+
+            for (name, stat) in &entries {
+                let path = format!("{}/{}", dir.trim_end_matches('/'), name);
+                if self.is_hidden(&path) {
+                    continue;
+                }
+                if stat.is_dir() {
+                    if !profile::skip_prefetch(&path) {
+                        queue.push_back(path);
+                    }
+                } else if stat.is_file()
+                    && self
+                        .max_file_bytes
+                        .is_some_and(|max| stat.size.unwrap_or(0) <= max)
+                {
+                    let cache = self.wait_until_quiet()?;
+                    let result = self.client.lock().read_file(&path);
+                    match result {
+                        Ok(data) => {
+                            cache.insert_content(&path, stat, data);
+                            files += 1;
+                        }
+                        Err(e) => debug!("[Warm] reading '{}' failed: {}", path, e),
+                    }
+                }
+            }
+        }
+        Some((dirs, files))
+    }
+
+    /// 드라이브 작업이 잠잠해질 때까지 대기 (언마운트되었거나 일시 중지되면 None)
+    fn wait_until_quiet(&self) -> Option<Arc<StatCache>> {
+        loop {
+            let stats = self.stats.upgrade()?;
+            let cache = self.cache.upgrade()?;
+            if cache.is_paused() {
+                return None;
+            }
+            if stats.idle_for() >= YIELD_WHILE_ACTIVE {
+                return Some(cache);
+            }
+            drop((stats, cache));
+            std::thread::sleep(ACTIVE_POLL);
+        }
+    }
+
+    fn is_hidden(&self, remote_path: &str) -> bool {
+        let relative = &remote_path[self.root.len()..];
+        self.exclude.is_excluded(relative) || self.access.is_hidden(remote_path)
+    }
+}
@@ -0,0 +1,820 @@
+//! 쓰기 지연(write-back) 모듈 - 쓰기 병합 버퍼와 충돌 복구용 저널
+//!
+//! 열린 파일에 대한 쓰기는 메모리에 병합해 두었다가 cleanup/flush/close 시점에
+//! 원격에 반영한다. 반영 전 데이터는 로컬 저널에도 기록되므로, 앱이 비정상 종료되어도
+//! 같은 연결을 다시 마운트할 때 재적용할 수 있다. 저널에는 기록한 계정도 남겨, 프로필의
+//! 사용자나 호스트가 바뀐 뒤에는 다른 계정으로 재적용하지 않는다.
+//!
+//! 변경 대기 모드(`queue_writes`)에서는 닫힌 파일의 쓰기를 올리지 않고 [`ChangeQueue`]에
+//! 쌓아 두었다가 사용자가 "변경 올리기"를 할 때 한꺼번에 반영한다. 읽기는 계속 서버에서
+//! 하고 대기 중인 내용을 덮어 보여준다. 대기 중인 쓰기도 저널에 남으므로 앱을 다시 켜면
+//! 같은 목록으로 되살아난다 (올리기 전까지 서버에는 반영하지 않음).
+
+use crate::cache::moved_path;
+use crate::sftp_client::SharedSftpClient;
+use crate::stats::StatsCollector;
+use crate::storage;
+use crate::streams::StreamPool;
+use crate::types::{PendingChange, PushReport, SshConnection};
+use crate::versions;
+use log::{info, warn};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+/// 버퍼에 쌓인 데이터가 이 크기를 넘으면 close를 기다리지 않고 반영
+pub const MAX_DIRTY_BYTES: usize = 8 * 1024 * 1024;
+
+/// 반영 도중 연결이 끊겼을 때 재연결 후 이어쓰기를 시도하는 최대 횟수
+const MAX_RESUME_ATTEMPTS: u32 = 3;
+
+/// 저널 레코드 길이 필드의 특수 값 - 이 시점부터 원격 반영이 시작되었음을 표시
+const FLUSH_STARTED_MARKER: u32 = u32::MAX;
+
+/// 저널 레코드 길이 필드의 특수 값 - 파일 이름이 바뀜 (오프셋 필드는 새 경로 길이)
+const RENAMED_MARKER: u32 = u32::MAX - 1;
+
+/// 저널 헤더 - 원격 파일이 저널 작성 이후 변경되었는지 판단하는 기준 정보
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct JournalHeader {
+    remote_path: String,
+    base_size: Option<u64>,
+    base_mtime: Option<u64>,
+    /// 저널을 기록한 계정 (이전 버전 저널에는 없음)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    identity: Option<String>,
+}
+
+/// 저널 파일 하나 (열린 파일 하나의 미반영 쓰기 기록)
+pub struct JournalFile {
+    path: PathBuf,
+    file: File,
+}
+
+impl JournalFile {
+    /// 쓰기 레코드 추가: [offset u64 LE][len u32 LE][data]
+    fn append(&mut self, offset: u64, data: &[u8]) -> Result<(), String> {
+        let mut record = Vec::with_capacity(12 + data.len());
+        record.extend_from_slice(&offset.to_le_bytes());
+        record.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        record.extend_from_slice(data);
+        self.file
+            .write_all(&record)
+            .map_err(|e| format!("저널 기록 실패: {}", e))
+    }
+
+    /// 원격 반영 시작 표시
+    ///
+    /// 반영 도중 앱이 종료되면 원격 파일은 우리 쓰기로 일부 바뀐 상태이므로,
+    /// 복구 시 이 표시가 있으면 변경 여부 검사 없이 재적용한다.
+    fn mark_flush_started(&mut self) -> Result<(), String> {
+        let mut record = Vec::with_capacity(12);
+        record.extend_from_slice(&0u64.to_le_bytes());
+        record.extend_from_slice(&FLUSH_STARTED_MARKER.to_le_bytes());
+        self.file
+            .write_all(&record)
+            .map_err(|e| format!("저널 기록 실패: {}", e))
+    }
+
+    /// 원격 파일 이름 변경 기록 (다시 불러올 때 새 경로로 반영하도록)
+    fn record_rename(&mut self, remote_path: &str) -> Result<(), String> {
+        let mut record = Vec::with_capacity(12 + remote_path.len());
+        record.extend_from_slice(&(remote_path.len() as u64).to_le_bytes());
+        record.extend_from_slice(&RENAMED_MARKER.to_le_bytes());
+        record.extend_from_slice(remote_path.as_bytes());
+        self.file
+            .write_all(&record)
+            .map_err(|e| format!("저널 기록 실패: {}", e))
+    }
+
+    /// 반영 완료된 저널 삭제
+    fn discard(self) {
+        drop(self.file);
+        if let Err(e) = fs::remove_file(&self.path) {
+            warn!("[Journal] removing {:?} failed: {}", self.path, e);
+        }
+    }
+}
+
+/// 연결별 쓰기 저널 디렉토리
+pub struct WriteJournal {
+    dir: PathBuf,
+    identity: String,
+    // 변경 대기 모드면 올리기를 기다리는 쓰기
+    queue: Option<Arc<ChangeQueue>>,
+}
+
+impl WriteJournal {
+    pub fn for_connection(connection: &SshConnection) -> Result<Self, String> {
+        let dir = storage::get_data_dir()
+            .ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?
+            .join("journal")
+            .join(&connection.id);
+        fs::create_dir_all(&dir).map_err(|e| format!("저널 디렉토리 생성 실패: {}", e))?;
+        Ok(Self {
+            dir,
+            identity: connection.identity(),
+            queue: connection
+                .queue_writes
+                .then(|| Arc::new(ChangeQueue::default())),
+        })
+    }
+
+    /// 변경 대기 모드의 대기열 (일반 모드면 None)
+    pub fn queue(&self) -> Option<&Arc<ChangeQueue>> {
+        self.queue.as_ref()
+    }
+
+    /// 새 저널 파일 생성 (원격 파일의 현재 크기/수정 시간을 헤더로 기록)
+    fn create_entry(
+        &self,
+        remote_path: &str,
+        base: Option<&ssh2::FileStat>,
+    ) -> Result<JournalFile, String> {
+        let path = self.dir.join(format!("{}.journal", Uuid::new_v4()));
+        let header = JournalHeader {
+            remote_path: remote_path.to_string(),
+            base_size: base.and_then(|s| s.size),
+            base_mtime: base.and_then(|s| s.mtime),
+            identity: Some(self.identity.clone()),
+        };
+        let mut line =
+            serde_json::to_string(&header).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create_new(true)
+            .write(true)
+            .open(&path)
+            .map_err(|e| format!("저널 파일 생성 실패: {}", e))?;
+        file.write_all(line.as_bytes())
+            .map_err(|e| format!("저널 기록 실패: {}", e))?;
+
+        Ok(JournalFile { path, file })
+    }
+
+    /// 이전 세션에서 남은 저널을 재적용 (변경 대기 모드면 대기열로 되살림)
+    ///
+    /// 원격 파일이 저널 작성 이후 변경되었거나 적용에 실패하면 저널을 `recovered`
+    /// 디렉토리로 옮기고, 사용자에게 보여줄 충돌 설명 목록을 반환한다.
+    pub fn recover(&self, client: &SharedSftpClient) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        let Ok(read_dir) = fs::read_dir(&self.dir) else {
+            return conflicts;
+        };
+
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("journal") {
+                continue;
+            }
+
+            if let Some(queue) = &self.queue {
+                match Self::requeue(&path, &self.identity) {
+                    Ok((_, dirty)) if dirty.buffer.is_empty() => dirty.discard(),
+                    Ok((remote_path, dirty)) => {
+                        info!("[Journal] re-queued pending writes for '{}'", remote_path);
+                        queue.put(&remote_path, dirty);
+                    }
+                    Err(reason) => {
+                        warn!("[Journal] re-queue failed for {:?}: {}", path, reason);
+                        let kept = self.move_to_recovered(&path);
+                        conflicts.push(match kept {
+                            Some(kept) => format!("{} (복구 파일: {})", reason, kept.display()),
+                            None => reason,
+                        });
+                    }
+                }
+                continue;
+            }
+
+            match Self::replay(&path, client, &self.identity) {
+                Ok(remote_path) => {
+                    info!("[Journal] replayed pending writes for '{}'", remote_path);
+                    if let Err(e) = fs::remove_file(&path) {
+                        warn!("[Journal] removing {:?} failed: {}", path, e);
+                    }
+                }
+                Err(reason) => {
+                    warn!("[Journal] replay failed for {:?}: {}", path, reason);
+                    let kept = self.move_to_recovered(&path);
+                    conflicts.push(match kept {
+                        Some(kept) => format!("{} (복구 파일: {})", reason, kept.display()),
+                        None => reason,
+                    });
+                }
+            }
+        }
+
+        conflicts
+    }
+
+    fn replay(path: &Path, client: &SharedSftpClient, identity: &str) -> Result<String, String> {
+        let (header, buffer, flush_started) = Self::read_entry(path, identity)?;
+        if buffer.is_empty() {
+            return Ok(header.remote_path);
+        }
+
+        let client = client.lock();
+        let current = client.stat(&header.remote_path).ok();
+        let unchanged = flush_started
+            || match &current {
+                Some(stat) => stat.size == header.base_size && stat.mtime == header.base_mtime,
+                None => header.base_size.is_none() && header.base_mtime.is_none(),
+            };
+        if !unchanged {
+            return Err(format!(
+                "'{}'가 마지막 세션 이후 서버에서 변경되어 미반영 쓰기를 적용하지 않았습니다.",
+                header.remote_path
+            ));
+        }
+
+        for (offset, data) in buffer.extents() {
+            client
+                .write_file_range(&header.remote_path, *offset, data)
+                .map_err(|e| format!("'{}' 미반영 쓰기 적용 실패: {}", header.remote_path, e))?;
+        }
+
+        Ok(header.remote_path)
+    }
+
+    /// 저널을 대기열 항목으로 되살림 (이어서 기록할 수 있도록 저널을 다시 엶)
+    fn requeue(path: &Path, identity: &str) -> Result<(String, DirtyFile), String> {
+        let (header, buffer, flush_started) = Self::read_entry(path, identity)?;
+        let file = OpenOptions::new()
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("저널 파일 열기 실패: {}", e))?;
+        let dirty = DirtyFile {
+            buffer,
+            journal: Some(JournalFile {
+                path: path.to_path_buf(),
+                file,
+            }),
+            base_size: header.base_size,
+            base_mtime: header.base_mtime,
+            flush_started,
+            queued_at: unix_now(),
+        };
+        Ok((header.remote_path, dirty))
+    }
+
+    /// 저널 파일 읽기 (헤더, 병합한 쓰기, 반영 시작 여부)
+    fn read_entry(
+        path: &Path,
+        identity: &str,
+    ) -> Result<(JournalHeader, WriteBuffer, bool), String> {
+        let file = File::open(path).map_err(|e| format!("저널 파일 열기 실패: {}", e))?;
+        let mut reader = BufReader::new(file);
+
+        let mut header_line = String::new();
+        reader
+            .read_line(&mut header_line)
+            .map_err(|e| format!("저널 파일 읽기 실패: {}", e))?;
+        let mut header: JournalHeader = serde_json::from_str(header_line.trim_end())
+            .map_err(|e| format!("저널 헤더 파싱 실패: {}", e))?;
+        if let Some(written_by) = header.identity.as_deref().filter(|w| *w != identity) {
+            return Err(format!(
+                "'{}' 미반영 쓰기는 다른 계정({})으로 기록되어 {}로 적용하지 않았습니다.",
+                header.remote_path, written_by, identity
+            ));
+        }
+
+        let mut buffer = WriteBuffer::default();
+        let mut flush_started = false;
+        loop {
+            let mut fixed = [0u8; 12];
+            match reader.read_exact(&mut fixed) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(format!("저널 파일 읽기 실패: {}", e)),
+            }
+            let offset = u64::from_le_bytes(fixed[..8].try_into().unwrap());
+            let len = u32::from_le_bytes(fixed[8..].try_into().unwrap());
+            if len == FLUSH_STARTED_MARKER {
+                flush_started = true;
+                continue;
+            }
+            if len == RENAMED_MARKER {
+                let mut name = vec![0u8; offset as usize];
+                if reader.read_exact(&mut name).is_err() {
+                    break;
+                }
+                header.remote_path = String::from_utf8_lossy(&name).into_owned();
+                continue;
+            }
+            let mut data = vec![0u8; len as usize];
+            if reader.read_exact(&mut data).is_err() {
+                // 기록 도중 종료된 마지막 레코드는 버림
+                break;
+            }
+            buffer.insert(offset, &data);
+        }
+
+        Ok((header, buffer, flush_started))
+    }
+
+    fn move_to_recovered(&self, path: &Path) -> Option<PathBuf> {
+        let recovered_dir = self.dir.join("recovered");
+        fs::create_dir_all(&recovered_dir).ok()?;
+        let target = recovered_dir.join(path.file_name()?);
+        fs::rename(path, &target).ok()?;
+        Some(target)
+    }
+}
+
+/// 겹치거나 인접한 쓰기를 하나로 합치는 오프셋 정렬 버퍼
+#[derive(Default)]
+pub struct WriteBuffer {
+    extents: BTreeMap<u64, Vec<u8>>,
+    dirty_bytes: usize,
+}
+
+impl WriteBuffer {
+    pub fn insert(&mut self, offset: u64, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+        let mut start = offset;
+        let mut end = offset + data.len() as u64;
+
+        // 새 범위와 겹치거나 맞닿는 기존 구간 수집
+        let overlapping: Vec<u64> = self
+            .extents
+            .range(..=end)
+            .filter(|(off, buf)| **off + buf.len() as u64 >= start)
+            .map(|(off, _)| *off)
+            .collect();
+
+        let mut merged_parts = Vec::new();
+        for off in overlapping {
+            let buf = self.extents.remove(&off).unwrap();
+            self.dirty_bytes -= buf.len();
+            start = start.min(off);
+            end = end.max(off + buf.len() as u64);
+            merged_parts.push((off, buf));
+        }
+
+        let mut merged = vec![0u8; (end - start) as usize];
+        for (off, buf) in merged_parts {
+            let at = (off - start) as usize;
+            merged[at..at + buf.len()].copy_from_slice(&buf);
+        }
+        // 새 데이터가 기존 데이터를 덮어씀
+        let at = (offset - start) as usize;
+        merged[at..at + data.len()].copy_from_slice(data);
+
+        self.dirty_bytes += merged.len();
+        self.extents.insert(start, merged);
+    }
+
+    /// 버퍼의 데이터를 읽기 요청 범위에 덮어씀 (아직 반영되지 않은 쓰기도 읽히도록)
+    pub fn overlay(&self, offset: u64, buffer: &mut [u8], valid_len: usize) -> usize {
+        // 원격 EOF 이후 영역은 0으로 (버퍼로 파일이 늘어난 경우)
+        buffer[valid_len..].fill(0);
+        let req_end = offset + buffer.len() as u64;
+        let mut valid_end = offset + valid_len as u64;
+        for (off, data) in self.extents.range(..req_end) {
+            let ext_end = off + data.len() as u64;
+            if ext_end <= offset {
+                continue;
+            }
+            let from = (*off).max(offset);
+            let to = ext_end.min(req_end);
+            buffer[(from - offset) as usize..(to - offset) as usize]
+                .copy_from_slice(&data[(from - off) as usize..(to - off) as usize]);
+            valid_end = valid_end.max(to);
+        }
+        (valid_end - offset) as usize
+    }
+
+    /// 버퍼에 기록된 가장 먼 오프셋 (파일 크기 계산용)
+    pub fn end_offset(&self) -> u64 {
+        self.extents
+            .iter()
+            .next_back()
+            .map(|(off, data)| off + data.len() as u64)
+            .unwrap_or(0)
+    }
+
+    /// 가장 앞쪽 구간을 꺼냄
+    pub fn pop_first(&mut self) -> Option<(u64, Vec<u8>)> {
+        let (offset, data) = self.extents.pop_first()?;
+        self.dirty_bytes -= data.len();
+        Some((offset, data))
+    }
+
+    pub fn extents(&self) -> impl Iterator<Item = (&u64, &Vec<u8>)> {
+        self.extents.iter()
+    }
+
+    pub fn dirty_bytes(&self) -> usize {
+        self.dirty_bytes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.extents.is_empty()
+    }
+}
+
+/// 열린 파일 하나의 미반영 쓰기 (버퍼 + 저널)
+pub struct DirtyFile {
+    pub buffer: WriteBuffer,
+    journal: Option<JournalFile>,
+    // 첫 쓰기 시점의 원격 크기/수정 시간 (대기열을 올릴 때 충돌 확인용)
+    base_size: Option<u64>,
+    base_mtime: Option<u64>,
+    // 원격 반영을 시작해 원격 파일이 이미 일부 바뀌었음
+    flush_started: bool,
+    // 첫 쓰기 시각 (유닉스 시간, 초)
+    queued_at: u64,
+}
+
+impl DirtyFile {
+    /// 첫 쓰기 시점에 생성 (저널 생성 실패 시 메모리 버퍼만 사용)
+    pub fn new(
+        journal_dir: Option<&WriteJournal>,
+        remote_path: &str,
+        base: Option<&ssh2::FileStat>,
+    ) -> Self {
+        let journal = journal_dir.and_then(|j| match j.create_entry(remote_path, base) {
+            Ok(file) => Some(file),
+            Err(e) => {
+                warn!("[Journal] {}", e);
+                None
+            }
+        });
+        Self {
+            buffer: WriteBuffer::default(),
+            journal,
+            base_size: base.and_then(|s| s.size),
+            base_mtime: base.and_then(|s| s.mtime),
+            flush_started: false,
+            queued_at: unix_now(),
+        }
+    }
+
+    /// 대기 중인 쓰기를 반영한 파일 크기
+    pub fn effective_size(&self) -> u64 {
+        self.buffer.end_offset().max(self.base_size.unwrap_or(0))
+    }
+
+    pub fn write(&mut self, offset: u64, data: &[u8]) {
+        if let Some(journal) = self.journal.as_mut() {
+            if let Err(e) = journal.append(offset, data) {
+                warn!("[Journal] {}", e);
+                self.journal = None;
+            }
+        }
+        self.buffer.insert(offset, data);
+    }
+
+    /// 반영하지 않고 버림 (파일이 삭제된 경우)
+    pub fn discard(self) {
+        if let Some(journal) = self.journal {
+            journal.discard();
+        }
+    }
+
+    /// 버퍼 내용을 원격에 반영하고 저널 삭제
+    ///
+    /// 서버가 수락한 부분은 즉시 버퍼에서 제거하므로, 연결이 끊기면 재연결 후
+    /// 마지막으로 확인된 오프셋부터 이어쓴다. 끝내 실패하면 남은 버퍼와 저널을 그대로
+    /// 돌려주어 다음 flush나 다음 마운트에서 재시도할 수 있게 한다.
+    pub fn flush(
+        self,
+        streams: &StreamPool,
+        remote_path: &str,
+        stats: &StatsCollector,
+    ) -> Result<(), (Box<Self>, String)> {
+        self.flush_to(streams, remote_path, stats)
+    }
+
+    fn flush_to(
+        mut self,
+        target: &impl UploadTarget,
+        remote_path: &str,
+        stats: &StatsCollector,
+    ) -> Result<(), (Box<Self>, String)> {
+        if self.buffer.is_empty() {
+            if let Some(journal) = self.journal {
+                journal.discard();
+            }
+            return Ok(());
+        }
+
+        if let Some(journal) = self.journal.as_mut() {
+            if let Err(e) = journal.mark_flush_started() {
+                warn!("[Journal] {}", e);
+            }
+        }
+        self.flush_started = true;
+
+        let result = self.upload(target, remote_path, stats);
+        // 실패해도 진행 항목은 지움 (남아 있으면 자동 언마운트와 절전 허용을 계속 막음)
+        stats.finish_upload(remote_path);
+        if let Err(e) = result {
+            return Err((Box::new(self), e));
+        }
+        if let Some(journal) = self.journal {
+            journal.discard();
+        }
+        Ok(())
+    }
+
+    /// 버퍼를 앞에서부터 올림 (끊기면 재연결 후 확인된 위치부터 이어씀)
+    fn upload(
+        &mut self,
+        target: &impl UploadTarget,
+        remote_path: &str,
+        stats: &StatsCollector,
+    ) -> Result<(), String> {
+        let mut confirmed = 0u64;
+        let mut attempts = 0;
+        while let Some((offset, data)) = self.buffer.pop_first() {
+            match target.write_range(remote_path, offset, &data) {
+                Ok(()) => confirmed += data.len() as u64,
+                Err((written, e)) => {
+                    confirmed += written as u64;
+                    self.buffer
+                        .insert(offset + written as u64, &data[written..]);
+                    stats.record_upload_progress(
+                        remote_path,
+                        confirmed,
+                        self.buffer.dirty_bytes() as u64,
+                    );
+
+                    attempts += 1;
+                    if attempts > MAX_RESUME_ATTEMPTS {
+                        return Err(e);
+                    }
+                    warn!(
+                        "[WriteBack] upload of '{}' interrupted at offset {}: {} - reconnecting",
+                        remote_path,
+                        offset + written as u64,
+                        e
+                    );
+                    target.reconnect()?;
+                    continue;
+                }
+            }
+            stats.record_upload_progress(remote_path, confirmed, self.buffer.dirty_bytes() as u64);
+        }
+        Ok(())
+    }
+}
+
+/// 미반영 쓰기를 올릴 원격 연산
+trait UploadTarget {
+    /// 범위 쓰기 (실패하면 앞에서부터 확인된 바이트 수와 오류)
+    fn write_range(&self, path: &str, offset: u64, data: &[u8]) -> Result<(), (usize, String)>;
+    fn reconnect(&self) -> Result<(), String>;
+}
+
+impl UploadTarget for StreamPool {
+    fn write_range(&self, path: &str, offset: u64, data: &[u8]) -> Result<(), (usize, String)> {
+        StreamPool::write_range(self, path, offset, data)
+    }
+
+    fn reconnect(&self) -> Result<(), String> {
+        StreamPool::reconnect(self)
+    }
+}
+
+/// 변경 대기 모드에서 올리기를 기다리는 쓰기 (원격 경로별)
+#[derive(Default)]
+pub struct ChangeQueue {
+    entries: Mutex<BTreeMap<String, DirtyFile>>,
+}
+
+impl ChangeQueue {
+    /// 닫힌 파일의 쓰기를 대기열에 넣음
+    pub fn put(&self, remote_path: &str, dirty: DirtyFile) {
+        self.entries.lock().insert(remote_path.to_string(), dirty);
+    }
+
+    /// 파일을 다시 열어 쓸 때 대기 중인 쓰기를 꺼냄 (이어서 기록)
+    pub fn take(&self, remote_path: &str) -> Option<DirtyFile> {
+        self.entries.lock().remove(remote_path)
+    }
+
+    pub fn contains(&self, remote_path: &str) -> bool {
+        self.entries.lock().contains_key(remote_path)
+    }
+
+    /// 대기 중인 쓰기를 반영한 파일 크기
+    pub fn size_of(&self, remote_path: &str) -> Option<u64> {
+        self.entries
+            .lock()
+            .get(remote_path)
+            .map(DirtyFile::effective_size)
+    }
+
+    /// 대기 중인 내용을 읽기 결과에 덮어씀 (대기 중인 파일이 아니면 None)
+    pub fn overlay(
+        &self,
+        remote_path: &str,
+        offset: u64,
+        buffer: &mut [u8],
+        valid_len: usize,
+    ) -> Option<usize> {
+        let entries = self.entries.lock();
+        let dirty = entries.get(remote_path)?;
+        // 대기 중인 크기 너머는 읽지 않음 (원격이 더 길어졌어도 대기 중인 내용 기준)
+        let end = dirty.effective_size().saturating_sub(offset);
+        let len = dirty
+            .buffer
+            .overlay(offset, buffer, valid_len)
+            .min(usize::try_from(end).unwrap_or(usize::MAX));
+        Some(len)
+    }
+
+    /// 원격 파일 이름이 바뀌면 대기 중인 쓰기도 따라감 (디렉토리면 그 아래 파일 모두)
+    pub fn rename(&self, from: &str, to: &str) {
+        let mut entries = self.entries.lock();
+        // 덮어쓴 대상의 대기 중인 쓰기는 버림
+        if let Some(replaced) = entries.remove(to) {
+            replaced.discard();
+        }
+        let moved: Vec<(String, String)> = entries
+            .keys()
+            .filter_map(|path| moved_path(path, from, to).map(|moved| (path.clone(), moved)))
+            .collect();
+        for (old, new) in moved {
+            let Some(mut dirty) = entries.remove(&old) else {
+                continue;
+            };
+            if let Some(journal) = dirty.journal.as_mut() {
+                if let Err(e) = journal.record_rename(&new) {
+                    warn!("[Journal] {}", e);
+                }
+            }
+            entries.insert(new, dirty);
+        }
+    }
+
+    /// 대기 중인 쓰기 버림 (되돌리기 또는 파일 삭제)
+    pub fn discard(&self, remote_path: &str) -> bool {
+        match self.entries.lock().remove(remote_path) {
+            Some(dirty) => {
+                dirty.discard();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+
+    /// 검토용 대기 목록
+    pub fn list(&self) -> Vec<PendingChange> {
+        self.entries
+            .lock()
+            .iter()
+            .map(|(path, dirty)| PendingChange {
+                path: path.clone(),
+                size: dirty.effective_size(),
+                pending_bytes: dirty.buffer.dirty_bytes() as u64,
+                queued_at: dirty.queued_at,
+            })
+            .collect()
+    }
+
+    /// 대기 중인 쓰기를 모두 원격에 반영 (반영한 경로를 `pushed`에 담아 반환)
+    ///
+    /// 대기열에 넣은 뒤 서버에서 바뀐 파일은 덮어쓰지 않고 남겨 두어 사용자가 확인한 뒤
+    /// 버리거나 다시 올리도록 한다. 실패한 파일도 대기열에 그대로 남는다.
+    pub fn push(
+        &self,
+        streams: &StreamPool,
+        stats: &StatsCollector,
+        keep_versions: Option<u32>,
+    ) -> PushReport {
+        let mut report = PushReport::default();
+        let paths: Vec<String> = self.entries.lock().keys().cloned().collect();
+        for path in paths {
+            let Some(dirty) = self.take(&path) else {
+                continue;
+            };
+            if !dirty.flush_started {
+                let current = streams.metadata().stat(&path).ok();
+                let unchanged = match &current {
+                    Some(stat) => stat.size == dirty.base_size && stat.mtime == dirty.base_mtime,
+                    None => dirty.base_size.is_none() && dirty.base_mtime.is_none(),
+                };
+                if !unchanged {
+                    report.errors.push(format!(
+                        "'{}'가 변경 대기 이후 서버에서 바뀌어 올리지 않았습니다.",
+                        path
+                    ));
+                    self.put(&path, dirty);
+                    continue;
+                }
+                if let Some(keep) = keep_versions {
+                    if let Err(e) = versions::save(&streams.metadata(), &path, keep) {
+                        warn!("[WriteBack] keeping a version of '{}' failed: {}", path, e);
+                    }
+                }
+            }
+            match dirty.flush(streams, &path, stats) {
+                Ok(()) => report.pushed.push(path),
+                Err((dirty, e)) => {
+                    report.errors.push(format!("'{}' 올리기 실패: {}", path, e));
+                    self.put(&path, *dirty);
+                }
+            }
+        }
+        info!(
+            "[WriteBack] pushed {} queued files, {} left",
+            report.pushed.len(),
+            self.len()
+        );
+        report
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queued(queue: &ChangeQueue, path: &str, data: &[u8]) {
+        let mut dirty = DirtyFile::new(None, path, None);
+        dirty.write(0, data);
+        queue.put(path, dirty);
+    }
+
+    fn paths(queue: &ChangeQueue) -> Vec<String> {
+        queue.list().into_iter().map(|change| change.path).collect()
+    }
+
+    #[test]
+    fn rename_moves_queued_files_under_directory() {
+        let queue = ChangeQueue::default();
+        queued(&queue, "/d/a/x.txt", b"x");
+        queued(&queue, "/d/a/sub/y.txt", b"y");
+        queued(&queue, "/d/ab.txt", b"z");
+        queue.rename("/d/a", "/d/c");
+        assert_eq!(
+            paths(&queue),
+            vec!["/d/ab.txt", "/d/c/sub/y.txt", "/d/c/x.txt"]
+        );
+    }
+
+    #[test]
+    fn rename_over_queued_file_replaces_it() {
+        let queue = ChangeQueue::default();
+        queued(&queue, "/d/a.txt", b"new");
+        queued(&queue, "/d/b.txt", b"old, longer");
+        queue.rename("/d/a.txt", "/d/b.txt");
+        assert_eq!(paths(&queue), vec!["/d/b.txt"]);
+        assert_eq!(queue.size_of("/d/b.txt"), Some(3));
+    }
+
+    /// 앞의 `accept` 바이트만 받고 끊기며 재연결도 실패하는 서버
+    struct DroppingServer {
+        accept: usize,
+    }
+
+    impl UploadTarget for DroppingServer {
+        fn write_range(&self, _: &str, _: u64, data: &[u8]) -> Result<(), (usize, String)> {
+            Err((self.accept.min(data.len()), "연결 끊김".to_string()))
+        }
+
+        fn reconnect(&self) -> Result<(), String> {
+            Err("재연결 실패".to_string())
+        }
+    }
+
+    #[test]
+    fn failed_flush_returns_buffer_and_ends_upload() {
+        let stats = StatsCollector::default();
+        let mut dirty = DirtyFile::new(None, "/d/big.bin", None);
+        dirty.write(0, b"0123456789");
+
+        let (dirty, _) = dirty
+            .flush_to(&DroppingServer { accept: 4 }, "/d/big.bin", &stats)
+            .expect_err("upload should fail");
+        // 서버가 받은 부분은 빼고 나머지를 돌려주되 올리는 중으로 남지 않음
+        assert_eq!(dirty.buffer.dirty_bytes(), 6);
+        assert!(!stats.is_uploading());
+    }
+}
@@ -0,0 +1,146 @@
+//! 확장 속성 모듈 - POSIX xattr(user.*)을 WinFsp EA(FILE_FULL_EA_INFORMATION)로 전달
+//!
+//! libssh2는 xattr용 SFTP 확장을 지원하지 않으므로 exec 채널의 getfattr/setfattr로 읽고 쓴다.
+//! Windows EA 이름은 대소문자를 구분하지 않아 `user.` 접두사를 뗀 이름을 대문자로 보여주고,
+//! 설정할 때는 소문자로 바꿔 `user.` 접두사를 붙인다.
+
+use crate::sftp_client::{shell_quote, SftpClient};
+
+/// 전달 대상 xattr 네임스페이스 (다른 네임스페이스는 권한이 필요)
+const XATTR_PREFIX: &str = "user.";
+
+/// 원격 파일의 user.* xattr 목록 (Windows EA 이름, 값)
+pub fn read_xattrs(client: &SftpClient, path: &str) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let output = client
+        .exec(&format!(
+            "getfattr --absolute-names -d -m '^user\\.' -e hex -- {}",
+            shell_quote(&client.server_path(path))
+        ))
+        .map_err(|e| format!("확장 속성 읽기 실패: {}", e))?;
+    Ok(parse_getfattr(&output))
+}
+
+/// 원격 파일의 xattr 설정 (값이 비어 있으면 삭제)
+pub fn write_xattr(
+    client: &SftpClient,
+    path: &str,
+    ea_name: &str,
+    value: &[u8],
+) -> Result<(), String> {
+    let name = shell_quote(&format!("{}{}", XATTR_PREFIX, ea_name.to_ascii_lowercase()));
+    let path = shell_quote(&client.server_path(path));
+    let command = if value.is_empty() {
+        format!("setfattr -x {} -- {}", name, path)
+    } else {
+        format!("setfattr -n {} -v 0x{} -- {}", name, to_hex(value), path)
+    };
+    client
+        .exec(&command)
+        .map(|_| ())
+        .map_err(|e| format!("확장 속성 쓰기 실패: {}", e))
+}
+
+/// `getfattr -d -e hex` 출력 파싱 ("user.name=0x6869" 형식)
+fn parse_getfattr(output: &str) -> Vec<(String, Vec<u8>)> {
+    output
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let (name, value) = line.split_once('=').unwrap_or((line, ""));
+            let name = name.strip_prefix(XATTR_PREFIX)?;
+            let value = match value.strip_prefix("0x") {
+                Some(hex) => from_hex(hex)?,
+                None => value.trim_matches('"').as_bytes().to_vec(),
+            };
+            Some((name.to_ascii_uppercase(), value))
+        })
+        .collect()
+}
+
+/// EA 목록을 FILE_FULL_EA_INFORMATION 연결 목록으로 기록하고 기록한 바이트 수를 반환
+///
+/// 공간이 부족하면 들어가는 항목까지만 기록한다.
+pub fn encode_ea_buffer(entries: &[(String, Vec<u8>)], buffer: &mut [u8]) -> u32 {
+    let mut cursor = 0;
+    let mut written = 0;
+    let mut last_entry: Option<usize> = None;
+
+    for (name, value) in entries {
+        if name.len() > u8::MAX as usize || value.len() > u16::MAX as usize {
+            continue;
+        }
+        // NextEntryOffset(4) + Flags(1) + EaNameLength(1) + EaValueLength(2) + 이름 + NUL + 값
+        let entry_len = 8 + name.len() + 1 + value.len();
+        let start = cursor;
+        if start + entry_len > buffer.len() {
+            break;
+        }
+
+        let entry = &mut buffer[start..start + entry_len];
+        entry[0..4].copy_from_slice(&0u32.to_le_bytes());
+        entry[4] = 0;
+        entry[5] = name.len() as u8;
+        entry[6..8].copy_from_slice(&(value.len() as u16).to_le_bytes());
+        entry[8..8 + name.len()].copy_from_slice(name.as_bytes());
+        entry[8 + name.len()] = 0;
+        entry[9 + name.len()..].copy_from_slice(value);
+
+        if let Some(previous) = last_entry {
+            let offset = (start - previous) as u32;
+            buffer[previous..previous + 4].copy_from_slice(&offset.to_le_bytes());
+        }
+        last_entry = Some(start);
+        written = start + entry_len;
+
+        // 다음 항목은 4바이트 정렬
+        cursor = (written + 3) & !3;
+    }
+    written as u32
+}
+
+/// FILE_FULL_EA_INFORMATION 연결 목록을 (EA 이름, 값) 목록으로 해석
+pub fn decode_ea_buffer(buffer: &[u8]) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let mut entries = Vec::new();
+    let mut offset = 0;
+    loop {
+        let (next, name_len, value_len) =
+            read_entry_header(buffer, offset).ok_or("EA 버퍼 형식 오류")?;
+        let name_start = offset + 8;
+        let value_start = name_start + name_len + 1;
+        let name = buffer
+            .get(name_start..name_start + name_len)
+            .ok_or("EA 버퍼 형식 오류")?;
+        let value = buffer
+            .get(value_start..value_start + value_len)
+            .ok_or("EA 버퍼 형식 오류")?;
+        entries.push((String::from_utf8_lossy(name).into_owned(), value.to_vec()));
+
+        if next == 0 {
+            break;
+        }
+        offset += next;
+    }
+    Ok(entries)
+}
+
+/// 항목 헤더 (NextEntryOffset, EaNameLength, EaValueLength)
+fn read_entry_header(buffer: &[u8], offset: usize) -> Option<(usize, usize, usize)> {
+    let header = buffer.get(offset..offset + 8)?;
+    let next = u32::from_le_bytes([header[0], header[1], header[2], header[3]]) as usize;
+    let value_len = u16::from_le_bytes([header[6], header[7]]) as usize;
+    Some((next, header[5] as usize, value_len))
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub(crate) fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
@@ -0,0 +1,10 @@
+fn main() {
+    // WinFsp DLL 지연 로딩 설정 (서비스 시작 시 PATH에 WinFsp bin을 넣은 뒤 로드)
+    if cfg!(all(target_os = "windows", target_arch = "x86_64")) {
+        println!("cargo:rustc-link-lib=dylib=delayimp");
+        println!("cargo:rustc-link-arg=/DELAYLOAD:winfsp-x64.dll");
+    } else if cfg!(all(target_os = "windows", target_arch = "x86")) {
+        println!("cargo:rustc-link-lib=dylib=delayimp");
+        println!("cargo:rustc-link-arg=/DELAYLOAD:winfsp-x86.dll");
+    }
+}
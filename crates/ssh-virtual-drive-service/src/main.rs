@@ -0,0 +1,98 @@
+//! SSH 가상 드라이브 서비스 - 로그인 전에도 드라이브를 유지하는 Windows 서비스
+//!
+//! 서비스는 LocalSystem 계정으로 [`MountManager`]를 띄우고, GUI 앱은 이름 있는 파이프
+//! ([`SERVICE_PIPE`](ssh_virtual_drive_core::ipc::SERVICE_PIPE))로 드라이브를 추가하거나
+//! 내린다. 서비스가 마운트한 드라이브는 전역 드라이브라 모든 사용자 세션에 보이고,
+//! 목록은 서비스 계정의 데이터 디렉토리에 저장되어 다음 부팅 때 다시 마운트된다.
+//!
+//! ```text
+//! ssh-virtual-drive-service install     서비스 등록 (자동 시작, 관리자 권한 필요)
+//! ssh-virtual-drive-service uninstall   서비스 제거
+//! ssh-virtual-drive-service run         콘솔에서 직접 실행 (디버깅용)
+//! ```
+
+mod scm;
+mod server;
+
+use log::error;
+use server::Server;
+use ssh_virtual_drive_core::mount::MountManager;
+use ssh_virtual_drive_core::{logging, mount};
+use std::process::{Command, ExitCode};
+use std::sync::Arc;
+
+/// 서비스 이름 (`sc.exe`에서 사용)
+const SERVICE_NAME: &str = "SshVirtualDrive";
+
+/// 서비스 관리자에 보이는 이름
+const DISPLAY_NAME: &str = "SSH Virtual Drive";
+
+fn main() -> ExitCode {
+    logging::init();
+    let command = std::env::args().nth(1);
+    let result = match command.as_deref() {
+        Some("install") => install(),
+        Some("uninstall") => uninstall(),
+        Some("run") => start().serve(),
+        None => scm::dispatch(SERVICE_NAME),
+        Some(other) => Err(format!(
+            "알 수 없는 명령: {} (install, uninstall, run 중 하나)",
+            other
+        )),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            error!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// 저장된 드라이브 복원을 시작한 서버 (파이프는 `serve`에서 열림)
+fn start() -> Arc<Server> {
+    mount::setup_winfsp_path();
+    Server::start(MountManager::default())
+}
+
+fn install() -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| format!("실행 파일 경로 확인 실패: {}", e))?;
+    sc(&[
+        "create",
+        SERVICE_NAME,
+        "binPath=",
+        &format!("\"{}\"", exe.display()),
+        "start=",
+        "auto",
+        "DisplayName=",
+        DISPLAY_NAME,
+    ])?;
+    sc(&[
+        "description",
+        SERVICE_NAME,
+        "로그인 전에도 SSH 가상 드라이브를 마운트해 둡니다.",
+    ])?;
+    sc(&["start", SERVICE_NAME])
+}
+
+fn uninstall() -> Result<(), String> {
+    // 이미 멈춰 있으면 stop은 실패하므로 결과를 보지 않음
+    let _ = sc(&["stop", SERVICE_NAME]);
+    sc(&["delete", SERVICE_NAME])
+}
+
+fn sc(args: &[&str]) -> Result<(), String> {
+    let output = Command::new("sc.exe")
+        .args(args)
+        .output()
+        .map_err(|e| format!("sc.exe 실행 실패: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "sc.exe {} 실패: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stdout).trim()
+        ))
+    }
+}
@@ -0,0 +1,170 @@
+//! 서비스 제어 관리자(SCM) 연동 - 서비스 시작/중지 상태 보고
+
+use crate::server::Server;
+use log::info;
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+
+const SERVICE_WIN32_OWN_PROCESS: u32 = 0x0000_0010;
+const SERVICE_STOPPED: u32 = 1;
+const SERVICE_START_PENDING: u32 = 2;
+const SERVICE_STOP_PENDING: u32 = 3;
+const SERVICE_RUNNING: u32 = 4;
+const SERVICE_ACCEPT_STOP: u32 = 0x0000_0001;
+const SERVICE_ACCEPT_SHUTDOWN: u32 = 0x0000_0004;
+const SERVICE_CONTROL_STOP: u32 = 1;
+const SERVICE_CONTROL_INTERROGATE: u32 = 4;
+const SERVICE_CONTROL_SHUTDOWN: u32 = 5;
+const NO_ERROR: u32 = 0;
+const ERROR_CALL_NOT_IMPLEMENTED: u32 = 120;
+const ERROR_FAILED_SERVICE_CONTROLLER_CONNECT: i32 = 1063;
+
+/// 드라이브를 모두 내리는 데 걸릴 수 있는 시간 (SCM 대기 힌트, 밀리초)
+const STOP_WAIT_HINT_MS: u32 = 30_000;
+
+type ServiceMain = unsafe extern "system" fn(argc: u32, argv: *mut *mut u16);
+type HandlerEx = unsafe extern "system" fn(
+    control: u32,
+    event_type: u32,
+    event_data: *mut c_void,
+    context: *mut c_void,
+) -> u32;
+
+#[repr(C)]
+struct ServiceTableEntry {
+    service_name: *const u16,
+    service_proc: Option<ServiceMain>,
+}
+
+#[repr(C)]
+struct ServiceStatus {
+    service_type: u32,
+    current_state: u32,
+    controls_accepted: u32,
+    win32_exit_code: u32,
+    service_specific_exit_code: u32,
+    check_point: u32,
+    wait_hint: u32,
+}
+
+#[link(name = "advapi32")]
+extern "system" {
+    fn StartServiceCtrlDispatcherW(table: *const ServiceTableEntry) -> i32;
+    fn RegisterServiceCtrlHandlerExW(
+        service_name: *const u16,
+        handler: HandlerEx,
+        context: *mut c_void,
+    ) -> *mut c_void;
+    fn SetServiceStatus(handle: *mut c_void, status: *const ServiceStatus) -> i32;
+}
+
+/// 상태 보고 핸들 (RegisterServiceCtrlHandlerExW 결과)
+static STATUS_HANDLE: AtomicUsize = AtomicUsize::new(0);
+
+/// 중지 요청 때 드라이브를 내릴 서버
+static SERVER: OnceLock<Arc<Server>> = OnceLock::new();
+
+/// 서비스 이름 (서비스 메인에서 다시 필요)
+static SERVICE_NAME: OnceLock<Vec<u16>> = OnceLock::new();
+
+/// SCM에 서비스 진입점을 넘기고 서비스가 끝날 때까지 대기
+pub fn dispatch(service_name: &str) -> Result<(), String> {
+    let name = SERVICE_NAME.get_or_init(|| to_wide(service_name));
+    let table = [
+        ServiceTableEntry {
+            service_name: name.as_ptr(),
+            service_proc: Some(service_main),
+        },
+        ServiceTableEntry {
+            service_name: std::ptr::null(),
+            service_proc: None,
+        },
+    ];
+    if unsafe { StartServiceCtrlDispatcherW(table.as_ptr()) } != 0 {
+        return Ok(());
+    }
+    let error = std::io::Error::last_os_error();
+    if error.raw_os_error() == Some(ERROR_FAILED_SERVICE_CONTROLLER_CONNECT) {
+        Err(
+            "서비스 관리자가 실행한 것이 아닙니다. install, uninstall, run 중 하나를 지정해주세요."
+                .to_string(),
+        )
+    } else {
+        Err(format!("서비스 시작 실패: {}", error))
+    }
+}
+
+unsafe extern "system" fn service_main(_argc: u32, _argv: *mut *mut u16) {
+    let Some(name) = SERVICE_NAME.get() else {
+        return;
+    };
+    let handle =
+        RegisterServiceCtrlHandlerExW(name.as_ptr(), control_handler, std::ptr::null_mut());
+    if handle.is_null() {
+        return;
+    }
+    STATUS_HANDLE.store(handle as usize, Ordering::Release);
+
+    report(SERVICE_START_PENDING, 0);
+    let server = SERVER.get_or_init(crate::start).clone();
+    report(SERVICE_RUNNING, 0);
+    info!("[Service] running");
+
+    if let Err(e) = server.serve() {
+        log::error!("[Service] {}", e);
+        server.shutdown();
+        report(SERVICE_STOPPED, 0);
+    }
+}
+
+unsafe extern "system" fn control_handler(
+    control: u32,
+    _event_type: u32,
+    _event_data: *mut c_void,
+    _context: *mut c_void,
+) -> u32 {
+    match control {
+        SERVICE_CONTROL_STOP | SERVICE_CONTROL_SHUTDOWN => {
+            // 핸들러는 빨리 돌아와야 하므로 언마운트는 별도 스레드에서
+            std::thread::spawn(|| {
+                report(SERVICE_STOP_PENDING, STOP_WAIT_HINT_MS);
+                if let Some(server) = SERVER.get() {
+                    server.shutdown();
+                }
+                info!("[Service] stopped");
+                report(SERVICE_STOPPED, 0);
+                // 파이프 서버 스레드는 끝나지 않으므로 프로세스를 직접 종료
+                std::process::exit(0);
+            });
+            NO_ERROR
+        }
+        SERVICE_CONTROL_INTERROGATE => NO_ERROR,
+        _ => ERROR_CALL_NOT_IMPLEMENTED,
+    }
+}
+
+fn report(state: u32, wait_hint: u32) {
+    let handle = STATUS_HANDLE.load(Ordering::Acquire) as *mut c_void;
+    if handle.is_null() {
+        return;
+    }
+    let status = ServiceStatus {
+        service_type: SERVICE_WIN32_OWN_PROCESS,
+        current_state: state,
+        controls_accepted: if state == SERVICE_RUNNING {
+            SERVICE_ACCEPT_STOP | SERVICE_ACCEPT_SHUTDOWN
+        } else {
+            0
+        },
+        win32_exit_code: NO_ERROR,
+        service_specific_exit_code: 0,
+        check_point: u32::from(state == SERVICE_START_PENDING || state == SERVICE_STOP_PENDING),
+        wait_hint,
+    };
+    unsafe { SetServiceStatus(handle, &status) };
+}
+
+fn to_wide(value: &str) -> Vec<u16> {
+    value.encode_utf16().chain(std::iter::once(0)).collect()
+}
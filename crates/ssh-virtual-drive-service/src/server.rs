@@ -0,0 +1,315 @@
+//! 파이프 서버 - GUI 앱의 요청으로 서비스 드라이브를 마운트/언마운트
+//!
+//! | 메서드 | 매개변수 | 결과 |
+//! |---|---|---|
+//! | `list` | 없음 | `DriveStatus[]` |
+//! | `mount` | `{connection, drive_letter, secrets}` | `DriveStatus` |
+//! | `unmount` | `{drive_letter}` | `null` |
+//! | `stats` | `{drive_letter}` | `MountStats` |
+
+use log::{info, warn};
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde_json::Value;
+use ssh_virtual_drive_core::credentials;
+use ssh_virtual_drive_core::ipc::{self, RpcError, SERVICE_PIPE};
+use ssh_virtual_drive_core::mount::MountManager;
+use ssh_virtual_drive_core::reauth;
+use ssh_virtual_drive_core::storage;
+use ssh_virtual_drive_core::types::{
+    AuthType, ConnectionHooks, ConnectionSecrets, DriveStatus, ServiceMount, SshConnection,
+};
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 파이프 접근 권한: SYSTEM과 관리자만 (일반 사용자 프로세스는 연결 불가)
+const PIPE_SDDL: &str = "D:P(A;;GA;;;SY)(A;;GA;;;BA)";
+
+/// 부팅 직후 네트워크가 없어 실패한 드라이브를 다시 시도하는 주기
+const RESTORE_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Deserialize)]
+struct MountParams {
+    connection: SshConnection,
+    drive_letter: char,
+    #[serde(default)]
+    secrets: ConnectionSecrets,
+}
+
+#[derive(Deserialize)]
+struct DriveParams {
+    drive_letter: char,
+}
+
+/// 서비스 드라이브 관리자
+pub struct Server {
+    manager: MountManager,
+    /// 서비스가 맡은 드라이브 (마운트에 실패해 재시도 중인 것 포함)
+    mounts: Mutex<Vec<ServiceMount>>,
+}
+
+impl Server {
+    /// 저장된 드라이브 목록을 읽고 백그라운드에서 다시 마운트 시작
+    pub fn start(manager: MountManager) -> Arc<Self> {
+        let server = Arc::new(Self {
+            manager,
+            mounts: Mutex::new(load_mounts()),
+        });
+
+        let restorer = server.clone();
+        std::thread::spawn(move || loop {
+            if restorer.restore_pending() {
+                break;
+            }
+            std::thread::sleep(RESTORE_RETRY_INTERVAL);
+        });
+
+        server
+    }
+
+    /// 파이프 서버 실행 (돌아오지 않음)
+    pub fn serve(self: &Arc<Self>) -> Result<(), String> {
+        let server = self.clone();
+        info!("[Service] listening on {}", SERVICE_PIPE);
+        ipc::serve(
+            SERVICE_PIPE,
+            PIPE_SDDL,
+            Arc::new(move |method: &str, params: Value| server.dispatch(method, params)),
+        )
+    }
+
+    /// 서비스 중지 시 모든 드라이브 언마운트 (목록은 다음 시작을 위해 유지)
+    pub fn shutdown(&self) {
+        // 별칭은 가리키는 드라이브를 내릴 때 함께 내려감
+        for drive in self
+            .manager
+            .get_mounted_drives()
+            .into_iter()
+            .filter(|drive| drive.alias_of.is_none())
+        {
+            if let Err(e) = self.manager.unmount(drive.drive_letter) {
+                warn!("[Service] unmount {}: failed: {}", drive.drive_letter, e);
+            }
+        }
+    }
+
+    fn dispatch(&self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "list" => ipc::to_result(self.manager.get_mounted_drives()),
+            "mount" => {
+                let params: MountParams = ipc::parse_params(params)?;
+                let status = self
+                    .mount(params.connection, params.drive_letter, params.secrets)
+                    .map_err(RpcError::server)?;
+                ipc::to_result(status)
+            }
+            "unmount" => {
+                let params: DriveParams = ipc::parse_params(params)?;
+                self.unmount(params.drive_letter)
+                    .map_err(RpcError::server)?;
+                Ok(Value::Null)
+            }
+            "stats" => {
+                let params: DriveParams = ipc::parse_params(params)?;
+                let stats = self
+                    .manager
+                    .get_mount_stats(params.drive_letter)
+                    .map_err(RpcError::server)?;
+                ipc::to_result(stats)
+            }
+            _ => Err(RpcError::new(
+                ipc::METHOD_NOT_FOUND,
+                format!("알 수 없는 메서드: {}", method),
+            )),
+        }
+    }
+
+    fn mount(
+        &self,
+        mut connection: SshConnection,
+        drive_letter: char,
+        secrets: ConnectionSecrets,
+    ) -> Result<DriveStatus, String> {
+        if connection.key_id.is_some() {
+            return Err(
+                "키 저장소의 키는 서비스에서 사용할 수 없습니다. 키 파일 경로를 지정해주세요."
+                    .to_string(),
+            );
+        }
+        if connection.auth_type == AuthType::Agent {
+            return Err("서비스는 사용자의 SSH 에이전트에 접근할 수 없습니다.".to_string());
+        }
+        // 서비스 세션의 드라이브는 사용자 세션에서 보이도록 항상 전역으로 마운트
+        connection.global_mount = true;
+        // 훅은 LocalSystem 권한으로 실행되므로 서비스에서는 받지 않음
+        if !connection.hooks.is_empty() {
+            warn!("[Service] hooks ignored for {}", connection.id);
+            connection.hooks = ConnectionHooks::default();
+        }
+
+        save_secrets(&connection.id, &secrets)?;
+        // 새 비밀 값을 받았으므로 거부되었던 비밀번호로 멈춘 재시도 재개
+        reauth::clear(&connection.id);
+        let password = secrets.password.as_deref();
+        let status = match self.manager.mount(&connection, drive_letter, password) {
+            Ok(status) => status,
+            Err(e) => {
+                if !self.has_connection(&connection.id) {
+                    delete_secrets(&connection.id);
+                }
+                return Err(e);
+            }
+        };
+
+        info!("[Service] mounted {} on {}:", connection.id, drive_letter);
+        let mut mounts = self.mounts.lock();
+        mounts.retain(|m| m.drive_letter != drive_letter);
+        mounts.push(ServiceMount {
+            connection,
+            drive_letter,
+        });
+        save_mounts(&mounts);
+        Ok(status)
+    }
+
+    fn unmount(&self, drive_letter: char) -> Result<(), String> {
+        let removed = {
+            let mut mounts = self.mounts.lock();
+            let index = mounts.iter().position(|m| m.drive_letter == drive_letter);
+            let removed = index.map(|i| mounts.remove(i));
+            save_mounts(&mounts);
+            removed
+        };
+
+        let result = if self.manager.is_mounted(drive_letter) {
+            self.manager.unmount(drive_letter)
+        } else if removed.is_some() {
+            // 아직 다시 마운트하지 못한 드라이브는 목록에서만 제거
+            Ok(())
+        } else {
+            Err(format!(
+                "드라이브 {}:가 마운트되어 있지 않습니다.",
+                drive_letter
+            ))
+        };
+
+        if let Some(mount) = removed {
+            if !self.has_connection(&mount.connection.id) {
+                delete_secrets(&mount.connection.id);
+            }
+        }
+        result
+    }
+
+    /// 아직 마운트되지 않은 저장 드라이브 마운트 (모두 마운트되면 true)
+    fn restore_pending(&self) -> bool {
+        let pending: Vec<ServiceMount> = self
+            .mounts
+            .lock()
+            .iter()
+            .filter(|m| !self.manager.is_mounted(m.drive_letter))
+            .cloned()
+            .collect();
+
+        let mut done = true;
+        for mount in pending {
+            // 비밀번호가 거부된 드라이브는 앱에서 새 비밀번호로 다시 마운트할 때까지 쉼
+            if reauth::is_rejected(&mount.connection.id) {
+                continue;
+            }
+            let password = match mount.connection.auth_type {
+                AuthType::Password => credentials::get_password(&mount.connection.id)
+                    .ok()
+                    .flatten(),
+                AuthType::Key | AuthType::Agent => None,
+            };
+            // 재시도 사이에 GUI에서 내린 드라이브는 건너뜀
+            if !self
+                .mounts
+                .lock()
+                .iter()
+                .any(|m| m.drive_letter == mount.drive_letter)
+            {
+                continue;
+            }
+            match self
+                .manager
+                .mount(&mount.connection, mount.drive_letter, password.as_deref())
+            {
+                Ok(_) => info!(
+                    "[Service] restored {} on {}:",
+                    mount.connection.id, mount.drive_letter
+                ),
+                Err(e) => {
+                    warn!(
+                        "[Service] restore {}: failed, retrying: {}",
+                        mount.drive_letter, e
+                    );
+                    done = false;
+                }
+            }
+        }
+        done
+    }
+
+    fn has_connection(&self, connection_id: &str) -> bool {
+        self.mounts
+            .lock()
+            .iter()
+            .any(|m| m.connection.id == connection_id)
+    }
+}
+
+/// GUI에서 받은 비밀 값을 서비스 계정의 자격 증명 관리자에 저장
+fn save_secrets(connection_id: &str, secrets: &ConnectionSecrets) -> Result<(), String> {
+    if let Some(password) = &secrets.password {
+        credentials::save_password(connection_id, password)?;
+    }
+    if let Some(password) = &secrets.proxy_password {
+        credentials::save_password(&credentials::proxy_credential_key(connection_id), password)?;
+    }
+    if let Some(secret) = &secrets.totp_secret {
+        credentials::save_password(&credentials::totp_credential_key(connection_id), secret)?;
+    }
+    Ok(())
+}
+
+fn delete_secrets(connection_id: &str) {
+    let _ = credentials::delete_connection_credentials(&[connection_id.to_string()]);
+}
+
+fn load_mounts() -> Vec<ServiceMount> {
+    let Some(file) = storage::get_service_mounts_file() else {
+        return Vec::new();
+    };
+    let Ok(content) = fs::read_to_string(&file) else {
+        return Vec::new();
+    };
+    let mut mounts: Vec<ServiceMount> = serde_json::from_str(&content).unwrap_or_else(|e| {
+        warn!("[Service] service mount list ignored: {}", e);
+        Vec::new()
+    });
+    // 이전 버전이 훅과 함께 저장한 드라이브도 훅 없이 복원
+    for mount in &mut mounts {
+        mount.connection.hooks = ConnectionHooks::default();
+    }
+    mounts
+}
+
+fn save_mounts(mounts: &[ServiceMount]) {
+    let Some(file) = storage::get_service_mounts_file() else {
+        return;
+    };
+    if let Some(dir) = file.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    match serde_json::to_string_pretty(mounts) {
+        Ok(content) => {
+            if let Err(e) = fs::write(&file, content) {
+                warn!("[Service] service mount list save failed: {}", e);
+            }
+        }
+        Err(e) => warn!("[Service] service mount list save failed: {}", e),
+    }
+}
@@ -7,5 +7,93 @@ fn main() {
         println!("cargo:rustc-link-arg=/DELAYLOAD:winfsp-x86.dll");
     }
 
-    tauri_build::build()
+    // 명령마다 allow-/deny- 권한을 생성하고, permissions/ 의 권한 묶음으로 창에 허용
+    // (capabilities/ 에 없는 명령은 프론트엔드에서 호출할 수 없음)
+    tauri_build::try_build(
+        tauri_build::Attributes::new()
+            .app_manifest(tauri_build::AppManifest::new().commands(COMMANDS)),
+    )
+    .expect("failed to run tauri-build");
 }
+
+/// lib.rs의 generate_handler!에 등록한 명령 (새 명령은 여기와 permissions/ 에도 추가)
+const COMMANDS: &[&str] = &[
+    "check_prerequisites",
+    "start_winfsp_service",
+    "run_onboarding",
+    "get_connections",
+    "save_connection",
+    "delete_connection",
+    "undo_delete_connection",
+    "list_deleted_connections",
+    "get_available_drive_letters",
+    "mount_drive",
+    "list_bookmarks",
+    "add_bookmark",
+    "remove_bookmark",
+    "mount_bookmark",
+    "open_bookmark_terminal",
+    "check_unmount",
+    "unmount_drive",
+    "get_mounted_drives",
+    "get_mount_stats",
+    "get_connection_health_history",
+    "get_connection_usage",
+    "copy_remote_path",
+    "list_file_versions",
+    "restore_file_version",
+    "enqueue_upload",
+    "enqueue_download",
+    "stage_dropped_uploads",
+    "list_transfers",
+    "pause_transfer",
+    "resume_transfer",
+    "cancel_transfer",
+    "clear_finished_transfers",
+    "set_transfer_concurrency",
+    "search_remote",
+    "cancel_search",
+    "analyze_remote_usage",
+    "delete_remote_tree",
+    "read_remote_file_preview",
+    "read_remote_text_file",
+    "write_remote_text_file",
+    "validate_key_file",
+    "list_stored_keys",
+    "import_key",
+    "generate_key",
+    "delete_stored_key",
+    "test_connection",
+    "get_host_key_fingerprint",
+    "pin_host_key",
+    "import_connections",
+    "list_connection_templates",
+    "save_connection_template",
+    "delete_connection_template",
+    "create_connection_from_template",
+    "run_self_test",
+    "check_for_update",
+    "install_update",
+    "get_log_settings",
+    "set_log_settings",
+    "get_memory_settings",
+    "set_memory_settings",
+    "get_resource_limits",
+    "set_resource_limits",
+    "get_profile_sync_settings",
+    "set_profile_sync_settings",
+    "sync_profiles_now",
+    "get_app_settings",
+    "set_app_settings",
+    "refresh_snapshot",
+    "pause_drive",
+    "answer_protected_operation",
+    "resume_drive",
+    "list_pending_changes",
+    "push_changes",
+    "discard_pending_change",
+    "supply_password",
+    "get_service_drives",
+    "service_mount_drive",
+    "service_unmount_drive",
+];
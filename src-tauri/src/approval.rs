@@ -0,0 +1,94 @@
+//! 민감한 명령 승인 모듈 - 웹뷰 밖의 Windows 메시지 상자로 사용자에게 직접 확인
+//!
+//! 권한 범위(capabilities)는 원격 URL이 민감한 명령을 부르지 못하게 할 뿐, 앱에 묶인
+//! 프론트엔드가 변조되면 막지 못한다. 웹뷰 안의 확인 창은 변조된 스크립트가 스스로 누를 수
+//! 있으므로, 드라이브 마운트, 호스트 키 고정 변경, 다른 프로그램의 비밀번호 가져오기,
+//! 연결 프로필 동기화 대상 변경은 백엔드가 메시지 상자를 띄워 사용자가 [예]를 고른 경우에만
+//! 실행한다. 상자에는 프론트엔드가 넘긴 값이 아니라 백엔드가 저장소에서 읽은 대상을 보여 준다.
+//!
+//! 앱 시작 시 자동 마운트, 트레이, 스크립트용 파이프처럼 웹뷰를 거치지 않는 경로는 묻지 않는다.
+
+use crate::locale::{self, Text};
+use ssh_virtual_drive_core::types::{ImportSource, SshConnection};
+use std::ffi::c_void;
+use std::iter::once;
+
+const MB_YESNO: u32 = 0x0000_0004;
+const MB_ICONWARNING: u32 = 0x0000_0030;
+const MB_DEFBUTTON2: u32 = 0x0000_0100;
+const MB_SETFOREGROUND: u32 = 0x0001_0000;
+const MB_TOPMOST: u32 = 0x0004_0000;
+const IDYES: i32 = 6;
+
+#[link(name = "user32")]
+extern "system" {
+    fn MessageBoxW(owner: *mut c_void, text: *const u16, caption: *const u16, kind: u32) -> i32;
+}
+
+/// 드라이브 마운트 승인 (`path`는 드라이브 루트가 될 원격 경로)
+pub fn mount(connection: &SshConnection, path: &str, drive_letter: char) -> Result<(), String> {
+    let action = locale::text(Text::ApproveMount).replace("{drive}", &format!("{}:", drive_letter));
+    ask(&action, &format!("{}\n{}", target(connection), path))
+}
+
+/// 호스트 키 고정 변경 승인 (빈 지문은 고정 해제)
+pub fn pin_host_key(connection: &SshConnection, fingerprint: &str) -> Result<(), String> {
+    let fingerprint = match fingerprint.trim() {
+        "" => "-",
+        fingerprint => fingerprint,
+    };
+    ask(
+        locale::text(Text::ApprovePinHostKey),
+        &format!("{}\n{}", target(connection), fingerprint),
+    )
+}
+
+/// 다른 프로그램에 저장된 비밀번호 가져오기 승인
+pub fn import_passwords(source: ImportSource) -> Result<(), String> {
+    let program = match source {
+        ImportSource::WinScp => "WinSCP",
+        ImportSource::FileZilla => "FileZilla",
+        ImportSource::SshConfig => "OpenSSH",
+    };
+    ask(locale::text(Text::ApproveImportPasswords), program)
+}
+
+/// 연결 프로필 동기화 대상 승인 (`path`는 서버의 동기화 파일)
+pub fn profile_sync(connection: &SshConnection, path: &str) -> Result<(), String> {
+    ask(
+        locale::text(Text::ApproveProfileSync),
+        &format!("{}\n{}", target(connection), path),
+    )
+}
+
+/// 연결 이름과 서버 계정 (`'이름' user@host:port`)
+fn target(connection: &SshConnection) -> String {
+    format!("'{}' {}", connection.name, connection.identity())
+}
+
+/// 메시지 상자로 묻고 [예]가 아니면 오류 (기본 단추는 [아니요])
+fn ask(action: &str, details: &str) -> Result<(), String> {
+    let message = format!(
+        "{}\n\n{}\n\n{}",
+        action,
+        details,
+        locale::text(Text::ApprovalNote)
+    );
+    let text: Vec<u16> = message.encode_utf16().chain(once(0)).collect();
+    let caption: Vec<u16> = locale::text(Text::ApprovalTitle)
+        .encode_utf16()
+        .chain(once(0))
+        .collect();
+    let answer = unsafe {
+        MessageBoxW(
+            std::ptr::null_mut(),
+            text.as_ptr(),
+            caption.as_ptr(),
+            MB_YESNO | MB_ICONWARNING | MB_DEFBUTTON2 | MB_SETFOREGROUND | MB_TOPMOST,
+        )
+    };
+    if answer != IDYES {
+        return Err("확인 창에서 요청을 거부했습니다.".to_string());
+    }
+    Ok(())
+}
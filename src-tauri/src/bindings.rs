@@ -0,0 +1,486 @@
+//! 프론트엔드 바인딩 생성 모듈 - Rust 타입과 명령 목록에서 `src/lib/bindings.ts` 작성
+//!
+//! 타입은 `types.rs`의 `JsonSchema`에서, 명령과 이벤트는 아래 목록에서 만든다. 디버그
+//! 빌드로 앱을 실행하면(`npm run tauri dev`) 시작할 때 파일을 다시 쓰므로 타입이나 명령을
+//! 바꾼 뒤 한 번 실행하고 생성된 파일을 함께 커밋한다. 커밋된 파일이 생성 결과와 다르면
+//! `cargo test`가 실패한다. 명령 목록의 인자와 반환 타입은 컴파일할 때 commands.rs의 함수
+//! 시그니처와 같은지 확인하므로 목록이 어긋나면 빌드가 실패한다.
+
+use crate::commands;
+use crate::search::SearchManager;
+use crate::transfers::TransferQueue;
+use log::{info, warn};
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use ssh_virtual_drive_core::mount::MountManager;
+use ssh_virtual_drive_core::types::*;
+use std::collections::BTreeMap;
+use std::path::Path;
+use tauri::{AppHandle, State};
+
+/// 생성 파일 위치 (저장소의 프론트엔드 소스)
+const BINDINGS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../src/lib/bindings.ts");
+
+struct Command {
+    name: &'static str,
+    args: Vec<(&'static str, Schema)>,
+    output: Schema,
+}
+
+struct Event {
+    name: &'static str,
+    payload: Schema,
+}
+
+/// 프론트엔드에서 넘기는 인자 다음에 `;`로 Tauri가 채우는 인자(State, AppHandle)를 적고,
+/// 실패할 수 있는 명령은 `-> T, String`으로 `Result<T, String>`을 나타냄. `async` 명령은
+/// 인자 뒤에 `async`를 적음
+macro_rules! commands {
+    ($(
+        $name:ident($($arg:ident: $ty:ty),* $(; $($injected:ty),+)?) $($async:ident)?
+            -> $ok:ty $(, $err:ty)?;
+    )*) => {
+        fn command_list(gen: &mut SchemaGenerator) -> Vec<Command> {
+            $(
+                check_signature!(
+                    $($async)? $name($($arg: $ty),* $(; $($injected),+)?)
+                        -> output_type!($ok $(, $err)?)
+                );
+            )*
+            vec![$(Command {
+                name: stringify!($name),
+                args: vec![$((stringify!($arg), gen.subschema_for::<$ty>())),*],
+                output: gen.subschema_for::<$ok>(),
+            }),*]
+        }
+    };
+}
+
+macro_rules! check_signature {
+    (async $name:ident($($arg:ident: $ty:ty),*) -> $out:ty) => {
+        let _ = |$($arg: $ty),*| async move {
+            let output: $out = commands::$name($($arg),*).await;
+            output
+        };
+    };
+    ($name:ident($($arg:ident: $ty:ty),* $(; $($injected:ty),+)?) -> $out:ty) => {
+        let _: fn($($ty,)* $($($injected),+)?) -> $out = commands::$name;
+    };
+}
+
+macro_rules! output_type {
+    ($ok:ty) => { $ok };
+    ($ok:ty, $err:ty) => { Result<$ok, $err> };
+}
+
+macro_rules! events {
+    ($($name:literal: $payload:ty;)*) => {
+        fn event_list(gen: &mut SchemaGenerator) -> Vec<Event> {
+            vec![$(Event {
+                name: $name,
+                payload: gen.subschema_for::<$payload>(),
+            }),*]
+        }
+    };
+}
+
+commands! {
+    check_prerequisites() async -> PrerequisiteStatus, String;
+    start_winfsp_service() async -> (), String;
+    run_onboarding() async -> OnboardingChecklist, String;
+    get_connections() -> Vec<SshConnection>, String;
+    save_connection(
+        connection: SshConnection,
+        password: Option<String>,
+        proxy_password: Option<String>,
+        totp_secret: Option<String>
+    ) async -> SshConnection, String;
+    delete_connection(id: String) async -> (), String;
+    undo_delete_connection(id: String) -> SshConnection, String;
+    list_deleted_connections() -> Vec<DeletedConnection>;
+    get_available_drive_letters() -> Vec<char>;
+    mount_drive(connection_id: String, drive_letter: char; State<'_, MountManager>)
+        -> DriveStatus, String;
+    list_bookmarks(connection_id: Option<String>) -> Vec<Bookmark>, String;
+    add_bookmark(connection_id: String, name: String, path: String) -> Bookmark, String;
+    remove_bookmark(id: String) -> (), String;
+    mount_bookmark(bookmark_id: String, drive_letter: char; State<'_, MountManager>)
+        -> DriveStatus, String;
+    open_bookmark_terminal(bookmark_id: String) -> (), String;
+    check_unmount(drive_letter: char; State<'_, MountManager>) -> UnmountCheck, String;
+    unmount_drive(drive_letter: char, force: bool; State<'_, MountManager>) -> (), String;
+    get_mounted_drives(; State<'_, MountManager>) -> Vec<DriveStatus>;
+    get_mount_stats(drive_letter: char; State<'_, MountManager>) -> MountStats, String;
+    get_connection_health_history(id: String) -> Vec<HealthSample>;
+    get_connection_usage(id: String) -> ConnectionUsage, String;
+    copy_remote_path(drive_letter: char, from: String, to: String; State<'_, MountManager>)
+        -> (), String;
+    list_file_versions(drive_letter: char, path: String; State<'_, MountManager>)
+        -> Vec<FileVersion>, String;
+    restore_file_version(
+        drive_letter: char,
+        path: String,
+        version: String;
+        State<'_, MountManager>
+    ) -> (), String;
+    enqueue_upload(
+        connection_id: String,
+        local_path: String,
+        remote_path: String;
+        State<'_, TransferQueue>
+    ) -> TransferItem;
+    enqueue_download(
+        connection_id: String,
+        remote_path: String,
+        local_path: String;
+        State<'_, TransferQueue>
+    ) -> TransferItem;
+    stage_dropped_uploads(
+        connection_id: String,
+        remote_dir: String,
+        paths: Vec<String>;
+        State<'_, TransferQueue>
+    ) -> Vec<TransferItem>, String;
+    list_transfers(; State<'_, TransferQueue>) -> Vec<TransferItem>;
+    pause_transfer(id: String; State<'_, TransferQueue>) -> (), String;
+    resume_transfer(id: String; State<'_, TransferQueue>) -> (), String;
+    cancel_transfer(id: String; State<'_, TransferQueue>) -> (), String;
+    clear_finished_transfers(; State<'_, TransferQueue>) -> ();
+    set_transfer_concurrency(count: usize; State<'_, TransferQueue>) -> ();
+    search_remote(
+        connection_id: String,
+        root: String,
+        pattern: String,
+        max_results: usize;
+        AppHandle,
+        State<'_, SearchManager>
+    ) -> String, String;
+    cancel_search(search_id: String; State<'_, SearchManager>) -> (), String;
+    analyze_remote_usage(connection_id: String, path: String, depth: u32) -> UsageNode, String;
+    delete_remote_tree(connection_id: String, path: String; AppHandle) -> String, String;
+    read_remote_file_preview(connection_id: String, path: String, max_bytes: u64) async -> RemoteFilePreview, String;
+    read_remote_text_file(connection_id: String, path: String) async -> RemoteTextFile, String;
+    write_remote_text_file(connection_id: String, path: String, content: String, expected_etag: Option<String>, force: bool) async -> TextFileSaveResult, String;
+    validate_key_file(path: String) -> KeyFileInfo, String;
+    list_stored_keys() -> Vec<StoredKey>, String;
+    import_key(path: String, name: String, protect: bool) -> StoredKey, String;
+    generate_key(name: String, protect: bool) -> StoredKey, String;
+    delete_stored_key(id: String) -> (), String;
+    test_connection(connection: SshConnection, password: Option<String>) -> bool, String;
+    get_host_key_fingerprint(connection: SshConnection) -> String, String;
+    pin_host_key(connection_id: String, fingerprint: String) -> SshConnection, String;
+    import_connections(source: ImportSource, path: Option<String>, with_passwords: bool) -> ImportReport, String;
+    list_connection_templates() -> Vec<ConnectionTemplate>, String;
+    save_connection_template(template: ConnectionTemplate) -> ConnectionTemplate, String;
+    delete_connection_template(id: String) -> (), String;
+    create_connection_from_template(template_id: String, vars: BTreeMap<String, String>) async
+        -> SshConnection, String;
+    run_self_test(connection_id: String; State<'_, MountManager>)
+        -> Vec<SelfTestStep>, String;
+    check_for_update() -> UpdateInfo, String;
+    install_update(; AppHandle, State<'_, MountManager>) -> (), String;
+    get_log_settings() -> LogSettings;
+    set_log_settings(settings: LogSettings) -> (), String;
+    get_memory_settings() -> MemorySettings;
+    set_memory_settings(settings: MemorySettings) -> (), String;
+    get_resource_limits() -> ResourceLimits;
+    set_resource_limits(settings: ResourceLimits) -> (), String;
+    get_profile_sync_settings() -> ProfileSyncSettings;
+    set_profile_sync_settings(settings: ProfileSyncSettings, passphrase: Option<String>) -> (), String;
+    sync_profiles_now() async -> Option<ProfileSyncReport>, String;
+    get_app_settings() -> AppSettings;
+    set_app_settings(settings: AppSettings; AppHandle) -> (), String;
+    refresh_snapshot(drive_letter: char; State<'_, MountManager>) -> usize, String;
+    pause_drive(drive_letter: char; State<'_, MountManager>) -> (), String;
+    resume_drive(drive_letter: char; State<'_, MountManager>) -> (), String;
+    list_pending_changes(drive_letter: char; State<'_, MountManager>)
+        -> Vec<PendingChange>, String;
+    push_changes(drive_letter: char; State<'_, MountManager>) -> PushReport, String;
+    discard_pending_change(drive_letter: char, path: String; State<'_, MountManager>)
+        -> (), String;
+    answer_protected_operation(id: u64, approved: bool) -> (), String;
+    supply_password(connection_id: String, password: String) -> (), String;
+    get_service_drives() -> Vec<DriveStatus>, String;
+    service_mount_drive(connection_id: String, drive_letter: char) -> DriveStatus, String;
+    service_unmount_drive(drive_letter: char) -> (), String;
+}
+
+events! {
+    "transfer-progress": TransferItem;
+    "search-results": SearchResults;
+    "search-finished": SearchFinished;
+    "drive-auto-unmounted": char;
+    "fs-activity": Vec<FsActivity>;
+    "drives-remounted": Vec<char>;
+    "credentials-invalid": String;
+    "protected-operation": ProtectedRequest;
+    "usage-cap-reached": UsageCapWarning;
+    "profiles-synced": ProfileSyncReport;
+    "tree-delete-progress": TreeDeleteProgress;
+    "drives-changed": ();
+}
+
+/// 바인딩 파일을 다시 씀 (내용이 같으면 그대로 둠)
+pub fn export() {
+    let content = generate();
+    let path = Path::new(BINDINGS_PATH);
+    if std::fs::read_to_string(path).is_ok_and(|existing| existing == content) {
+        return;
+    }
+    match std::fs::write(path, content) {
+        Ok(()) => info!("[Bindings] wrote {}", path.display()),
+        Err(e) => warn!("[Bindings] writing {} failed: {}", path.display(), e),
+    }
+}
+
+fn generate() -> String {
+    let mut gen = SchemaSettings::draft07()
+        .with(|s| {
+            s.option_nullable = true;
+            s.option_add_null_type = false;
+        })
+        .into_generator();
+    let commands = command_list(&mut gen);
+    let events = event_list(&mut gen);
+
+    let mut out = String::from(
+        "// 자동 생성 파일 - 직접 고치지 마세요 (src-tauri/src/bindings.rs)\n\
+         import { invoke } from '@tauri-apps/api/core';\n\
+         import { listen, type UnlistenFn } from '@tauri-apps/api/event';\n",
+    );
+    for (name, schema) in gen.definitions() {
+        out.push('\n');
+        out.push_str(&definition(name, schema));
+    }
+
+    out.push_str("\n// 백엔드 명령 (인자 이름은 Tauri 규칙대로 camelCase로 넘김)\nexport const commands = {\n");
+    for command in &commands {
+        let params: Vec<String> = command
+            .args
+            .iter()
+            .enumerate()
+            .map(|(i, (arg, schema))| {
+                // 뒤따르는 인자가 모두 Option일 때만 생략 가능 (중간의 Option은 null로 넘김)
+                let optional = command.args[i..].iter().all(|(_, s)| is_optional(s));
+                let ty = ts_type(schema);
+                if optional {
+                    format!("{}?: {}", camel_case(arg), ty)
+                } else if is_optional(schema) {
+                    format!("{}: {} | null", camel_case(arg), ty)
+                } else {
+                    format!("{}: {}", camel_case(arg), ty)
+                }
+            })
+            .collect();
+        let output = match ts_type(&command.output).as_str() {
+            "null" => "void".to_string(),
+            output if is_optional(&command.output) => format!("{} | null", output),
+            output => output.to_string(),
+        };
+        let args = if command.args.is_empty() {
+            String::new()
+        } else {
+            let names: Vec<String> = command.args.iter().map(|(a, _)| camel_case(a)).collect();
+            format!(", {{ {} }}", names.join(", "))
+        };
+        out.push_str(&format!(
+            "  {}: ({}): Promise<{}> =>\n    invoke('{}'{}),\n",
+            camel_case(command.name),
+            params.join(", "),
+            output,
+            command.name,
+            args
+        ));
+    }
+    out.push_str("};\n");
+
+    out.push_str("\n// 백엔드 이벤트\nexport const events = {\n");
+    for event in &events {
+        let payload = ts_type(&event.payload);
+        out.push_str(&format!(
+            "  {}: (callback: (payload: {payload}) => void): Promise<UnlistenFn> =>\n    \
+             listen<{payload}>('{}', (event) => callback(event.payload)),\n",
+            camel_case(event.name),
+            event.name,
+        ));
+    }
+    out.push_str("};\n");
+    out
+}
+
+/// 타입 정의 하나 (구조체는 interface, 나머지는 type)
+fn definition(name: &str, schema: &Schema) -> String {
+    let mut out = String::new();
+    let Schema::Object(object) = schema else {
+        return format!("export type {} = unknown;\n", name);
+    };
+    if let Some(description) = object
+        .metadata
+        .as_ref()
+        .and_then(|m| m.description.as_ref())
+    {
+        for line in description.lines().filter(|line| !line.is_empty()) {
+            out.push_str(&format!("// {}\n", line));
+        }
+    }
+    match &object.object {
+        Some(validation) if !validation.properties.is_empty() && object.subschemas.is_none() => {
+            out.push_str(&format!("export interface {} {{\n", name));
+            for (field, schema) in &validation.properties {
+                let optional = !validation.required.contains(field) || is_optional(schema);
+                out.push_str(&format!(
+                    "  {}{}: {};\n",
+                    field,
+                    if optional { "?" } else { "" },
+                    ts_type(schema)
+                ));
+            }
+            out.push_str("}\n");
+        }
+        _ => out.push_str(&format!("export type {} = {};\n", name, ts_type(schema))),
+    }
+    out
+}
+
+/// 스키마 → TypeScript 타입 표기
+fn ts_type(schema: &Schema) -> String {
+    let Schema::Object(object) = schema else {
+        return "unknown".to_string();
+    };
+    if let Some(reference) = &object.reference {
+        return reference
+            .rsplit('/')
+            .next()
+            .unwrap_or(reference)
+            .to_string();
+    }
+    if let Some(values) = &object.enum_values {
+        let values: Vec<String> = values
+            .iter()
+            .map(|v| match v.as_str() {
+                Some(s) => format!("'{}'", s),
+                None => v.to_string(),
+            })
+            .collect();
+        return values.join(" | ");
+    }
+    if let Some(subschemas) = &object.subschemas {
+        let mut types: Vec<String> = Vec::new();
+        for schema in [&subschemas.all_of, &subschemas.any_of, &subschemas.one_of]
+            .into_iter()
+            .flatten()
+            .flatten()
+        {
+            let ts = ts_type(schema);
+            if ts != "null" && !types.contains(&ts) {
+                types.push(ts);
+            }
+        }
+        return types.join(" | ");
+    }
+
+    let instance_types = match &object.instance_type {
+        Some(SingleOrVec::Single(t)) => vec![**t],
+        Some(SingleOrVec::Vec(types)) => types.clone(),
+        None => return "unknown".to_string(),
+    };
+    let types: Vec<String> = instance_types
+        .into_iter()
+        .filter(|t| *t != InstanceType::Null)
+        .map(|t| match t {
+            InstanceType::Boolean => "boolean".to_string(),
+            InstanceType::Integer | InstanceType::Number => "number".to_string(),
+            InstanceType::String => "string".to_string(),
+            InstanceType::Array => array_type(object),
+            InstanceType::Object => object_type(object),
+            InstanceType::Null => "null".to_string(),
+        })
+        .collect();
+    if types.is_empty() {
+        "null".to_string()
+    } else {
+        types.join(" | ")
+    }
+}
+
+fn array_type(object: &SchemaObject) -> String {
+    match object.array.as_ref().and_then(|a| a.items.as_ref()) {
+        Some(SingleOrVec::Single(item)) => {
+            let item = ts_type(item);
+            if item.contains(' ') {
+                format!("({})[]", item)
+            } else {
+                format!("{}[]", item)
+            }
+        }
+        Some(SingleOrVec::Vec(items)) => {
+            let items: Vec<String> = items.iter().map(ts_type).collect();
+            format!("[{}]", items.join(", "))
+        }
+        None => "unknown[]".to_string(),
+    }
+}
+
+fn object_type(object: &SchemaObject) -> String {
+    let Some(validation) = &object.object else {
+        return "Record<string, unknown>".to_string();
+    };
+    if validation.properties.is_empty() {
+        let value = validation
+            .additional_properties
+            .as_deref()
+            .map_or_else(|| "unknown".to_string(), ts_type);
+        return format!("Record<string, {}>", value);
+    }
+    let fields: Vec<String> = validation
+        .properties
+        .iter()
+        .map(|(field, schema)| {
+            let optional = !validation.required.contains(field) || is_optional(schema);
+            format!(
+                "{}{}: {}",
+                field,
+                if optional { "?" } else { "" },
+                ts_type(schema)
+            )
+        })
+        .collect();
+    format!("{{ {} }}", fields.join("; "))
+}
+
+/// Option 인자/필드인지 (생성기 설정상 Option은 null 타입 대신 `nullable`로 표시됨)
+fn is_optional(schema: &Schema) -> bool {
+    matches!(schema, Schema::Object(object)
+        if object.extensions.get("nullable").and_then(|v| v.as_bool()) == Some(true))
+}
+
+/// `snake_case`/`kebab-case` → `camelCase`
+fn camel_case(name: &str) -> String {
+    let mut out = String::new();
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' || c == '-' {
+            upper = true;
+        } else if upper {
+            out.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn committed_bindings_match_rust_types() {
+        let committed = std::fs::read_to_string(BINDINGS_PATH).unwrap_or_default();
+        assert!(
+            committed.replace("\r\n", "\n") == generate(),
+            "src/lib/bindings.ts is out of date, run `npm run tauri dev` once and commit it"
+        );
+    }
+}
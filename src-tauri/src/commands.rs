@@ -1,18 +1,57 @@
 //! Tauri 명령 모듈 - 프론트엔드에서 호출 가능한 백엔드 API
 
-use crate::credentials;
-use crate::mount;
-use crate::mount::MountManager;
-use crate::sftp_client::SftpClient;
-use crate::storage;
-use crate::types::{AuthType, DriveStatus, PrerequisiteStatus, SshConnection};
-use tauri::State;
+use crate::approval;
+use crate::editor;
+use crate::preview;
+use crate::search::SearchManager;
+use crate::selftest;
+use crate::service;
+use crate::settings;
+use crate::shell;
+use crate::transfers::TransferQueue;
+use crate::tree_delete;
+use crate::update;
+use crate::usage;
+use log::warn;
+use ssh_virtual_drive_core::mount::{self, MountManager};
+use ssh_virtual_drive_core::sftp_client::SftpClient;
+use ssh_virtual_drive_core::types::{
+    AppSettings, AuthType, Bookmark, ConnectionTemplate, ConnectionUsage, DeletedConnection,
+    DriveStatus, FileVersion, HealthSample, ImportReport, ImportSource, KeyFileInfo, LogSettings,
+    MemorySettings, MountStats, OnboardingChecklist, PendingChange, PrerequisiteStatus,
+    ProfileSyncReport, ProfileSyncSettings, PushReport, RemoteFilePreview, RemoteTextFile,
+    ResourceLimits, SelfTestStep, SshConnection, StoredKey, TextFileSaveResult, TransferDirection,
+    TransferItem, UnmountCheck, UpdateInfo, UsageNode,
+};
+use ssh_virtual_drive_core::{
+    bookmarks, confirm, credentials, health, keyfile, keystore, limits, logging, memory,
+    onboarding, profile_sync, reauth, site_import, storage, templates, totp, traffic, trash,
+};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tauri::{AppHandle, State};
 use uuid::Uuid;
 
-/// 사전 요구사항 확인 (WinFsp만 필요)
+/// 사전 요구사항 확인 (WinFsp만 필요)과 실행 환경 점검
+///
+/// 백신 조회 등 몇 초 걸릴 수 있는 확인이 있어 작업 스레드에서 실행한다.
 #[tauri::command]
-pub fn check_prerequisites() -> PrerequisiteStatus {
-    mount::check_prerequisites()
+pub async fn check_prerequisites() -> Result<PrerequisiteStatus, String> {
+    blocking(|| Ok(mount::check_prerequisites())).await
+}
+
+/// WinFsp 런처 서비스 시작 (관리자 권한이 필요하면 UAC 요청, 실행될 때까지 기다림)
+#[tauri::command]
+pub async fn start_winfsp_service() -> Result<(), String> {
+    blocking(mount::start_winfsp_service).await
+}
+
+/// 첫 실행 점검 (WinFsp, SSH 키, 가져올 수 있는 사이트, 저장된 연결)
+///
+/// 사전 요구사항 확인과 같은 느린 확인이 있어 작업 스레드에서 실행한다.
+#[tauri::command]
+pub async fn run_onboarding() -> Result<OnboardingChecklist, String> {
+    blocking(|| Ok(onboarding::run())).await
 }
 
 /// 저장된 연결 목록 가져오기
@@ -22,14 +61,65 @@ pub fn get_connections() -> Result<Vec<SshConnection>, String> {
 }
 
 /// 연결 프로필 저장
+///
+/// 자격 증명 관리자 작업은 느릴 수 있으므로 IPC 스레드를 막지 않게 작업 스레드에서 실행한다.
 #[tauri::command]
-pub fn save_connection(
+pub async fn save_connection(
+    connection: SshConnection,
+    password: Option<String>,
+    proxy_password: Option<String>,
+    totp_secret: Option<String>,
+) -> Result<SshConnection, String> {
+    blocking(move || store_connection(connection, password, proxy_password, totp_secret)).await
+}
+
+fn store_connection(
     mut connection: SshConnection,
     password: Option<String>,
+    proxy_password: Option<String>,
+    totp_secret: Option<String>,
 ) -> Result<SshConnection, String> {
-    // 새 연결이면 ID 생성
-    if connection.id.is_empty() {
+    // 새 연결의 ID는 항상 여기서 만들고, 수정은 이미 저장된 프로필의 ID만 받음
+    // (ID를 위조해 다른 프로필이나 자격 증명 항목을 덮어쓰지 않도록)
+    let previous = if connection.id.is_empty() {
         connection.id = Uuid::new_v4().to_string();
+        None
+    } else {
+        Some(saved_connection(&connection.id)?)
+    };
+
+    // 메모와 아이콘은 비어 있으면 저장하지 않음 (아이콘은 이모지 한두 글자)
+    connection.notes = trimmed(connection.notes);
+    connection.label_icon = trimmed(connection.label_icon);
+    if connection
+        .label_icon
+        .as_ref()
+        .is_some_and(|icon| icon.chars().count() > 8)
+    {
+        return Err("아이콘은 이모지 한두 글자만 쓸 수 있습니다.".to_string());
+    }
+    if connection.dscp.is_some_and(|dscp| dscp > 63) {
+        return Err("DSCP 값은 0~63 사이여야 합니다.".to_string());
+    }
+
+    // 호스트나 사용자가 바뀌면 저장된 비밀 값은 이전 계정의 것이므로 새 계정에 보내지 않음
+    if previous.is_some_and(|p| p.identity() != connection.identity()) {
+        if connection.auth_type == AuthType::Password
+            && password.is_none()
+            && credentials::get_password(&connection.id)?.is_some()
+        {
+            return Err(format!(
+                "서버 계정이 {}(으)로 바뀌었습니다. 이전 계정의 비밀번호를 보내지 않도록 비밀번호를 다시 입력해주세요.",
+                connection.identity()
+            ));
+        }
+        if totp_secret.is_none() {
+            warn!(
+                "[Connection] account of {} changed, stored TOTP secret removed",
+                connection.id
+            );
+            let _ = credentials::delete_password(&credentials::totp_credential_key(&connection.id));
+        }
     }
 
     // 비밀번호 저장 (비밀번호 인증인 경우)
@@ -39,33 +129,67 @@ pub fn save_connection(
         }
     }
 
-    // 연결 목록 업데이트
-    let mut connections = storage::load_connections().unwrap_or_default();
+    // 프록시 비밀번호 저장 (프록시 인증을 쓰는 경우)
+    let proxy_key = credentials::proxy_credential_key(&connection.id);
+    match connection.proxy.as_ref() {
+        Some(proxy) if proxy.username.is_some() => {
+            if let Some(pwd) = proxy_password {
+                credentials::save_password(&proxy_key, &pwd)?;
+            }
+        }
+        _ => {
+            let _ = credentials::delete_password(&proxy_key);
+        }
+    }
 
-    // 기존 연결 업데이트 또는 새 연결 추가
-    if let Some(idx) = connections.iter().position(|c| c.id == connection.id) {
-        connections[idx] = connection.clone();
+    // TOTP 비밀 키 저장 (2단계 인증을 쓰는 경우)
+    let totp_key = credentials::totp_credential_key(&connection.id);
+    if connection.totp {
+        if let Some(secret) = totp_secret {
+            totp::validate_secret(&secret)?;
+            credentials::save_password(&totp_key, secret.trim())?;
+        }
     } else {
-        connections.push(connection.clone());
+        let _ = credentials::delete_password(&totp_key);
     }
 
-    storage::save_connections(&connections)?;
+    // 기존 연결 업데이트 또는 새 연결 추가
+    storage::update_connections(|connections| {
+        if let Some(idx) = connections.iter().position(|c| c.id == connection.id) {
+            connections[idx] = connection.clone();
+        } else {
+            connections.push(connection.clone());
+        }
+        Ok(())
+    })?;
 
     Ok(connection)
 }
 
-/// 연결 프로필 삭제
+/// 연결 프로필 삭제 (보관 기간 동안은 `undo_delete_connection`으로 되돌릴 수 있음)
+///
+/// 보관 기간이 지난 연결의 자격 증명 정리도 함께 하므로 작업 스레드에서 실행한다.
 #[tauri::command]
-pub fn delete_connection(id: String) -> Result<(), String> {
-    // 비밀번호 삭제
-    let _ = credentials::delete_password(&id);
+pub async fn delete_connection(id: String) -> Result<(), String> {
+    blocking(move || {
+        saved_connection(&id)?;
+        trash::delete(&id)?;
+        trash::purge_expired();
+        Ok(())
+    })
+    .await
+}
 
-    // 연결 목록에서 제거
-    let mut connections = storage::load_connections().unwrap_or_default();
-    connections.retain(|c| c.id != id);
-    storage::save_connections(&connections)?;
+/// 삭제한 연결 프로필 되돌리기
+#[tauri::command]
+pub fn undo_delete_connection(id: String) -> Result<SshConnection, String> {
+    trash::restore(&id)
+}
 
-    Ok(())
+/// 삭제 후 보관 중인 연결 프로필 목록
+#[tauri::command]
+pub fn list_deleted_connections() -> Vec<DeletedConnection> {
+    trash::list()
 }
 
 /// 사용 가능한 드라이브 문자 목록
@@ -74,32 +198,75 @@ pub fn get_available_drive_letters() -> Vec<char> {
     mount::get_available_drive_letters()
 }
 
-/// 드라이브 마운트
+/// 드라이브 마운트 (확인 창에서 승인해야 실행)
 #[tauri::command]
 pub fn mount_drive(
     connection_id: String,
     drive_letter: char,
     state: State<'_, MountManager>,
 ) -> Result<DriveStatus, String> {
-    // 연결 정보 가져오기
-    let connection = storage::get_connection_by_id(&connection_id)?
-        .ok_or_else(|| "연결을 찾을 수 없습니다.".to_string())?;
+    let connection = saved_connection(&connection_id)?;
+    approval::mount(&connection, &connection.remote_path, drive_letter)?;
+    state.mount_saved(&connection_id, drive_letter)
+}
 
-    // 비밀번호 가져오기 (비밀번호 인증인 경우)
-    let password = if connection.auth_type == AuthType::Password {
-        credentials::get_password(&connection_id)?
-    } else {
-        None
-    };
+/// 북마크 목록 (`connection_id`가 있으면 그 연결의 북마크만)
+#[tauri::command]
+pub fn list_bookmarks(connection_id: Option<String>) -> Result<Vec<Bookmark>, String> {
+    bookmarks::list(connection_id.as_deref())
+}
+
+/// 원격 경로 북마크 추가 (이름을 비우면 경로의 마지막 이름)
+#[tauri::command]
+pub fn add_bookmark(connection_id: String, name: String, path: String) -> Result<Bookmark, String> {
+    bookmarks::add(&connection_id, &name, &path)
+}
+
+/// 북마크 삭제
+#[tauri::command]
+pub fn remove_bookmark(id: String) -> Result<(), String> {
+    bookmarks::remove(&id)
+}
+
+/// 북마크 경로를 루트로 드라이브 마운트 (확인 창에서 승인해야 실행)
+#[tauri::command]
+pub fn mount_bookmark(
+    bookmark_id: String,
+    drive_letter: char,
+    state: State<'_, MountManager>,
+) -> Result<DriveStatus, String> {
+    let bookmark = bookmarks::get(&bookmark_id)?;
+    let connection = saved_connection(&bookmark.connection_id)?;
+    approval::mount(&connection, &bookmark.path, drive_letter)?;
+    state.mount_bookmark(&bookmark_id, drive_letter)
+}
+
+/// 북마크 경로에서 시작하는 ssh 터미널 열기
+#[tauri::command]
+pub fn open_bookmark_terminal(bookmark_id: String) -> Result<(), String> {
+    let bookmark = bookmarks::get(&bookmark_id)?;
+    let connection = storage::get_connection_by_id(&bookmark.connection_id)?
+        .ok_or_else(|| "북마크의 연결을 찾을 수 없습니다.".to_string())?;
+    shell::open_terminal(&connection, &bookmark.path)
+}
 
-    // 마운트 실행
-    state.mount(&connection, drive_letter, password.as_deref())
+/// 언마운트 전 확인 (열린 파일 핸들 수와 최근 작업 파일)
+#[tauri::command]
+pub fn check_unmount(
+    drive_letter: char,
+    state: State<'_, MountManager>,
+) -> Result<UnmountCheck, String> {
+    state.check_unmount(drive_letter)
 }
 
-/// 드라이브 언마운트
+/// 드라이브 언마운트 (`force`가 아니면 열린 파일이나 최근 작업이 있을 때 거부)
 #[tauri::command]
-pub fn unmount_drive(drive_letter: char, state: State<'_, MountManager>) -> Result<(), String> {
-    state.unmount(drive_letter)
+pub fn unmount_drive(
+    drive_letter: char,
+    force: bool,
+    state: State<'_, MountManager>,
+) -> Result<(), String> {
+    state.unmount_checked(drive_letter, force)
 }
 
 /// 현재 마운트된 드라이브 목록
@@ -108,6 +275,253 @@ pub fn get_mounted_drives(state: State<'_, MountManager>) -> Vec<DriveStatus> {
     state.get_mounted_drives()
 }
 
+/// 마운트 통계 조회 (캐시 적중/실패/축출 카운터)
+#[tauri::command]
+pub fn get_mount_stats(
+    drive_letter: char,
+    state: State<'_, MountManager>,
+) -> Result<MountStats, String> {
+    state.get_mount_stats(drive_letter)
+}
+
+/// 연결의 상태 기록 (마운트 중 주기적으로 잰 서버 응답 시간, 오래된 것부터)
+#[tauri::command]
+pub fn get_connection_health_history(id: String) -> Vec<HealthSample> {
+    health::history(&id)
+}
+
+/// 연결의 누적 전송량과 월별 전송량 (이번 달 포함)
+#[tauri::command]
+pub fn get_connection_usage(id: String) -> Result<ConnectionUsage, String> {
+    Ok(traffic::usage(&saved_connection(&id)?))
+}
+
+/// 서버 측 복사 (같은 드라이브 안의 경로끼리, 데이터를 내려받지 않음)
+#[tauri::command]
+pub fn copy_remote_path(
+    drive_letter: char,
+    from: String,
+    to: String,
+    state: State<'_, MountManager>,
+) -> Result<(), String> {
+    state.copy_remote(drive_letter, &from, &to)
+}
+
+/// 파일의 서버에 보관된 이전 버전 목록 (경로는 드라이브 기준)
+#[tauri::command]
+pub fn list_file_versions(
+    drive_letter: char,
+    path: String,
+    state: State<'_, MountManager>,
+) -> Result<Vec<FileVersion>, String> {
+    state.list_versions(drive_letter, &path)
+}
+
+/// 보관된 버전으로 파일을 되돌림
+#[tauri::command]
+pub fn restore_file_version(
+    drive_letter: char,
+    path: String,
+    version: String,
+    state: State<'_, MountManager>,
+) -> Result<(), String> {
+    state.restore_version(drive_letter, &path, &version)
+}
+
+/// 업로드 대기열에 추가
+#[tauri::command]
+pub fn enqueue_upload(
+    connection_id: String,
+    local_path: String,
+    remote_path: String,
+    queue: State<'_, TransferQueue>,
+) -> TransferItem {
+    queue.enqueue(
+        &connection_id,
+        TransferDirection::Upload,
+        &local_path,
+        &remote_path,
+    )
+}
+
+/// 다운로드 대기열에 추가
+#[tauri::command]
+pub fn enqueue_download(
+    connection_id: String,
+    remote_path: String,
+    local_path: String,
+    queue: State<'_, TransferQueue>,
+) -> TransferItem {
+    queue.enqueue(
+        &connection_id,
+        TransferDirection::Download,
+        &local_path,
+        &remote_path,
+    )
+}
+
+/// 창에 끌어다 놓은 파일/폴더를 원격 폴더로 업로드 예약
+#[tauri::command]
+pub fn stage_dropped_uploads(
+    connection_id: String,
+    remote_dir: String,
+    paths: Vec<String>,
+    queue: State<'_, TransferQueue>,
+) -> Result<Vec<TransferItem>, String> {
+    queue.enqueue_dropped(&connection_id, &remote_dir, &paths)
+}
+
+/// 전송 대기열 목록
+#[tauri::command]
+pub fn list_transfers(queue: State<'_, TransferQueue>) -> Vec<TransferItem> {
+    queue.list()
+}
+
+/// 전송 일시 정지
+#[tauri::command]
+pub fn pause_transfer(id: String, queue: State<'_, TransferQueue>) -> Result<(), String> {
+    queue.pause(&id)
+}
+
+/// 전송 재개
+#[tauri::command]
+pub fn resume_transfer(id: String, queue: State<'_, TransferQueue>) -> Result<(), String> {
+    queue.resume(&id)
+}
+
+/// 전송 취소
+#[tauri::command]
+pub fn cancel_transfer(id: String, queue: State<'_, TransferQueue>) -> Result<(), String> {
+    queue.cancel(&id)
+}
+
+/// 완료/취소된 전송 항목 정리
+#[tauri::command]
+pub fn clear_finished_transfers(queue: State<'_, TransferQueue>) {
+    queue.clear_finished()
+}
+
+/// 동시 전송 수 설정
+#[tauri::command]
+pub fn set_transfer_concurrency(count: usize, queue: State<'_, TransferQueue>) {
+    queue.set_max_concurrent(count)
+}
+
+/// 원격 파일 이름 검색 시작 (결과는 `search-results`/`search-finished` 이벤트로 전달)
+#[tauri::command]
+pub fn search_remote(
+    connection_id: String,
+    root: String,
+    pattern: String,
+    max_results: usize,
+    app: AppHandle,
+    search: State<'_, SearchManager>,
+) -> Result<String, String> {
+    search.start(app, &connection_id, &root, &pattern, max_results)
+}
+
+/// 진행 중인 원격 검색 취소
+#[tauri::command]
+pub fn cancel_search(search_id: String, search: State<'_, SearchManager>) -> Result<(), String> {
+    search.cancel(&search_id)
+}
+
+/// 원격 파일이나 디렉토리 트리 삭제 시작 (진행 상황은 `tree-delete-progress` 이벤트로 전달)
+///
+/// 셸을 쓸 수 있는 서버는 `rm -rf`로 서버 안에서 지우고, 아니면 SFTP로 하나씩 지운다.
+#[tauri::command]
+pub fn delete_remote_tree(
+    connection_id: String,
+    path: String,
+    app: AppHandle,
+) -> Result<String, String> {
+    tree_delete::start(app, &connection_id, &path)
+}
+
+/// 원격 디렉토리별 사용량 분석 (`depth` 단계까지의 하위 디렉토리 트리)
+#[tauri::command]
+pub fn analyze_remote_usage(
+    connection_id: String,
+    path: String,
+    depth: u32,
+) -> Result<UsageNode, String> {
+    let client = SftpClient::connect_saved(&connection_id)?;
+    usage::analyze(&client, &path, depth)
+}
+
+/// 원격 파일 앞부분 미리보기 (마운트 없이, 텍스트나 이미지, 최대 8MB)
+#[tauri::command]
+pub async fn read_remote_file_preview(
+    connection_id: String,
+    path: String,
+    max_bytes: u64,
+) -> Result<RemoteFilePreview, String> {
+    blocking(move || {
+        let client = SftpClient::connect_saved(&connection_id)?;
+        preview::read(&client, &path, max_bytes)
+    })
+    .await
+}
+
+/// 원격 텍스트 파일 읽기 (마운트 없이 편집할 때, 최대 4MB)
+#[tauri::command]
+pub async fn read_remote_text_file(
+    connection_id: String,
+    path: String,
+) -> Result<RemoteTextFile, String> {
+    blocking(move || {
+        let client = SftpClient::connect_saved(&connection_id)?;
+        editor::read(&client, &path)
+    })
+    .await
+}
+
+/// 원격 텍스트 파일 저장 (읽은 뒤 바뀌었으면 저장하지 않고 `saved: false`, `force`면 덮어쓰기)
+#[tauri::command]
+pub async fn write_remote_text_file(
+    connection_id: String,
+    path: String,
+    content: String,
+    expected_etag: Option<String>,
+    force: bool,
+) -> Result<TextFileSaveResult, String> {
+    blocking(move || {
+        let client = SftpClient::connect_saved(&connection_id)?;
+        editor::write(&client, &path, &content, expected_etag.as_deref(), force)
+    })
+    .await
+}
+
+/// SSH 키 파일 점검 (형식, 암호 보호 여부, 공개 키 지문)
+#[tauri::command]
+pub fn validate_key_file(path: String) -> Result<KeyFileInfo, String> {
+    keyfile::validate_key_file(&path)
+}
+
+/// 키 저장소의 키 목록
+#[tauri::command]
+pub fn list_stored_keys() -> Result<Vec<StoredKey>, String> {
+    keystore::list()
+}
+
+/// 키 파일을 키 저장소로 가져오기 (`protect`면 자격 증명 관리자의 키로 암호화)
+#[tauri::command]
+pub fn import_key(path: String, name: String, protect: bool) -> Result<StoredKey, String> {
+    keystore::import(&path, &name, protect)
+}
+
+/// 키 저장소에 새 ed25519 키 생성
+#[tauri::command]
+pub fn generate_key(name: String, protect: bool) -> Result<StoredKey, String> {
+    keystore::generate(&name, protect)
+}
+
+/// 키 저장소에서 키 삭제 (사용 중인 연결이 있으면 실패)
+#[tauri::command]
+pub fn delete_stored_key(id: String) -> Result<(), String> {
+    keystore::delete(&id)
+}
+
 /// SSH 연결 테스트 (Rust 네이티브 ssh2 사용)
 #[tauri::command]
 pub fn test_connection(
@@ -118,3 +532,286 @@ pub fn test_connection(
     let _client = SftpClient::connect(&connection, password.as_deref())?;
     Ok(true)
 }
+
+/// 서버의 현재 호스트 키 지문 조회 (인증하지 않음)
+#[tauri::command]
+pub fn get_host_key_fingerprint(connection: SshConnection) -> Result<String, String> {
+    SftpClient::fetch_host_key_fingerprint(&connection)
+}
+
+/// 확인한 호스트 키 지문을 연결에 다시 고정 (확인 창에서 승인해야 실행)
+#[tauri::command]
+pub fn pin_host_key(connection_id: String, fingerprint: String) -> Result<SshConnection, String> {
+    approval::pin_host_key(&saved_connection(&connection_id)?, &fingerprint)?;
+    storage::update_connections(|connections| {
+        let connection = connections
+            .iter_mut()
+            .find(|c| c.id == connection_id)
+            .ok_or_else(|| "연결을 찾을 수 없습니다.".to_string())?;
+        connection.host_key_fingerprint =
+            Some(fingerprint.trim().to_string()).filter(|f| !f.is_empty());
+        Ok(connection.clone())
+    })
+}
+
+/// 다른 프로그램(WinSCP/FileZilla/OpenSSH)에 저장된 사이트를 연결로 가져오기
+///
+/// `path`가 없으면 그 프로그램의 기본 위치에서 읽는다. 비밀번호도 가져오려면 확인 창에서
+/// 승인해야 한다.
+#[tauri::command]
+pub fn import_connections(
+    source: ImportSource,
+    path: Option<String>,
+    with_passwords: bool,
+) -> Result<ImportReport, String> {
+    if with_passwords {
+        approval::import_passwords(source)?;
+    }
+    site_import::import(source, path.as_deref().map(Path::new), with_passwords)
+}
+
+/// 연결 템플릿 목록
+#[tauri::command]
+pub fn list_connection_templates() -> Result<Vec<ConnectionTemplate>, String> {
+    templates::list()
+}
+
+/// 연결 템플릿 저장 (ID가 비어 있으면 새 템플릿)
+#[tauri::command]
+pub fn save_connection_template(
+    template: ConnectionTemplate,
+) -> Result<ConnectionTemplate, String> {
+    templates::save(template)
+}
+
+/// 연결 템플릿 삭제 (템플릿으로 만든 연결은 그대로 둠)
+#[tauri::command]
+pub fn delete_connection_template(id: String) -> Result<(), String> {
+    templates::delete(&id)
+}
+
+/// 템플릿의 `{변수}`를 채워 새 연결 저장 (비밀번호는 마운트할 때 입력)
+#[tauri::command]
+pub async fn create_connection_from_template(
+    template_id: String,
+    vars: BTreeMap<String, String>,
+) -> Result<SshConnection, String> {
+    blocking(move || {
+        let connection = templates::instantiate(&template_id, &vars)?;
+        store_connection(connection, None, None, None)
+    })
+    .await
+}
+
+/// 임시 드라이브 문자로 마운트해 파일 작업을 단계별로 점검
+#[tauri::command]
+pub fn run_self_test(
+    connection_id: String,
+    state: State<'_, MountManager>,
+) -> Result<Vec<SelfTestStep>, String> {
+    selftest::run(&state, &connection_id)
+}
+
+/// 새 버전 확인
+#[tauri::command]
+pub fn check_for_update() -> Result<UpdateInfo, String> {
+    update::check()
+}
+
+/// 모든 드라이브를 언마운트하고 업데이트 설치 (사용자 확인 후 호출, 성공하면 앱 종료)
+#[tauri::command]
+pub fn install_update(app: AppHandle, state: State<'_, MountManager>) -> Result<(), String> {
+    update::install(&app, &state)
+}
+
+/// 로그 설정 가져오기
+#[tauri::command]
+pub fn get_log_settings() -> LogSettings {
+    logging::settings()
+}
+
+/// 로그 설정 변경 (경로/호스트 가림 모드)
+#[tauri::command]
+pub fn set_log_settings(settings: LogSettings) -> Result<(), String> {
+    logging::apply_settings(&settings)
+}
+
+/// 메모리 설정 가져오기
+#[tauri::command]
+pub fn get_memory_settings() -> MemorySettings {
+    memory::settings()
+}
+
+/// 메모리 설정 변경 (메모리 절약 모드, 다음 마운트부터 적용)
+#[tauri::command]
+pub fn set_memory_settings(settings: MemorySettings) -> Result<(), String> {
+    memory::save_settings(&settings)
+}
+
+/// 자원 한도 가져오기
+#[tauri::command]
+pub fn get_resource_limits() -> ResourceLimits {
+    limits::settings()
+}
+
+/// 자원 한도 변경 (드라이브 수, 작업 스레드, 캐시 메모리 합계, 다음 마운트부터 적용)
+#[tauri::command]
+pub fn set_resource_limits(settings: ResourceLimits) -> Result<(), String> {
+    limits::save_settings(&settings)
+}
+
+/// 연결 프로필 동기화 설정 가져오기
+#[tauri::command]
+pub fn get_profile_sync_settings() -> ProfileSyncSettings {
+    profile_sync::settings()
+}
+
+/// 연결 프로필 동기화 설정 변경 (`passphrase`가 있으면 동기화 파일 암호도 바꿈)
+#[tauri::command]
+pub fn set_profile_sync_settings(
+    settings: ProfileSyncSettings,
+    passphrase: Option<String>,
+) -> Result<(), String> {
+    // 연결 목록을 올릴 서버나 경로가 바뀌면 확인 창에서 승인해야 저장
+    let current = profile_sync::settings();
+    let retargeted = !current.enabled
+        || current.connection_id != settings.connection_id
+        || current.remote_path.trim() != settings.remote_path.trim();
+    if settings.enabled && retargeted {
+        if let Some(connection_id) = settings.connection_id.as_deref() {
+            approval::profile_sync(
+                &saved_connection(connection_id)?,
+                settings.remote_path.trim(),
+            )?;
+        }
+    }
+    profile_sync::save_settings(&settings, passphrase.as_deref())
+}
+
+/// 지금 연결 프로필 동기화 (꺼져 있으면 null)
+#[tauri::command]
+pub async fn sync_profiles_now() -> Result<Option<ProfileSyncReport>, String> {
+    blocking(profile_sync::sync).await
+}
+
+/// 앱 설정 가져오기
+#[tauri::command]
+pub fn get_app_settings() -> AppSettings {
+    settings::load()
+}
+
+/// 앱 설정 변경 (언어를 바꾸면 트레이 메뉴도 바로 바뀜)
+#[tauri::command]
+pub fn set_app_settings(settings: AppSettings, app: AppHandle) -> Result<(), String> {
+    settings::save(&app, &settings)
+}
+
+/// 스냅샷 드라이브 새로 고침 (지금 서버 상태로 목록을 다시 읽고 고정)
+#[tauri::command]
+pub fn refresh_snapshot(
+    drive_letter: char,
+    state: State<'_, MountManager>,
+) -> Result<usize, String> {
+    state.refresh_snapshot(drive_letter)
+}
+
+/// 드라이브 일시 중지 (드라이브 문자는 유지, 캐시로만 답하고 데이터 작업은 거부)
+#[tauri::command]
+pub fn pause_drive(drive_letter: char, state: State<'_, MountManager>) -> Result<(), String> {
+    state.pause_drive(drive_letter)
+}
+
+/// 일시 중지한 드라이브 재개
+#[tauri::command]
+pub fn resume_drive(drive_letter: char, state: State<'_, MountManager>) -> Result<(), String> {
+    state.resume_drive(drive_letter)
+}
+
+/// 변경 대기 모드 드라이브의 대기 중인 변경 목록
+#[tauri::command]
+pub fn list_pending_changes(
+    drive_letter: char,
+    state: State<'_, MountManager>,
+) -> Result<Vec<PendingChange>, String> {
+    state.list_pending_changes(drive_letter)
+}
+
+/// 대기 중인 변경을 서버에 올림
+#[tauri::command]
+pub fn push_changes(
+    drive_letter: char,
+    state: State<'_, MountManager>,
+) -> Result<PushReport, String> {
+    state.push_changes(drive_letter)
+}
+
+/// 대기 중인 변경 하나를 버림
+#[tauri::command]
+pub fn discard_pending_change(
+    drive_letter: char,
+    path: String,
+    state: State<'_, MountManager>,
+) -> Result<(), String> {
+    state.discard_pending_change(drive_letter, &path)
+}
+
+/// 보호 경로 작업 확인 요청에 답함 (승인하면 기다리던 삭제/이름 변경이 진행됨)
+#[tauri::command]
+pub fn answer_protected_operation(id: u64, approved: bool) -> Result<(), String> {
+    confirm::answer(id, approved)
+}
+
+/// 서버가 거부한 비밀번호 대신 쓸 새 비밀번호 저장 (멈춘 재연결 재개)
+#[tauri::command]
+pub fn supply_password(connection_id: String, password: String) -> Result<(), String> {
+    saved_connection(&connection_id)?;
+    reauth::supply_password(&connection_id, &password)
+}
+
+/// 백그라운드 서비스가 유지하는 드라이브 목록
+#[tauri::command]
+pub fn get_service_drives() -> Result<Vec<DriveStatus>, String> {
+    service::list_drives()
+}
+
+/// 저장된 연결을 백그라운드 서비스로 마운트 (로그인 전에도 유지, 관리자 권한 필요,
+/// 확인 창에서 승인해야 실행)
+#[tauri::command]
+pub fn service_mount_drive(
+    connection_id: String,
+    drive_letter: char,
+) -> Result<DriveStatus, String> {
+    let connection = saved_connection(&connection_id)?;
+    approval::mount(&connection, &connection.remote_path, drive_letter)?;
+    service::mount(&connection_id, drive_letter)
+}
+
+/// 백그라운드 서비스 드라이브 언마운트
+#[tauri::command]
+pub fn service_unmount_drive(drive_letter: char) -> Result<(), String> {
+    service::unmount(drive_letter)
+}
+
+/// 블로킹 작업(자격 증명 관리자 등)을 작업 스레드 풀에서 실행하고 결과를 기다림
+async fn blocking<T: Send + 'static>(
+    task: impl FnOnce() -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    tauri::async_runtime::spawn_blocking(task)
+        .await
+        .map_err(|e| format!("작업 실행 실패: {}", e))?
+}
+
+/// 프론트엔드가 보낸 ID가 저장된 연결 프로필의 것인지 확인
+fn saved_connection(id: &str) -> Result<SshConnection, String> {
+    if !Uuid::parse_str(id).is_ok_and(|uuid| uuid.to_string() == id) {
+        return Err("연결 ID 형식이 올바르지 않습니다.".to_string());
+    }
+    storage::get_connection_by_id(id)?.ok_or_else(|| "연결을 찾을 수 없습니다.".to_string())
+}
+
+/// 앞뒤 공백을 지운 값 (비어 있으면 None)
+fn trimmed(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
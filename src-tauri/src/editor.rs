@@ -0,0 +1,77 @@
+//! 원격 텍스트 편집 모듈 - 마운트 없이 설정 파일 같은 작은 텍스트 파일을 읽고 저장
+//!
+//! 읽을 때 파일 크기와 수정 시각으로 만든 etag를 함께 넘기고, 저장할 때 서버의 현재 etag가
+//! 읽을 때와 다르면 (그 사이 다른 곳에서 고쳤으면) 쓰지 않고 충돌로 알린다. 확인과 쓰기 사이의
+//! 짧은 틈은 막지 못하지만, 편집 화면을 열어 둔 동안 생긴 변경은 덮어쓰지 않는다.
+
+use log::info;
+use ssh_virtual_drive_core::sftp_client::{is_not_found, SftpClient};
+use ssh_virtual_drive_core::types::{RemoteTextFile, TextFileSaveResult};
+
+/// 편집할 수 있는 최대 파일 크기
+const MAX_TEXT_FILE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// 원격 텍스트 파일 읽기
+pub fn read(client: &SftpClient, path: &str) -> Result<RemoteTextFile, String> {
+    let stat = client.stat(path)?;
+    if stat.is_dir() {
+        return Err("디렉토리는 편집할 수 없습니다.".to_string());
+    }
+    let size = stat.size.unwrap_or(0);
+    if size > MAX_TEXT_FILE_BYTES {
+        return Err(format!(
+            "파일이 너무 큽니다 ({}MB 이하만 편집할 수 있습니다).",
+            MAX_TEXT_FILE_BYTES / (1024 * 1024)
+        ));
+    }
+    let data = client.read_file(path)?;
+    if data.contains(&0) {
+        return Err("텍스트 파일이 아닙니다.".to_string());
+    }
+    let content =
+        String::from_utf8(data).map_err(|_| "UTF-8 텍스트 파일이 아닙니다.".to_string())?;
+    Ok(RemoteTextFile {
+        path: path.to_string(),
+        content,
+        etag: etag(stat.size, stat.mtime),
+    })
+}
+
+/// 원격 텍스트 파일 저장
+///
+/// `expected_etag`가 있으면 서버의 현재 etag와 같을 때만 저장하고, 없으면 새 파일로 보고
+/// 같은 이름의 파일이 이미 있을 때 충돌로 알린다. `force`면 확인 없이 덮어쓴다.
+pub fn write(
+    client: &SftpClient,
+    path: &str,
+    content: &str,
+    expected_etag: Option<&str>,
+    force: bool,
+) -> Result<TextFileSaveResult, String> {
+    if !force {
+        let current = match client.stat(path) {
+            Ok(stat) if stat.is_dir() => return Err("디렉토리에는 쓸 수 없습니다.".to_string()),
+            Ok(stat) => Some(etag(stat.size, stat.mtime)),
+            Err(e) if is_not_found(&e) => None,
+            Err(e) => return Err(e),
+        };
+        if current.as_deref() != expected_etag {
+            info!("[Editor] Save conflict on {}", path);
+            return Ok(TextFileSaveResult {
+                saved: false,
+                etag: current,
+            });
+        }
+    }
+    client.write_file(path, content.as_bytes())?;
+    let stat = client.stat(path)?;
+    Ok(TextFileSaveResult {
+        saved: true,
+        etag: Some(etag(stat.size, stat.mtime)),
+    })
+}
+
+/// 파일 크기와 수정 시각으로 만든 etag
+fn etag(size: Option<u64>, mtime: Option<u64>) -> String {
+    format!("{}-{}", size.unwrap_or(0), mtime.unwrap_or(0))
+}
@@ -1,56 +1,112 @@
 //! SSH 가상 드라이브 관리자 - Tauri 백엔드
 
+mod approval;
+#[cfg(debug_assertions)]
+mod bindings;
 mod commands;
-mod credentials;
-mod filesystem;
-mod mount;
-mod sftp_client;
-mod storage;
-mod types;
-
-use mount::MountManager;
-use std::env;
+mod editor;
+mod locale;
+mod power;
+mod preview;
+mod rpc;
+mod search;
+mod selftest;
+mod service;
+mod settings;
+mod shell;
+mod transfers;
+mod tree_delete;
+mod update;
+mod usage;
+
+use locale::Text;
+use log::warn;
+use search::SearchManager;
+use ssh_virtual_drive_core::mount::MountManager;
+use ssh_virtual_drive_core::{confirm, health, logging, profile_sync, reauth, traffic, trash};
+use std::time::Duration;
 use tauri::{
     menu::{Menu, MenuItem},
     tray::TrayIconBuilder,
-    Manager,
+    AppHandle, Emitter, Manager, Runtime,
 };
+use transfers::TransferQueue;
 
-pub fn setup_winfsp_path() {
-    if let Some(dll_path) = mount::find_winfsp_path() {
-        if let Some(bin_dir) = std::path::Path::new(&dll_path).parent() {
-            if let Ok(current_path) = env::var("PATH") {
-                let bin_str = bin_dir.to_string_lossy().to_string();
-                if !current_path.contains(&bin_str) {
-                    let new_path = format!("{};{}", current_path, bin_str);
-                    env::set_var("PATH", new_path);
-                    println!("WinFsp path added to PATH: {}", bin_str);
-                }
-            }
-        }
-    } else {
-        println!("Warning: Could not find WinFsp installation");
+/// 시스템 트레이 아이콘 ID (언어가 바뀌면 메뉴를 다시 만들 때 찾음)
+const TRAY_ID: &str = "main";
+
+/// 유휴 드라이브 확인 주기
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 연결 프로필 동기화 주기 (켜 두었을 때)
+const PROFILE_SYNC_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// 파일시스템 활동 이벤트 전달 주기
+const ACTIVITY_EMIT_INTERVAL: Duration = Duration::from_millis(500);
+
+pub use ssh_virtual_drive_core::mount::setup_winfsp_path;
+
+/// 지금 언어로 트레이 메뉴 생성
+fn tray_menu<R: Runtime, M: Manager<R>>(app: &M) -> tauri::Result<Menu<R>> {
+    let quit = MenuItem::with_id(
+        app,
+        "quit",
+        locale::text(Text::TrayQuit),
+        true,
+        None::<&str>,
+    )?;
+    let show = MenuItem::with_id(
+        app,
+        "show",
+        locale::text(Text::TrayShow),
+        true,
+        None::<&str>,
+    )?;
+    Menu::with_items(app, &[&show, &quit])
+}
+
+/// 언어가 바뀐 뒤 트레이 메뉴를 다시 만듦
+pub(crate) fn refresh_tray_menu(app: &AppHandle) -> tauri::Result<()> {
+    match app.tray_by_id(TRAY_ID) {
+        Some(tray) => tray.set_menu(Some(tray_menu(app)?)),
+        None => Ok(()),
     }
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    logging::init();
+    // 탐색기 메뉴에서 실행된 경우 실행 중인 앱에 작업만 넘기고 끝냄
+    if shell::forward_invocation() {
+        return;
+    }
+    // 개발 실행마다 프론트엔드 타입 정의를 Rust 쪽에 맞춤
+    #[cfg(debug_assertions)]
+    bindings::export();
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .manage(MountManager::default())
+        .manage(SearchManager::default())
         .setup(|app| {
-            // 시스템 트레이 메뉴 설정
-            let quit = MenuItem::with_id(app, "quit", "종료", true, None::<&str>)?;
-            let show = MenuItem::with_id(app, "show", "창 열기", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&show, &quit])?;
+            // 전송 대기열 (중단되었던 항목은 이어서 전송)
+            app.manage(TransferQueue::load(app.handle().clone()));
+
+            // 트레이 메뉴와 알림은 설정 언어로 그림
+            let settings = settings::load();
+            locale::set_language(settings.language);
 
             // 시스템 트레이 아이콘 생성
-            let _tray = TrayIconBuilder::new()
+            let _tray = TrayIconBuilder::with_id(TRAY_ID)
                 .icon(app.default_window_icon().unwrap().clone())
-                .menu(&menu)
+                .menu(&tray_menu(app)?)
                 .show_menu_on_left_click(false)
                 .on_menu_event(|app, event| match event.id.as_ref() {
                     "quit" => {
+                        if let Some(window) = app.get_webview_window("main") {
+                            settings::save_window(&window.as_ref().window());
+                        }
+                        shell::sync(Vec::new());
+                        traffic::save();
                         app.exit(0);
                     }
                     "show" => {
@@ -71,25 +127,183 @@ pub fn run() {
                 })
                 .build(app)?;
 
+            // 창은 숨긴 채 만들어지므로 지난번 위치로 옮긴 뒤,
+            // 트레이로 시작하는 설정이 아니면 여기서 띄움
+            if let Some(window) = app.get_webview_window("main") {
+                if let Some(state) = &settings.window {
+                    settings::restore_window(&window.as_ref().window(), state);
+                }
+                if !settings.start_minimized {
+                    let _ = window.show();
+                    if settings.window.is_some_and(|state| state.maximized) {
+                        let _ = window.maximize();
+                    }
+                }
+            }
+
+            // 전송 중 절전 방지
+            power::set_enabled(settings.prevent_sleep);
+            power::start(app.handle().clone());
+
+            // 탐색기 메뉴 (이전 실행이 남긴 메뉴는 지우고 마운트에 맞춰 다시 등록)
+            shell::set_enabled(settings.shell_integration);
+            shell::clear();
+
+            // 스크립트용 로컬 제어 파이프
+            rpc::start(app.handle().clone());
+
+            // 보관 기간이 지난 삭제한 연결 정리 (자격 증명 관리자 작업이 느릴 수 있어 스레드에서)
+            std::thread::spawn(trash::purge_expired);
+
+            // 업데이트 설치 전에 내렸던 드라이브 다시 마운트
+            let handle = app.handle().clone();
+            std::thread::spawn(move || {
+                update::resume_pending_mounts(&handle, &handle.state::<MountManager>());
+            });
+
+            // 연결 프로필 동기화 (시작할 때 한 번, 그 뒤 주기적으로)
+            let handle = app.handle().clone();
+            std::thread::spawn(move || loop {
+                match profile_sync::sync() {
+                    Ok(Some(report)) => {
+                        let _ = handle.emit("profiles-synced", report);
+                    }
+                    Ok(None) => {}
+                    Err(e) => warn!("[ProfileSync] sync failed: {}", e),
+                }
+                std::thread::sleep(PROFILE_SYNC_INTERVAL);
+            });
+
+            // 유휴 드라이브 자동 언마운트
+            let handle = app.handle().clone();
+            std::thread::spawn(move || loop {
+                std::thread::sleep(IDLE_CHECK_INTERVAL);
+                for letter in handle.state::<MountManager>().unmount_idle_drives() {
+                    let _ = handle.emit("drive-auto-unmounted", letter);
+                }
+            });
+
+            // 마운트된 연결의 서버 응답 시간을 주기적으로 기록 (모아 둔 전송량도 함께 저장)
+            let handle = app.handle().clone();
+            std::thread::spawn(move || loop {
+                std::thread::sleep(health::PROBE_INTERVAL);
+                handle.state::<MountManager>().probe_health();
+                traffic::save();
+            });
+
+            // 파일시스템 활동을 모아서 주기적으로 전달
+            let handle = app.handle().clone();
+            std::thread::spawn(move || loop {
+                std::thread::sleep(ACTIVITY_EMIT_INTERVAL);
+                let activity = handle.state::<MountManager>().take_activity();
+                if !activity.is_empty() {
+                    let _ = handle.emit("fs-activity", activity);
+                }
+                // 서버가 저장된 비밀번호를 거부한 연결은 새 비밀번호를 물음
+                for connection_id in reauth::take_rejected() {
+                    let _ = handle.emit("credentials-invalid", connection_id);
+                }
+                // 보호 경로의 삭제/이름 변경은 사용자 확인을 기다림
+                for request in confirm::take_requests() {
+                    let _ = handle.emit("protected-operation", request);
+                }
+                // 이번 달 전송량이 한도에 닿은 연결을 알림
+                for warning in traffic::take_cap_warnings() {
+                    let _ = handle.emit("usage-cap-reached", warning);
+                }
+                // 마운트된 드라이브가 바뀌면 탐색기 메뉴도 맞춤
+                shell::sync(handle.state::<MountManager>().drive_letters());
+            });
+
             Ok(())
         })
         .on_window_event(|window, event| {
             // 창 닫기 시 트레이로 최소화
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                settings::save_window(window);
                 let _ = window.hide();
                 api.prevent_close();
             }
         })
         .invoke_handler(tauri::generate_handler![
             commands::check_prerequisites,
+            commands::start_winfsp_service,
+            commands::run_onboarding,
             commands::get_connections,
             commands::save_connection,
             commands::delete_connection,
+            commands::undo_delete_connection,
+            commands::list_deleted_connections,
             commands::get_available_drive_letters,
             commands::mount_drive,
+            commands::list_bookmarks,
+            commands::add_bookmark,
+            commands::remove_bookmark,
+            commands::mount_bookmark,
+            commands::open_bookmark_terminal,
+            commands::check_unmount,
             commands::unmount_drive,
             commands::get_mounted_drives,
+            commands::get_mount_stats,
+            commands::get_connection_health_history,
+            commands::get_connection_usage,
+            commands::copy_remote_path,
+            commands::list_file_versions,
+            commands::restore_file_version,
+            commands::enqueue_upload,
+            commands::enqueue_download,
+            commands::stage_dropped_uploads,
+            commands::list_transfers,
+            commands::pause_transfer,
+            commands::resume_transfer,
+            commands::cancel_transfer,
+            commands::clear_finished_transfers,
+            commands::set_transfer_concurrency,
+            commands::search_remote,
+            commands::cancel_search,
+            commands::analyze_remote_usage,
+            commands::delete_remote_tree,
+            commands::read_remote_file_preview,
+            commands::read_remote_text_file,
+            commands::write_remote_text_file,
+            commands::validate_key_file,
+            commands::list_stored_keys,
+            commands::import_key,
+            commands::generate_key,
+            commands::delete_stored_key,
             commands::test_connection,
+            commands::get_host_key_fingerprint,
+            commands::pin_host_key,
+            commands::import_connections,
+            commands::list_connection_templates,
+            commands::save_connection_template,
+            commands::delete_connection_template,
+            commands::create_connection_from_template,
+            commands::run_self_test,
+            commands::check_for_update,
+            commands::install_update,
+            commands::get_log_settings,
+            commands::set_log_settings,
+            commands::get_memory_settings,
+            commands::set_memory_settings,
+            commands::get_resource_limits,
+            commands::set_resource_limits,
+            commands::get_profile_sync_settings,
+            commands::set_profile_sync_settings,
+            commands::sync_profiles_now,
+            commands::get_app_settings,
+            commands::set_app_settings,
+            commands::refresh_snapshot,
+            commands::pause_drive,
+            commands::answer_protected_operation,
+            commands::resume_drive,
+            commands::list_pending_changes,
+            commands::push_changes,
+            commands::discard_pending_change,
+            commands::supply_password,
+            commands::get_service_drives,
+            commands::service_mount_drive,
+            commands::service_unmount_drive,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
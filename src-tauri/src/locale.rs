@@ -0,0 +1,93 @@
+//! 지역화 모듈 - 백엔드가 직접 그리는 문자열(트레이 메뉴, 탐색기 메뉴, 승인 창)을 설정 언어로 돌려줌
+//!
+//! 화면의 문자열은 프론트엔드가 그리므로 여기에는 창이 없을 때도 보이는 문자열만 둔다.
+//! 언어는 시작할 때 앱 설정에서 읽고, 설정을 바꾸면 [`set_language`]로 바로 바꾼다.
+
+use parking_lot::Mutex;
+use ssh_virtual_drive_core::types::Language;
+
+/// 지금 쓰는 언어
+static LANGUAGE: Mutex<Language> = Mutex::new(Language::Ko);
+
+/// 백엔드 문자열 목록
+#[derive(Debug, Clone, Copy)]
+pub enum Text {
+    /// 트레이 메뉴 - 창 열기
+    TrayShow,
+    /// 트레이 메뉴 - 종료
+    TrayQuit,
+    /// 탐색기 메뉴 - 하위 메뉴 이름
+    ShellMenu,
+    /// 탐색기 메뉴 - 원격 경로 복사
+    ShellCopyPath,
+    /// 탐색기 메뉴 - sftp:// URL 복사
+    ShellCopyUrl,
+    /// 탐색기 메뉴 - 여기서 터미널 열기
+    ShellTerminal,
+    /// 승인 창 - 제목
+    ApprovalTitle,
+    /// 승인 창 - 드라이브 마운트 (`{drive}`는 드라이브 문자)
+    ApproveMount,
+    /// 승인 창 - 호스트 키 고정 변경
+    ApprovePinHostKey,
+    /// 승인 창 - 다른 프로그램의 비밀번호 가져오기
+    ApproveImportPasswords,
+    /// 승인 창 - 연결 프로필 동기화 대상 변경
+    ApproveProfileSync,
+    /// 승인 창 - 요청 출처 안내
+    ApprovalNote,
+}
+
+pub fn language() -> Language {
+    *LANGUAGE.lock()
+}
+
+pub fn set_language(language: Language) {
+    *LANGUAGE.lock() = language;
+}
+
+/// 지금 언어의 문자열
+pub fn text(key: Text) -> &'static str {
+    match (language(), key) {
+        (Language::Ko, Text::TrayShow) => "창 열기",
+        (Language::Ko, Text::TrayQuit) => "종료",
+        (Language::Ko, Text::ShellMenu) => "SSH 가상 드라이브",
+        (Language::Ko, Text::ShellCopyPath) => "원격 경로 복사",
+        (Language::Ko, Text::ShellCopyUrl) => "sftp:// URL 복사",
+        (Language::Ko, Text::ShellTerminal) => "여기서 터미널 열기",
+        (Language::Ko, Text::ApprovalTitle) => "SSH 가상 드라이브 - 확인",
+        (Language::Ko, Text::ApproveMount) => "이 서버 경로를 {drive} 드라이브로 마운트할까요?",
+        (Language::Ko, Text::ApprovePinHostKey) => {
+            "이 서버의 호스트 키 고정을 바꿀까요? 직접 확인한 지문이 아니면 다른 서버에 연결될 수 있습니다."
+        }
+        (Language::Ko, Text::ApproveImportPasswords) => {
+            "다른 프로그램에 저장된 비밀번호를 가져와 자격 증명 관리자에 저장할까요?"
+        }
+        (Language::Ko, Text::ApproveProfileSync) => {
+            "모든 연결 프로필을 이 서버 경로에 암호화해 올리도록 동기화를 설정할까요?"
+        }
+        (Language::Ko, Text::ApprovalNote) => {
+            "앱 화면에서 온 요청입니다. 직접 요청하지 않았다면 [아니요]를 누르세요."
+        }
+        (Language::En, Text::TrayShow) => "Open window",
+        (Language::En, Text::TrayQuit) => "Quit",
+        (Language::En, Text::ShellMenu) => "SSH Virtual Drive",
+        (Language::En, Text::ShellCopyPath) => "Copy remote path",
+        (Language::En, Text::ShellCopyUrl) => "Copy sftp:// URL",
+        (Language::En, Text::ShellTerminal) => "Open terminal here",
+        (Language::En, Text::ApprovalTitle) => "SSH Virtual Drive - Confirm",
+        (Language::En, Text::ApproveMount) => "Mount this server path as drive {drive}?",
+        (Language::En, Text::ApprovePinHostKey) => {
+            "Change the pinned host key of this server? Unless you checked the fingerprint yourself, the drive may connect to a different server."
+        }
+        (Language::En, Text::ApproveImportPasswords) => {
+            "Import the passwords saved by the other program into Credential Manager?"
+        }
+        (Language::En, Text::ApproveProfileSync) => {
+            "Sync all connection profiles, encrypted, to this server path?"
+        }
+        (Language::En, Text::ApprovalNote) => {
+            "This request came from the app window. Choose No if you did not ask for it."
+        }
+    }
+}
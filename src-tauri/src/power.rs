@@ -0,0 +1,57 @@
+//! 절전 방지 모듈 - 전송 중에는 Windows가 절전 모드로 들어가지 않게 함
+//!
+//! 노트북이 업로드 도중 잠들면 연결이 끊겨 전송이 실패한다. 전송 대기열에 진행 중인
+//! 항목이 있거나 마운트한 드라이브가 파일을 올리는 중이면 실행 상태 요청
+//! (`SetThreadExecutionState`)을 걸어 두고, 모두 끝나면 푼다. 요청은 건 스레드가 살아 있는
+//! 동안만 유효하므로 확인 스레드 하나가 걸고 푼다. 화면 끄기는 막지 않는다.
+
+use crate::transfers::TransferQueue;
+use log::info;
+use ssh_virtual_drive_core::mount::MountManager;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// 전송 상태 확인 주기
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+const ES_CONTINUOUS: u32 = 0x8000_0000;
+const ES_SYSTEM_REQUIRED: u32 = 0x0000_0001;
+
+#[link(name = "kernel32")]
+extern "system" {
+    fn SetThreadExecutionState(flags: u32) -> u32;
+}
+
+/// 앱 설정의 절전 방지 여부
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// 전송 상태를 주기적으로 확인해 절전 방지 요청을 걸고 푸는 스레드 시작
+pub fn start(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut held = false;
+        loop {
+            std::thread::sleep(CHECK_INTERVAL);
+            let busy = ENABLED.load(Ordering::Relaxed)
+                && (app.state::<TransferQueue>().has_active()
+                    || app.state::<MountManager>().has_active_transfers());
+            if busy == held {
+                continue;
+            }
+            let flags = match busy {
+                true => ES_CONTINUOUS | ES_SYSTEM_REQUIRED,
+                false => ES_CONTINUOUS,
+            };
+            unsafe { SetThreadExecutionState(flags) };
+            held = busy;
+            info!(
+                "[Power] sleep prevention {}",
+                if busy { "requested" } else { "released" }
+            );
+        }
+    });
+}
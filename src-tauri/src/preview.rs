@@ -0,0 +1,82 @@
+//! 원격 파일 미리보기 모듈 - 마운트 없이 파일 앞부분을 읽어 앱 화면에 보여 줌
+//!
+//! 앞부분 `max_bytes`만 받아 종류를 판단한다. 이미지는 파일 시그니처로 알아보고 base64로
+//! 넘겨 `data:` URL로 그릴 수 있게 하며 (잘린 이미지는 그릴 수 없으므로 끝까지 읽었을 때만),
+//! UTF-8로 읽히는 내용은 텍스트로 넘긴다 (끝에서 잘린 글자는 버림). 그 밖은 바이너리로 보고
+//! 내용은 넘기지 않는다.
+
+use base64::Engine;
+use ssh_virtual_drive_core::sftp_client::{CancelToken, SftpClient};
+use ssh_virtual_drive_core::types::{PreviewKind, RemoteFilePreview};
+
+/// 한 번에 미리 볼 수 있는 최대 크기
+pub const MAX_PREVIEW_BYTES: u64 = 8 * 1024 * 1024;
+
+/// 파일 시그니처로 알아보는 이미지 형식
+const IMAGE_SIGNATURES: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"BM", "image/bmp"),
+    (b"\x00\x00\x01\x00", "image/x-icon"),
+];
+
+/// 원격 파일 앞부분 미리보기
+pub fn read(client: &SftpClient, path: &str, max_bytes: u64) -> Result<RemoteFilePreview, String> {
+    let stat = client.stat(path)?;
+    if stat.is_dir() {
+        return Err("디렉토리는 미리 볼 수 없습니다.".to_string());
+    }
+    let size = stat.size.unwrap_or(0);
+    let limit = max_bytes.min(MAX_PREVIEW_BYTES);
+    let data = client.read_file_range_full(path, 0, limit as usize, &CancelToken::default())?;
+    let truncated = size > data.len() as u64;
+
+    let mut preview = RemoteFilePreview {
+        path: path.to_string(),
+        size,
+        kind: PreviewKind::Binary,
+        mime: None,
+        text: None,
+        data_base64: None,
+        truncated,
+    };
+    if let Some(mime) = image_mime(&data) {
+        preview.kind = PreviewKind::Image;
+        preview.mime = Some(mime.to_string());
+        if !truncated {
+            preview.data_base64 = Some(base64::engine::general_purpose::STANDARD.encode(&data));
+        }
+    } else if let Some(text) = decode_text(&data, truncated) {
+        preview.kind = PreviewKind::Text;
+        preview.text = Some(text);
+    }
+    Ok(preview)
+}
+
+fn image_mime(data: &[u8]) -> Option<&'static str> {
+    if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+    IMAGE_SIGNATURES
+        .iter()
+        .find(|(signature, _)| data.starts_with(signature))
+        .map(|(_, mime)| *mime)
+}
+
+/// UTF-8 텍스트로 읽히면 문자열 (BOM 제외, 잘렸으면 끝의 불완전한 글자는 버림)
+fn decode_text(data: &[u8], truncated: bool) -> Option<String> {
+    let data = data.strip_prefix(b"\xef\xbb\xbf").unwrap_or(data);
+    // NUL이 섞여 있으면 텍스트가 아님 (UTF-16 텍스트도 바이너리로 봄)
+    if data.contains(&0) {
+        return None;
+    }
+    match std::str::from_utf8(data) {
+        Ok(text) => Some(text.to_string()),
+        Err(e) if truncated && e.error_len().is_none() => {
+            Some(String::from_utf8_lossy(&data[..e.valid_up_to()]).into_owned())
+        }
+        Err(_) => None,
+    }
+}
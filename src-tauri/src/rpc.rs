@@ -0,0 +1,100 @@
+//! 로컬 제어 인터페이스 - PowerShell 스크립트 등에서 앱의 드라이브를 관리
+//!
+//! 앱이 실행되는 동안 [`ipc::app_pipe_name`] 파이프에서 JSON-RPC 2.0 요청을 받는다
+//! (한 줄에 요청 하나). 메서드는 같은 이름의 Tauri 명령과 결과가 같다.
+//!
+//! | 메서드 | 매개변수 | 결과 |
+//! |---|---|---|
+//! | `list` | 없음 | `DriveStatus[]` |
+//! | `mount` | `{connection_id, drive_letter}` | `DriveStatus` |
+//! | `unmount` | `{drive_letter, force?}` | `null` (열린 파일이나 최근 작업이 있으면 `force` 필요) |
+//! | `stats` | `{drive_letter}` | `MountStats` |
+//! | `shell` | `{action, path}` | `null` (탐색기 메뉴 작업, [`crate::shell`] 참고) |
+
+use crate::shell::{self, ShellAction};
+use log::error;
+use serde::Deserialize;
+use serde_json::Value;
+use ssh_virtual_drive_core::ipc::{self, RpcError};
+use ssh_virtual_drive_core::mount::MountManager;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 파이프 접근 권한: 파이프를 만든 사용자만 (다른 사용자 세션은 연결 불가)
+const APP_PIPE_SDDL: &str = "D:P(A;;GA;;;OW)";
+
+#[derive(Deserialize)]
+struct MountParams {
+    connection_id: String,
+    drive_letter: char,
+}
+
+#[derive(Deserialize)]
+struct DriveParams {
+    drive_letter: char,
+}
+
+#[derive(Deserialize)]
+struct ShellParams {
+    action: ShellAction,
+    path: String,
+}
+
+#[derive(Deserialize)]
+struct UnmountParams {
+    drive_letter: char,
+    #[serde(default)]
+    force: bool,
+}
+
+/// 백그라운드 스레드에서 제어 파이프 열기
+pub fn start(app: AppHandle) {
+    std::thread::spawn(move || {
+        let handler = move |method: &str, params: Value| dispatch(&app, method, params);
+        let served = ipc::app_pipe_name()
+            .and_then(|name| ipc::serve(&name, APP_PIPE_SDDL, Arc::new(handler)));
+        if let Err(e) = served {
+            error!("[RPC] {}", e);
+        }
+    });
+}
+
+fn dispatch(app: &AppHandle, method: &str, params: Value) -> Result<Value, RpcError> {
+    let manager = app.state::<MountManager>();
+    match method {
+        "list" => ipc::to_result(manager.get_mounted_drives()),
+        "mount" => {
+            let params: MountParams = ipc::parse_params(params)?;
+            let status = manager
+                .mount_saved(&params.connection_id, params.drive_letter)
+                .map_err(RpcError::server)?;
+            // 창에 보이는 목록도 갱신
+            let _ = app.emit("drives-changed", ());
+            ipc::to_result(status)
+        }
+        "unmount" => {
+            let params: UnmountParams = ipc::parse_params(params)?;
+            manager
+                .unmount_checked(params.drive_letter, params.force)
+                .map_err(RpcError::server)?;
+            let _ = app.emit("drives-changed", ());
+            Ok(Value::Null)
+        }
+        "stats" => {
+            let params: DriveParams = ipc::parse_params(params)?;
+            let stats = manager
+                .get_mount_stats(params.drive_letter)
+                .map_err(RpcError::server)?;
+            ipc::to_result(stats)
+        }
+        "shell" => {
+            let params: ShellParams = ipc::parse_params(params)?;
+            shell::perform(&manager, params.action, &params.path).map_err(RpcError::server)?;
+            Ok(Value::Null)
+        }
+        _ => Err(RpcError::new(
+            ipc::METHOD_NOT_FOUND,
+            format!("알 수 없는 메서드: {}", method),
+        )),
+    }
+}
@@ -0,0 +1,244 @@
+//! 원격 검색 모듈 - 이름 패턴으로 원격 트리에서 파일 찾기
+//!
+//! 셸을 쓸 수 있는 서버는 `find`로 서버 안에서 검색하고, exec가 막혀 있거나
+//! `find`가 실패하면 SFTP로 디렉토리를 직접 순회한다.
+//! 결과는 `search-results` 이벤트로 나누어 보내고 끝나면 `search-finished`를 보낸다.
+
+use log::{debug, info};
+use parking_lot::Mutex;
+use ssh_virtual_drive_core::path_filter::glob_match;
+use ssh_virtual_drive_core::sftp_client::{shell_quote, SftpClient};
+use ssh_virtual_drive_core::types::{SearchFinished, SearchHit, SearchResults};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use uuid::Uuid;
+
+/// 한 번에 요청할 수 있는 최대 결과 수
+const MAX_RESULTS_LIMIT: usize = 10_000;
+
+/// 중간 결과를 묶어 보내는 개수
+const BATCH_SIZE: usize = 50;
+
+/// 중간 결과 최소 전송 간격
+const BATCH_INTERVAL: Duration = Duration::from_millis(300);
+
+/// 진행 중인 검색 (Tauri state로 공유)
+#[derive(Clone, Default)]
+pub struct SearchManager {
+    active: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+}
+
+impl SearchManager {
+    /// 백그라운드 검색 시작 후 검색 ID 반환
+    pub fn start(
+        &self,
+        app: AppHandle,
+        connection_id: &str,
+        root: &str,
+        pattern: &str,
+        max_results: usize,
+    ) -> Result<String, String> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return Err("검색어를 입력해주세요.".to_string());
+        }
+        let root = root.trim();
+        if !root.starts_with('/') {
+            return Err("검색 위치는 원격 절대 경로여야 합니다.".to_string());
+        }
+        // 와일드카드가 없으면 이름에 포함된 항목을 찾음
+        let pattern = if pattern.contains(['*', '?']) {
+            pattern.to_string()
+        } else {
+            format!("*{}*", pattern)
+        };
+
+        let search_id = Uuid::new_v4().to_string();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.active
+            .lock()
+            .insert(search_id.clone(), cancelled.clone());
+
+        let mut search = Search {
+            app,
+            search_id: search_id.clone(),
+            cancelled,
+            max_results: max_results.clamp(1, MAX_RESULTS_LIMIT),
+            batch: Vec::new(),
+            last_emit: Instant::now(),
+            total: 0,
+        };
+        let connection_id = connection_id.to_string();
+        let root = root.to_string();
+        let manager = self.clone();
+        std::thread::spawn(move || {
+            let result = search.run(&connection_id, &root, &pattern);
+            manager.active.lock().remove(&search.search_id);
+            search.finish(result);
+        });
+
+        Ok(search_id)
+    }
+
+    /// 진행 중인 검색 취소 (지금까지 찾은 결과는 유지)
+    pub fn cancel(&self, search_id: &str) -> Result<(), String> {
+        let active = self.active.lock();
+        let cancelled = active
+            .get(search_id)
+            .ok_or_else(|| "진행 중인 검색을 찾을 수 없습니다.".to_string())?;
+        cancelled.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// 검색 한 건의 진행 상태 (작업 스레드 전용)
+struct Search {
+    app: AppHandle,
+    search_id: String,
+    cancelled: Arc<AtomicBool>,
+    max_results: usize,
+    batch: Vec<SearchHit>,
+    last_emit: Instant,
+    total: usize,
+}
+
+impl Search {
+    fn run(&mut self, connection_id: &str, root: &str, pattern: &str) -> Result<(), String> {
+        let client = SftpClient::connect_saved(connection_id)?;
+
+        match self.find_with_exec(&client, root, pattern) {
+            Ok(true) => return Ok(()),
+            // 일부 결과를 이미 보냈으면 중복을 피하려고 순회로 넘어가지 않음
+            Ok(false) if self.total > 0 => return Ok(()),
+            Err(e) if self.total > 0 => return Err(e),
+            Ok(false) => debug!("[Search] find unavailable, walking with SFTP"),
+            Err(e) => debug!("[Search] find failed ({}), walking with SFTP", e),
+        }
+        self.walk_with_sftp(&client, root, &pattern.to_lowercase())
+    }
+
+    /// 서버에서 `find` 실행 (정상 종료 또는 중단 시 true, 결과를 믿을 수 없으면 false)
+    fn find_with_exec(
+        &mut self,
+        client: &SftpClient,
+        root: &str,
+        pattern: &str,
+    ) -> Result<bool, String> {
+        let command = format!(
+            "find {} -mindepth 1 -iname {} -printf '%y %s %p\\n' 2>/dev/null",
+            shell_quote(&client.server_path(root)),
+            shell_quote(pattern)
+        );
+        // 결과 없이 오래 순회하는 동안에도 취소되도록 취소 플래그를 넘김
+        let cancelled = self.cancelled.clone();
+        let status = client.exec_lines_cancellable(&command, &cancelled, |line| {
+            let mut fields = line.splitn(3, ' ');
+            let (Some(kind), Some(size), Some(path)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return true;
+            };
+            self.push(SearchHit {
+                path: client.local_path(root, path),
+                is_directory: kind == "d",
+                size: size.parse().unwrap_or(0),
+            })
+        })?;
+        // 권한 없는 하위 폴더가 있어도 find는 1로 끝남 (찾은 결과가 있으면 호출자가 그대로 사용)
+        Ok(match status {
+            None | Some(0) => true,
+            Some(_) => false,
+        })
+    }
+
+    /// SFTP로 너비 우선 순회 (심볼릭 링크 디렉토리는 따라가지 않음)
+    fn walk_with_sftp(
+        &mut self,
+        client: &SftpClient,
+        root: &str,
+        pattern: &str,
+    ) -> Result<(), String> {
+        let mut pending = VecDeque::from([root.trim_end_matches('/').to_string()]);
+        let mut first = true;
+
+        while let Some(dir) = pending.pop_front() {
+            if self.cancelled.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            let entries = match client.read_dir(if dir.is_empty() { "/" } else { &dir }) {
+                Ok(entries) => entries,
+                Err(e) if first => return Err(e),
+                Err(e) => {
+                    debug!("[Search] skipping '{}': {}", dir, e);
+                    continue;
+                }
+            };
+            first = false;
+
+            for (name, stat) in entries {
+                if name == "." || name == ".." {
+                    continue;
+                }
+                let path = format!("{}/{}", dir, name);
+                let is_directory = stat.is_dir();
+                if is_directory && !stat.file_type().is_symlink() {
+                    pending.push_back(path.clone());
+                }
+                if glob_match(pattern, &name.to_lowercase())
+                    && !self.push(SearchHit {
+                        path,
+                        is_directory,
+                        size: stat.size.unwrap_or(0),
+                    })
+                {
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 결과 추가 (계속 검색하면 true, 최대 결과 수 도달 또는 취소 시 false)
+    fn push(&mut self, hit: SearchHit) -> bool {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return false;
+        }
+        self.batch.push(hit);
+        self.total += 1;
+        if self.batch.len() >= BATCH_SIZE || self.last_emit.elapsed() >= BATCH_INTERVAL {
+            self.flush();
+        }
+        self.total < self.max_results
+    }
+
+    fn flush(&mut self) {
+        self.last_emit = Instant::now();
+        if self.batch.is_empty() {
+            return;
+        }
+        let results = SearchResults {
+            search_id: self.search_id.clone(),
+            hits: std::mem::take(&mut self.batch),
+        };
+        let _ = self.app.emit("search-results", &results);
+    }
+
+    fn finish(mut self, result: Result<(), String>) {
+        self.flush();
+        let finished = SearchFinished {
+            search_id: self.search_id.clone(),
+            total: self.total,
+            truncated: self.total >= self.max_results,
+            cancelled: self.cancelled.load(Ordering::Relaxed),
+            error: result.err(),
+        };
+        info!(
+            "[Search] {} finished with {} results",
+            finished.search_id, finished.total
+        );
+        let _ = self.app.emit("search-finished", &finished);
+    }
+}
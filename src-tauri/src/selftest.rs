@@ -0,0 +1,161 @@
+//! 자가 진단 모듈 - 임시 드라이브 문자로 마운트해 실제 파일 작업을 순서대로 점검
+//!
+//! 점검은 WinFsp를 거치는 일반 파일 API(`std::fs`)로 하므로 캐시, 쓰기 지연, 이름 변경
+//! 경로까지 실제 사용과 같은 경로를 지난다. 모든 작업은 원격 루트 아래 임시 디렉토리에서
+//! 이루어지고 끝나면 지운다.
+
+use ssh_virtual_drive_core::mount::{self, MountManager};
+use ssh_virtual_drive_core::types::SelfTestStep;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// 마운트 후 드라이브가 탐색기에 나타날 때까지 기다리는 시간
+const DRIVE_READY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 큰 파일 점검 크기
+const LARGE_FILE_SIZE: usize = 16 * 1024 * 1024;
+
+/// 작은 파일 점검 개수
+const SMALL_FILE_COUNT: usize = 200;
+
+/// 연결을 임시 드라이브 문자로 마운트해 점검한 뒤 언마운트
+pub fn run(manager: &MountManager, connection_id: &str) -> Result<Vec<SelfTestStep>, String> {
+    // 사용자가 고를 가능성이 낮은 뒤쪽 문자부터 사용
+    let letter = mount::get_available_drive_letters()
+        .pop()
+        .ok_or_else(|| "자가 진단에 사용할 빈 드라이브 문자가 없습니다.".to_string())?;
+
+    let mut steps = Vec::new();
+    let mounted = step(&mut steps, &format!("{}: 마운트", letter), || {
+        manager.mount_saved(connection_id, letter).map(|_| ())
+    });
+    if !mounted {
+        return Ok(steps);
+    }
+
+    let dir = PathBuf::from(format!("{}:\\.svd-selftest-{}", letter, Uuid::new_v4()));
+    if step(&mut steps, "드라이브 준비", || wait_for_drive(letter))
+        && step(&mut steps, "디렉토리 생성", || {
+            fs::create_dir(&dir).map_err(|e| e.to_string())
+        })
+    {
+        run_file_steps(&mut steps, &dir);
+        step(&mut steps, "정리", || {
+            fs::remove_dir_all(&dir).map_err(|e| e.to_string())
+        });
+    }
+
+    step(&mut steps, "언마운트", || manager.unmount(letter));
+    Ok(steps)
+}
+
+fn run_file_steps(steps: &mut Vec<SelfTestStep>, dir: &Path) {
+    let file = dir.join("hello.txt");
+    let renamed = dir.join("hello-renamed.txt");
+    let content = b"SSH Virtual Drive self test\r\n";
+
+    let written = step(steps, "파일 쓰기", || {
+        fs::write(&file, content).map_err(|e| e.to_string())
+    });
+    if written {
+        step(steps, "다시 읽어 비교", || {
+            let read = fs::read(&file).map_err(|e| e.to_string())?;
+            expect(read == content, "읽은 내용이 쓴 내용과 다릅니다.")
+        });
+        let moved = step(steps, "이름 변경", || {
+            fs::rename(&file, &renamed).map_err(|e| e.to_string())?;
+            expect(
+                renamed.exists() && !file.exists(),
+                "이름 변경 후 파일 상태가 올바르지 않습니다.",
+            )
+        });
+        let target = if moved { &renamed } else { &file };
+        step(steps, "파일 삭제", || {
+            fs::remove_file(target).map_err(|e| e.to_string())?;
+            expect(!target.exists(), "삭제한 파일이 아직 보입니다.")
+        });
+    }
+
+    step(
+        steps,
+        &format!("큰 파일 ({} MB)", LARGE_FILE_SIZE / (1024 * 1024)),
+        || {
+            let path = dir.join("large.bin");
+            let data = pattern(LARGE_FILE_SIZE);
+            fs::write(&path, &data).map_err(|e| e.to_string())?;
+            let read = fs::read(&path).map_err(|e| e.to_string())?;
+            let _ = fs::remove_file(&path);
+            expect(read == data, "큰 파일의 내용이 일치하지 않습니다.")
+        },
+    );
+
+    step(
+        steps,
+        &format!("작은 파일 {}개", SMALL_FILE_COUNT),
+        || {
+            let small = dir.join("small");
+            fs::create_dir(&small).map_err(|e| e.to_string())?;
+            for i in 0..SMALL_FILE_COUNT {
+                fs::write(small.join(format!("{:04}.txt", i)), i.to_string())
+                    .map_err(|e| format!("{:04}.txt: {}", i, e))?;
+            }
+            let listed = fs::read_dir(&small).map_err(|e| e.to_string())?.count();
+            expect(
+                listed == SMALL_FILE_COUNT,
+                &format!("목록에 {}개만 보입니다.", listed),
+            )
+        },
+    );
+}
+
+/// 단계를 실행해 결과를 기록하고 성공 여부 반환
+fn step(
+    steps: &mut Vec<SelfTestStep>,
+    name: &str,
+    action: impl FnOnce() -> Result<(), String>,
+) -> bool {
+    let started = Instant::now();
+    let result = action();
+    steps.push(SelfTestStep {
+        name: name.to_string(),
+        passed: result.is_ok(),
+        message: result.as_ref().err().cloned(),
+        duration_ms: started.elapsed().as_millis() as u64,
+    });
+    result.is_ok()
+}
+
+fn expect(condition: bool, message: &str) -> Result<(), String> {
+    if condition {
+        Ok(())
+    } else {
+        Err(message.to_string())
+    }
+}
+
+fn wait_for_drive(letter: char) -> Result<(), String> {
+    let root = format!("{}:\\", letter);
+    let started = Instant::now();
+    while !Path::new(&root).exists() {
+        if started.elapsed() > DRIVE_READY_TIMEOUT {
+            return Err("마운트했지만 드라이브가 나타나지 않습니다.".to_string());
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    Ok(())
+}
+
+/// 압축이나 중복 제거에 가려지지 않도록 위치마다 다른 바이트 패턴
+fn pattern(len: usize) -> Vec<u8> {
+    let mut state: u32 = 0x2545_F491;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect()
+}
@@ -0,0 +1,85 @@
+//! 백그라운드 서비스 클라이언트 - 서비스가 유지하는 드라이브 관리
+//!
+//! 서비스(`ssh-virtual-drive-service`)는 LocalSystem 계정으로 돌기 때문에 이 앱의
+//! 자격 증명 관리자에 접근할 수 없다. 마운트할 때 연결 프로필과 비밀 값을 함께 넘기면
+//! 서비스가 자기 계정의 자격 증명 관리자에 저장해 두고 재부팅 후에도 다시 마운트한다.
+//! 비밀 값은 파이프 서버가 LocalSystem 프로세스일 때만 보내며, 연결 훅은 서비스에서 실행하지 않는다.
+
+use serde_json::{json, Value};
+use ssh_virtual_drive_core::credentials;
+use ssh_virtual_drive_core::ipc::{self, SERVICE_PIPE};
+use ssh_virtual_drive_core::storage;
+use ssh_virtual_drive_core::types::{AuthType, ConnectionSecrets, DriveStatus};
+
+/// 파이프가 없음 (서비스가 설치되지 않았거나 멈춤)
+const ERROR_FILE_NOT_FOUND: &str = "os error 2)";
+/// 파이프 권한 없음 (앱이 관리자 권한으로 실행되지 않음)
+const ERROR_ACCESS_DENIED: &str = "os error 5)";
+
+/// 서비스가 마운트한 드라이브 목록
+pub fn list_drives() -> Result<Vec<DriveStatus>, String> {
+    from_value(call("list", Value::Null)?)
+}
+
+/// 저장된 연결을 서비스 드라이브로 마운트
+pub fn mount(connection_id: &str, drive_letter: char) -> Result<DriveStatus, String> {
+    let connection = storage::get_connection_by_id(connection_id)?
+        .ok_or_else(|| "연결을 찾을 수 없습니다.".to_string())?;
+    if connection.key_id.is_some() {
+        return Err("키 저장소의 키를 쓰는 연결은 서비스로 마운트할 수 없습니다. 키 파일 경로를 지정해주세요.".to_string());
+    }
+    if connection.auth_type == AuthType::Agent {
+        return Err("SSH 에이전트 인증 연결은 서비스로 마운트할 수 없습니다. 서비스는 사용자의 에이전트에 접근할 수 없습니다.".to_string());
+    }
+
+    let secrets = ConnectionSecrets {
+        password: if connection.auth_type == AuthType::Password {
+            credentials::get_password(&connection.id)?
+        } else {
+            None
+        },
+        proxy_password: match connection.proxy.as_ref() {
+            Some(proxy) if proxy.username.is_some() => {
+                credentials::get_password(&credentials::proxy_credential_key(&connection.id))?
+            }
+            _ => None,
+        },
+        totp_secret: if connection.totp {
+            credentials::get_password(&credentials::totp_credential_key(&connection.id))?
+        } else {
+            None
+        },
+    };
+
+    let result = call(
+        "mount",
+        json!({
+            "connection": connection,
+            "drive_letter": drive_letter,
+            "secrets": secrets,
+        }),
+    )?;
+    from_value(result)
+}
+
+/// 서비스 드라이브 언마운트 (서비스의 드라이브 목록에서도 제거)
+pub fn unmount(drive_letter: char) -> Result<(), String> {
+    call("unmount", json!({ "drive_letter": drive_letter })).map(|_| ())
+}
+
+fn call(method: &str, params: Value) -> Result<Value, String> {
+    ipc::call_system(SERVICE_PIPE, method, params).map_err(|e| {
+        if e.ends_with(ERROR_FILE_NOT_FOUND) {
+            "백그라운드 서비스가 실행 중이 아닙니다. 서비스를 설치하고 시작해주세요.".to_string()
+        } else if e.ends_with(ERROR_ACCESS_DENIED) {
+            "백그라운드 서비스에 접근할 권한이 없습니다. 앱을 관리자 권한으로 실행해주세요."
+                .to_string()
+        } else {
+            e
+        }
+    })
+}
+
+fn from_value<T: for<'de> serde::Deserialize<'de>>(value: Value) -> Result<T, String> {
+    serde_json::from_value(value).map_err(|e| format!("서비스 응답 해석 실패: {}", e))
+}
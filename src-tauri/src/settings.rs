@@ -0,0 +1,95 @@
+//! 앱 설정 모듈 - 언어, 창 위치 등 앱 전체 설정을 저장하고 바로 적용
+
+use crate::{locale, power, shell};
+use log::warn;
+use ssh_virtual_drive_core::storage;
+use ssh_virtual_drive_core::types::{AppSettings, WindowState};
+use std::fs;
+use tauri::{AppHandle, PhysicalPosition, PhysicalSize, Runtime, Window};
+
+/// 저장된 앱 설정 (없으면 기본값)
+pub fn load() -> AppSettings {
+    storage::get_app_settings_file()
+        .and_then(|file| fs::read_to_string(file).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// 앱 설정 저장 후 적용 (언어가 바뀌면 트레이 메뉴를 다시 만듦)
+pub fn save(app: &AppHandle, settings: &AppSettings) -> Result<(), String> {
+    let mut settings = settings.clone();
+    // 창 위치는 앱이 직접 기록하므로 프론트엔드가 넘기지 않으면 저장된 값을 유지
+    if settings.window.is_none() {
+        settings.window = load().window;
+    }
+    write(&settings)?;
+
+    power::set_enabled(settings.prevent_sleep);
+    // 탐색기 메뉴는 다음 드라이브 확인 때 켬/끔과 언어에 맞춰 다시 등록됨
+    shell::set_enabled(settings.shell_integration);
+    if locale::language() != settings.language {
+        locale::set_language(settings.language);
+        crate::refresh_tray_menu(app).map_err(|e| format!("트레이 메뉴 갱신 실패: {}", e))?;
+    }
+    Ok(())
+}
+
+/// 지금 창 위치와 크기를 기록 (숨겼거나 최소화한 창은 기록하지 않음)
+pub fn save_window<R: Runtime>(window: &Window<R>) {
+    if !window.is_visible().unwrap_or(false) || window.is_minimized().unwrap_or(false) {
+        return;
+    }
+    let (Ok(position), Ok(size)) = (window.outer_position(), window.inner_size()) else {
+        return;
+    };
+    let maximized = window.is_maximized().unwrap_or(false);
+    let mut settings = load();
+    let state = match settings.window {
+        // 최대화한 창의 위치와 크기는 화면 전체이므로 최대화 전 값을 유지
+        Some(previous) if maximized => WindowState {
+            maximized,
+            ..previous
+        },
+        _ => WindowState {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+            maximized,
+        },
+    };
+    settings.window = Some(state);
+    if let Err(e) = write(&settings) {
+        warn!("[Settings] saving window state failed: {}", e);
+    }
+}
+
+/// 저장된 창 위치와 크기 복원 (연결된 모니터 밖이면 위치는 기본값 유지)
+///
+/// 최대화는 숨긴 창도 띄우므로 창을 보일 때 따로 적용한다.
+pub fn restore_window<R: Runtime>(window: &Window<R>, state: &WindowState) {
+    let _ = window.set_size(PhysicalSize::new(state.width, state.height));
+    let visible = window.available_monitors().is_ok_and(|monitors| {
+        monitors.iter().any(|monitor| {
+            let (origin, size) = (monitor.position(), monitor.size());
+            state.x >= origin.x
+                && state.y >= origin.y
+                && state.x < origin.x + size.width as i32
+                && state.y < origin.y + size.height as i32
+        })
+    });
+    if visible {
+        let _ = window.set_position(PhysicalPosition::new(state.x, state.y));
+    }
+}
+
+fn write(settings: &AppSettings) -> Result<(), String> {
+    let file = storage::get_app_settings_file()
+        .ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("디렉토리 생성 실패: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(settings).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+    fs::write(&file, content).map_err(|e| format!("파일 저장 실패: {}", e))
+}
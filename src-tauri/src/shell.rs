@@ -0,0 +1,334 @@
+//! 탐색기 메뉴 모듈 - 마운트한 드라이브의 파일/폴더 오른쪽 클릭 메뉴에 원격 작업 추가
+//!
+//! 앱 설정에서 켜면 사용자별 `Software\Classes`의 `*`, `Directory`, `Directory\Background`
+//! 아래에 "원격 경로 복사", "sftp:// URL 복사", "여기서 터미널 열기" 하위 메뉴를 등록한다.
+//! 메뉴는 `AppliesTo` 조건으로 마운트된 드라이브에서만 보이고, 마운트된 드라이브가 바뀌면 다시
+//! 쓴다. 메뉴를 고르면 탐색기가 앱 실행 파일을 `--shell-action <작업> <경로>`로 실행하고, 새
+//! 프로세스는 창을 띄우지 않고 실행 중인 앱의 제어 파이프([`crate::rpc`]의 `shell` 메서드)로
+//! 작업을 넘긴 뒤 끝난다.
+
+use crate::locale::{self, Text};
+use log::warn;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde_json::json;
+use ssh_virtual_drive_core::ipc;
+use ssh_virtual_drive_core::mount::MountManager;
+use ssh_virtual_drive_core::sftp_client::shell_quote;
+use ssh_virtual_drive_core::types::{AuthType, Language, SshConnection};
+use std::ffi::c_void;
+use std::iter::once;
+use std::os::windows::process::CommandExt;
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use winreg::enums::{HKEY_CURRENT_USER, KEY_ALL_ACCESS};
+use winreg::RegKey;
+
+/// 메뉴에서 실행될 때 받는 인자
+const ACTION_ARG: &str = "--shell-action";
+
+/// 메뉴를 등록하는 키 (모든 파일, 폴더, 폴더 빈 곳)와 경로 자리 표시
+///
+/// 드라이브 루트의 빈 곳에서는 `%V`가 `X:\`로 바뀌어 닫는 따옴표가 `\"`로 읽히므로
+/// `%V.`로 넘기고 받을 때 `\.`을 뗀다.
+const MENU_KEYS: [(&str, &str); 3] = [
+    (r"Software\Classes\*\shell\SshVirtualDrive", "%1"),
+    (r"Software\Classes\Directory\shell\SshVirtualDrive", "%1"),
+    (
+        r"Software\Classes\Directory\Background\shell\SshVirtualDrive",
+        "%V.",
+    ),
+];
+
+const CREATE_NEW_CONSOLE: u32 = 0x0000_0010;
+const CF_UNICODETEXT: u32 = 13;
+const GMEM_MOVEABLE: u32 = 0x0002;
+const MB_ICONERROR: u32 = 0x0000_0010;
+
+#[link(name = "user32")]
+extern "system" {
+    fn OpenClipboard(owner: *mut c_void) -> i32;
+    fn EmptyClipboard() -> i32;
+    fn SetClipboardData(format: u32, memory: *mut c_void) -> *mut c_void;
+    fn CloseClipboard() -> i32;
+    fn MessageBoxW(owner: *mut c_void, text: *const u16, caption: *const u16, kind: u32) -> i32;
+}
+
+#[link(name = "kernel32")]
+extern "system" {
+    fn GlobalAlloc(flags: u32, bytes: usize) -> *mut c_void;
+    fn GlobalLock(memory: *mut c_void) -> *mut c_void;
+    fn GlobalUnlock(memory: *mut c_void) -> i32;
+    fn GlobalFree(memory: *mut c_void) -> *mut c_void;
+}
+
+/// 메뉴 작업
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShellAction {
+    CopyPath,
+    CopyUrl,
+    Terminal,
+}
+
+impl ShellAction {
+    const ALL: [ShellAction; 3] = [Self::CopyPath, Self::CopyUrl, Self::Terminal];
+
+    fn arg(self) -> &'static str {
+        match self {
+            Self::CopyPath => "copy-path",
+            Self::CopyUrl => "copy-url",
+            Self::Terminal => "terminal",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        locale::text(match self {
+            Self::CopyPath => Text::ShellCopyPath,
+            Self::CopyUrl => Text::ShellCopyUrl,
+            Self::Terminal => Text::ShellTerminal,
+        })
+    }
+}
+
+/// 앱 설정의 탐색기 메뉴 사용 여부
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 지금 등록해 둔 메뉴 (드라이브 문자, 언어) - 바뀌었을 때만 레지스트리를 다시 씀
+static REGISTERED: Mutex<Option<(Vec<char>, Language)>> = Mutex::new(None);
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// 마운트된 드라이브에 맞춰 메뉴 등록/해제 (드라이브나 언어가 바뀌었을 때만 씀)
+pub fn sync(letters: Vec<char>) {
+    let wanted = (ENABLED.load(Ordering::Relaxed) && !letters.is_empty())
+        .then(|| (letters, locale::language()));
+    let mut registered = REGISTERED.lock();
+    if *registered == wanted {
+        return;
+    }
+    let result = match &wanted {
+        Some((letters, _)) => register(letters),
+        None => unregister(),
+    };
+    if let Err(e) = result {
+        warn!("[Shell] updating Explorer menu failed: {}", e);
+    }
+    *registered = wanted;
+}
+
+/// 이전 실행이 남긴 메뉴 제거 (시작할 때)
+pub fn clear() {
+    if let Err(e) = unregister() {
+        warn!("[Shell] removing Explorer menu failed: {}", e);
+    }
+}
+
+fn register(letters: &[char]) -> std::io::Result<()> {
+    let exe = std::env::current_exe()?;
+    let exe = exe.to_string_lossy();
+    // 경로가 마운트된 드라이브 중 하나로 시작할 때만 메뉴를 보임
+    let applies_to = letters
+        .iter()
+        .map(|letter| format!("System.ItemPathDisplay:~<\"{}:\\\"", letter))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    for (key, placeholder) in MENU_KEYS {
+        let (menu, _) = hkcu.create_subkey(key)?;
+        menu.set_value("MUIVerb", &locale::text(Text::ShellMenu))?;
+        menu.set_value("SubCommands", &"")?;
+        menu.set_value("Icon", &exe.as_ref())?;
+        menu.set_value("AppliesTo", &applies_to)?;
+        for (i, action) in ShellAction::ALL.iter().enumerate() {
+            let (verb, _) = menu.create_subkey(format!(r"shell\{:02}{}", i, action.arg()))?;
+            verb.set_value("MUIVerb", &action.label())?;
+            let (command, _) = verb.create_subkey("command")?;
+            command.set_value(
+                "",
+                &format!(
+                    "\"{}\" {} {} \"{}\"",
+                    exe,
+                    ACTION_ARG,
+                    action.arg(),
+                    placeholder
+                ),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn unregister() -> std::io::Result<()> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    for (key, _) in MENU_KEYS {
+        let (parent, name) = key.rsplit_once('\\').unwrap_or(("", key));
+        let Ok(parent) = hkcu.open_subkey_with_flags(parent, KEY_ALL_ACCESS) else {
+            continue;
+        };
+        if parent.open_subkey(name).is_ok() {
+            parent.delete_subkey_all(name)?;
+        }
+    }
+    Ok(())
+}
+
+/// 메뉴에서 실행된 프로세스면 실행 중인 앱에 작업을 넘기고 true (앱은 시작하지 않음)
+pub fn forward_invocation() -> bool {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [flag, action, path] = args.as_slice() else {
+        return false;
+    };
+    if flag != ACTION_ARG {
+        return false;
+    }
+    let path = path.strip_suffix(r"\.").unwrap_or(path);
+    let result = ipc::app_pipe_name()
+        .and_then(|name| ipc::call(&name, "shell", json!({ "action": action, "path": path })));
+    if let Err(e) = result {
+        show_error(&e);
+    }
+    true
+}
+
+/// 메뉴 작업 실행 (앱의 제어 파이프에서 호출)
+pub fn perform(manager: &MountManager, action: ShellAction, path: &str) -> Result<(), String> {
+    let (connection, remote_path) = manager.resolve_local_path(path)?;
+    match action {
+        ShellAction::CopyPath => copy_to_clipboard(&remote_path),
+        ShellAction::CopyUrl => copy_to_clipboard(&sftp_url(&connection, &remote_path)),
+        ShellAction::Terminal => {
+            // 파일에서 고르면 파일이 있는 폴더에서 엶
+            let dir = match Path::new(path).is_dir() {
+                true => remote_path.as_str(),
+                false => remote_path
+                    .rsplit_once('/')
+                    .map(|(parent, _)| if parent.is_empty() { "/" } else { parent })
+                    .unwrap_or("/"),
+            };
+            open_terminal(&connection, dir)
+        }
+    }
+}
+
+/// 연결과 서버 경로로 만든 `sftp://user@host:port/path` URL (비밀번호는 넣지 않음)
+fn sftp_url(connection: &SshConnection, remote_path: &str) -> String {
+    let host = match connection.host.contains(':') {
+        true => format!("[{}]", connection.host),
+        false => connection.host.clone(),
+    };
+    let port = match connection.port {
+        22 => String::new(),
+        port => format!(":{}", port),
+    };
+    format!(
+        "sftp://{}@{}{}{}",
+        percent_encode(&connection.username, false),
+        host,
+        port,
+        percent_encode(remote_path, true)
+    )
+}
+
+/// URL에 넣을 수 있게 인코딩 (`keep_slash`면 경로 구분자는 그대로)
+fn percent_encode(text: &str, keep_slash: bool) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            b'/' if keep_slash => "/".to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// 서버의 폴더에서 시작하는 ssh 터미널 열기 (Windows Terminal이 없으면 새 콘솔 창)
+pub(crate) fn open_terminal(connection: &SshConnection, dir: &str) -> Result<(), String> {
+    // `-`로 시작하는 사용자/호스트는 ssh가 옵션(`-oProxyCommand=...`)으로 읽음
+    if connection.username.starts_with('-') || connection.host.starts_with('-') {
+        return Err("사용자 이름이나 호스트가 '-'로 시작해 터미널을 열 수 없습니다.".to_string());
+    }
+    let mut args = vec![
+        "-t".to_string(),
+        "-p".to_string(),
+        connection.port.to_string(),
+    ];
+    if connection.auth_type == AuthType::Key {
+        if let Some(key_path) = &connection.key_path {
+            args.extend(["-i".to_string(), key_path.clone()]);
+        }
+    }
+    args.push("--".to_string());
+    args.push(format!("{}@{}", connection.username, connection.host));
+    args.push(format!("cd {} && exec \"$SHELL\" -l", shell_quote(dir)));
+
+    // wt는 명령줄을 `;`에서 나눠 다음 하위 명령으로 실행하므로 인자 안의 `;`는 `\;`로 넘김
+    if Command::new("wt.exe")
+        .arg("ssh")
+        .args(args.iter().map(|arg| arg.replace(';', "\\;")))
+        .spawn()
+        .is_ok()
+    {
+        return Ok(());
+    }
+    Command::new("ssh")
+        .args(&args)
+        .creation_flags(CREATE_NEW_CONSOLE)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("터미널 실행 실패: {}", e))
+}
+
+fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    let wide: Vec<u16> = text.encode_utf16().chain(once(0)).collect();
+    unsafe {
+        if OpenClipboard(std::ptr::null_mut()) == 0 {
+            return Err("클립보드를 열 수 없습니다.".to_string());
+        }
+        EmptyClipboard();
+        let memory = GlobalAlloc(GMEM_MOVEABLE, wide.len() * 2);
+        let target = if memory.is_null() {
+            std::ptr::null_mut()
+        } else {
+            GlobalLock(memory)
+        };
+        if target.is_null() {
+            if !memory.is_null() {
+                GlobalFree(memory);
+            }
+            CloseClipboard();
+            return Err("클립보드 메모리 할당 실패".to_string());
+        }
+        std::ptr::copy_nonoverlapping(wide.as_ptr(), target as *mut u16, wide.len());
+        GlobalUnlock(memory);
+        // 성공하면 메모리는 클립보드가 가짐
+        if SetClipboardData(CF_UNICODETEXT, memory).is_null() {
+            GlobalFree(memory);
+            CloseClipboard();
+            return Err("클립보드에 복사할 수 없습니다.".to_string());
+        }
+        CloseClipboard();
+    }
+    Ok(())
+}
+
+/// 메뉴에서 실행된 프로세스는 창이 없으므로 실패는 메시지 상자로 알림
+fn show_error(message: &str) {
+    let text: Vec<u16> = message.encode_utf16().chain(once(0)).collect();
+    let caption: Vec<u16> = locale::text(Text::ShellMenu)
+        .encode_utf16()
+        .chain(once(0))
+        .collect();
+    unsafe {
+        MessageBoxW(
+            std::ptr::null_mut(),
+            text.as_ptr(),
+            caption.as_ptr(),
+            MB_ICONERROR,
+        )
+    };
+}
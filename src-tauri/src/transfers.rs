@@ -0,0 +1,449 @@
+//! 전송 대기열 모듈 - 마운트를 거치지 않는 직접 업로드/다운로드 관리
+//!
+//! 항목은 데이터 디렉토리의 transfers.json에 저장되어 앱을 다시 시작해도 유지되며,
+//! 중단된 항목은 마지막으로 기록한 바이트 위치부터 이어서 전송한다.
+//! 진행 상황은 `transfer-progress` 이벤트로 프론트엔드에 전달한다.
+
+use log::{info, warn};
+use parking_lot::Mutex;
+use ssh_virtual_drive_core::sftp_client::SftpClient;
+use ssh_virtual_drive_core::storage;
+use ssh_virtual_drive_core::types::{TransferDirection, TransferItem, TransferState};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use uuid::Uuid;
+
+/// 기본 동시 전송 수
+const DEFAULT_MAX_CONCURRENT: usize = 2;
+
+/// 동시 전송 수 상한
+const MAX_CONCURRENT_LIMIT: usize = 8;
+
+/// 진행 이벤트/저장 최소 간격
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+struct QueueInner {
+    items: Mutex<Vec<TransferItem>>,
+    // 작업 스레드가 아직 돌고 있는 항목 ID (일시 정지 후에도 현재 청크가 끝날 때까지 남음)
+    workers: Mutex<HashSet<String>>,
+    max_concurrent: AtomicUsize,
+    app: AppHandle,
+}
+
+/// 전송 대기열 (Tauri state로 공유, 복제해도 같은 대기열을 가리킴)
+#[derive(Clone)]
+pub struct TransferQueue {
+    inner: Arc<QueueInner>,
+}
+
+impl TransferQueue {
+    /// 저장된 대기열을 불러오고 중단되었던 항목을 다시 대기 상태로 돌림
+    pub fn load(app: AppHandle) -> Self {
+        let mut items = storage::load_transfers().unwrap_or_else(|e| {
+            warn!("[Transfers] failed to load queue: {}", e);
+            Vec::new()
+        });
+        for item in items.iter_mut() {
+            if item.state == TransferState::Active {
+                item.state = TransferState::Pending;
+            }
+        }
+
+        let queue = Self {
+            inner: Arc::new(QueueInner {
+                items: Mutex::new(items),
+                workers: Mutex::new(HashSet::new()),
+                max_concurrent: AtomicUsize::new(DEFAULT_MAX_CONCURRENT),
+                app,
+            }),
+        };
+        queue.schedule();
+        queue
+    }
+
+    /// 전송 항목 추가
+    pub fn enqueue(
+        &self,
+        connection_id: &str,
+        direction: TransferDirection,
+        local_path: &str,
+        remote_path: &str,
+    ) -> TransferItem {
+        let item = new_item(connection_id, direction, local_path, remote_path);
+        self.add(vec![item.clone()]);
+        item
+    }
+
+    /// 항목들을 한 번에 추가하고 저장 (대기열 파일은 한 번만 씀)
+    fn add(&self, new_items: Vec<TransferItem>) {
+        if new_items.is_empty() {
+            return;
+        }
+        self.inner.items.lock().extend(new_items.iter().cloned());
+        self.persist();
+        for item in &new_items {
+            self.emit(item);
+        }
+        self.schedule();
+    }
+
+    /// 창에 끌어다 놓은 로컬 파일/폴더를 원격 폴더로 업로드 예약
+    ///
+    /// 폴더는 하위 구조를 그대로 원격에 만들고 안의 파일을 각각 대기열에 넣는다.
+    pub fn enqueue_dropped(
+        &self,
+        connection_id: &str,
+        remote_dir: &str,
+        paths: &[String],
+    ) -> Result<Vec<TransferItem>, String> {
+        let mut files = Vec::new();
+        let mut dirs = Vec::new();
+        for path in paths {
+            collect_upload(Path::new(path), remote_dir, &mut files, &mut dirs)?;
+        }
+
+        if !dirs.is_empty() {
+            let client = SftpClient::connect_saved(connection_id)?;
+            for dir in &dirs {
+                if client.stat(dir).is_err() {
+                    client.create_dir(dir)?;
+                }
+            }
+        }
+
+        let items: Vec<TransferItem> = files
+            .into_iter()
+            .map(|(local, remote)| {
+                new_item(connection_id, TransferDirection::Upload, &local, &remote)
+            })
+            .collect();
+        self.add(items.clone());
+        Ok(items)
+    }
+
+    /// 전체 항목 목록
+    pub fn list(&self) -> Vec<TransferItem> {
+        self.inner.items.lock().clone()
+    }
+
+    /// 진행 중인 항목이 있는지
+    pub fn has_active(&self) -> bool {
+        self.inner
+            .items
+            .lock()
+            .iter()
+            .any(|item| item.state == TransferState::Active)
+    }
+
+    /// 대기/진행 중인 항목 일시 정지 (진행 중이면 현재 청크가 끝난 뒤 멈춤)
+    pub fn pause(&self, id: &str) -> Result<(), String> {
+        self.transition(
+            id,
+            &[TransferState::Pending, TransferState::Active],
+            TransferState::Paused,
+        )
+    }
+
+    /// 일시 정지/실패 항목 재개 (기록된 위치부터 이어서 전송)
+    pub fn resume(&self, id: &str) -> Result<(), String> {
+        self.transition(
+            id,
+            &[TransferState::Paused, TransferState::Failed],
+            TransferState::Pending,
+        )?;
+        self.schedule();
+        Ok(())
+    }
+
+    /// 완료되지 않은 항목 취소
+    pub fn cancel(&self, id: &str) -> Result<(), String> {
+        self.transition(
+            id,
+            &[
+                TransferState::Pending,
+                TransferState::Active,
+                TransferState::Paused,
+                TransferState::Failed,
+            ],
+            TransferState::Cancelled,
+        )
+    }
+
+    /// 끝난 항목(완료/취소) 목록에서 제거
+    pub fn clear_finished(&self) {
+        self.inner.items.lock().retain(|item| {
+            !matches!(
+                item.state,
+                TransferState::Completed | TransferState::Cancelled
+            )
+        });
+        self.persist();
+    }
+
+    /// 동시 전송 수 변경
+    pub fn set_max_concurrent(&self, count: usize) {
+        self.inner
+            .max_concurrent
+            .store(count.clamp(1, MAX_CONCURRENT_LIMIT), Ordering::Relaxed);
+        self.schedule();
+    }
+
+    fn transition(
+        &self,
+        id: &str,
+        from: &[TransferState],
+        to: TransferState,
+    ) -> Result<(), String> {
+        let item = {
+            let mut items = self.inner.items.lock();
+            let item = items
+                .iter_mut()
+                .find(|item| item.id == id)
+                .ok_or_else(|| "전송 항목을 찾을 수 없습니다.".to_string())?;
+            if !from.contains(&item.state) {
+                return Err("현재 상태에서는 변경할 수 없습니다.".to_string());
+            }
+            item.state = to;
+            if to == TransferState::Pending {
+                item.error = None;
+            }
+            item.clone()
+        };
+        self.persist();
+        self.emit(&item);
+        Ok(())
+    }
+
+    /// 동시 전송 한도 안에서 대기 항목 시작
+    ///
+    /// 일시 정지 직후 재개한 항목은 이전 작업 스레드가 끝난 뒤에 다시 시작한다
+    /// (두 스레드가 같은 파일에 쓰지 않도록).
+    fn schedule(&self) {
+        let max = self.inner.max_concurrent.load(Ordering::Relaxed);
+        let started: Vec<TransferItem> = {
+            let mut items = self.inner.items.lock();
+            let mut workers = self.inner.workers.lock();
+            let mut started = Vec::new();
+            for item in items.iter_mut() {
+                if workers.len() >= max {
+                    break;
+                }
+                if item.state == TransferState::Pending && !workers.contains(&item.id) {
+                    item.state = TransferState::Active;
+                    workers.insert(item.id.clone());
+                    started.push(item.clone());
+                }
+            }
+            started
+        };
+
+        for item in started {
+            self.emit(&item);
+            let queue = self.clone();
+            std::thread::spawn(move || queue.run(item));
+        }
+    }
+
+    /// 작업 스레드 - 항목을 전송한 뒤 같은 연결의 대기 항목을 같은 SSH 연결로 이어서 전송
+    fn run(&self, first: TransferItem) {
+        let mut client = None;
+        let mut next = Some(first);
+        while let Some(item) = next {
+            self.run_one(&item, &mut client);
+            next = self.claim_next(&item.id, &item.connection_id);
+        }
+        self.schedule();
+    }
+
+    /// 끝난 항목의 작업 자리를 같은 연결의 다음 대기 항목에 넘김 (없으면 자리를 비움)
+    fn claim_next(&self, finished_id: &str, connection_id: &str) -> Option<TransferItem> {
+        let next = {
+            let mut items = self.inner.items.lock();
+            let mut workers = self.inner.workers.lock();
+            workers.remove(finished_id);
+            let max = self.inner.max_concurrent.load(Ordering::Relaxed);
+            if workers.len() >= max {
+                return None;
+            }
+            let item = items.iter_mut().find(|item| {
+                item.state == TransferState::Pending
+                    && item.connection_id == connection_id
+                    && !workers.contains(&item.id)
+            })?;
+            item.state = TransferState::Active;
+            workers.insert(item.id.clone());
+            item.clone()
+        };
+        self.emit(&next);
+        Some(next)
+    }
+
+    /// 항목 하나 전송 (실패하면 연결을 버려 다음 항목은 새로 연결)
+    fn run_one(&self, item: &TransferItem, client: &mut Option<SftpClient>) {
+        let result = self.transfer(item, client);
+        if result.is_err() {
+            *client = None;
+        }
+        let finished = {
+            let mut items = self.inner.items.lock();
+            items.iter_mut().find(|i| i.id == item.id).map(|current| {
+                match result {
+                    // 일시 정지/취소로 멈춘 경우 바뀐 상태를 유지
+                    Ok(false) => {}
+                    Ok(true) => current.state = TransferState::Completed,
+                    Err(ref e) => {
+                        if current.state == TransferState::Active {
+                            current.state = TransferState::Failed;
+                            current.error = Some(e.clone());
+                        }
+                    }
+                }
+                current.clone()
+            })
+        };
+
+        if let Some(item) = finished {
+            info!(
+                "[Transfers] {} '{}' -> {:?}",
+                item.id, item.remote_path, item.state
+            );
+            self.persist();
+            self.emit(&item);
+        }
+    }
+
+    /// 실제 전송 (완료 true, 일시 정지/취소로 중단 false)
+    fn transfer(
+        &self,
+        item: &TransferItem,
+        client: &mut Option<SftpClient>,
+    ) -> Result<bool, String> {
+        let client = match client {
+            Some(client) => client,
+            None => client.insert(SftpClient::connect_saved(&item.connection_id)?),
+        };
+        let local_path = Path::new(&item.local_path);
+
+        let total = match item.direction {
+            TransferDirection::Upload => std::fs::metadata(local_path)
+                .map_err(|e| format!("로컬 파일 확인 실패: {}", e))?
+                .len(),
+            TransferDirection::Download => client.stat(&item.remote_path)?.size.unwrap_or(0),
+        };
+        // 원본 크기보다 많이 기록된 경우(원본이 바뀜) 처음부터 다시 전송
+        let offset = if item.transferred_bytes <= total {
+            item.transferred_bytes
+        } else {
+            0
+        };
+        self.update(&item.id, |current| {
+            current.total_bytes = total;
+            current.transferred_bytes = offset;
+        });
+
+        let mut last_report = Instant::now();
+        let progress = |transferred: u64| {
+            let active = self
+                .update(&item.id, |current| current.transferred_bytes = transferred)
+                .is_some_and(|current| current.state == TransferState::Active);
+            if last_report.elapsed() >= PROGRESS_INTERVAL {
+                last_report = Instant::now();
+                if let Some(current) = self.get(&item.id) {
+                    self.persist();
+                    self.emit(&current);
+                }
+            }
+            active
+        };
+
+        match item.direction {
+            TransferDirection::Upload => {
+                client.upload_file(local_path, &item.remote_path, offset, progress)
+            }
+            TransferDirection::Download => {
+                client.download_file(&item.remote_path, local_path, offset, progress)
+            }
+        }
+    }
+
+    fn get(&self, id: &str) -> Option<TransferItem> {
+        self.inner.items.lock().iter().find(|i| i.id == id).cloned()
+    }
+
+    fn update(&self, id: &str, change: impl FnOnce(&mut TransferItem)) -> Option<TransferItem> {
+        let mut items = self.inner.items.lock();
+        let item = items.iter_mut().find(|i| i.id == id)?;
+        change(item);
+        Some(item.clone())
+    }
+
+    fn persist(&self) {
+        let items = self.inner.items.lock().clone();
+        if let Err(e) = storage::save_transfers(&items) {
+            warn!("[Transfers] failed to save queue: {}", e);
+        }
+    }
+
+    fn emit(&self, item: &TransferItem) {
+        let _ = self.inner.app.emit("transfer-progress", item);
+    }
+}
+
+/// 대기 상태의 새 전송 항목
+fn new_item(
+    connection_id: &str,
+    direction: TransferDirection,
+    local_path: &str,
+    remote_path: &str,
+) -> TransferItem {
+    TransferItem {
+        id: Uuid::new_v4().to_string(),
+        connection_id: connection_id.to_string(),
+        direction,
+        local_path: local_path.to_string(),
+        remote_path: remote_path.to_string(),
+        total_bytes: 0,
+        transferred_bytes: 0,
+        state: TransferState::Pending,
+        error: None,
+    }
+}
+
+/// 업로드할 (로컬 파일, 원격 경로)와 만들 원격 폴더 수집 (상위 폴더가 먼저 오도록)
+///
+/// 심볼릭 링크와 정션은 따라가지 않고 건너뛴다 (자기 자신을 가리키는 정션에서 끝없이 돌지 않도록).
+fn collect_upload(
+    local: &Path,
+    remote_dir: &str,
+    files: &mut Vec<(String, String)>,
+    dirs: &mut Vec<String>,
+) -> Result<(), String> {
+    let name = local
+        .file_name()
+        .ok_or_else(|| format!("잘못된 경로: {}", local.display()))?
+        .to_string_lossy();
+    let remote = format!("{}/{}", remote_dir.trim_end_matches('/'), name);
+
+    let metadata = std::fs::symlink_metadata(local)
+        .map_err(|e| format!("로컬 파일 확인 실패 ({}): {}", local.display(), e))?;
+    if metadata.file_type().is_symlink() {
+        warn!("[Transfers] skipping link '{}'", local.display());
+        return Ok(());
+    }
+    if metadata.is_dir() {
+        dirs.push(remote.clone());
+        let entries =
+            std::fs::read_dir(local).map_err(|e| format!("로컬 폴더 읽기 실패: {}", e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("로컬 폴더 읽기 실패: {}", e))?;
+            collect_upload(&entry.path(), &remote, files, dirs)?;
+        }
+    } else {
+        files.push((local.to_string_lossy().to_string(), remote));
+    }
+    Ok(())
+}
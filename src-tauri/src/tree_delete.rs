@@ -0,0 +1,178 @@
+//! 원격 트리 삭제 모듈 - 큰 디렉토리를 항목마다 SFTP 요청을 보내지 않고 한 번에 지움
+//!
+//! 셸을 쓸 수 있는 서버는 `rm -rfv`로 서버 안에서 지우고 출력 줄 수로 진행 상황을 센다.
+//! exec가 막혀 있거나 `rm`이 실패하면 남은 항목을 SFTP로 깊이 우선 순회하며 지운다
+//! (심볼릭 링크는 따라가지 않고 링크만 지움). 진행 상황은 `tree-delete-progress` 이벤트로
+//! 나누어 보내고, 마지막 알림은 `done`과 오류를 담는다.
+
+use log::{debug, info, warn};
+use ssh_virtual_drive_core::mount::MountManager;
+use ssh_virtual_drive_core::path_filter::{AccessRules, ProtectedPaths};
+use ssh_virtual_drive_core::sftp_client::{is_not_found, shell_quote, SftpClient};
+use ssh_virtual_drive_core::storage;
+use ssh_virtual_drive_core::types::TreeDeleteProgress;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use uuid::Uuid;
+
+/// 진행 상황 최소 전송 간격
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// 원격 트리 삭제를 백그라운드에서 시작하고 삭제 ID 반환
+pub fn start(app: AppHandle, connection_id: &str, path: &str) -> Result<String, String> {
+    let path = path.trim();
+    if path.chars().all(|c| c == '/') && !path.is_empty() {
+        return Err("루트 디렉토리는 지울 수 없습니다.".to_string());
+    }
+    let path = path.trim_end_matches('/');
+    if !path.starts_with('/') {
+        return Err("삭제할 위치는 원격 절대 경로여야 합니다.".to_string());
+    }
+    // 접두 경로로 비교하는 규칙을 `..`로 비켜 가지 못하도록 정규화되지 않은 경로는 거부
+    if path.split('/').any(|c| c == "." || c == "..") {
+        return Err("삭제할 경로에 '.'이나 '..'을 쓸 수 없습니다.".to_string());
+    }
+    let connection = storage::get_connection_by_id(connection_id)?
+        .ok_or_else(|| "연결을 찾을 수 없습니다.".to_string())?;
+    // 트리 전체를 지우므로 경로 자신뿐 아니라 그 아래에 걸린 규칙도 확인
+    let access = AccessRules::new(&connection.access_rules);
+    if access.is_read_only(path) || access.has_rule_within(path) {
+        return Err(format!(
+            "'{}'나 그 아래에 읽기 전용/숨김 규칙이 있어 지울 수 없습니다.",
+            path
+        ));
+    }
+    let protected = ProtectedPaths::new(&connection.protected_paths);
+    if let Some(rule) = protected
+        .rule_for(path)
+        .or_else(|| protected.rule_within(path))
+    {
+        return Err(format!(
+            "'{}'나 그 아래가 보호 경로 규칙({})에 걸려 지울 수 없습니다.",
+            path, rule
+        ));
+    }
+
+    let mut delete = TreeDelete {
+        app,
+        progress: TreeDeleteProgress {
+            delete_id: Uuid::new_v4().to_string(),
+            connection_id: connection_id.to_string(),
+            path: path.to_string(),
+            deleted: 0,
+            server_side: false,
+            done: false,
+            error: None,
+        },
+        last_emit: Instant::now(),
+    };
+    let delete_id = delete.progress.delete_id.clone();
+    std::thread::spawn(move || {
+        let result = delete.run();
+        delete.finish(result);
+    });
+    Ok(delete_id)
+}
+
+/// 삭제 한 건의 진행 상태 (작업 스레드 전용)
+struct TreeDelete {
+    app: AppHandle,
+    progress: TreeDeleteProgress,
+    last_emit: Instant,
+}
+
+impl TreeDelete {
+    fn run(&mut self) -> Result<(), String> {
+        let client = SftpClient::connect_saved(&self.progress.connection_id)?;
+        let path = self.progress.path.clone();
+        let stat = client.lstat(&path)?;
+        if !stat.is_dir() || stat.file_type().is_symlink() {
+            client.remove_file(&path)?;
+            self.count();
+            return Ok(());
+        }
+
+        if client.capabilities().exec {
+            match self.remove_with_exec(&client, &path) {
+                Ok(true) => return Ok(()),
+                Ok(false) => debug!("[TreeDelete] rm failed, removing the rest with SFTP"),
+                Err(e) => debug!("[TreeDelete] rm unavailable ({}), removing with SFTP", e),
+            }
+            // 일부가 이미 지워졌으면 남은 항목만 순회
+            match client.lstat(&path) {
+                Err(e) if is_not_found(&e) => return Ok(()),
+                _ => {}
+            }
+        }
+        self.remove_with_sftp(&client, &path)
+    }
+
+    /// 서버에서 `rm -rfv` 실행 (정상 종료면 true)
+    fn remove_with_exec(&mut self, client: &SftpClient, path: &str) -> Result<bool, String> {
+        self.progress.server_side = true;
+        let command = format!(
+            "rm -rfv -- {} 2>/dev/null",
+            shell_quote(&client.server_path(path))
+        );
+        let status = client.exec_lines(&command, |_| {
+            self.count();
+            true
+        })?;
+        self.progress.server_side = false;
+        Ok(status == Some(0))
+    }
+
+    /// SFTP로 깊이 우선 순회하며 파일을 지우고, 디렉토리는 비운 뒤 지움
+    fn remove_with_sftp(&mut self, client: &SftpClient, root: &str) -> Result<(), String> {
+        let mut pending = vec![(root.to_string(), false)];
+        while let Some((dir, emptied)) = pending.pop() {
+            if emptied {
+                client.remove_dir(&dir)?;
+                self.count();
+                continue;
+            }
+            pending.push((dir.clone(), true));
+            for (name, stat) in client.read_dir(&dir)? {
+                if name == "." || name == ".." {
+                    continue;
+                }
+                let child = format!("{}/{}", dir, name);
+                if stat.is_dir() && !stat.file_type().is_symlink() {
+                    pending.push((child, false));
+                } else {
+                    client.remove_file(&child)?;
+                    self.count();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn count(&mut self) {
+        self.progress.deleted += 1;
+        if self.last_emit.elapsed() >= PROGRESS_INTERVAL {
+            self.last_emit = Instant::now();
+            let _ = self.app.emit("tree-delete-progress", &self.progress);
+        }
+    }
+
+    fn finish(mut self, result: Result<(), String>) {
+        // 성공하지 못했어도 일부는 지워졌으므로 마운트된 드라이브의 캐시는 버림
+        self.app
+            .state::<MountManager>()
+            .invalidate_remote_tree(&self.progress.connection_id, &self.progress.path);
+        match &result {
+            Ok(()) => info!(
+                "[TreeDelete] removed '{}' ({} entries)",
+                self.progress.path, self.progress.deleted
+            ),
+            Err(e) => warn!(
+                "[TreeDelete] removing '{}' stopped after {} entries: {}",
+                self.progress.path, self.progress.deleted, e
+            ),
+        }
+        self.progress.done = true;
+        self.progress.error = result.err();
+        let _ = self.app.emit("tree-delete-progress", &self.progress);
+    }
+}
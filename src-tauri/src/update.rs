@@ -0,0 +1,321 @@
+//! 업데이트 모듈 - 새 버전 확인 및 드라이브를 안전하게 내린 뒤 설치 프로그램 실행
+//!
+//! 최신 릴리스 정보와 설치 파일은 Windows에 포함된 PowerShell로 받는다 (HTTPS를 위한
+//! TLS 크레이트를 따로 두지 않기 위함). 설치 전에 마운트된 드라이브 목록을 기록하고
+//! 모두 언마운트하며, 새 버전이 시작되면 기록된 드라이브를 다시 마운트한다.
+//! 마운트한 채로 설치 프로그램이 앱을 교체하면 WinFsp 볼륨이 주인 없이 남기 때문이다.
+//!
+//! 설치 파일은 릴리스에 적힌 SHA-256과 일치하고, 지금 실행 중인 앱과 같은 게시자의 유효한
+//! Authenticode 서명이 있을 때만 실행한다. 해시는 같은 릴리스 정보에서 오므로 릴리스 자체가
+//! 바뀐 경우는 서명 확인으로만 막을 수 있다.
+
+use log::{info, warn};
+use sha2::{Digest, Sha256};
+use ssh_virtual_drive_core::mount::MountManager;
+use ssh_virtual_drive_core::storage;
+use ssh_virtual_drive_core::types::{PendingMount, UpdateInfo};
+use ssh_virtual_drive_core::xattr::to_hex;
+use std::fs;
+use std::os::windows::process::CommandExt;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tauri::{AppHandle, Emitter};
+
+/// 콘솔 창을 띄우지 않고 실행
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// 최신 릴리스 정보 (GitHub Releases API)
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/freeeebie/tauri-virtual-drive/releases/latest";
+
+/// 릴리스에서 찾을 설치 파일 이름 접미사 (NSIS 사용자별 설치)
+const INSTALLER_SUFFIX: &str = "-setup.exe";
+
+/// 최신 버전 확인
+pub fn check() -> Result<UpdateInfo, String> {
+    let release = fetch_latest_release()?;
+    let current = env!("CARGO_PKG_VERSION").to_string();
+    let latest = release["tag_name"]
+        .as_str()
+        .map(|tag| tag.trim_start_matches('v').to_string())
+        .ok_or_else(|| "릴리스 정보에 버전이 없습니다.".to_string())?;
+    let installer = release["assets"].as_array().and_then(|assets| {
+        assets.iter().find(|asset| {
+            asset["name"]
+                .as_str()
+                .is_some_and(|name| name.to_ascii_lowercase().ends_with(INSTALLER_SUFFIX))
+        })
+    });
+
+    Ok(UpdateInfo {
+        update_available: is_newer(&latest, &current),
+        current_version: current,
+        latest_version: latest,
+        release_notes: release["body"].as_str().map(str::to_string),
+        published_at: release["published_at"].as_str().map(str::to_string),
+        download_url: installer
+            .and_then(|asset| asset["browser_download_url"].as_str())
+            .map(str::to_string),
+        sha256: installer
+            .and_then(|asset| asset["digest"].as_str())
+            .and_then(|digest| digest.strip_prefix("sha256:"))
+            .map(str::to_ascii_lowercase),
+    })
+}
+
+/// 설치 파일을 받은 뒤 모든 드라이브를 언마운트하고 설치 프로그램 실행 후 앱 종료
+///
+/// 프론트엔드에서 사용자 확인을 받은 뒤 호출한다. 언마운트가 하나라도 실패하면
+/// 이미 내린 드라이브를 다시 마운트하고 설치를 취소한다.
+pub fn install(app: &AppHandle, manager: &MountManager) -> Result<(), String> {
+    let info = check()?;
+    if !info.update_available {
+        return Err("이미 최신 버전입니다.".to_string());
+    }
+    let url = info
+        .download_url
+        .ok_or_else(|| "릴리스에 Windows 설치 파일이 없습니다.".to_string())?;
+    let expected = info.sha256.ok_or_else(|| {
+        "릴리스에 설치 파일 해시가 없어 업데이트를 설치하지 않습니다.".to_string()
+    })?;
+
+    // 드라이브를 내리기 전에 내려받아 두어 다운로드 중에도 드라이브를 쓸 수 있게 함
+    let installer = std::env::temp_dir().join(format!(
+        "ssh-virtual-drive-{}{}",
+        info.latest_version, INSTALLER_SUFFIX
+    ));
+    download(&url, &installer)?;
+    let verified = sha256_file(&installer).and_then(|actual| {
+        if actual != expected {
+            return Err("내려받은 설치 파일의 해시가 일치하지 않습니다.".to_string());
+        }
+        verify_publisher(&installer)
+    });
+    if let Err(e) = verified {
+        let _ = fs::remove_file(&installer);
+        return Err(e);
+    }
+
+    let drives = manager.get_mounted_drives();
+    let pending: Vec<PendingMount> = drives
+        .iter()
+        .map(|drive| PendingMount {
+            connection_id: drive.connection_id.clone(),
+            drive_letter: drive.drive_letter,
+        })
+        .collect();
+    save_pending(&pending)?;
+
+    for (index, drive) in drives.iter().enumerate() {
+        // 별칭은 가리키는 드라이브를 내릴 때 함께 내려감 (다시 마운트할 때는 별칭으로 돌아옴)
+        if drive.alias_of.is_some() {
+            continue;
+        }
+        if let Err(e) = manager.unmount(drive.drive_letter) {
+            warn!(
+                "[Update] unmount of {}: failed, cancelling update: {}",
+                drive.drive_letter, e
+            );
+            remount(manager, &pending[..index]);
+            clear_pending();
+            return Err(format!(
+                "드라이브 {}: 언마운트 실패로 업데이트를 취소했습니다: {}",
+                drive.drive_letter, e
+            ));
+        }
+    }
+
+    if let Err(e) = Command::new(&installer).spawn() {
+        remount(manager, &pending);
+        clear_pending();
+        return Err(format!("설치 프로그램 실행 실패: {}", e));
+    }
+
+    info!(
+        "[Update] installer for {} started, {} drive(s) will be remounted",
+        info.latest_version,
+        pending.len()
+    );
+    app.exit(0);
+    Ok(())
+}
+
+/// 업데이트 전에 기록해 둔 드라이브 다시 마운트 (앱 시작 시 호출)
+pub fn resume_pending_mounts(app: &AppHandle, manager: &MountManager) {
+    let Some(file) = storage::get_pending_mounts_file() else {
+        return;
+    };
+    let Ok(content) = fs::read_to_string(&file) else {
+        return;
+    };
+    // 다시 마운트하다 실패해도 매번 재시도하지 않도록 먼저 삭제
+    clear_pending();
+
+    let pending: Vec<PendingMount> = match serde_json::from_str(&content) {
+        Ok(pending) => pending,
+        Err(e) => {
+            warn!("[Update] pending mount list ignored: {}", e);
+            return;
+        }
+    };
+    let remounted = remount(manager, &pending);
+    if !remounted.is_empty() {
+        let _ = app.emit("drives-remounted", remounted);
+    }
+}
+
+fn remount(manager: &MountManager, pending: &[PendingMount]) -> Vec<char> {
+    pending
+        .iter()
+        .filter(
+            |drive| match manager.mount_saved(&drive.connection_id, drive.drive_letter) {
+                Ok(_) => true,
+                Err(e) => {
+                    warn!("[Update] remount of {}: failed: {}", drive.drive_letter, e);
+                    false
+                }
+            },
+        )
+        .map(|drive| drive.drive_letter)
+        .collect()
+}
+
+fn save_pending(pending: &[PendingMount]) -> Result<(), String> {
+    let file = storage::get_pending_mounts_file()
+        .ok_or_else(|| "데이터 디렉토리를 찾을 수 없습니다.".to_string())?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("디렉토리 생성 실패: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(pending).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+    fs::write(&file, content).map_err(|e| format!("파일 저장 실패: {}", e))
+}
+
+fn clear_pending() {
+    if let Some(file) = storage::get_pending_mounts_file() {
+        let _ = fs::remove_file(file);
+    }
+}
+
+fn fetch_latest_release() -> Result<serde_json::Value, String> {
+    let output = powershell(
+        "(Invoke-WebRequest -UseBasicParsing -Uri $env:SVD_UPDATE_URL \
+         -Headers @{ 'User-Agent' = 'ssh-virtual-drive'; 'Accept' = 'application/vnd.github+json' }).Content",
+        LATEST_RELEASE_URL,
+        None,
+    )?;
+    serde_json::from_slice(&output).map_err(|e| format!("릴리스 정보 파싱 실패: {}", e))
+}
+
+fn download(url: &str, target: &Path) -> Result<(), String> {
+    powershell(
+        "Invoke-WebRequest -UseBasicParsing -Uri $env:SVD_UPDATE_URL -OutFile $env:SVD_UPDATE_FILE \
+         -Headers @{ 'User-Agent' = 'ssh-virtual-drive' }",
+        url,
+        Some(target),
+    )?;
+    let size = fs::metadata(target).map(|m| m.len()).unwrap_or(0);
+    if size == 0 {
+        return Err("설치 파일 다운로드 실패: 빈 파일".to_string());
+    }
+    Ok(())
+}
+
+/// URL/경로는 따옴표 처리 문제를 피하려고 환경 변수로 넘김
+fn powershell(script: &str, url: &str, file: Option<&Path>) -> Result<Vec<u8>, String> {
+    let mut command = Command::new("powershell");
+    command
+        .args(["-NoProfile", "-NonInteractive", "-Command"])
+        .arg(format!(
+            "$ProgressPreference = 'SilentlyContinue'; \
+             [Console]::OutputEncoding = [Text.Encoding]::UTF8; \
+             [Net.ServicePointManager]::SecurityProtocol = [Net.SecurityProtocolType]::Tls12; {}",
+            script
+        ))
+        .env("SVD_UPDATE_URL", url)
+        .stdin(Stdio::null())
+        .creation_flags(CREATE_NO_WINDOW);
+    if let Some(file) = file {
+        command.env("SVD_UPDATE_FILE", file);
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| format!("PowerShell 실행 실패: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "업데이트 서버 요청 실패: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
+
+fn sha256_file(path: &Path) -> Result<String, String> {
+    let data = fs::read(path).map_err(|e| format!("설치 파일 읽기 실패: {}", e))?;
+    Ok(to_hex(&Sha256::digest(&data)))
+}
+
+/// 설치 파일이 지금 실행 중인 앱과 같은 게시자의 유효한 서명을 가졌는지 확인
+fn verify_publisher(installer: &Path) -> Result<(), String> {
+    let current =
+        std::env::current_exe().map_err(|e| format!("실행 파일 경로 확인 실패: {}", e))?;
+    let publisher = signer_subject(&current).map_err(|e| {
+        format!(
+            "현재 앱의 서명을 확인할 수 없어 업데이트를 설치하지 않습니다: {}",
+            e
+        )
+    })?;
+    let signer =
+        signer_subject(installer).map_err(|e| format!("설치 파일 서명 확인 실패: {}", e))?;
+    if signer != publisher {
+        return Err(format!(
+            "설치 파일 게시자({})가 앱 게시자({})와 다릅니다.",
+            signer, publisher
+        ));
+    }
+    Ok(())
+}
+
+/// 파일의 Authenticode 서명을 확인하고 서명 인증서 주체 반환 (서명이 없거나 유효하지 않으면 실패)
+fn signer_subject(path: &Path) -> Result<String, String> {
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command"])
+        .arg(
+            "[Console]::OutputEncoding = [Text.Encoding]::UTF8; \
+             $sig = Get-AuthenticodeSignature -LiteralPath $env:SVD_SIGNED_FILE; \
+             if ($sig.Status -ne 'Valid') { [Console]::Error.Write($sig.StatusMessage); exit 1 }; \
+             $sig.SignerCertificate.Subject",
+        )
+        .env("SVD_SIGNED_FILE", path)
+        .stdin(Stdio::null())
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| format!("PowerShell 실행 실패: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    let subject = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if subject.is_empty() {
+        return Err("서명 인증서 정보가 없습니다.".to_string());
+    }
+    Ok(subject)
+}
+
+/// `1.2.10` > `1.2.9` 처럼 숫자 단위로 비교 (시험판 접미사는 무시, `1.2` == `1.2.0`)
+fn is_newer(latest: &str, current: &str) -> bool {
+    fn parts(version: &str) -> Vec<u64> {
+        let mut parts: Vec<u64> = version
+            .split(['-', '+'])
+            .next()
+            .unwrap_or("")
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect();
+        while parts.last() == Some(&0) {
+            parts.pop();
+        }
+        parts
+    }
+    parts(latest) > parts(current)
+}
@@ -0,0 +1,126 @@
+//! 디스크 사용량 분석 모듈 - 원격 디렉토리별 크기 트리 계산
+//!
+//! 셸을 쓸 수 있으면 `du`로 서버에서 디스크 사용량을 구하고, 안 되면 SFTP로
+//! 전체 트리를 순회하며 파일 크기를 더한다 (이 경우 블록 크기가 아닌 실제 파일 크기).
+//! 트리에는 요청한 깊이까지의 디렉토리만 담지만 크기는 하위 전체를 포함한다.
+
+use log::debug;
+use ssh_virtual_drive_core::sftp_client::{shell_quote, SftpClient};
+use ssh_virtual_drive_core::types::UsageNode;
+use std::collections::HashMap;
+
+/// 허용하는 최대 트리 깊이
+const MAX_DEPTH: u32 = 8;
+
+/// `path` 아래 디렉토리별 사용량 계산
+pub fn analyze(client: &SftpClient, path: &str, depth: u32) -> Result<UsageNode, String> {
+    let root = match path.trim().trim_end_matches('/') {
+        "" => "/".to_string(),
+        trimmed => trimmed.to_string(),
+    };
+    if !client.stat(&root)?.is_dir() {
+        return Err("디렉토리가 아닙니다.".to_string());
+    }
+    let depth = depth.min(MAX_DEPTH);
+
+    match du_tree(client, &root, depth) {
+        Ok(Some(tree)) => return Ok(tree),
+        Ok(None) => debug!("[Usage] du unavailable, walking with SFTP"),
+        Err(e) => debug!("[Usage] du failed ({}), walking with SFTP", e),
+    }
+    Ok(walk(client, &root, depth))
+}
+
+/// `du` 결과로 트리 구성 (출력이 없으면 None)
+fn du_tree(client: &SftpClient, root: &str, depth: u32) -> Result<Option<UsageNode>, String> {
+    let command = format!(
+        "du -k -d {} -- {} 2>/dev/null",
+        depth,
+        shell_quote(&client.server_path(root))
+    );
+    let mut nodes: HashMap<String, UsageNode> = HashMap::new();
+    // 권한 없는 하위 폴더가 있으면 du는 0이 아닌 코드로 끝나지만 나머지 결과는 유효함
+    client.exec_lines(&command, |line| {
+        if let Some((kb, path)) = line.split_once('\t') {
+            if let Ok(kb) = kb.trim().parse::<u64>() {
+                // 서버 이름으로 나온 경로를 드라이브에 보이는 이름으로
+                let path = client.local_path(root, path);
+                nodes.insert(
+                    path.clone(),
+                    UsageNode {
+                        path,
+                        size: kb * 1024,
+                        children: Vec::new(),
+                    },
+                );
+            }
+        }
+        true
+    })?;
+    if !nodes.contains_key(root) {
+        return Ok(None);
+    }
+
+    // 깊은 경로부터 부모에 붙임
+    let mut paths: Vec<String> = nodes.keys().filter(|p| *p != root).cloned().collect();
+    paths.sort_by_key(|p| std::cmp::Reverse(p.matches('/').count()));
+    for path in paths {
+        let Some(mut node) = nodes.remove(&path) else {
+            continue;
+        };
+        sort_children(&mut node);
+        let parent = match path.rsplit_once('/') {
+            Some(("", _)) => "/",
+            Some((parent, _)) => parent,
+            None => continue,
+        };
+        if let Some(parent) = nodes.get_mut(parent) {
+            parent.children.push(node);
+        }
+    }
+
+    let mut tree = nodes.remove(root);
+    if let Some(tree) = tree.as_mut() {
+        sort_children(tree);
+    }
+    Ok(tree)
+}
+
+/// SFTP로 하위 전체를 순회하며 크기 합산 (심볼릭 링크 디렉토리는 따라가지 않음)
+fn walk(client: &SftpClient, path: &str, remaining: u32) -> UsageNode {
+    let mut node = UsageNode {
+        path: path.to_string(),
+        size: 0,
+        children: Vec::new(),
+    };
+    let entries = match client.read_dir(path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            debug!("[Usage] skipping '{}': {}", path, e);
+            return node;
+        }
+    };
+
+    for (name, stat) in entries {
+        if name == "." || name == ".." {
+            continue;
+        }
+        if stat.is_dir() && !stat.file_type().is_symlink() {
+            let child_path = format!("{}/{}", path.trim_end_matches('/'), name);
+            let child = walk(client, &child_path, remaining.saturating_sub(1));
+            node.size += child.size;
+            if remaining > 0 {
+                node.children.push(child);
+            }
+        } else {
+            node.size += stat.size.unwrap_or(0);
+        }
+    }
+    sort_children(&mut node);
+    node
+}
+
+fn sort_children(node: &mut UsageNode) {
+    node.children
+        .sort_by_key(|child| std::cmp::Reverse(child.size));
+}
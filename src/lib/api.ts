@@ -1,33 +1,94 @@
-import { invoke } from '@tauri-apps/api/core';
-import type { SshConnection, DriveStatus, PrerequisiteStatus } from './types';
+import type { UnlistenFn } from '@tauri-apps/api/event';
+import { commands, events } from './bindings';
+import type {
+    SshConnection,
+    DeletedConnection,
+    DriveStatus,
+    MountStats,
+    HealthSample,
+    ConnectionUsage,
+    UsageCapWarning,
+    UnmountCheck,
+    PrerequisiteStatus,
+    OnboardingChecklist,
+    TransferItem,
+    SearchResults,
+    SearchFinished,
+    TreeDeleteProgress,
+    UsageNode,
+    RemoteFilePreview,
+    RemoteTextFile,
+    TextFileSaveResult,
+    FsActivity,
+    KeyFileInfo,
+    StoredKey,
+    Bookmark,
+    ConnectionTemplate,
+    UpdateInfo,
+    SelfTestStep,
+    LogSettings,
+    MemorySettings,
+    ResourceLimits,
+    ProfileSyncSettings,
+    ProfileSyncReport,
+    AppSettings,
+    ProtectedRequest,
+    PendingChange,
+    PushReport,
+    FileVersion,
+    ImportSource,
+    ImportReport,
+} from './types';
 
 // 사전 요구사항 확인
 export async function checkPrerequisites(): Promise<PrerequisiteStatus> {
-    return await invoke('check_prerequisites');
+    return await commands.checkPrerequisites();
+}
+
+// WinFsp 런처 서비스 시작 (관리자 권한 요청)
+export async function startWinfspService(): Promise<void> {
+    return await commands.startWinfspService();
+}
+
+// 첫 실행 점검
+export async function runOnboarding(): Promise<OnboardingChecklist> {
+    return await commands.runOnboarding();
 }
 
 // 저장된 연결 목록 가져오기
 export async function getConnections(): Promise<SshConnection[]> {
-    return await invoke('get_connections');
+    return await commands.getConnections();
 }
 
 // 연결 프로필 저장
 export async function saveConnection(
     connection: SshConnection | Omit<SshConnection, 'id'>,
-    password?: string
+    password?: string,
+    proxyPassword?: string,
+    totpSecret?: string
 ): Promise<SshConnection> {
     const conn = 'id' in connection ? connection : { ...connection, id: '' };
-    return await invoke('save_connection', { connection: conn, password });
+    return await commands.saveConnection(conn, password, proxyPassword, totpSecret);
 }
 
 // 연결 프로필 삭제
 export async function deleteConnection(id: string): Promise<void> {
-    return await invoke('delete_connection', { id });
+    return await commands.deleteConnection(id);
+}
+
+// 삭제한 연결 프로필 되돌리기 (보관 기간 안에만 가능)
+export async function undoDeleteConnection(id: string): Promise<SshConnection> {
+    return await commands.undoDeleteConnection(id);
+}
+
+// 삭제 후 보관 중인 연결 프로필 목록
+export async function listDeletedConnections(): Promise<DeletedConnection[]> {
+    return await commands.listDeletedConnections();
 }
 
 // 사용 가능한 드라이브 문자 목록
 export async function getAvailableDriveLetters(): Promise<string[]> {
-    return await invoke('get_available_drive_letters');
+    return await commands.getAvailableDriveLetters();
 }
 
 // 드라이브 마운트
@@ -35,22 +96,252 @@ export async function mountDrive(
     connectionId: string,
     driveLetter: string
 ): Promise<DriveStatus> {
-    return await invoke('mount_drive', {
-        connectionId,
-        driveLetter: driveLetter.charAt(0),
-    });
+    return await commands.mountDrive(connectionId, driveLetter.charAt(0));
+}
+
+// 북마크 목록 (connectionId가 있으면 그 연결의 북마크만)
+export async function listBookmarks(connectionId?: string): Promise<Bookmark[]> {
+    return await commands.listBookmarks(connectionId);
+}
+
+// 원격 경로 북마크 추가 (이름을 비우면 경로의 마지막 이름)
+export async function addBookmark(
+    connectionId: string,
+    name: string,
+    path: string
+): Promise<Bookmark> {
+    return await commands.addBookmark(connectionId, name, path);
+}
+
+// 북마크 삭제
+export async function removeBookmark(id: string): Promise<void> {
+    return await commands.removeBookmark(id);
+}
+
+// 북마크 경로를 루트로 드라이브 마운트
+export async function mountBookmark(
+    bookmarkId: string,
+    driveLetter: string
+): Promise<DriveStatus> {
+    return await commands.mountBookmark(bookmarkId, driveLetter.charAt(0));
+}
+
+// 북마크 경로에서 ssh 터미널 열기
+export async function openBookmarkTerminal(bookmarkId: string): Promise<void> {
+    return await commands.openBookmarkTerminal(bookmarkId);
+}
+
+// 언마운트 전 확인 (열린 파일 핸들 수와 최근 작업 파일)
+export async function checkUnmount(driveLetter: string): Promise<UnmountCheck> {
+    return await commands.checkUnmount(driveLetter.charAt(0));
 }
 
-// 드라이브 언마운트
-export async function unmountDrive(driveLetter: string): Promise<void> {
-    return await invoke('unmount_drive', {
-        driveLetter: driveLetter.charAt(0),
-    });
+// 드라이브 언마운트 (force가 아니면 열린 파일이나 최근 작업이 있을 때 거부)
+export async function unmountDrive(
+    driveLetter: string,
+    force = false
+): Promise<void> {
+    return await commands.unmountDrive(driveLetter.charAt(0), force);
 }
 
 // 현재 마운트된 드라이브 목록
 export async function getMountedDrives(): Promise<DriveStatus[]> {
-    return await invoke('get_mounted_drives');
+    return await commands.getMountedDrives();
+}
+
+// 마운트 통계 조회
+export async function getMountStats(driveLetter: string): Promise<MountStats> {
+    return await commands.getMountStats(driveLetter.charAt(0));
+}
+
+// 연결 상태 기록 (마운트 중 1분마다 잰 서버 응답 시간, 오래된 것부터)
+export async function getConnectionHealthHistory(id: string): Promise<HealthSample[]> {
+    return await commands.getConnectionHealthHistory(id);
+}
+
+// 연결의 누적 전송량과 월별 전송량 (UTC 기준 달, 이번 달 포함)
+export async function getConnectionUsage(id: string): Promise<ConnectionUsage> {
+    return await commands.getConnectionUsage(id);
+}
+
+// 서버 측 복사 (같은 드라이브 안의 경로끼리)
+export async function copyRemotePath(
+    driveLetter: string,
+    from: string,
+    to: string
+): Promise<void> {
+    return await commands.copyRemotePath(driveLetter.charAt(0), from, to);
+}
+
+// 업로드 대기열에 추가
+export async function enqueueUpload(
+    connectionId: string,
+    localPath: string,
+    remotePath: string
+): Promise<TransferItem> {
+    return await commands.enqueueUpload(connectionId, localPath, remotePath);
+}
+
+// 다운로드 대기열에 추가
+export async function enqueueDownload(
+    connectionId: string,
+    remotePath: string,
+    localPath: string
+): Promise<TransferItem> {
+    return await commands.enqueueDownload(connectionId, remotePath, localPath);
+}
+
+// 창에 끌어다 놓은 파일/폴더를 원격 폴더로 업로드 예약
+export async function stageDroppedUploads(
+    connectionId: string,
+    remoteDir: string,
+    paths: string[]
+): Promise<TransferItem[]> {
+    return await commands.stageDroppedUploads(connectionId, remoteDir, paths);
+}
+
+// 전송 대기열 목록
+export async function listTransfers(): Promise<TransferItem[]> {
+    return await commands.listTransfers();
+}
+
+// 전송 일시 정지
+export async function pauseTransfer(id: string): Promise<void> {
+    return await commands.pauseTransfer(id);
+}
+
+// 전송 재개
+export async function resumeTransfer(id: string): Promise<void> {
+    return await commands.resumeTransfer(id);
+}
+
+// 전송 취소
+export async function cancelTransfer(id: string): Promise<void> {
+    return await commands.cancelTransfer(id);
+}
+
+// 완료/취소된 전송 항목 정리
+export async function clearFinishedTransfers(): Promise<void> {
+    return await commands.clearFinishedTransfers();
+}
+
+// 동시 전송 수 설정
+export async function setTransferConcurrency(count: number): Promise<void> {
+    return await commands.setTransferConcurrency(count);
+}
+
+// 전송 진행 상황 구독
+export async function onTransferProgress(
+    callback: (item: TransferItem) => void
+): Promise<UnlistenFn> {
+    return await events.transferProgress(callback);
+}
+
+// 원격 파일 이름 검색 시작 (검색 ID 반환)
+export async function searchRemote(
+    connectionId: string,
+    root: string,
+    pattern: string,
+    maxResults: number
+): Promise<string> {
+    return await commands.searchRemote(connectionId, root, pattern, maxResults);
+}
+
+// 진행 중인 원격 검색 취소
+export async function cancelSearch(searchId: string): Promise<void> {
+    return await commands.cancelSearch(searchId);
+}
+
+// 검색 중간 결과 구독
+export async function onSearchResults(
+    callback: (results: SearchResults) => void
+): Promise<UnlistenFn> {
+    return await events.searchResults(callback);
+}
+
+// 검색 종료 구독
+export async function onSearchFinished(
+    callback: (finished: SearchFinished) => void
+): Promise<UnlistenFn> {
+    return await events.searchFinished(callback);
+}
+
+// 원격 디렉토리별 사용량 분석
+export async function analyzeRemoteUsage(
+    connectionId: string,
+    path: string,
+    depth: number
+): Promise<UsageNode> {
+    return await commands.analyzeRemoteUsage(connectionId, path, depth);
+}
+
+// 원격 파일/디렉토리 트리 삭제 시작 (삭제 ID 반환, 진행 상황은 onTreeDeleteProgress로 받음)
+export async function deleteRemoteTree(
+    connectionId: string,
+    path: string
+): Promise<string> {
+    return await commands.deleteRemoteTree(connectionId, path);
+}
+
+// 원격 트리 삭제 진행 상황 구독 (마지막 알림은 done이 true)
+export async function onTreeDeleteProgress(
+    callback: (progress: TreeDeleteProgress) => void
+): Promise<UnlistenFn> {
+    return await events.treeDeleteProgress(callback);
+}
+
+// 원격 파일 앞부분 미리보기 (마운트 없이, 이미지는 끝까지 읽었을 때만 data_base64가 채워짐)
+export async function readRemoteFilePreview(
+    connectionId: string,
+    path: string,
+    maxBytes: number
+): Promise<RemoteFilePreview> {
+    return await commands.readRemoteFilePreview(connectionId, path, maxBytes);
+}
+
+// 원격 텍스트 파일 읽기 (저장할 때 넘길 etag 포함)
+export async function readRemoteTextFile(connectionId: string, path: string): Promise<RemoteTextFile> {
+    return await commands.readRemoteTextFile(connectionId, path);
+}
+
+// 원격 텍스트 파일 저장 (expectedEtag가 서버와 다르면 saved: false, 새 파일이면 null)
+export async function writeRemoteTextFile(
+    connectionId: string,
+    path: string,
+    content: string,
+    expectedEtag: string | null,
+    force = false
+): Promise<TextFileSaveResult> {
+    return await commands.writeRemoteTextFile(connectionId, path, content, expectedEtag, force);
+}
+
+// SSH 키 파일 점검 (형식, 암호 보호 여부, 공개 키 지문)
+export async function validateKeyFile(path: string): Promise<KeyFileInfo> {
+    return await commands.validateKeyFile(path);
+}
+
+// 키 저장소의 키 목록
+export async function listStoredKeys(): Promise<StoredKey[]> {
+    return await commands.listStoredKeys();
+}
+
+// 키 파일을 키 저장소로 가져오기 (protect면 자격 증명 관리자의 키로 암호화)
+export async function importKey(
+    path: string,
+    name: string,
+    protect: boolean
+): Promise<StoredKey> {
+    return await commands.importKey(path, name, protect);
+}
+
+// 키 저장소에 새 ed25519 키 생성
+export async function generateKey(name: string, protect: boolean): Promise<StoredKey> {
+    return await commands.generateKey(name, protect);
+}
+
+// 키 저장소에서 키 삭제
+export async function deleteStoredKey(id: string): Promise<void> {
+    return await commands.deleteStoredKey(id);
 }
 
 // SSH 연결 테스트
@@ -59,5 +350,257 @@ export async function testConnection(
     password?: string
 ): Promise<boolean> {
     const conn = 'id' in connection ? connection : { ...connection, id: '' };
-    return await invoke('test_connection', { connection: conn, password });
+    return await commands.testConnection(conn, password);
+}
+
+// 서버의 현재 호스트 키 지문 조회 (인증하지 않음)
+export async function getHostKeyFingerprint(
+    connection: SshConnection | Omit<SshConnection, 'id'>
+): Promise<string> {
+    const conn = 'id' in connection ? connection : { ...connection, id: '' };
+    return await commands.getHostKeyFingerprint(conn);
+}
+
+// 확인한 호스트 키 지문을 연결에 다시 고정
+export async function pinHostKey(
+    connectionId: string,
+    fingerprint: string
+): Promise<SshConnection> {
+    return await commands.pinHostKey(connectionId, fingerprint);
+}
+
+// WinSCP/FileZilla에 저장된 사이트를 연결로 가져오기 (경로가 없으면 기본 위치)
+export async function importConnections(
+    source: ImportSource,
+    path: string | null,
+    withPasswords: boolean
+): Promise<ImportReport> {
+    return await commands.importConnections(source, path, withPasswords);
+}
+
+// 연결 템플릿 목록
+export async function listConnectionTemplates(): Promise<ConnectionTemplate[]> {
+    return await commands.listConnectionTemplates();
+}
+
+// 연결 템플릿 저장 (id가 비어 있으면 새 템플릿, variables는 백엔드가 다시 계산)
+export async function saveConnectionTemplate(
+    template: ConnectionTemplate
+): Promise<ConnectionTemplate> {
+    return await commands.saveConnectionTemplate(template);
+}
+
+// 연결 템플릿 삭제
+export async function deleteConnectionTemplate(id: string): Promise<void> {
+    return await commands.deleteConnectionTemplate(id);
+}
+
+// 템플릿의 {변수}를 채워 새 연결 저장
+export async function createConnectionFromTemplate(
+    templateId: string,
+    vars: Record<string, string>
+): Promise<SshConnection> {
+    return await commands.createConnectionFromTemplate(templateId, vars);
+}
+
+// 임시 드라이브 문자로 마운트해 파일 작업을 단계별로 점검
+export async function runSelfTest(connectionId: string): Promise<SelfTestStep[]> {
+    return await commands.runSelfTest(connectionId);
+}
+
+// 새 버전 확인
+export async function checkForUpdate(): Promise<UpdateInfo> {
+    return await commands.checkForUpdate();
+}
+
+// 모든 드라이브를 언마운트하고 업데이트 설치 (성공하면 앱이 종료되고 설치 후 다시 마운트)
+export async function installUpdate(): Promise<void> {
+    return await commands.installUpdate();
+}
+
+// 로그 설정 가져오기
+export async function getLogSettings(): Promise<LogSettings> {
+    return await commands.getLogSettings();
+}
+
+// 로그 설정 변경 (redact면 경로와 호스트 이름을 해시로 가림)
+export async function setLogSettings(settings: LogSettings): Promise<void> {
+    return await commands.setLogSettings(settings);
+}
+
+// 메모리 설정 가져오기
+export async function getMemorySettings(): Promise<MemorySettings> {
+    return await commands.getMemorySettings();
+}
+
+// 메모리 설정 변경 (다음 마운트부터 적용)
+export async function setMemorySettings(settings: MemorySettings): Promise<void> {
+    return await commands.setMemorySettings(settings);
+}
+
+// 자원 한도 가져오기
+export async function getResourceLimits(): Promise<ResourceLimits> {
+    return await commands.getResourceLimits();
+}
+
+// 자원 한도 변경 (드라이브 수, 작업 스레드, 캐시 메모리 합계, 다음 마운트부터 적용)
+export async function setResourceLimits(settings: ResourceLimits): Promise<void> {
+    return await commands.setResourceLimits(settings);
+}
+
+// 연결 프로필 동기화 설정 가져오기
+export async function getProfileSyncSettings(): Promise<ProfileSyncSettings> {
+    return await commands.getProfileSyncSettings();
+}
+
+// 연결 프로필 동기화 설정 변경 (passphrase를 넘기면 동기화 파일 암호도 바꿈)
+export async function setProfileSyncSettings(
+    settings: ProfileSyncSettings,
+    passphrase?: string
+): Promise<void> {
+    return await commands.setProfileSyncSettings(settings, passphrase);
+}
+
+// 지금 연결 프로필 동기화 (꺼져 있으면 null)
+export async function syncProfilesNow(): Promise<ProfileSyncReport | null> {
+    return await commands.syncProfilesNow();
+}
+
+// 앱 설정 가져오기
+export async function getAppSettings(): Promise<AppSettings> {
+    return await commands.getAppSettings();
+}
+
+// 앱 설정 변경 (언어를 바꾸면 트레이 메뉴도 바로 바뀜)
+export async function setAppSettings(settings: AppSettings): Promise<void> {
+    return await commands.setAppSettings(settings);
+}
+
+// 스냅샷 드라이브 목록을 지금 서버 상태로 다시 읽음 (읽은 항목 수 반환)
+export async function refreshSnapshot(driveLetter: string): Promise<number> {
+    return await commands.refreshSnapshot(driveLetter);
+}
+
+// 드라이브 일시 중지 (드라이브 문자는 유지하고 서버 통신을 멈춤)
+export async function pauseDrive(driveLetter: string): Promise<void> {
+    return await commands.pauseDrive(driveLetter);
+}
+
+// 일시 중지한 드라이브 재개
+export async function resumeDrive(driveLetter: string): Promise<void> {
+    return await commands.resumeDrive(driveLetter);
+}
+
+// 변경 대기 모드 드라이브의 대기 중인 변경 목록
+export async function listPendingChanges(driveLetter: string): Promise<PendingChange[]> {
+    return await commands.listPendingChanges(driveLetter);
+}
+
+// 대기 중인 변경을 서버에 올림 (서버에서 바뀐 파일은 남김)
+export async function pushChanges(driveLetter: string): Promise<PushReport> {
+    return await commands.pushChanges(driveLetter);
+}
+
+// 대기 중인 변경 하나를 버림 (path는 서버 경로)
+export async function discardPendingChange(driveLetter: string, path: string): Promise<void> {
+    return await commands.discardPendingChange(driveLetter, path);
+}
+
+// 백그라운드 서비스가 유지하는 드라이브 목록
+export async function getServiceDrives(): Promise<DriveStatus[]> {
+    return await commands.getServiceDrives();
+}
+
+// 저장된 연결을 백그라운드 서비스로 마운트 (앱을 관리자 권한으로 실행해야 함)
+export async function serviceMountDrive(
+    connectionId: string,
+    driveLetter: string
+): Promise<DriveStatus> {
+    return await commands.serviceMountDrive(connectionId, driveLetter);
+}
+
+// 백그라운드 서비스 드라이브 언마운트
+export async function serviceUnmountDrive(driveLetter: string): Promise<void> {
+    return await commands.serviceUnmountDrive(driveLetter);
+}
+
+// 파일의 서버에 보관된 이전 버전 목록 (경로는 드라이브 기준, 최근 것부터)
+export async function listFileVersions(
+    driveLetter: string,
+    path: string
+): Promise<FileVersion[]> {
+    return await commands.listFileVersions(driveLetter.charAt(0), path);
+}
+
+// 보관된 버전으로 파일을 되돌림 (version은 .versions 안의 파일 이름)
+export async function restoreFileVersion(
+    driveLetter: string,
+    path: string,
+    version: string
+): Promise<void> {
+    return await commands.restoreFileVersion(driveLetter.charAt(0), path, version);
+}
+
+// 유휴 자동 언마운트 알림 구독
+export async function onDriveAutoUnmounted(
+    callback: (driveLetter: string) => void
+): Promise<UnlistenFn> {
+    return await events.driveAutoUnmounted(callback);
+}
+
+// 파일시스템 활동 구독 (0.5초마다 모아서 전달)
+export async function onFsActivity(
+    callback: (activity: FsActivity[]) => void
+): Promise<UnlistenFn> {
+    return await events.fsActivity(callback);
+}
+
+// 업데이트 후 다시 마운트된 드라이브 알림 구독
+export async function onDrivesRemounted(
+    callback: (driveLetters: string[]) => void
+): Promise<UnlistenFn> {
+    return await events.drivesRemounted(callback);
+}
+
+// 서버가 저장된 비밀번호를 거부한 연결 알림 구독 (재연결은 새 비밀번호를 받을 때까지 멈춤)
+export async function onCredentialsInvalid(
+    callback: (connectionId: string) => void
+): Promise<UnlistenFn> {
+    return await events.credentialsInvalid(callback);
+}
+
+// 거부된 비밀번호 대신 쓸 새 비밀번호 저장 (멈춘 재연결 재개)
+export async function supplyPassword(connectionId: string, password: string): Promise<void> {
+    return await commands.supplyPassword(connectionId, password);
+}
+
+// 이번 달 전송량이 한도에 닿은 연결 알림 구독 (연결마다 한 달에 한 번)
+export async function onUsageCapReached(
+    callback: (warning: UsageCapWarning) => void
+): Promise<UnlistenFn> {
+    return await events.usageCapReached(callback);
+}
+
+// 백그라운드 연결 프로필 동기화 결과 구독 (주기적으로, 켜 두었을 때만)
+export async function onProfilesSynced(
+    callback: (report: ProfileSyncReport) => void
+): Promise<UnlistenFn> {
+    return await events.profilesSynced(callback);
+}
+
+// 보호 경로의 삭제/이름 변경 확인 요청 구독
+export async function onProtectedOperation(
+    callback: (request: ProtectedRequest) => void
+): Promise<UnlistenFn> {
+    return await events.protectedOperation(callback);
+}
+
+// 보호 경로 작업 확인 요청에 답함
+export async function answerProtectedOperation(id: number, approved: boolean): Promise<void> {
+    return await commands.answerProtectedOperation(id, approved);
+}
+
+// 로컬 제어 파이프에서 드라이브가 마운트/언마운트되면 알림
+export async function onDrivesChanged(callback: () => void): Promise<UnlistenFn> {
+    return await events.drivesChanged(() => callback());
 }
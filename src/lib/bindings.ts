@@ -0,0 +1,719 @@
+// 자동 생성 파일 - 직접 고치지 마세요 (src-tauri/src/bindings.rs)
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+// 사전 요구사항 확인 결과
+export interface PrerequisiteStatus {
+  winfsp_installed: boolean;
+  sshfs_installed: boolean;
+  winfsp_path?: string;
+  sshfs_path?: string;
+  environment: EnvironmentReport;
+}
+
+// 실행 환경 점검 결과 (마운트 문제 진단용)
+export interface EnvironmentReport {
+  windows_version?: string;
+  windows_build?: number;
+  windows_edition?: string;
+  developer_mode: boolean;
+  symlink_allowed: boolean;
+  winfsp_launcher_running?: boolean;
+  app_arch: string;
+  winfsp_arch?: string;
+  arch_mismatch: boolean;
+  antivirus: string[];
+  hints: string[];
+}
+
+// 첫 실행 점검 결과 (UI 마법사가 순서대로 보여 줌)
+export interface OnboardingChecklist {
+  steps: OnboardingStep[];
+  ready: boolean;
+}
+
+// 첫 실행 점검 항목 하나
+export interface OnboardingStep {
+  id: string;
+  title: string;
+  status: OnboardingStatus;
+  detail: string;
+  action?: OnboardingAction;
+  import_source?: ImportSource;
+}
+
+// 첫 실행 점검 항목 상태
+export type OnboardingStatus = 'ok' | 'recommended' | 'required';
+
+// 점검 항목에서 UI가 제안할 작업
+export type OnboardingAction = 'install_winfsp' | 'start_winfsp_service' | 'generate_key' | 'import_connections' | 'add_connection';
+
+// 연결을 가져올 다른 SFTP 프로그램
+export type ImportSource = 'winscp' | 'filezilla' | 'sshconfig';
+
+// SSH 연결 프로필
+export interface SshConnection {
+  id: string;
+  name: string;
+  host: string;
+  port: number;
+  username: string;
+  auth_type: AuthType;
+  key_path?: string;
+  key_id?: string;
+  remote_path: string;
+  drive_letter?: string;
+  transfer_streams?: number;
+  inflight_limit_mb?: number;
+  mount_profile?: MountProfile;
+  symlink_policy?: SymlinkPolicy;
+  name_normalization?: NameNormalization;
+  reserved_names?: ReservedNameEscape;
+  illegal_chars?: IllegalCharEscape;
+  xattr_passthrough?: boolean;
+  metadata_friendly?: boolean;
+  exclude_patterns?: string[];
+  access_rules?: AccessRule[];
+  protected_paths?: string[];
+  keep_versions?: number;
+  soft_quota_bytes?: number;
+  server_quota?: boolean;
+  monthly_transfer_cap_bytes?: number;
+  hooks?: ConnectionHooks;
+  auto_unmount_minutes?: number;
+  proxy?: ProxySettings;
+  host_overrides?: Record<string, string>;
+  tcp_nodelay?: boolean;
+  tcp_keepalive_secs?: number;
+  dscp?: number;
+  totp?: boolean;
+  time_offset_minutes?: number;
+  creation_time_policy?: CreationTimePolicy;
+  blocked_processes?: string[];
+  indexing_opt_out?: boolean;
+  global_mount?: boolean;
+  queue_writes?: boolean;
+  atomic_uploads?: boolean;
+  host_key_fingerprint?: string;
+  pinned_folders?: string[];
+  warm_file_max_kb?: number;
+  offline_paths?: string[];
+  notes?: string;
+  label_color?: LabelColor;
+  label_icon?: string;
+  drive_label?: string;
+  drive_icon?: string;
+}
+
+// SSH 연결 인증 방식
+export type AuthType = 'password' | 'key' | 'agent';
+
+// 마운트 프로필
+export type MountProfile = 'default' | 'developer' | 'snapshot';
+
+// 심볼릭 링크 생성 정책
+export type SymlinkPolicy = 'allow' | 'deny';
+
+// 원격 이름 정규화 정책 (서버에서 받은 이름은 NFC로 보이고, 새 이름은 정한 형태로 보냄)
+export type NameNormalization = 'nfc' | 'nfd' | 'off';
+
+// Windows 예약 이름 처리 방식 (서버에서 받은 이름을 바꿔 보이고, 서버로 보낼 때 되돌림)
+export type ReservedNameEscape = 'suffix' | 'off';
+
+// Windows 이름에 쓸 수 없는 문자 처리 방식 (서버에서 받은 이름을 바꿔 보이고, 서버로 보낼 때 되돌림)
+export type IllegalCharEscape = 'unicode' | 'fullwidth' | 'off';
+
+// 경로별 접근 규칙 (원격 절대 경로 glob, 하위 경로 전체에 적용)
+export interface AccessRule {
+  path: string;
+  access: AccessMode;
+}
+
+// 접근 규칙 종류
+export type AccessMode = 'read_only' | 'hidden';
+
+// 연결별 이벤트 훅 (`cmd /C`로 실행, 환경 변수 SVD_DRIVE 등으로 드라이브 정보 전달)
+export interface ConnectionHooks {
+  on_mount?: string;
+  on_unmount?: string;
+  on_connection_lost?: string;
+}
+
+// 프록시 설정
+export interface ProxySettings {
+  kind: ProxyKind;
+  host: string;
+  port: number;
+  username?: string;
+}
+
+// 프록시 종류
+export type ProxyKind = 'socks5' | 'http';
+
+// 생성 시간 합성 정책 (SFTP에는 생성 시간이 없음)
+export type CreationTimePolicy = 'modified' | 'fixed';
+
+// 연결 색 라벨
+export type LabelColor = 'red' | 'orange' | 'yellow' | 'green' | 'blue' | 'purple' | 'gray';
+
+// 삭제 후 되돌릴 수 있게 보관 중인 연결 프로필
+export interface DeletedConnection {
+  connection: SshConnection;
+  deleted_at: number;
+}
+
+// 마운트된 드라이브 정보
+export interface DriveStatus {
+  drive_letter: string;
+  connection_id: string;
+  status: DriveStatusType;
+  error_message?: string;
+  journal_conflicts?: string[];
+  scope_notes?: string[];
+  reconnect?: ReconnectStatus;
+  session_uptime_secs?: number;
+  last_success_at?: number;
+  avg_latency_ms?: number;
+  last_error?: string;
+  paused?: boolean;
+  pending_changes?: number;
+  alias_of?: string;
+  same_path_drives?: string[];
+}
+
+// 드라이브 상태
+export type DriveStatusType = 'connected' | 'disconnected' | 'error';
+
+// 재연결 실패 상태
+export interface ReconnectStatus {
+  failures: number;
+  circuit: CircuitState;
+  retry_in_secs?: number;
+}
+
+// 재연결 회로 상태
+export type CircuitState = 'closed' | 'open' | 'half_open';
+
+// 연결 안의 원격 경로 북마크
+export interface Bookmark {
+  id: string;
+  connection_id: string;
+  name: string;
+  path: string;
+  created_at: number;
+}
+
+// 언마운트 전 확인 (열린 파일과 최근 작업)
+export interface UnmountCheck {
+  drive_letter: string;
+  open_handles: number;
+  recent_files: string[];
+  idle_secs: number;
+  busy: boolean;
+}
+
+// 마운트 통계 (캐시 적중률 등)
+export interface MountStats {
+  drive_letter: string;
+  connection_id: string;
+  cache_hits: number;
+  cache_misses: number;
+  cache_evictions: number;
+  batched_stats: number;
+  preview_reads_denied: number;
+  uploads: UploadProgress[];
+  capabilities: SftpCapabilities;
+  processes: ProcessStats[];
+}
+
+// 파일 하나의 업로드 진행 상황
+export interface UploadProgress {
+  path: string;
+  confirmed_bytes: number;
+  pending_bytes: number;
+}
+
+// 서버가 지원하는 SFTP 확장 (연결 시 확인)
+export interface SftpCapabilities {
+  statvfs: boolean;
+  fsync: boolean;
+  exec: boolean;
+}
+
+// 프로세스별 접근 통계
+export interface ProcessStats {
+  name: string;
+  operations: number;
+  bytes_read: number;
+  bytes_written: number;
+  blocked_reads: number;
+}
+
+// 연결 상태 기록의 측정값 하나 (마운트 중 주기적으로 측정)
+export interface HealthSample {
+  at: number;
+  latency_ms?: number;
+}
+
+// 연결의 누적 전송량
+export interface ConnectionUsage {
+  connection_id: string;
+  bytes_received: number;
+  bytes_sent: number;
+  since?: number;
+  current_month: MonthlyUsage;
+  months: MonthlyUsage[];
+  monthly_transfer_cap_bytes?: number;
+}
+
+// 한 달 동안 서버와 주고받은 파일 데이터 양 (UTC 기준)
+export interface MonthlyUsage {
+  month: string;
+  bytes_received: number;
+  bytes_sent: number;
+}
+
+// 서버에 보관된 파일 버전
+export interface FileVersion {
+  name: string;
+  saved_at: number;
+  size: number;
+}
+
+// 전송 대기열 항목
+export interface TransferItem {
+  id: string;
+  connection_id: string;
+  direction: TransferDirection;
+  local_path: string;
+  remote_path: string;
+  total_bytes: number;
+  transferred_bytes: number;
+  state: TransferState;
+  error?: string;
+}
+
+// 전송 방향
+export type TransferDirection = 'upload' | 'download';
+
+// 전송 항목 상태
+export type TransferState = 'pending' | 'active' | 'paused' | 'completed' | 'failed' | 'cancelled';
+
+// 원격 디렉토리 사용량 트리 노드
+export interface UsageNode {
+  path: string;
+  size: number;
+  children?: UsageNode[];
+}
+
+// 원격 파일 앞부분 미리보기
+export interface RemoteFilePreview {
+  path: string;
+  size: number;
+  kind: PreviewKind;
+  mime?: string;
+  text?: string;
+  data_base64?: string;
+  truncated: boolean;
+}
+
+// 원격 파일 미리보기 종류
+export type PreviewKind = 'text' | 'image' | 'binary';
+
+// 편집하려고 읽은 원격 텍스트 파일
+export interface RemoteTextFile {
+  path: string;
+  content: string;
+  etag: string;
+}
+
+// 원격 텍스트 파일 저장 결과
+export interface TextFileSaveResult {
+  saved: boolean;
+  etag?: string;
+}
+
+// 키 파일 점검 결과
+export interface KeyFileInfo {
+  format: KeyFormat;
+  key_type?: string;
+  encrypted: boolean;
+  fingerprint?: string;
+  warnings?: string[];
+}
+
+// SSH 키 파일 형식
+export type KeyFormat = 'unknown' | 'openssh' | 'pem' | 'ppk' | 'public';
+
+// 키 저장소에 보관된 SSH 키 (개인 키 내용은 저장소 파일에만 있음)
+export interface StoredKey {
+  id: string;
+  name: string;
+  key_type?: string;
+  fingerprint?: string;
+  protected: boolean;
+  created_at: number;
+}
+
+// 다른 프로그램에서 연결 가져오기 결과
+export interface ImportReport {
+  imported: SshConnection[];
+  skipped: string[];
+  notes: string[];
+}
+
+// `{변수}` 자리 표시가 든 연결 템플릿 (호스트나 사용자만 다른 같은 구성의 연결을 찍어 낼 때)
+export interface ConnectionTemplate {
+  id: string;
+  name: string;
+  connection: SshConnection;
+  variables?: string[];
+}
+
+// 자가 진단 단계 결과
+export interface SelfTestStep {
+  name: string;
+  passed: boolean;
+  message?: string;
+  duration_ms: number;
+}
+
+// 최신 릴리스 확인 결과
+export interface UpdateInfo {
+  current_version: string;
+  latest_version: string;
+  update_available: boolean;
+  release_notes?: string;
+  published_at?: string;
+  download_url?: string;
+  sha256?: string;
+}
+
+// 로그 설정
+export interface LogSettings {
+  redact?: boolean;
+}
+
+// 메모리 설정 (다음 마운트부터 적용)
+export interface MemorySettings {
+  low_memory?: boolean;
+  limit_mb?: number;
+}
+
+// 모든 드라이브에 걸친 자원 한도 (None이면 제한 없음, 다음 마운트부터 적용)
+export interface ResourceLimits {
+  max_mounts?: number;
+  max_worker_threads?: number;
+  max_cache_mb?: number;
+}
+
+// 연결 프로필 동기화 설정 (암호화한 연결 목록을 SFTP 서버의 파일 하나로 주고받음)
+export interface ProfileSyncSettings {
+  enabled?: boolean;
+  connection_id?: string;
+  remote_path?: string;
+}
+
+// 연결 프로필 동기화 결과
+export interface ProfileSyncReport {
+  added: number;
+  updated: number;
+  removed: number;
+  conflicts?: string[];
+  uploaded: boolean;
+  synced_at: number;
+}
+
+// 앱 설정 (바꾸면 바로 적용)
+export interface AppSettings {
+  language?: Language;
+  start_minimized?: boolean;
+  prevent_sleep?: boolean;
+  shell_integration?: boolean;
+  window?: WindowState;
+}
+
+// 앱이 직접 그리는 문자열(트레이 메뉴 등)의 언어
+export type Language = 'ko' | 'en';
+
+// 창 위치와 크기 (물리 픽셀, 최대화했으면 최대화 전 위치와 크기)
+export interface WindowState {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  maximized?: boolean;
+}
+
+// 변경 대기 모드에서 올리기를 기다리는 파일
+export interface PendingChange {
+  path: string;
+  size: number;
+  pending_bytes: number;
+  queued_at: number;
+}
+
+// 대기 중인 변경 올리기 결과
+export interface PushReport {
+  pushed: string[];
+  errors: string[];
+}
+
+// 검색 중간 결과 (`search-results` 이벤트)
+export interface SearchResults {
+  search_id: string;
+  hits: SearchHit[];
+}
+
+// 원격 검색 결과 항목
+export interface SearchHit {
+  path: string;
+  is_directory: boolean;
+  size: number;
+}
+
+// 검색 종료 알림 (`search-finished` 이벤트)
+export interface SearchFinished {
+  search_id: string;
+  total: number;
+  truncated: boolean;
+  cancelled: boolean;
+  error?: string;
+}
+
+// 파일시스템 활동 (`fs-activity` 이벤트, 연속된 같은 작업은 합쳐서 전달)
+export interface FsActivity {
+  drive_letter: string;
+  path: string;
+  op: FsOp;
+  pid: number;
+  process: string;
+  bytes: number;
+  duration_ms: number;
+  count: number;
+}
+
+// 파일시스템 작업 종류 (활동 패널 표시용)
+export type FsOp = 'open' | 'create' | 'read' | 'write' | 'flush' | 'rename' | 'delete' | 'list';
+
+// 보호 경로 작업 확인 요청 (앱에 이벤트로 전달)
+export interface ProtectedRequest {
+  id: number;
+  drive_letter: string;
+  operation: ProtectedOp;
+  path: string;
+  target?: string;
+  rule: string;
+  timeout_secs: number;
+}
+
+// 보호 경로에서 확인이 필요한 작업
+export type ProtectedOp = 'delete' | 'rename';
+
+// 월 전송량 한도 도달 알림 (연결마다 한 달에 한 번)
+export interface UsageCapWarning {
+  connection_id: string;
+  connection_name: string;
+  month: string;
+  used_bytes: number;
+  cap_bytes: number;
+}
+
+// 원격 트리 삭제 진행 상황 (`tree-delete-progress` 이벤트, 마지막 알림은 `done`)
+export interface TreeDeleteProgress {
+  delete_id: string;
+  connection_id: string;
+  path: string;
+  deleted: number;
+  server_side: boolean;
+  done: boolean;
+  error?: string;
+}
+
+// 백엔드 명령 (인자 이름은 Tauri 규칙대로 camelCase로 넘김)
+export const commands = {
+  checkPrerequisites: (): Promise<PrerequisiteStatus> =>
+    invoke('check_prerequisites'),
+  startWinfspService: (): Promise<void> =>
+    invoke('start_winfsp_service'),
+  runOnboarding: (): Promise<OnboardingChecklist> =>
+    invoke('run_onboarding'),
+  getConnections: (): Promise<SshConnection[]> =>
+    invoke('get_connections'),
+  saveConnection: (connection: SshConnection, password?: string, proxyPassword?: string, totpSecret?: string): Promise<SshConnection> =>
+    invoke('save_connection', { connection, password, proxyPassword, totpSecret }),
+  deleteConnection: (id: string): Promise<void> =>
+    invoke('delete_connection', { id }),
+  undoDeleteConnection: (id: string): Promise<SshConnection> =>
+    invoke('undo_delete_connection', { id }),
+  listDeletedConnections: (): Promise<DeletedConnection[]> =>
+    invoke('list_deleted_connections'),
+  getAvailableDriveLetters: (): Promise<string[]> =>
+    invoke('get_available_drive_letters'),
+  mountDrive: (connectionId: string, driveLetter: string): Promise<DriveStatus> =>
+    invoke('mount_drive', { connectionId, driveLetter }),
+  listBookmarks: (connectionId?: string): Promise<Bookmark[]> =>
+    invoke('list_bookmarks', { connectionId }),
+  addBookmark: (connectionId: string, name: string, path: string): Promise<Bookmark> =>
+    invoke('add_bookmark', { connectionId, name, path }),
+  removeBookmark: (id: string): Promise<void> =>
+    invoke('remove_bookmark', { id }),
+  mountBookmark: (bookmarkId: string, driveLetter: string): Promise<DriveStatus> =>
+    invoke('mount_bookmark', { bookmarkId, driveLetter }),
+  openBookmarkTerminal: (bookmarkId: string): Promise<void> =>
+    invoke('open_bookmark_terminal', { bookmarkId }),
+  checkUnmount: (driveLetter: string): Promise<UnmountCheck> =>
+    invoke('check_unmount', { driveLetter }),
+  unmountDrive: (driveLetter: string, force: boolean): Promise<void> =>
+    invoke('unmount_drive', { driveLetter, force }),
+  getMountedDrives: (): Promise<DriveStatus[]> =>
+    invoke('get_mounted_drives'),
+  getMountStats: (driveLetter: string): Promise<MountStats> =>
+    invoke('get_mount_stats', { driveLetter }),
+  getConnectionHealthHistory: (id: string): Promise<HealthSample[]> =>
+    invoke('get_connection_health_history', { id }),
+  getConnectionUsage: (id: string): Promise<ConnectionUsage> =>
+    invoke('get_connection_usage', { id }),
+  copyRemotePath: (driveLetter: string, from: string, to: string): Promise<void> =>
+    invoke('copy_remote_path', { driveLetter, from, to }),
+  listFileVersions: (driveLetter: string, path: string): Promise<FileVersion[]> =>
+    invoke('list_file_versions', { driveLetter, path }),
+  restoreFileVersion: (driveLetter: string, path: string, version: string): Promise<void> =>
+    invoke('restore_file_version', { driveLetter, path, version }),
+  enqueueUpload: (connectionId: string, localPath: string, remotePath: string): Promise<TransferItem> =>
+    invoke('enqueue_upload', { connectionId, localPath, remotePath }),
+  enqueueDownload: (connectionId: string, remotePath: string, localPath: string): Promise<TransferItem> =>
+    invoke('enqueue_download', { connectionId, remotePath, localPath }),
+  stageDroppedUploads: (connectionId: string, remoteDir: string, paths: string[]): Promise<TransferItem[]> =>
+    invoke('stage_dropped_uploads', { connectionId, remoteDir, paths }),
+  listTransfers: (): Promise<TransferItem[]> =>
+    invoke('list_transfers'),
+  pauseTransfer: (id: string): Promise<void> =>
+    invoke('pause_transfer', { id }),
+  resumeTransfer: (id: string): Promise<void> =>
+    invoke('resume_transfer', { id }),
+  cancelTransfer: (id: string): Promise<void> =>
+    invoke('cancel_transfer', { id }),
+  clearFinishedTransfers: (): Promise<void> =>
+    invoke('clear_finished_transfers'),
+  setTransferConcurrency: (count: number): Promise<void> =>
+    invoke('set_transfer_concurrency', { count }),
+  searchRemote: (connectionId: string, root: string, pattern: string, maxResults: number): Promise<string> =>
+    invoke('search_remote', { connectionId, root, pattern, maxResults }),
+  cancelSearch: (searchId: string): Promise<void> =>
+    invoke('cancel_search', { searchId }),
+  analyzeRemoteUsage: (connectionId: string, path: string, depth: number): Promise<UsageNode> =>
+    invoke('analyze_remote_usage', { connectionId, path, depth }),
+  deleteRemoteTree: (connectionId: string, path: string): Promise<string> =>
+    invoke('delete_remote_tree', { connectionId, path }),
+  readRemoteFilePreview: (connectionId: string, path: string, maxBytes: number): Promise<RemoteFilePreview> =>
+    invoke('read_remote_file_preview', { connectionId, path, maxBytes }),
+  readRemoteTextFile: (connectionId: string, path: string): Promise<RemoteTextFile> =>
+    invoke('read_remote_text_file', { connectionId, path }),
+  writeRemoteTextFile: (connectionId: string, path: string, content: string, expectedEtag: string | null, force: boolean): Promise<TextFileSaveResult> =>
+    invoke('write_remote_text_file', { connectionId, path, content, expectedEtag, force }),
+  validateKeyFile: (path: string): Promise<KeyFileInfo> =>
+    invoke('validate_key_file', { path }),
+  listStoredKeys: (): Promise<StoredKey[]> =>
+    invoke('list_stored_keys'),
+  importKey: (path: string, name: string, protect: boolean): Promise<StoredKey> =>
+    invoke('import_key', { path, name, protect }),
+  generateKey: (name: string, protect: boolean): Promise<StoredKey> =>
+    invoke('generate_key', { name, protect }),
+  deleteStoredKey: (id: string): Promise<void> =>
+    invoke('delete_stored_key', { id }),
+  testConnection: (connection: SshConnection, password?: string): Promise<boolean> =>
+    invoke('test_connection', { connection, password }),
+  getHostKeyFingerprint: (connection: SshConnection): Promise<string> =>
+    invoke('get_host_key_fingerprint', { connection }),
+  pinHostKey: (connectionId: string, fingerprint: string): Promise<SshConnection> =>
+    invoke('pin_host_key', { connectionId, fingerprint }),
+  importConnections: (source: ImportSource, path: string | null, withPasswords: boolean): Promise<ImportReport> =>
+    invoke('import_connections', { source, path, withPasswords }),
+  listConnectionTemplates: (): Promise<ConnectionTemplate[]> =>
+    invoke('list_connection_templates'),
+  saveConnectionTemplate: (template: ConnectionTemplate): Promise<ConnectionTemplate> =>
+    invoke('save_connection_template', { template }),
+  deleteConnectionTemplate: (id: string): Promise<void> =>
+    invoke('delete_connection_template', { id }),
+  createConnectionFromTemplate: (templateId: string, vars: Record<string, string>): Promise<SshConnection> =>
+    invoke('create_connection_from_template', { templateId, vars }),
+  runSelfTest: (connectionId: string): Promise<SelfTestStep[]> =>
+    invoke('run_self_test', { connectionId }),
+  checkForUpdate: (): Promise<UpdateInfo> =>
+    invoke('check_for_update'),
+  installUpdate: (): Promise<void> =>
+    invoke('install_update'),
+  getLogSettings: (): Promise<LogSettings> =>
+    invoke('get_log_settings'),
+  setLogSettings: (settings: LogSettings): Promise<void> =>
+    invoke('set_log_settings', { settings }),
+  getMemorySettings: (): Promise<MemorySettings> =>
+    invoke('get_memory_settings'),
+  setMemorySettings: (settings: MemorySettings): Promise<void> =>
+    invoke('set_memory_settings', { settings }),
+  getResourceLimits: (): Promise<ResourceLimits> =>
+    invoke('get_resource_limits'),
+  setResourceLimits: (settings: ResourceLimits): Promise<void> =>
+    invoke('set_resource_limits', { settings }),
+  getProfileSyncSettings: (): Promise<ProfileSyncSettings> =>
+    invoke('get_profile_sync_settings'),
+  setProfileSyncSettings: (settings: ProfileSyncSettings, passphrase?: string): Promise<void> =>
+    invoke('set_profile_sync_settings', { settings, passphrase }),
+  syncProfilesNow: (): Promise<ProfileSyncReport | null> =>
+    invoke('sync_profiles_now'),
+  getAppSettings: (): Promise<AppSettings> =>
+    invoke('get_app_settings'),
+  setAppSettings: (settings: AppSettings): Promise<void> =>
+    invoke('set_app_settings', { settings }),
+  refreshSnapshot: (driveLetter: string): Promise<number> =>
+    invoke('refresh_snapshot', { driveLetter }),
+  pauseDrive: (driveLetter: string): Promise<void> =>
+    invoke('pause_drive', { driveLetter }),
+  resumeDrive: (driveLetter: string): Promise<void> =>
+    invoke('resume_drive', { driveLetter }),
+  listPendingChanges: (driveLetter: string): Promise<PendingChange[]> =>
+    invoke('list_pending_changes', { driveLetter }),
+  pushChanges: (driveLetter: string): Promise<PushReport> =>
+    invoke('push_changes', { driveLetter }),
+  discardPendingChange: (driveLetter: string, path: string): Promise<void> =>
+    invoke('discard_pending_change', { driveLetter, path }),
+  answerProtectedOperation: (id: number, approved: boolean): Promise<void> =>
+    invoke('answer_protected_operation', { id, approved }),
+  supplyPassword: (connectionId: string, password: string): Promise<void> =>
+    invoke('supply_password', { connectionId, password }),
+  getServiceDrives: (): Promise<DriveStatus[]> =>
+    invoke('get_service_drives'),
+  serviceMountDrive: (connectionId: string, driveLetter: string): Promise<DriveStatus> =>
+    invoke('service_mount_drive', { connectionId, driveLetter }),
+  serviceUnmountDrive: (driveLetter: string): Promise<void> =>
+    invoke('service_unmount_drive', { driveLetter }),
+};
+
+// 백엔드 이벤트
+export const events = {
+  transferProgress: (callback: (payload: TransferItem) => void): Promise<UnlistenFn> =>
+    listen<TransferItem>('transfer-progress', (event) => callback(event.payload)),
+  searchResults: (callback: (payload: SearchResults) => void): Promise<UnlistenFn> =>
+    listen<SearchResults>('search-results', (event) => callback(event.payload)),
+  searchFinished: (callback: (payload: SearchFinished) => void): Promise<UnlistenFn> =>
+    listen<SearchFinished>('search-finished', (event) => callback(event.payload)),
+  driveAutoUnmounted: (callback: (payload: string) => void): Promise<UnlistenFn> =>
+    listen<string>('drive-auto-unmounted', (event) => callback(event.payload)),
+  fsActivity: (callback: (payload: FsActivity[]) => void): Promise<UnlistenFn> =>
+    listen<FsActivity[]>('fs-activity', (event) => callback(event.payload)),
+  drivesRemounted: (callback: (payload: string[]) => void): Promise<UnlistenFn> =>
+    listen<string[]>('drives-remounted', (event) => callback(event.payload)),
+  credentialsInvalid: (callback: (payload: string) => void): Promise<UnlistenFn> =>
+    listen<string>('credentials-invalid', (event) => callback(event.payload)),
+  protectedOperation: (callback: (payload: ProtectedRequest) => void): Promise<UnlistenFn> =>
+    listen<ProtectedRequest>('protected-operation', (event) => callback(event.payload)),
+  usageCapReached: (callback: (payload: UsageCapWarning) => void): Promise<UnlistenFn> =>
+    listen<UsageCapWarning>('usage-cap-reached', (event) => callback(event.payload)),
+  profilesSynced: (callback: (payload: ProfileSyncReport) => void): Promise<UnlistenFn> =>
+    listen<ProfileSyncReport>('profiles-synced', (event) => callback(event.payload)),
+  treeDeleteProgress: (callback: (payload: TreeDeleteProgress) => void): Promise<UnlistenFn> =>
+    listen<TreeDeleteProgress>('tree-delete-progress', (event) => callback(event.payload)),
+  drivesChanged: (callback: (payload: null) => void): Promise<UnlistenFn> =>
+    listen<null>('drives-changed', (event) => callback(event.payload)),
+};
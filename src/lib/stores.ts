@@ -20,16 +20,31 @@ export const isLoading = writable(false);
 // 에러 메시지
 export const errorMessage = writable<string | null>(null);
 
+// 방금 삭제해 되돌릴 수 있는 연결
+export const lastDeletedConnection = writable<SshConnection | null>(null);
+
 // 연결 상태를 포함한 연결 목록 (derived store)
 export const connectionsWithStatus = derived(
     [connections, mountedDrives],
     ([$connections, $mountedDrives]) => {
         return $connections.map((conn) => {
-            const mounted = $mountedDrives.find((d) => d.connection_id === conn.id);
+            const mounted = $mountedDrives.find(
+                (d) => d.connection_id === conn.id && !d.alias_of
+            );
             return {
                 ...conn,
                 isConnected: mounted?.status === 'connected',
                 mountedDriveLetter: mounted?.drive_letter,
+                // 같은 드라이브를 가리키는 별칭 문자
+                aliasDriveLetters: $mountedDrives
+                    .filter((d) => d.connection_id === conn.id && d.alias_of)
+                    .map((d) => d.drive_letter),
+                scopeNotes: mounted?.scope_notes ?? [],
+                journalConflicts: mounted?.journal_conflicts ?? [],
+                reconnect: mounted?.reconnect,
+                paused: mounted?.paused ?? false,
+                pendingChanges: mounted?.pending_changes,
+                health: mounted,
             };
         });
     }
@@ -81,11 +96,42 @@ export async function saveConnectionStore(
     }
 }
 
+// 템플릿으로 새 연결 만들기
+export async function createConnectionFromTemplateStore(
+    templateId: string,
+    vars: Record<string, string>
+) {
+    try {
+        const created = await api.createConnectionFromTemplate(templateId, vars);
+        connections.update((conns) => [...conns, created]);
+        return created;
+    } catch (error) {
+        errorMessage.set(error instanceof Error ? error.message : String(error));
+        throw error;
+    }
+}
+
 // 연결 삭제
 export async function deleteConnectionStore(id: string) {
     try {
         await api.deleteConnection(id);
-        connections.update((conns) => conns.filter((c) => c.id !== id));
+        connections.update((conns) => {
+            lastDeletedConnection.set(conns.find((c) => c.id === id) ?? null);
+            return conns.filter((c) => c.id !== id);
+        });
+    } catch (error) {
+        errorMessage.set(error instanceof Error ? error.message : String(error));
+        throw error;
+    }
+}
+
+// 삭제한 연결 되돌리기
+export async function undoDeleteConnectionStore(id: string) {
+    try {
+        const restored = await api.undoDeleteConnection(id);
+        connections.update((conns) => [...conns, restored]);
+        lastDeletedConnection.set(null);
+        return restored;
     } catch (error) {
         errorMessage.set(error instanceof Error ? error.message : String(error));
         throw error;
@@ -108,10 +154,25 @@ export async function mountDriveStore(connectionId: string, driveLetter: string)
     }
 }
 
+// 북마크 경로를 루트로 드라이브 마운트
+export async function mountBookmarkStore(bookmarkId: string, driveLetter: string) {
+    try {
+        const status = await api.mountBookmark(bookmarkId, driveLetter);
+        mountedDrives.update((drives) => [...drives, status]);
+        availableDriveLetters.update((letters) =>
+            letters.filter((l) => l !== driveLetter)
+        );
+        return status;
+    } catch (error) {
+        errorMessage.set(error instanceof Error ? error.message : String(error));
+        throw error;
+    }
+}
+
 // 드라이브 언마운트
-export async function unmountDriveStore(driveLetter: string) {
+export async function unmountDriveStore(driveLetter: string, force = false) {
     try {
-        await api.unmountDrive(driveLetter);
+        await api.unmountDrive(driveLetter, force);
         mountedDrives.update((drives) =>
             drives.filter((d) => d.drive_letter !== driveLetter)
         );
@@ -1,37 +1,89 @@
-// SSH 연결 인증 방식
-export type AuthType = 'password' | 'key';
+// 백엔드와 주고받는 타입은 Rust 정의에서 생성한 bindings.ts를 그대로 씀
+// (타입을 바꾸려면 types.rs를 고치고 개발 모드로 한 번 실행)
+import type { LabelColor, SshConnection } from './bindings';
 
-// SSH 연결 프로필
-export interface SshConnection {
-  id: string;
-  name: string;
-  host: string;
-  port: number;
-  username: string;
-  auth_type: AuthType;
-  key_path?: string;
-  remote_path: string;
-  drive_letter?: string;
-}
-
-// 드라이브 상태 타입
-export type DriveStatusType = 'connected' | 'disconnected' | 'error';
+export type {
+  AuthType,
+  SshConnection,
+  DeletedConnection,
+  MountProfile,
+  CreationTimePolicy,
+  ProxySettings,
+  ProxyKind,
+  ConnectionHooks,
+  AccessRule,
+  AccessMode,
+  SymlinkPolicy,
+  NameNormalization,
+  ReservedNameEscape,
+  IllegalCharEscape,
+  LabelColor,
+  DriveStatusType,
+  CircuitState,
+  ReconnectStatus,
+  DriveStatus,
+  PendingChange,
+  PushReport,
+  FileVersion,
+  ProtectedOp,
+  ProtectedRequest,
+  MountStats,
+  HealthSample,
+  MonthlyUsage,
+  ConnectionUsage,
+  UsageCapWarning,
+  UnmountCheck,
+  ProcessStats,
+  SftpCapabilities,
+  UploadProgress,
+  PrerequisiteStatus,
+  OnboardingStatus,
+  OnboardingAction,
+  OnboardingStep,
+  OnboardingChecklist,
+  TransferDirection,
+  TransferState,
+  TransferItem,
+  SearchHit,
+  SearchResults,
+  SearchFinished,
+  TreeDeleteProgress,
+  UsageNode,
+  PreviewKind,
+  RemoteFilePreview,
+  RemoteTextFile,
+  TextFileSaveResult,
+  FsOp,
+  FsActivity,
+  KeyFormat,
+  KeyFileInfo,
+  StoredKey,
+  Bookmark,
+  ConnectionTemplate,
+  UpdateInfo,
+  SelfTestStep,
+  LogSettings,
+  ImportSource,
+  ImportReport,
+  MemorySettings,
+  ResourceLimits,
+  ProfileSyncSettings,
+  ProfileSyncReport,
+  AppSettings,
+  Language,
+  WindowState,
+} from './bindings';
 
-// 마운트된 드라이브 정보
-export interface DriveStatus {
-  drive_letter: string;
-  connection_id: string;
-  status: DriveStatusType;
-  error_message?: string;
-}
-
-// 사전 요구사항 확인 결과
-export interface PrerequisiteStatus {
-  winfsp_installed: boolean;
-  sshfs_installed: boolean;
-  winfsp_path?: string;
-  sshfs_path?: string;
-}
+// 색 라벨 이름과 표시 색
+export const labelColors: Record<LabelColor, { name: string; color: string }> = {
+  red: { name: '빨강 (운영)', color: '#f38ba8' },
+  orange: { name: '주황', color: '#fab387' },
+  yellow: { name: '노랑 (스테이징)', color: '#f9e2af' },
+  green: { name: '초록 (개발)', color: '#a6e3a1' },
+  blue: { name: '파랑', color: '#89b4fa' },
+  purple: { name: '보라', color: '#cba6f7' },
+  gray: { name: '회색', color: '#9399b2' },
+};
 
 // 새 연결 폼 기본값
 export function createEmptyConnection(): Omit<SshConnection, 'id'> {
@@ -42,5 +94,12 @@ export function createEmptyConnection(): Omit<SshConnection, 'id'> {
     username: '',
     auth_type: 'password',
     remote_path: '/',
+    mount_profile: 'default',
+    queue_writes: false,
+    atomic_uploads: false,
+    name_normalization: 'nfc',
+    reserved_names: 'suffix',
+    illegal_chars: 'unicode',
+    tcp_nodelay: true,
   };
 }